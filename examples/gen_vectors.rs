@@ -0,0 +1,46 @@
+// Generator of the wire-compatibility vectors checked by tests/vectors.rs. Run it with
+// `cargo run --example gen_vectors --features testing` only when the serialized layout is changed on purpose:
+// it overwrites the committed fixtures in tests/vectors/.
+
+use optrand_pvss::{
+    modified_scrape::{decryption::DecryptedShare, participant::ParticipantId},
+    testing::fixtures::FixtureBuilder,
+};
+
+use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+use ark_serialize::CanonicalSerialize;
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+
+use std::{fs, path::Path};
+
+// Seed all fixtures are derived from.
+const SEED: [u8; 32] = [42u8; 32];
+
+fn write<T: CanonicalSerialize>(dir: &Path, name: &str, value: &T) {
+    let mut bytes = vec![];
+    value.serialize(&mut bytes).unwrap();
+    fs::write(dir.join(name), &bytes).unwrap();
+
+    println!("{}: {} bytes", name, bytes.len());
+}
+
+fn main() {
+    // Everyone deals; the shares are aggregated.
+    let mut fixture = FixtureBuilder::new(1, 3).build_from_rng::<E>(ChaChaRng::from_seed(SEED)).unwrap();
+    let (conf, shares, aggregated) = (&fixture.config, &fixture.shares, &fixture.transcript);
+
+    // Participant 1 decrypts its share of the aggregate.
+    let sk = &fixture.keypairs[1].secret_key;
+    let decrypted = DecryptedShare::generate(&mut fixture.rng, conf, &aggregated.pvss_core.encs[1], sk, ParticipantId(1)).unwrap();
+
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("vectors");
+    fs::create_dir_all(&dir).unwrap();
+
+    write(&dir, "config.bin", conf);
+    write(&dir, "registry.bin", &fixture.registry);
+    write(&dir, "share.bin", &shares[0]);
+    write(&dir, "aggregated.bin", aggregated);
+    write(&dir, "decomp_proof.bin", shares[0].signed_proof.decomp_proof());
+    write(&dir, "decrypted_share.bin", &decrypted);
+}
@@ -0,0 +1,309 @@
+// C interface for verifying Optrand transcripts and shares over BLS12-381, e.g., from a
+// consensus client written in another language.
+//
+// Every function returns OPTRAND_OK (0) on success, a positive PVSSErrorCode if verification
+// (or decoding of the inputs) fails, or one of the negative OPTRAND_ERR_* codes below. Panics
+// never cross the boundary: they are caught and reported as OPTRAND_ERR_PANIC.
+//
+// Ownership: input buffers are only borrowed for the duration of the call, and remain owned by
+// the caller. Buffers returned by this library (see optrand_error_message) are owned by the
+// caller, and must be released with optrand_free, passing back the length that was returned
+// alongside them; they must not be released with the caller's own allocator.
+
+use crate::modified_scrape::{
+    checked::CountingReader,
+    config::Config,
+    errors::{PVSSError, PVSSErrorCode},
+    participant::ParticipantRegistry,
+    roles::OptrandScheme,
+    share::PVSSShare,
+    verifier::{verify_epoch, EpochTranscript},
+};
+
+use ark_bls12_381::Bls12_381 as E;
+
+use ark_std::convert::TryFrom;
+use std::{panic, ptr, slice};
+
+use rand::thread_rng;
+
+
+// Success.
+pub const OPTRAND_OK: i32 = 0;
+
+// A required pointer argument was null.
+pub const OPTRAND_ERR_NULL_POINTER: i32 = -1;
+
+// The library panicked; this indicates a bug, not an invalid input.
+pub const OPTRAND_ERR_PANIC: i32 = -2;
+
+
+// Utility function for borrowing a caller-owned buffer; a null pointer is only accepted
+// along with a zero length.
+unsafe fn borrow<'a>(data: *const u8, len: usize) -> Option<&'a [u8]> {
+    if len == 0 {
+        Some(&[])
+    } else if data.is_null() {
+        None
+    } else {
+        Some(slice::from_raw_parts(data, len))
+    }
+}
+
+
+// Utility function for running f, mapping its outcome to a return code, and keeping any panic
+// from unwinding into the caller.
+fn guard<F: FnOnce() -> Result<(), PVSSError<E>>>(f: F) -> i32 {
+    match panic::catch_unwind(panic::AssertUnwindSafe(f)) {
+        Ok(Ok(())) => OPTRAND_OK,
+        Ok(Err(e)) => i32::from(e.code()),
+        Err(_) => OPTRAND_ERR_PANIC,
+    }
+}
+
+
+// Function for decoding the verification setup, i.e., a serialized Config followed by the
+// serialized ParticipantRegistry of the committee.
+fn read_setup(bytes: &[u8]) -> Result<(Config<E>, ParticipantRegistry<E>), PVSSError<E>> {
+    let mut reader = CountingReader::new(bytes);
+    let config = reader.read_field("config")?;
+    let registry = reader.read_field("registry")?;
+
+    Ok((config, registry))
+}
+
+
+/// Verifies a serialized EpochTranscript against the setup in config_bytes (see read_setup),
+/// as verify_epoch does. On success, the beacon output is written to out_beacon32; it is left
+/// untouched otherwise.
+///
+/// # Safety
+///
+/// transcript_bytes and config_bytes must each be null with a zero length, or point to
+/// transcript_len (resp. config_len) readable bytes. out_beacon32 must point to 32 writable
+/// bytes not overlapping the inputs. All buffers stay owned by the caller, and are only
+/// borrowed for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn optrand_verify_transcript(
+    transcript_bytes: *const u8,
+    transcript_len: usize,
+    config_bytes: *const u8,
+    config_len: usize,
+    out_beacon32: *mut u8,
+) -> i32 {
+    let (transcript_bytes, config_bytes) = match (borrow(transcript_bytes, transcript_len), borrow(config_bytes, config_len)) {
+        (Some(transcript_bytes), Some(config_bytes)) if !out_beacon32.is_null() => (transcript_bytes, config_bytes),
+        _ => return OPTRAND_ERR_NULL_POINTER,
+    };
+
+    guard(|| {
+        let (config, registry) = read_setup(config_bytes)?;
+        let transcript: EpochTranscript<E> = CountingReader::new(transcript_bytes).read_field("transcript")?;
+
+        let beacon = verify_epoch(&mut thread_rng(), &config, &registry, &transcript)?;
+        ptr::copy_nonoverlapping(beacon.as_ptr(), out_beacon32, beacon.len());
+
+        Ok(())
+    })
+}
+
+
+/// Verifies a serialized PVSSShare against the setup in config_bytes (see read_setup), with its
+/// decomposition proof bound to the given context bytes (e.g., chain_context of the epoch the
+/// share was dealt in; an empty context is passed as a zero length).
+///
+/// # Safety
+///
+/// share_bytes, config_bytes and context must each be null with a zero length, or point to
+/// share_len (resp. config_len, context_len) readable bytes. All buffers stay owned by the
+/// caller, and are only borrowed for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn optrand_verify_share(
+    share_bytes: *const u8,
+    share_len: usize,
+    config_bytes: *const u8,
+    config_len: usize,
+    context: *const u8,
+    context_len: usize,
+) -> i32 {
+    let (share_bytes, config_bytes, context) = match (
+        borrow(share_bytes, share_len),
+        borrow(config_bytes, config_len),
+        borrow(context, context_len),
+    ) {
+        (Some(share_bytes), Some(config_bytes), Some(context)) => (share_bytes, config_bytes, context),
+        _ => return OPTRAND_ERR_NULL_POINTER,
+    };
+
+    guard(|| {
+        let (config, registry) = read_setup(config_bytes)?;
+        let share: PVSSShare<E> = CountingReader::new(share_bytes).read_field("share")?;
+
+        OptrandScheme::new(config)
+            .aggregator(&registry)
+            .share_verify_with_context(&mut thread_rng(), &share, context)
+    })
+}
+
+
+/// Returns a description of a return code of this library, as a buffer of out_len UTF-8 bytes
+/// (without a trailing NUL). Returns null if out_len is null.
+///
+/// # Safety
+///
+/// out_len must be null or point to a writable usize. The returned buffer is owned by the
+/// caller, and must be released exactly once with optrand_free, passing back the length written
+/// to out_len.
+#[no_mangle]
+pub unsafe extern "C" fn optrand_error_message(code: i32, out_len: *mut usize) -> *mut u8 {
+    if out_len.is_null() {
+        return ptr::null_mut();
+    }
+
+    let message = match code {
+        OPTRAND_OK => "ok".to_string(),
+        OPTRAND_ERR_NULL_POINTER => "null pointer argument".to_string(),
+        OPTRAND_ERR_PANIC => "internal panic".to_string(),
+        code => match u16::try_from(code).ok().and_then(|code| PVSSErrorCode::try_from(code).ok()) {
+            Some(code) => format!("{:?}", code),
+            None => format!("unknown code {}", code),
+        },
+    };
+
+    let buf = message.into_bytes().into_boxed_slice();
+    *out_len = buf.len();
+    Box::into_raw(buf) as *mut u8
+}
+
+
+/// Releases a buffer returned by this library, given the length returned along with it.
+/// Passing null is a no-op.
+///
+/// # Safety
+///
+/// data must be null, or a buffer returned by this library (see optrand_error_message) that has
+/// not been released yet, and len must be the length returned along with it. The buffer must not
+/// be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn optrand_free(data: *mut u8, len: usize) {
+    if !data.is_null() {
+        drop(Box::from_raw(slice::from_raw_parts_mut(data, len) as *mut [u8]));
+    }
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{
+        optrand_error_message, optrand_free, optrand_verify_share, optrand_verify_transcript,
+        OPTRAND_ERR_NULL_POINTER, OPTRAND_OK,
+    };
+    use crate::{
+        modified_scrape::{
+            decryption::decrypt_with_proof,
+            epoch::EpochState,
+            errors::PVSSErrorCode,
+            recon::{beacon, reconstruct},
+        },
+        testing::fixtures::FixtureBuilder,
+    };
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_serialize::CanonicalSerialize;
+
+    use rand::thread_rng;
+
+    use std::{collections::BTreeMap, ptr, slice};
+
+    // The C ABI signatures of the exported functions.
+    type VerifyTranscriptFn = unsafe extern "C" fn(*const u8, usize, *const u8, usize, *mut u8) -> i32;
+    type VerifyShareFn = unsafe extern "C" fn(*const u8, usize, *const u8, usize, *const u8, usize) -> i32;
+    type ErrorMessageFn = unsafe extern "C" fn(i32, *mut usize) -> *mut u8;
+    type FreeFn = unsafe extern "C" fn(*mut u8, usize);
+
+    fn serialize<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+        let mut bytes = vec![];
+        value.serialize(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_ffi_verify() {
+        let verify_transcript: VerifyTranscriptFn = optrand_verify_transcript;
+        let verify_share: VerifyShareFn = optrand_verify_share;
+        let error_message: ErrorMessageFn = optrand_error_message;
+        let free: FreeFn = optrand_free;
+
+        let rng = &mut thread_rng();
+        let (t, n) = (1, 4);
+
+        // The committee's dealings are bound to the epoch, so only its keys are used.
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let (conf, registry) = (fixture.config, fixture.registry);
+        let setup = [serialize(&conf), serialize(&registry)].concat();
+
+        // Everyone deals; every share verifies through the C interface before being aggregated.
+        let mut state = EpochState::new(&conf, [0u8; 32]);
+        let context = state.context();
+        for (id, keys) in fixture.keypairs.iter().enumerate() {
+            let (share, _) = state.deal(rng, &conf, &registry, id, &keys.secret_key_ed).unwrap();
+            let share_bytes = serialize(&share);
+
+            let code = unsafe {
+                verify_share(share_bytes.as_ptr(), share_bytes.len(), setup.as_ptr(), setup.len(), context.as_ptr(), context.len())
+            };
+            assert_eq!(code, OPTRAND_OK);
+
+            // Without the context, the decomposition proof does not verify.
+            let code = unsafe { verify_share(share_bytes.as_ptr(), share_bytes.len(), setup.as_ptr(), setup.len(), ptr::null(), 0) };
+            assert_eq!(code, PVSSErrorCode::NIZKProofDoesNotVerify as i32);
+
+            state.receive_share(rng, &conf, &registry, &share).unwrap();
+        }
+
+        let decryptions = fixture
+            .keypairs
+            .iter()
+            .enumerate()
+            .map(|(id, keys)| (id, decrypt_with_proof(rng, &conf, &state.aggregate.pvss_core.encs[id], &keys.secret_key).unwrap()))
+            .collect::<BTreeMap<_, _>>();
+        let shares = decryptions.iter().map(|(id, (dec, _))| (*id, *dec)).collect();
+        let value = beacon(&conf, &reconstruct(&shares, &conf).unwrap()).unwrap();
+        let mut transcript = state.transcript(decryptions, value);
+
+        // An honest transcript verifies, and its beacon output is written out.
+        let bytes = serialize(&transcript);
+        let mut out = [0u8; 32];
+        let code = unsafe { verify_transcript(bytes.as_ptr(), bytes.len(), setup.as_ptr(), setup.len(), out.as_mut_ptr()) };
+        assert_eq!(code, OPTRAND_OK);
+        assert_eq!(out, value);
+
+        // A wrong beacon output is reported through its error code, and out is left untouched.
+        transcript.beacon = [1u8; 32];
+        let bytes = serialize(&transcript);
+        let mut out = [0u8; 32];
+        let code = unsafe { verify_transcript(bytes.as_ptr(), bytes.len(), setup.as_ptr(), setup.len(), out.as_mut_ptr()) };
+        assert_eq!(code, PVSSErrorCode::BeaconMismatchError as i32);
+        assert_eq!(out, [0u8; 32]);
+
+        // Truncated inputs fail to decode, and null pointers are rejected.
+        let code = unsafe { verify_transcript(bytes.as_ptr(), bytes.len() / 2, setup.as_ptr(), setup.len(), out.as_mut_ptr()) };
+        assert_eq!(code, PVSSErrorCode::DeserializationFailed as i32);
+        let code = unsafe { verify_transcript(ptr::null(), bytes.len(), setup.as_ptr(), setup.len(), out.as_mut_ptr()) };
+        assert_eq!(code, OPTRAND_ERR_NULL_POINTER);
+        let code = unsafe { verify_transcript(bytes.as_ptr(), bytes.len(), setup.as_ptr(), setup.len(), ptr::null_mut()) };
+        assert_eq!(code, OPTRAND_ERR_NULL_POINTER);
+
+        // Error messages are returned in library-owned buffers, released with optrand_free.
+        let mut len = 0usize;
+        let message = unsafe { error_message(PVSSErrorCode::BeaconMismatchError as i32, &mut len) };
+        assert_eq!(unsafe { slice::from_raw_parts(message, len) }, b"BeaconMismatchError");
+        unsafe { free(message, len) };
+
+        assert!(unsafe { error_message(OPTRAND_OK, ptr::null_mut()) }.is_null());
+        unsafe { free(ptr::null_mut(), 0) };
+    }
+}
@@ -1,23 +1,44 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
 #[macro_use]
 extern crate ark_std;
 
+#[cfg(not(feature = "std"))]
+#[macro_use]
+extern crate alloc;
+
 pub mod modified_scrape;
 pub mod signature;
 pub mod nizk;
+pub mod prelude;
+
+#[cfg(all(feature = "std", feature = "cffi"))]
+pub mod ffi;
+
+#[cfg(all(feature = "std", any(test, feature = "testing")))]
+pub mod testing;
 
 use ark_poly::univariate::DensePolynomial;
 use ark_ec::PairingEngine;
+use ark_ff::{PrimeField, Zero};
+
+use modified_scrape::{
+    decomp::{Blake2sDigestHasher, DigestHasher},
+    errors::PVSSError,
+};
 
 // EdDSA imports
 
 use ed25519_dalek as dalek;
 use ed25519_dalek::ed25519;
 use ed25519_dalek::Signer as _;
+#[cfg(feature = "std")]
 use rand::rngs::OsRng;
 use rand::{CryptoRng, RngCore};
-use std::array::TryFromSliceError;
-use std::convert::{TryFrom, TryInto};
-use std::fmt;
+use ark_std::array::TryFromSliceError;
+use ark_std::convert::{TryFrom, TryInto};
+use ark_std::fmt;
+use ark_std::{string::String, vec::Vec};
 use ark_serialize::{CanonicalSerialize, CanonicalDeserialize, SerializationError, Read, Write};
 
 ///////////////////////////////////////////////////////////////////
@@ -30,6 +51,44 @@ pub type Scalar<E> = <E as PairingEngine>::Fr;
 /// A polynomial with the various coefficients in the Scalar Group
 pub type Polynomial<E> = DensePolynomial<Scalar<E>>;
 
+// Enumeration Endianness selects the byte order of scalar encodings. Arkworks serializes
+// little-endian, whereas many other implementations encode field elements big-endian.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+// Function for decoding a scalar from its canonical encoding in the given byte order: the input
+// must have the exact length of an encoded scalar, and represent an integer below the field modulus.
+pub fn scalar_from_bytes<E: PairingEngine>(bytes: &[u8], endianness: Endianness) -> Result<Scalar<E>, PVSSError<E>> {
+    if bytes.len() != Scalar::<E>::zero().into_repr().serialized_size() {
+        return Err(PVSSError::NonCanonicalScalar);
+    }
+
+    let mut little_endian = bytes.to_vec();
+    if endianness == Endianness::Big {
+        little_endian.reverse();
+    }
+
+    let repr = <Scalar<E> as PrimeField>::BigInt::deserialize(&little_endian[..]).map_err(|_| PVSSError::NonCanonicalScalar)?;
+
+    Scalar::<E>::from_repr(repr).ok_or(PVSSError::NonCanonicalScalar)
+}
+
+// Function for encoding a scalar canonically, i.e., as the bytes of its reduced representation
+// in the given byte order.
+pub fn scalar_to_bytes<E: PairingEngine>(scalar: &Scalar<E>, endianness: Endianness) -> Vec<u8> {
+    let mut bytes = vec![];
+    scalar.into_repr().serialize(&mut bytes).unwrap();
+
+    if endianness == Endianness::Big {
+        bytes.reverse();
+    }
+
+    bytes
+}
+
 ///////////////////////////////////////////////////////////////////
 
 
@@ -51,6 +110,42 @@ impl Digest {
     pub fn size(&self) -> usize {
         self.0.len()
     }
+
+    // Method returning the digest in lowercase hex.
+    pub fn to_hex(&self) -> String {
+        encode_hex(&self.0)
+    }
+}
+
+// Function encoding bytes in lowercase hex.
+pub fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Function decoding hex (in either case); returns None on odd lengths or non-hex characters.
+pub fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+// Number of hex characters kept in a fingerprint.
+const FINGERPRINT_LEN: usize = 16;
+
+// Function returning a short fingerprint of a serializable value, for logs and Debug output:
+// the leading hex characters of the BLAKE2s digest of its serialization.
+pub fn fingerprint<T: CanonicalSerialize>(value: &T) -> String {
+    let mut bytes = vec![];
+    value.serialize(&mut bytes).unwrap();
+
+    let mut hex = Digest(Blake2sDigestHasher::hash(&bytes)).to_hex();
+    hex.truncate(FINGERPRINT_LEN);
+    hex
 }
 
 impl fmt::Debug for Digest {
@@ -100,13 +195,18 @@ impl CanonicalSerialize for Digest {
 impl CanonicalDeserialize for Digest {
     #[inline]
     fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
-        let result = Digest( core::array::from_fn(|_| {
-            u8::deserialize(&mut reader).unwrap()
-        }) );
-        Ok(result)
+        Ok(Digest(read_array(&mut reader)?))
     }
 }
 
+// Utility function for reading a fixed-size byte array; fails (instead of panicking)
+// if the reader runs out of bytes.
+fn read_array<R: Read, const N: usize>(mut reader: R) -> Result<[u8; N], SerializationError> {
+    let mut bytes = [0u8; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
 pub trait Hash {
     fn digest(&self) -> Digest;
 }
@@ -153,10 +253,7 @@ impl CanonicalSerialize for PublicKey {
 impl CanonicalDeserialize for PublicKey {
     #[inline]
     fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
-        let result = PublicKey( core::array::from_fn(|_| {
-            u8::deserialize(&mut reader).unwrap()
-        }) );
-        Ok(result)
+        Ok(PublicKey(read_array(&mut reader)?))
     }
 }
 
@@ -221,13 +318,11 @@ impl CanonicalSerialize for SecretKey {
 impl CanonicalDeserialize for SecretKey {
     #[inline]
     fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
-        let result = SecretKey( core::array::from_fn(|_| {
-            u8::deserialize(&mut reader).unwrap()
-        }) );
-        Ok(result)
+        Ok(SecretKey(read_array(&mut reader)?))
     }
 }
 
+#[cfg(feature = "std")]
 pub fn generate_production_keypair() -> (PublicKey, SecretKey) {
     generate_keypair(&mut OsRng)
 }
@@ -242,6 +337,27 @@ where
     (public, secret)
 }
 
+// Utility function verifying each signature on the message at the same index under the key at
+// the same index, as a single batch.
+#[cfg(feature = "std")]
+fn verify_all(messages: &[&[u8]], signatures: &[dalek::Signature], keys: &[dalek::PublicKey]) -> Result<(), CryptoError> {
+    dalek::verify_batch(messages, signatures, keys)
+}
+
+// Without std, batch verification is unavailable, and the signatures are verified one by one.
+#[cfg(not(feature = "std"))]
+fn verify_all(messages: &[&[u8]], signatures: &[dalek::Signature], keys: &[dalek::PublicKey]) -> Result<(), CryptoError> {
+    if messages.len() != signatures.len() || messages.len() != keys.len() {
+        return Err(CryptoError::new());
+    }
+
+    messages
+        .iter()
+        .zip(signatures.iter())
+        .zip(keys.iter())
+        .try_for_each(|((message, signature), key)| key.verify_strict(message, signature))
+}
+
 /* Struct representing an EdDSA signature. */
 
 #[derive(Clone, Default, Debug, Copy, PartialEq)]
@@ -284,7 +400,23 @@ impl Signature {
             signatures.push(ed25519::signature::Signature::from_bytes(&sig.flatten())?);
             keys.push(dalek::PublicKey::from_bytes(&key.0)?);
         }
-        dalek::verify_batch(&messages[..], &signatures[..], &keys[..])
+        verify_all(&messages[..], &signatures[..], &keys[..])
+    }
+
+    // Batch verification of signatures over (possibly) different digests.
+    pub fn verify_batch_digests<'a, I>(items: I) -> Result<(), CryptoError>
+    where
+        I: IntoIterator<Item = (&'a Digest, &'a PublicKey, &'a Signature)>,
+    {
+        let mut messages: Vec<&[u8]> = Vec::new();
+        let mut signatures: Vec<dalek::Signature> = Vec::new();
+        let mut keys: Vec<dalek::PublicKey> = Vec::new();
+        for (digest, key, sig) in items.into_iter() {
+            messages.push(&digest.0[..]);
+            signatures.push(ed25519::signature::Signature::from_bytes(&sig.flatten())?);
+            keys.push(dalek::PublicKey::from_bytes(&key.0)?);
+        }
+        verify_all(&messages[..], &signatures[..], &keys[..])
     }
 
 
@@ -322,9 +454,7 @@ impl CanonicalSerialize for Signature {
 impl CanonicalDeserialize for Signature {
     #[inline]
     fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
-        let result: [u8; 64 as usize] = core::array::from_fn(|_| {
-            u8::deserialize(&mut reader).unwrap()
-        });
+        let result: [u8; 64 as usize] = read_array(&mut reader)?;
         let pt1 = result[..32].try_into().expect("Unexpected signature length");
         let pt2 = result[32..64].try_into().expect("Unexpected signature length");
         Ok(Signature {part1: pt1, part2: pt2} )
@@ -356,3 +486,92 @@ pub type CommitmentP<E> = <E as PairingEngine>::G1Projective;
 // The Encryption group is the same as the public key group
 // Which is G1 for type 3 pairings
 //pub type Encryptions<E> = PublicKey<E>;
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{decode_hex, encode_hex, scalar_from_bytes, scalar_to_bytes, Endianness, Scalar};
+    use crate::modified_scrape::errors::PVSSError;
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ff::{Field, FpParameters, One, PrimeField, Zero};
+    use ark_serialize::CanonicalSerialize;
+    use ark_std::UniformRand;
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_scalar_bytes() {
+        let rng = &mut thread_rng();
+
+        // Valid scalars round-trip, and are encoded as their serialization.
+        for scalar in [Scalar::<E>::zero(), Scalar::<E>::one(), -Scalar::<E>::one(), Scalar::<E>::rand(rng)].iter() {
+            let bytes = scalar_to_bytes::<E>(scalar, Endianness::Little);
+            assert_eq!(scalar_from_bytes::<E>(&bytes, Endianness::Little).unwrap(), *scalar);
+
+            let mut serialized = vec![];
+            scalar.serialize(&mut serialized).unwrap();
+            assert_eq!(bytes, serialized);
+        }
+
+        // An overflowing encoding is rejected, and so is the modulus itself.
+        assert!(matches!(scalar_from_bytes::<E>(&[0xFF; 32], Endianness::Little), Err(PVSSError::NonCanonicalScalar)));
+
+        let mut modulus = vec![];
+        <Scalar<E> as PrimeField>::Params::MODULUS.serialize(&mut modulus).unwrap();
+        assert!(matches!(scalar_from_bytes::<E>(&modulus, Endianness::Little), Err(PVSSError::NonCanonicalScalar)));
+
+        // Encodings of the wrong length are rejected.
+        let bytes = scalar_to_bytes::<E>(&Scalar::<E>::one(), Endianness::Little);
+        assert!(matches!(scalar_from_bytes::<E>(&bytes[..31], Endianness::Little), Err(PVSSError::NonCanonicalScalar)));
+        assert!(matches!(scalar_from_bytes::<E>(&[bytes, vec![0]].concat(), Endianness::Little), Err(PVSSError::NonCanonicalScalar)));
+    }
+
+    #[test]
+    fn test_scalar_bytes_endianness() {
+        let rng = &mut thread_rng();
+
+        // Both encodings round-trip, and are byte-reverses of each other.
+        for scalar in [Scalar::<E>::zero(), Scalar::<E>::one(), -Scalar::<E>::one(), Scalar::<E>::rand(rng)].iter() {
+            let little = scalar_to_bytes::<E>(scalar, Endianness::Little);
+            let big = scalar_to_bytes::<E>(scalar, Endianness::Big);
+            assert_eq!(big, little.iter().rev().cloned().collect::<Vec<_>>());
+
+            assert_eq!(scalar_from_bytes::<E>(&little, Endianness::Little).unwrap(), *scalar);
+            assert_eq!(scalar_from_bytes::<E>(&big, Endianness::Big).unwrap(), *scalar);
+        }
+
+        // One is encoded as 0x00...01 big-endian.
+        let one = scalar_to_bytes::<E>(&Scalar::<E>::one(), Endianness::Big);
+        assert!(one[..31].iter().all(|&b| b == 0) && one[31] == 1);
+
+        // Canonicalness is checked in both orders: the modulus is rejected either way.
+        let mut modulus = vec![];
+        <Scalar<E> as PrimeField>::Params::MODULUS.serialize(&mut modulus).unwrap();
+        assert!(matches!(scalar_from_bytes::<E>(&modulus, Endianness::Little), Err(PVSSError::NonCanonicalScalar)));
+        modulus.reverse();
+        assert!(matches!(scalar_from_bytes::<E>(&modulus, Endianness::Big), Err(PVSSError::NonCanonicalScalar)));
+
+        // Decoding in the wrong order yields another scalar (2^248 for one), or fails.
+        let scalar = Scalar::<E>::rand(rng);
+        let big = scalar_to_bytes::<E>(&scalar, Endianness::Big);
+        assert!(scalar_from_bytes::<E>(&big, Endianness::Little).map_or(true, |s| s != scalar));
+        assert_eq!(scalar_from_bytes::<E>(&one, Endianness::Little).unwrap(), Scalar::<E>::from(2u64).pow([248u64]));
+    }
+
+    #[test]
+    fn test_hex() {
+        let bytes = (0..=255u8).collect::<Vec<_>>();
+        assert_eq!(decode_hex(&encode_hex(&bytes)).unwrap(), bytes);
+        assert_eq!(encode_hex(&[0x00, 0xab, 0x7f]), "00ab7f");
+        assert_eq!(decode_hex("00AB7f").unwrap(), vec![0x00, 0xab, 0x7f]);
+
+        // Odd lengths, signs and non-hex characters are rejected.
+        for hex in ["abc", "+f", "0g", "é0"].iter() {
+            assert!(decode_hex(hex).is_none());
+        }
+    }
+}
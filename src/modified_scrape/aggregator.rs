@@ -1,34 +1,28 @@
 use crate::{
-    Digest,
     modified_scrape::{
 	config::Config,
-        decomp::DecompProof,
+        decomp::{DecompProof, ProofGroup},
         errors::PVSSError,
-        participant::Participant,
+        participant::{Participant, ParticipantId, ParticipantRegistry},
         poly::{ensure_degree, lagrange_interpolation_simple},   // poly::Polynomial, lagrange_interpolation
         pvss::PVSSCore,
         share::{PVSSAggregatedShare, PVSSShare},
     },
+    nizk::dlk::{DLKProof, srs::SRS as DLKSRS},
     PublicKey,
     Signature,
     signature::scheme::BatchVerifiableSignatureScheme,
 };
 
-use ark_ec::{PairingEngine, ProjectiveCurve};   // msm::VariableBaseMSM, AffineCurve
+use ark_ec::{PairingEngine, ProjectiveCurve};   // msm::VariableBaseMSM
 use ark_ff::{One, Zero};
 use ark_std::{
     collections::BTreeMap,
     //ops::AddAssign,
+    vec::Vec,
 };
 
-use rand::Rng;
-use std::{
-    collections::hash_map::DefaultHasher,
-    hash::{Hash, Hasher},
-    iter::Zip,
-    ops::Neg,
-    slice::Iter,
-};
+use rand::{CryptoRng, Rng};
 
 
 /* A PVSSAggregator is responsible for receiving PVSS shares, verifying them, and
@@ -39,10 +33,10 @@ where
     //<E as PairingEngine>::G2Affine: AddAssign,
     SSIG: BatchVerifiableSignatureScheme<PublicKey = E::G1Affine, Secret = E::Fr>,
 {
-    pub config: Config<E>,                                     // the "global" configuration parameters
-    pub scheme_sig: SSIG,                                      // scheme for encryption
-    pub participants: BTreeMap<usize, Participant<E, SSIG>>,   // maps ids to Participant instances (incl. their pks)
-    pub aggregated_tx: PVSSAggregatedShare<E>,                 // aggregated transcript of PVSS shares
+    pub config: Config<E>,                                             // the "global" configuration parameters
+    pub scheme_sig: SSIG,                                              // scheme for encryption
+    pub participants: BTreeMap<ParticipantId, Participant<E, SSIG>>,   // maps ids to Participant instances (incl. their pks)
+    pub aggregated_tx: PVSSAggregatedShare<E>,                         // aggregated transcript of PVSS shares
 }
 
 
@@ -53,12 +47,25 @@ where
     SSIG: BatchVerifiableSignatureScheme<PublicKey = E::G1Affine, Secret = E::Fr>,
 {
     // Utility method for verifying individual "core" PVSS shares.
-    pub fn core_verify<R: Rng>(
+    pub fn core_verify<R: Rng + CryptoRng>(
         &self,
         rng: &mut R,
 	decomp_proof: &DecompProof<E>,   // need to pass on separately since cores do not have decomps attached
         core: &PVSSCore<E>,
     ) -> Result<(), PVSSError<E>> {
+        self.core_verify_with_context(rng, decomp_proof, core, &[])
+    }
+
+
+    // Utility method for verifying individual "core" PVSS shares, whose decomposition proof
+    // is bound to some context bytes.
+    pub fn core_verify_with_context<R: Rng + CryptoRng>(
+        &self,
+        rng: &mut R,
+	decomp_proof: &DecompProof<E>,
+        core: &PVSSCore<E>,
+        context: &[u8],
+    ) -> Result<(), PVSSError<E>> {
 
 	// Check that the sizes of commitments and encryptions are correct.
 	if core.encs.len() != self.config.num_participants ||
@@ -85,7 +92,7 @@ where
 	}
 
 	// Verify decomposition proof against our config.
-        if decomp_proof.verify(&self.config).is_err() {
+        if decomp_proof.verify_with_context(&self.config, context).is_err() {
 	    return Err(PVSSError::DecompProofVerificationError);
 	}
 
@@ -95,10 +102,22 @@ where
 
     // Method for verifying a received PVSSShare instance.
     // Essentially performs the checks from "verify_sharing".
-    pub fn share_verify<R: Rng>(
+    pub fn share_verify<R: Rng + CryptoRng>(
+        &mut self,
+        rng: &mut R,
+        share: &PVSSShare<E>,
+    ) -> Result<(), PVSSError<E>> {
+        self.share_verify_with_context(rng, share, &[])
+    }
+
+
+    // Method for verifying a received PVSSShare instance, whose decomposition proof is bound
+    // to some context bytes.
+    pub fn share_verify_with_context<R: Rng + CryptoRng>(
         &mut self,
         rng: &mut R,
-        share: &mut PVSSShare<E>,
+        share: &PVSSShare<E>,
+        context: &[u8],
     ) -> Result<(), PVSSError<E>> {
 
         // Retrieve the Participant instance using the id within the augmented share.
@@ -107,26 +126,42 @@ where
         let participant = self
             .participants
             .get(&participant_id)
-            .ok_or(PVSSError::<E>::InvalidParticipantId(participant_id))?;
-
-	// Verify correctness of encryption: e(pk_i, v_i) = e(enc_i, g_2).
-	let pairs = [
-            (participant.public_key_sig.into(), share.pvss_core.comms[participant_id].into_affine().into()),
-            (share.pvss_core.encs[participant_id].neg().into_affine().into(), self.config.srs.g2.into()),
-        ];
+            .ok_or(PVSSError::<E>::InvalidParticipantId(participant_id.index()))?;
+        let i = participant_id.index();
+
+	// Check the sizes of the core before indexing into it.
+	if share.pvss_core.encs.len() != self.config.num_participants ||
+           share.pvss_core.comms.len() != self.config.num_participants {
+	        return Err(PVSSError::MismatchedCommitsEncryptionsParticipantsError(share.pvss_core.encs.len(),
+			    share.pvss_core.comms.len(), self.config.num_participants));
+	}
 
-        if !E::product_of_pairings(pairs.iter()).is_one() {
-            return Err(PVSSError::EncryptionCorrectnessError);
-        }
+	// Check that the share was dealt under our configuration, SRS included.
+	if share.config_digest != self.config.digest() {
+	    return Err(PVSSError::ConfigMismatch);
+	}
 
 	// Verify the "core" PVSS share against the provided decomposition proof.
-	self.core_verify(rng, &share.signed_proof.decomp_proof, &share.pvss_core)?;
+	self.core_verify_with_context(rng, share.signed_proof.decomp_proof(), &share.pvss_core, context)
+	    .map_err(|e| match e {
+		PVSSError::DecompProofVerificationError => PVSSError::NIZKProofDoesNotVerify { participant_id: i },
+		e => e,
+	    })?;
+
+	// Verify correctness of encryption: e(pk_j, v_j) = e(enc_j, g_2) for every j, batched.
+	// All the slots are checked, not only the dealer's: otherwise, a share encrypted to the
+	// wrong keys would be accepted, and only break the aggregated transcript's check.
+	let public_keys = self.public_keys()?;
+
+	if !share.pvss_core.verify_encryptions_batched(rng, &self.config, &public_keys) {
+	    return Err(PVSSError::EncryptionCorrectnessError);
+	}
 
         // Verify signature on decomposition proof against participant i's public key:
-	let digest = share.signed_proof.decomp_proof.digest();
+	let digest = share.signed_proof.digest();
 
-	if share.signed_proof.signature_on_decomp.verify(&digest, &participant.public_key_ed).is_err() {
-	    return Err(PVSSError::EdDSAInvalidSignatureError);
+	if share.signed_proof.signature_on_decomp.verify(digest, &participant.public_key_ed).is_err() {
+	    return Err(PVSSError::SignatureVerificationFailed { participant_id: i });
 	}
 
         Ok(())
@@ -135,12 +170,29 @@ where
 
     // Method for verifying aggregation in a PVSSAggregatedShare instance.
     // Essentially performs the checks from "verify_aggregation".
-    pub fn aggregation_verify<R: Rng>(
+    pub fn aggregation_verify<R: Rng + CryptoRng>(
+        &mut self,
+        rng: &mut R,
+        agg_share: &PVSSAggregatedShare<E>,
+    ) -> Result<(), PVSSError<E>> {
+        self.aggregation_verify_with_context(rng, agg_share, &[])
+    }
+
+
+    // Method for verifying aggregation in a PVSSAggregatedShare instance, whose contributions'
+    // decomposition proofs are bound to some context bytes.
+    pub fn aggregation_verify_with_context<R: Rng + CryptoRng>(
         &mut self,
         rng: &mut R,
         agg_share: &PVSSAggregatedShare<E>,
+        context: &[u8],
     ) -> Result<(), PVSSError<E>> {
 
+        // An aggregate without contributions (e.g., a fresh empty one) is never valid.
+        if agg_share.contributions.is_empty() {
+            return Err(PVSSError::EmptyTranscript);
+        }
+
         // Check that the sizes of commitments and encryptions are correct.
 	if agg_share.pvss_core.encs.len() != self.config.num_participants ||
            agg_share.pvss_core.comms.len() != self.config.num_participants {
@@ -150,94 +202,119 @@ where
                             self.config.num_participants));
 	}
 
+	// Check that the contributions were dealt under our configuration, SRS included.
+	if agg_share.config_digest != self.config.digest() {
+	    return Err(PVSSError::ConfigMismatch);
+	}
+
         // if agg_share.contributions.len() < self.config.degree {}
 
+	// The aggregated core is kept in affine form; the checks below work on projective points.
+	let pvss_core = agg_share.pvss_core.to_projective();
+
 	// Coding check for the commitments to ensure that they represent a
 	// commitment to a degree t polynomial.
-	if ensure_degree::<E, _>(rng, &agg_share.pvss_core.comms, self.config.degree as u64).is_err() {
+	if ensure_degree::<E, _>(rng, &pvss_core.comms, self.config.degree as u64).is_err() {
             return Err(PVSSError::DualCodeError);
         }
 	
-	// Pairing check: e(pk_i, com_i) = e(enc_i, g2).
-
-	let correct_encryptions = (0..self.config.num_participants)
-	        .all(|i| { let pairs = [
-            	    (self.participants.get(&i).unwrap().public_key_sig.into(), agg_share.pvss_core.comms[i].into_affine().into()),
-            	    (agg_share.pvss_core.encs[i].neg().into_affine().into(), self.config.srs.g2.into()),
-        	];
-
-		E::product_of_pairings(pairs.iter()).is_one()
-	    }
-	);
+	// Pairing check: e(pk_i, com_i) = e(enc_i, g2), batched over all i.
+	let public_keys = self.public_keys()?;
 
-	if !correct_encryptions {
+	if !pvss_core.verify_encryptions_batched(rng, &self.config, &public_keys) {
 	    return Err(PVSSError::EncryptionCorrectnessError);
 	}
 
 	// Decomposition proof check:
 
-	let point = lagrange_interpolation_simple::<E>(&agg_share.pvss_core.comms, self.config.degree as u64).unwrap();   // E::G2Projective
+	let point = lagrange_interpolation_simple::<E>(&pvss_core.comms, self.config.degree as u64).unwrap();   // E::G2Projective
 
 	//let mut gs_total = E::G2Affine::zero();
         let mut gs_total = E::G2Projective::zero();
 
 	// Contributions are essentially signed decomposition proofs.
-	for (_participant_id, contribution) in agg_share.contributions.iter() {
-	    if contribution.decomp_proof.verify(&self.config).is_err() {
-		return Err(PVSSError::DecompositionInTranscriptError);
+	for (participant_id, contribution) in agg_share.contributions.iter() {
+	    if contribution.decomp_proof().verify_with_context(&self.config, context).is_err() {
+		return Err(PVSSError::NIZKProofDoesNotVerify { participant_id: participant_id.index() });
 	    }
 
-            //gs_total += contribution.decomp_proof.gs;
-            gs_total.add_assign_mixed(&contribution.decomp_proof.gs);
+            //gs_total += contribution.decomp_proof().gs;
+            gs_total.add_assign_mixed(&contribution.decomp_proof().gs);
 	}
 
 	if gs_total != point {   // if gs_total != point.into_affine()
 	    return Err(PVSSError::AggregationReconstructionMismatchError);
 	}
 
-	// Batch-verification of signatures:
-	let mut dproofs = Vec::new();
+	// Batch-verification of signatures. Every contribution is signed over its own
+	// decomposition proof, so each signature is checked against that proof's digest
+	// (a single digest over all the proofs matches none of them).
+	let mut digests = Vec::new();
 	let mut pks: Vec<PublicKey> = Vec::new();
-	let mut sigs: Vec<Signature> = Vec::new();
 
 	for (participant_id, contribution) in agg_share.contributions.iter() {
-	    dproofs.push(contribution.decomp_proof.clone());
-	    sigs.push(contribution.signature_on_decomp.clone());
-	    pks.push(self.participants.get(&participant_id).unwrap().public_key_ed.into());
+	    digests.push(contribution.digest());
+	    pks.push(self.participants.get(&participant_id)
+		     .ok_or(PVSSError::<E>::InvalidParticipantId(participant_id.index()))?
+		     .public_key_ed);
 	}
 
-	let mut hasher = DefaultHasher::new();
-	for dproof in dproofs {   // not very elegant
-	    dproof.hash(&mut hasher);
+	let items = agg_share.contributions
+	    .values()
+	    .zip(digests.iter().zip(pks.iter()))
+	    .map(|(contribution, (digest, pk))| (*digest, pk, &contribution.signature_on_decomp));
+
+	if Signature::verify_batch_digests(items).is_err() {
+	    // Pinpoint the offending contribution.
+	    for ((participant_id, contribution), (digest, pk)) in agg_share.contributions.iter().zip(digests.iter().zip(pks.iter())) {
+		if contribution.signature_on_decomp.verify(digest, pk).is_err() {
+		    return Err(PVSSError::SignatureVerificationFailed { participant_id: participant_id.index() });
+		}
+	    }
+	    return Err(PVSSError::EdDSAInvalidSignatureBatchError);
 	}
 
-        let byte_array= hasher.finish().to_ne_bytes();   // TODO: use cryptographically secure hash
-        let mut arr = [0; 32];
-        arr[..byte_array.len()].copy_from_slice(&byte_array);
+        Ok(())
+    }
 
-	let digest = Digest(arr);
-	let votes: Zip<Iter<'_, PublicKey>, Iter<'_, Signature>> = pks.iter().zip(sigs.iter());
-	
-	if Signature::verify_batch(&digest, votes).is_err() {
-		return Err(PVSSError::EdDSAInvalidSignatureBatchError);
-	}
+    // Utility method returning the committee's public (encryption) keys, in id order.
+    fn public_keys(&self) -> Result<Vec<E::G1Affine>, PVSSError<E>> {
+        committee_public_keys(self.config.num_participants, &self.participants).map_err(PVSSError::InvalidParticipantId)
+    }
+
+    // Method returning whether the aggregated transcript has enough contributions
+    // (i.e., at least degree + 1) for the secret to be reconstructible.
+    pub fn is_ready(&self) -> bool {
+        self.ensure_ready().is_ok()
+    }
+
+
+    // Fallible counterpart of is_ready, reporting how many contributions are present.
+    pub fn ensure_ready(&self) -> Result<(), PVSSError<E>> {
+        let threshold = self.config.degree + 1;
+        let got = self.aggregated_tx.contributions.len();
+
+        if got < threshold {
+            return Err(PVSSError::ThresholdNotMet { threshold, got });
+        }
 
         Ok(())
     }
 
+
     // Method for handling a received PVSSShare instance.
     // The share is aggregated into the aggregator's currently aggregated transcript.
-    pub fn receive_share<R: Rng>(
+    pub fn receive_share<R: Rng + CryptoRng>(
         &mut self,
         rng: &mut R,
-        share: &mut PVSSShare<E>,
+        share: &PVSSShare<E>,
     ) -> Result<(), PVSSError<E>> {
 
 	// Verify the PVSS share.
         self.share_verify(rng, share)?;
 
 	// Aggregate the PVSS share into the aggregator's internal aggregated transcript.
-	self.aggregated_tx = self.aggregated_tx.aggregate_pvss_share(&share)?;
+	self.aggregated_tx = self.aggregated_tx.aggregate_pvss_share(share)?;
 
         Ok(())
     }
@@ -245,7 +322,7 @@ where
 
     // Method for handling a received PVSSAggregatedShare instance.
     // The share is aggregated into the aggregator's currently aggregated transcript.
-    pub fn receive_aggregated_share<R: Rng>(
+    pub fn receive_aggregated_share<R: Rng + CryptoRng>(
         &mut self,
         rng: &mut R,
         agg_share: &PVSSAggregatedShare<E>,
@@ -260,4 +337,297 @@ where
         Ok(())
     }
 
+
+    // Method for handling a burst of received PVSSShare instances at once. The shares are
+    // verified together (see verify_shares_batch) and every valid one is aggregated into the
+    // aggregator's transcript. Failures are reported by position in shares.
+    pub fn receive_shares<R: Rng + CryptoRng>(
+        &mut self,
+        rng: &mut R,
+        shares: &[PVSSShare<E>],
+    ) -> Result<(), Vec<(usize, PVSSError<E>)>> {
+
+        let mut failures = verify_shares_batch_with(&self.config, &self.participants, shares, rng)
+            .err()
+            .unwrap_or_default();
+        let rejected = failures.iter().map(|(i, _)| *i).collect::<Vec<_>>();
+
+        for (i, share) in shares.iter().enumerate() {
+            if rejected.contains(&i) {
+                continue;
+            }
+
+            if let Err(e) = self.aggregated_tx.fold_share(share) {
+                failures.push((i, e));
+            }
+        }
+
+        if failures.is_empty() {
+            return Ok(());
+        }
+
+        failures.sort_by_key(|(i, _)| *i);
+        Err(failures)
+    }
+
+}
+
+
+// Utility function returning the public (encryption) keys of a committee of num_participants,
+// in id order. Fails with the first id that has no participant.
+fn committee_public_keys<E, SSIG>(
+    num_participants: usize,
+    participants: &BTreeMap<ParticipantId, Participant<E, SSIG>>,
+) -> Result<Vec<E::G1Affine>, usize>
+where
+    E: PairingEngine,
+    SSIG: BatchVerifiableSignatureScheme<PublicKey = E::G1Affine, Secret = E::Fr>,
+{
+    (0..num_participants)
+        .map(|j| {
+            participants
+                .get(&ParticipantId::from_index(j))
+                .map(|participant| participant.public_key_sig)
+                .ok_or(j)
+        })
+        .collect()
+}
+
+
+// Function for verifying a burst of PVSSShare instances against the committee at once. The
+// shares' EdDSA signatures and decomposition proofs are each batch-verified, and the checks
+// e(pk_j, v_j) = e(enc_j, g2) of every index j of all shares are merged into a single product
+// of pairings.
+// Only when a batch fails are its members checked one by one, so as to report the failures
+// (indexed by position in shares) with the same errors as PVSSAggregator::share_verify.
+// The decomposition proofs must not be bound to any context.
+pub fn verify_shares_batch<E: PairingEngine, R: Rng + CryptoRng>(
+    conf: &Config<E>,
+    registry: &ParticipantRegistry<E>,
+    shares: &[PVSSShare<E>],
+    rng: &mut R,
+) -> Result<(), Vec<(usize, PVSSError<E>)>> {
+    verify_shares_batch_with(conf, &registry.participants, shares, rng)
+}
+
+
+// Same as verify_shares_batch, against a map from ids to participants of any signature scheme.
+fn verify_shares_batch_with<E, SSIG, R>(
+    conf: &Config<E>,
+    participants: &BTreeMap<ParticipantId, Participant<E, SSIG>>,
+    shares: &[PVSSShare<E>],
+    rng: &mut R,
+) -> Result<(), Vec<(usize, PVSSError<E>)>>
+where
+    E: PairingEngine,
+    SSIG: BatchVerifiableSignatureScheme<PublicKey = E::G1Affine, Secret = E::Fr>,
+    R: Rng + CryptoRng,
+{
+    let mut failures = Vec::new();
+    let mut candidates = Vec::with_capacity(shares.len());   // (position, dealer) of shares passing the cheap checks
+    let config_digest = conf.digest();
+
+    // Per-share checks not involving pairings or signatures.
+    for (i, share) in shares.iter().enumerate() {
+        let participant_id = share.participant_id;
+
+        let participant = match participants.get(&participant_id) {
+            Some(participant) => participant,
+            None => {
+                failures.push((i, PVSSError::InvalidParticipantId(participant_id.index())));
+                continue;
+            }
+        };
+
+        let core = &share.pvss_core;
+        if core.encs.len() != conf.num_participants || core.comms.len() != conf.num_participants {
+            failures.push((i, PVSSError::MismatchedCommitsEncryptionsParticipantsError(
+                core.encs.len(), core.comms.len(), conf.num_participants)));
+            continue;
+        }
+
+        if share.config_digest != config_digest {
+            failures.push((i, PVSSError::ConfigMismatch));
+            continue;
+        }
+
+        if ensure_degree::<E, _>(rng, &core.comms, conf.degree as u64).is_err() {
+            failures.push((i, PVSSError::DualCodeError));
+            continue;
+        }
+
+        let point = lagrange_interpolation_simple::<E>(&core.comms, conf.degree as u64).unwrap();
+        if point.into_affine() != share.signed_proof.decomp_proof().gs {
+            failures.push((i, PVSSError::GSCheckError));
+            continue;
+        }
+
+        candidates.push((i, participant));
+    }
+
+    // Decomposition proofs, batch-verified.
+    let dlk = DLKProof { srs: DLKSRS::<ProofGroup<E>> { g_public_key: conf.srs.g2 } };
+    let stmnts = candidates.iter().map(|(i, _)| shares[*i].signed_proof.decomp_proof().gs).collect::<Vec<_>>();
+    let proofs = candidates.iter().map(|(i, _)| shares[*i].signed_proof.decomp_proof().proof).collect::<Vec<_>>();
+
+    if dlk.batch_verify(rng, &stmnts, &proofs).is_err() {
+        candidates.retain(|(i, participant)| {
+            let valid = shares[*i].signed_proof.decomp_proof().verify(conf).is_ok();
+            if !valid {
+                failures.push((*i, PVSSError::NIZKProofDoesNotVerify { participant_id: participant.id.index() }));
+            }
+            valid
+        });
+    }
+
+    // Encryption correctness, merged over all shares: the terms of the batched check of each
+    // share (see PVSSCore::verify_encryptions_batched), drawn with independent randomness, go
+    // into a single product of pairings.
+    let public_keys = match committee_public_keys(conf.num_participants, participants) {
+        Ok(public_keys) => public_keys,
+        Err(err) => {
+            failures.extend(candidates.drain(..).map(|(i, _)| (i, PVSSError::InvalidParticipantId(err))));
+            vec![]
+        }
+    };
+
+    let mut pairs = Vec::with_capacity(candidates.len() * (conf.num_participants + 1));
+    for (i, _) in candidates.iter() {
+        // The cores passed the length check, so building their terms cannot fail.
+        pairs.extend(shares[*i].pvss_core.batched_encryption_pairs(rng, conf, &public_keys).unwrap_or_default());
+    }
+
+    if !E::final_exponentiation(&E::miller_loop(pairs.iter())).map_or(false, |p| p.is_one()) {
+        candidates.retain(|(i, _)| {
+            let valid = shares[*i].pvss_core.verify_encryptions_batched(rng, conf, &public_keys);
+            if !valid {
+                failures.push((*i, PVSSError::EncryptionCorrectnessError));
+            }
+            valid
+        });
+    }
+
+    // Signatures on the decomposition proofs, batch-verified.
+    let items = candidates
+        .iter()
+        .map(|(i, participant)| (shares[*i].signed_proof.digest(), &participant.public_key_ed, &shares[*i].signed_proof.signature_on_decomp));
+
+    if Signature::verify_batch_digests(items).is_err() {
+        for (i, participant) in candidates.iter() {
+            let signed_proof = &shares[*i].signed_proof;
+            if signed_proof.signature_on_decomp.verify(signed_proof.digest(), &participant.public_key_ed).is_err() {
+                failures.push((*i, PVSSError::SignatureVerificationFailed { participant_id: participant.id.index() }));
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        return Ok(());
+    }
+
+    failures.sort_by_key(|(i, _)| *i);
+    Err(failures)
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{verify_shares_batch, PVSSAggregator};
+    use crate::{
+        modified_scrape::{
+            errors::PVSSError,
+            participant::ParticipantId,
+            roles::{Dealer, OptrandScheme},
+            share::{PVSSAggregatedShare, SignedProof},
+        },
+        signature::schnorr::{SchnorrSignature, srs::SRS as SCHSRS},
+        testing::{fixtures::FixtureBuilder, tamper::corrupt_signature},
+        Signature,
+    };
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ec::{AffineCurve, PairingEngine};
+    use ark_std::UniformRand;
+    use rand::thread_rng;
+
+    #[test]
+    fn test_verify_shares_batch() {
+        let rng = &mut thread_rng();
+        let t = 6;
+        let n = 20;
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let (conf, registry) = (&fixture.config, &fixture.registry);
+
+        // Every participant deals once; share 4 carries an inconsistent encryption and
+        // share 13 is signed with someone else's key.
+        let scheme = OptrandScheme::new(conf.clone());
+        let mut shares = fixture.shares.clone();
+        shares[13] = scheme.deal(rng, registry, 13, &fixture.keypairs[0].secret_key_ed).unwrap().0;
+        shares[4].pvss_core.encs[4] += conf.srs.g1.into_projective();
+
+        assert!(verify_shares_batch(conf, registry, &shares[5..13], rng).is_ok());
+
+        let failures = verify_shares_batch(conf, registry, &shares, rng).unwrap_err();
+        assert_eq!(failures.len(), 2);
+        match &failures[..] {
+            [(4, PVSSError::EncryptionCorrectnessError), (13, PVSSError::SignatureVerificationFailed { participant_id: 13 })] => {},
+            _ => panic!("unexpected failures: {:?}", failures),
+        }
+
+        // The aggregator keeps the valid shares and reports the same two.
+        let mut aggregator = PVSSAggregator {
+            config: conf.clone(),
+            scheme_sig: SchnorrSignature { srs: SCHSRS { g_public_key: conf.srs.g1 } },
+            participants: registry.participants.clone(),
+            aggregated_tx: PVSSAggregatedShare::empty(t, n),
+        };
+
+        let failures = aggregator.receive_shares(rng, &shares).unwrap_err();
+        assert_eq!(failures.iter().map(|(i, _)| *i).collect::<Vec<_>>(), vec![4, 13]);
+        assert_eq!(aggregator.aggregated_tx.contributions.len(), n - 2);
+        assert!(!aggregator.aggregated_tx.contributions.contains_key(&ParticipantId(4)));
+        aggregator.aggregation_verify(rng, &aggregator.aggregated_tx.clone()).unwrap();
+    }
+
+    #[test]
+    fn test_aggregation_verify_tampered_contribution() {
+        let rng = &mut thread_rng();
+        let (t, n) = (2, 5);
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let conf = &fixture.config;
+
+        let mut aggregator = PVSSAggregator {
+            config: conf.clone(),
+            scheme_sig: SchnorrSignature { srs: SCHSRS { g_public_key: conf.srs.g1 } },
+            participants: fixture.registry.participants.clone(),
+            aggregated_tx: PVSSAggregatedShare::empty(t, n),
+        };
+        aggregator.aggregation_verify(rng, &fixture.transcript).unwrap();
+
+        // Contribution 2's proof is malformed, and signed as such, so that only its NIZK is faulty.
+        let mut bad_proof = fixture.transcript.clone();
+        let mut dproof = *fixture.shares[2].signed_proof.decomp_proof();
+        dproof.proof.1 = <E as PairingEngine>::Fr::rand(rng);
+        let sig = Signature::new(&dproof.digest(), &fixture.keypairs[2].secret_key_ed);
+        *bad_proof.contributions.get_mut(&ParticipantId(2)).unwrap() = SignedProof::new(dproof, sig);
+
+        match aggregator.aggregation_verify(rng, &bad_proof) {
+            Err(PVSSError::NIZKProofDoesNotVerify { participant_id }) => assert_eq!(participant_id, 2),
+            _ => panic!("expected NIZKProofDoesNotVerify"),
+        }
+
+        // Contribution 3's signature is corrupted, its proof is left intact.
+        let mut bad_signature = fixture.transcript.clone();
+        corrupt_signature(bad_signature.contributions.get_mut(&ParticipantId(3)).unwrap());
+
+        match aggregator.aggregation_verify(rng, &bad_signature) {
+            Err(PVSSError::SignatureVerificationFailed { participant_id }) => assert_eq!(participant_id, 3),
+            _ => panic!("expected SignatureVerificationFailed"),
+        }
+    }
 }
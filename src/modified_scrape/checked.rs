@@ -0,0 +1,220 @@
+use crate::modified_scrape::errors::PVSSError;
+
+use ark_ec::PairingEngine;
+use ark_serialize::{CanonicalDeserialize, Read};
+
+use ark_std::{cmp::min, io, vec::Vec};
+
+
+// Upper bound on the number of elements pre-allocated for a vector, regardless of the length
+// prefix found in the input; longer vectors simply grow as their elements are read.
+const MAX_PREALLOC: usize = 1024;
+
+
+/* Struct CountingReader wraps a reader and keeps track of the number of bytes consumed,
+   so that deserialization failures can be attributed to a byte offset and a field. */
+pub struct CountingReader<R: Read> {
+    inner: R,        // the underlying reader
+    offset: usize,   // number of bytes consumed so far
+}
+
+impl<R: Read> CountingReader<R> {
+
+    // Function for wrapping a reader.
+    pub fn new(inner: R) -> Self {
+        Self { inner, offset: 0 }
+    }
+
+    // Method returning the number of bytes consumed so far.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    // Method for deserializing the next field, labelling any failure with the field's
+    // name and the offset at which reading stopped.
+    pub fn read_field<T, E>(&mut self, field: &'static str) -> Result<T, PVSSError<E>>
+    where
+        T: CanonicalDeserialize,
+        E: PairingEngine,
+    {
+        T::deserialize(&mut *self).map_err(|source| PVSSError::DeserializationFailed {
+            field,
+            offset: self.offset,
+            source,
+        })
+    }
+
+    // Method for deserializing a length-prefixed vector field. Unlike the blanket
+    // implementation, the length prefix cannot trigger an arbitrarily large allocation.
+    pub fn read_vec<T, E>(&mut self, field: &'static str) -> Result<Vec<T>, PVSSError<E>>
+    where
+        T: CanonicalDeserialize,
+        E: PairingEngine,
+    {
+        let len: u64 = self.read_field(field)?;
+
+        let mut values = Vec::with_capacity(min(len, MAX_PREALLOC as u64) as usize);
+        for _ in 0..len {
+            values.push(self.read_field(field)?);
+        }
+
+        Ok(values)
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.offset += n;
+        Ok(n)
+    }
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::CountingReader;
+    use crate::modified_scrape::{
+        config::Config,
+        decomp::{Decomp, DecompProof},
+        errors::{PVSSError, RejectionReason},
+        participant::ParticipantId,
+        poly::Polynomial,
+        pvss::PVSSCore,
+        share::{PVSSAggregatedShare, PVSSShare, SignedProof},
+        srs::SRS,
+        tbls::{partial_sign, PartialSig},
+    };
+    use crate::{generate_production_keypair, Digest, PublicKey, Signature};
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ec::PairingEngine;
+    use ark_poly::UVPolynomial;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use ark_std::UniformRand;
+
+    use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+
+    #[test]
+    fn test_counting_reader() {
+        let mut bytes = vec![];
+        7u64.serialize(&mut bytes).unwrap();
+        9u32.serialize(&mut bytes).unwrap();
+
+        let mut reader = CountingReader::new(&bytes[..]);
+        let a: u64 = reader.read_field::<_, E>("a").unwrap();
+        assert_eq!((a, reader.offset()), (7, 8));
+
+        // Only 4 bytes remain.
+        match reader.read_field::<u64, E>("b") {
+            Err(PVSSError::DeserializationFailed { field, offset, .. }) => assert_eq!((field, offset), ("b", 12)),
+            _ => panic!("expected DeserializationFailed"),
+        }
+    }
+
+    #[test]
+    fn test_read_vec_inflated_length() {
+        let mut bytes = vec![];
+        vec![1u32, 2, 3].serialize(&mut bytes).unwrap();
+
+        let mut reader = CountingReader::new(&bytes[..]);
+        assert_eq!(reader.read_vec::<u32, E>("v").unwrap(), vec![1, 2, 3]);
+
+        // Claim u64::MAX elements: reading fails at the end of input, without a huge allocation.
+        bytes[..8].copy_from_slice(&u64::MAX.to_le_bytes());
+        let mut reader = CountingReader::new(&bytes[..]);
+        assert!(reader.read_vec::<u32, E>("v").is_err());
+    }
+
+    // Mutates a valid encoding (bit flips, truncations, length-field inflation) and feeds each
+    // mutant to the given deserializer; the test passes if none of them panics.
+    fn mutate_and_decode<F: Fn(&[u8])>(bytes: &[u8], cases: usize, decode: F) {
+        let rng = &mut StdRng::seed_from_u64(0x5eed);
+        let len = bytes.len();
+
+        decode(bytes);
+
+        for _ in 0..cases {
+            // Bit flips
+            let mut mutant = bytes.to_vec();
+            for _ in 0..rng.gen_range(1, 4) {
+                let i = rng.gen_range(0, len);
+                mutant[i] ^= 1 << rng.gen_range(0, 8);
+            }
+            decode(&mutant);
+
+            // Truncation
+            decode(&bytes[..rng.gen_range(0, len)]);
+
+            // Length-field inflation: overwrite 8 bytes with a huge little-endian integer.
+            if len >= 8 {
+                let mut mutant = bytes.to_vec();
+                let i = rng.gen_range(0, len - 7);
+                let huge = [u64::MAX, u32::MAX as u64, 1 << 40][rng.gen_range(0, 3)];
+                mutant[i..i + 8].copy_from_slice(&huge.to_le_bytes());
+                decode(&mutant);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mutated_encodings_do_not_panic() {
+        let rng = &mut thread_rng();
+        let t = 2;
+        let n = 4;
+        let cases = 2_000;   // rounds per type, each decoding three mutants
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+        let (pk_sig, sk_sig) = generate_production_keypair();
+
+        let poly = Polynomial::<E>::rand(t, rng);
+        let dproof = Decomp::<E>::generate(rng, &conf, &poly[0]).unwrap();
+        let sig = Signature::new(&dproof.digest(), &sk_sig);
+
+        let core = PVSSCore::<E>::new(
+            vec![<E as PairingEngine>::G1Projective::rand(rng); n],
+            vec![<E as PairingEngine>::G2Projective::rand(rng); n],
+        ).unwrap();
+        let signed_proof = SignedProof::new(dproof, sig);
+        let share = PVSSShare { participant_id: ParticipantId(1), degree: t, num_participants: n, config_digest: conf.digest(), pvss_core: core.clone(), signed_proof: signed_proof.clone() };
+        let agg_share = PVSSAggregatedShare::empty(t, n).aggregate_pvss_share(&share).unwrap();
+        let reason = RejectionReason::from_error(&PVSSError::<E>::DualCodeError, Some(3));
+        let partial = partial_sign::<E>(b"msg", &poly[0]).unwrap();
+
+        fn encode<T: CanonicalSerialize>(obj: &T) -> Vec<u8> {
+            let mut bytes = vec![];
+            obj.serialize(&mut bytes).unwrap();
+            bytes
+        }
+
+        mutate_and_decode(&encode(&core), cases, |b| {
+            let _ = PVSSCore::<E>::deserialize(b);
+            let _ = PVSSCore::<E>::deserialize_checked(b);
+        });
+        mutate_and_decode(&encode(&dproof), cases, |b| {
+            let _ = DecompProof::<E>::deserialize(b);
+            let _ = DecompProof::<E>::deserialize_checked(b);
+        });
+        mutate_and_decode(&encode(&signed_proof), cases, |b| {
+            let _ = SignedProof::<E>::deserialize(b);
+            let _ = SignedProof::<E>::deserialize_checked(b);
+        });
+        mutate_and_decode(&encode(&share), cases, |b| {
+            let _ = PVSSShare::<E>::deserialize(b);
+            let _ = PVSSShare::<E>::deserialize_checked(b);
+        });
+        mutate_and_decode(&encode(&agg_share), cases, |b| {
+            let _ = PVSSAggregatedShare::<E>::deserialize(b);
+            let _ = PVSSAggregatedShare::<E>::deserialize_checked(b);
+        });
+        mutate_and_decode(&encode(&dproof.digest()), cases, |b| { let _ = Digest::deserialize(b); });
+        mutate_and_decode(&encode(&pk_sig), cases, |b| { let _ = PublicKey::deserialize(b); });
+        mutate_and_decode(&encode(&sig), cases, |b| { let _ = Signature::deserialize(b); });
+        mutate_and_decode(&encode(&reason), cases, |b| { let _ = RejectionReason::deserialize(b); });
+        mutate_and_decode(&encode(&partial), cases, |b| { let _ = PartialSig::<E>::deserialize(b); });
+    }
+}
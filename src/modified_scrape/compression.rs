@@ -0,0 +1,282 @@
+use crate::modified_scrape::{errors::PVSSError, share::PVSSAggregatedShare, verifier::EpochTranscript};
+
+use ark_ec::PairingEngine;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{convert::TryFrom, format, vec::Vec};
+
+use miniz_oxide::{deflate::compress_to_vec, inflate::decompress_to_vec_with_limit};
+
+
+// Version of the compressed wire format, written to the first byte of the header. Any change
+// to the header or to the payload encodings must bump it.
+pub const WIRE_VERSION: u8 = 2;
+
+// Length of the wire header: the version byte, the mode byte, and the length of the canonical
+// serialization as a little-endian u64.
+pub const WIRE_HEADER_LEN: usize = 10;
+
+// Level handed to the deflate compressor (miniz's default trade-off).
+const DEFLATE_LEVEL: u8 = 6;
+
+
+/* WireMode records, in the wire header, how the payload following the header is encoded. */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WireMode {
+    Raw = 0,        // the canonical serialization, as is
+    Deflate = 1,    // the canonical serialization, deflate-compressed
+}
+
+impl WireMode {
+    // Method returning the mode recorded in the given header byte, if any.
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(WireMode::Raw),
+            1 => Some(WireMode::Deflate),
+            _ => None,
+        }
+    }
+}
+
+
+// Function encoding value as a wire header followed by its deflate-compressed canonical
+// serialization. Should compression not shrink the serialization, it is stored raw instead.
+fn to_wire_compressed<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+    let mut raw = Vec::with_capacity(value.serialized_size());
+    value.serialize(&mut raw).unwrap();
+
+    let compressed = compress_to_vec(&raw, DEFLATE_LEVEL);
+    let (mode, payload) = if compressed.len() < raw.len() {
+        (WireMode::Deflate, compressed)
+    } else {
+        (WireMode::Raw, raw.clone())
+    };
+
+    let mut bytes = Vec::with_capacity(WIRE_HEADER_LEN + payload.len());
+    bytes.push(WIRE_VERSION);
+    bytes.push(mode as u8);
+    bytes.extend_from_slice(&(raw.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&payload);
+
+    bytes
+}
+
+// Function decoding a value encoded by to_wire_compressed. At most max_size bytes are ever
+// decompressed: a header claiming more is rejected before touching the payload, and the
+// decompressor is capped at the claimed length, so that a small input cannot expand into an
+// arbitrarily large allocation. The value must consume the decoded payload exactly.
+fn from_wire_compressed<E, T>(bytes: &[u8], max_size: usize) -> Result<T, PVSSError<E>>
+where
+    E: PairingEngine,
+    T: CanonicalDeserialize,
+{
+    if bytes.len() < WIRE_HEADER_LEN {
+        return Err(PVSSError::InvalidWireFormat(format!("truncated header ({} bytes)", bytes.len())));
+    }
+
+    let (header, payload) = bytes.split_at(WIRE_HEADER_LEN);
+    if header[0] != WIRE_VERSION {
+        return Err(PVSSError::InvalidWireFormat(format!("unsupported version {}", header[0])));
+    }
+    let mode = WireMode::from_byte(header[1])
+        .ok_or_else(|| PVSSError::InvalidWireFormat(format!("unknown mode {}", header[1])))?;
+
+    let mut len_bytes = [0u8; 8];
+    len_bytes.copy_from_slice(&header[2..]);
+    let len = u64::from_le_bytes(len_bytes);
+    let len = match usize::try_from(len) {
+        Ok(len) if len <= max_size => len,
+        _ => return Err(PVSSError::WireSizeExceeded(usize::try_from(len).unwrap_or(usize::MAX), max_size)),
+    };
+
+    let decoded = match mode {
+        WireMode::Raw => payload.to_vec(),
+        WireMode::Deflate => decompress_to_vec_with_limit(payload, len)
+            .map_err(|status| PVSSError::InvalidWireFormat(format!("decompression failed ({:?})", status)))?,
+    };
+    if decoded.len() != len {
+        return Err(PVSSError::InvalidWireFormat(format!("payload of {} bytes, header claims {}", decoded.len(), len)));
+    }
+
+    let mut reader = &decoded[..];
+    let value = T::deserialize(&mut reader)?;
+    if !reader.is_empty() {
+        return Err(PVSSError::InvalidWireFormat(format!("{} trailing bytes", reader.len())));
+    }
+
+    Ok(value)
+}
+
+
+impl<E: PairingEngine> PVSSAggregatedShare<E> {
+    // Method encoding the aggregated share for the wire, compressed (see to_wire_compressed).
+    pub fn to_wire_compressed(&self) -> Vec<u8> {
+        to_wire_compressed(self)
+    }
+
+    // Function decoding an aggregated share encoded by to_wire_compressed, decompressing at most
+    // max_size bytes. No verification is done beyond decoding.
+    pub fn from_wire_compressed(bytes: &[u8], max_size: usize) -> Result<Self, PVSSError<E>> {
+        from_wire_compressed(bytes, max_size)
+    }
+}
+
+impl<E: PairingEngine> EpochTranscript<E> {
+    // Method encoding the transcript for the wire, compressed (see to_wire_compressed).
+    pub fn to_wire_compressed(&self) -> Vec<u8> {
+        to_wire_compressed(self)
+    }
+
+    // Function decoding a transcript encoded by to_wire_compressed, decompressing at most
+    // max_size bytes. No verification is done beyond decoding (see verify_epoch).
+    pub fn from_wire_compressed(bytes: &[u8], max_size: usize) -> Result<Self, PVSSError<E>> {
+        from_wire_compressed(bytes, max_size)
+    }
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{WireMode, WIRE_HEADER_LEN, WIRE_VERSION};
+    use crate::{
+        generate_production_keypair,
+        modified_scrape::{
+            config::Config,
+            decomp::Decomp,
+            errors::PVSSError,
+            participant::ParticipantId,
+            poly::Polynomial as Poly,
+            pvss::AffinePVSSCore,
+            share::{PVSSAggregatedShare, SignedProof},
+            srs::SRS,
+            verifier::EpochTranscript,
+        },
+        Signature,
+    };
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ec::{PairingEngine, ProjectiveCurve};
+    use ark_poly::UVPolynomial;
+    use ark_serialize::CanonicalSerialize;
+    use ark_std::UniformRand;
+
+    use miniz_oxide::deflate::compress_to_vec;
+
+    use rand::thread_rng;
+
+    use std::collections::BTreeMap;
+
+    // Builds a (structurally complete, but not verifying) transcript with random elements.
+    fn random_transcript() -> EpochTranscript<E> {
+        let rng = &mut thread_rng();
+        let t = 2;
+        let n = 5;
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+
+        let mut aggregated = PVSSAggregatedShare::<E>::empty(t, n);
+        aggregated.pvss_core = AffinePVSSCore {
+            encs:  (0..n).map(|_| <E as PairingEngine>::G1Projective::rand(rng).into_affine()).collect(),
+            comms: (0..n).map(|_| <E as PairingEngine>::G2Projective::rand(rng).into_affine()).collect(),
+        };
+        for id in [0, 2, 3].iter() {
+            let poly = Poly::<E>::rand(t, rng);
+            let (_pk_sig, sk_sig) = generate_production_keypair();
+            let dproof = Decomp::<E>::generate(rng, &conf, &poly[0]).unwrap();
+            let sig = Signature::new(&dproof.digest(), &sk_sig);
+            aggregated.contributions.insert(ParticipantId(*id), SignedProof::new(dproof, sig)).unwrap();
+        }
+
+        let decryptions = [1, 4]
+            .iter()
+            .map(|id| {
+                let mut g1 = || <E as PairingEngine>::G1Projective::rand(rng).into_affine();
+                let (share, commitment_1, commitment_2) = (g1(), g1(), g1());
+                let proof = ((commitment_1, commitment_2), <E as PairingEngine>::Fr::rand(rng), <E as PairingEngine>::Fr::rand(rng));
+                (*id, (share, proof))
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        EpochTranscript { epoch: 7, prev_output: [3u8; 32], aggregated, decryptions, beacon: [9u8; 32] }
+    }
+
+    fn bytes<T: CanonicalSerialize>(value: &T) -> Vec<u8> {
+        let mut bytes = vec![];
+        value.serialize(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_wire_compressed_roundtrip() {
+        let transcript = random_transcript();
+        let max_size = bytes(&transcript).len();
+
+        let wire = transcript.to_wire_compressed();
+        assert_eq!(wire[0], WIRE_VERSION);
+        let imported = EpochTranscript::<E>::from_wire_compressed(&wire, max_size).unwrap();
+        assert_eq!(bytes(&imported), bytes(&transcript));
+
+        // An aggregate of identity elements compresses, and round-trips as well.
+        let aggregated = PVSSAggregatedShare::<E>::empty(2, 1024);
+        let raw_len = bytes(&aggregated).len();
+        let wire = aggregated.to_wire_compressed();
+        assert_eq!(WireMode::from_byte(wire[1]), Some(WireMode::Deflate));
+        assert!(wire.len() < raw_len / 10);
+        assert!(PVSSAggregatedShare::<E>::from_wire_compressed(&wire, raw_len).unwrap() == aggregated);
+
+        // A limit below the decoded size is rejected.
+        match PVSSAggregatedShare::<E>::from_wire_compressed(&wire, raw_len - 1) {
+            Err(PVSSError::WireSizeExceeded(got, max)) => assert_eq!((got, max), (raw_len, raw_len - 1)),
+            _ => panic!("expected WireSizeExceeded"),
+        }
+
+        // Truncated inputs, unknown versions and unknown modes are rejected.
+        let mut edits = vec![wire[..WIRE_HEADER_LEN - 1].to_vec(), wire[..wire.len() - 1].to_vec()];
+        for &(index, byte) in [(0, WIRE_VERSION + 1), (1, 2)].iter() {
+            let mut edited = wire.clone();
+            edited[index] = byte;
+            edits.push(edited);
+        }
+        for edited in edits.iter() {
+            assert!(matches!(
+                PVSSAggregatedShare::<E>::from_wire_compressed(edited, raw_len),
+                Err(PVSSError::InvalidWireFormat(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_wire_compressed_rejects_bombs() {
+        let max_size = 1 << 16;
+
+        let wire_with = |len: u64, payload: &[u8]| {
+            let mut wire = vec![WIRE_VERSION, WireMode::Deflate as u8];
+            wire.extend_from_slice(&len.to_le_bytes());
+            wire.extend_from_slice(payload);
+            wire
+        };
+
+        // A megabyte of zeros deflates to about a kilobyte.
+        let bomb = compress_to_vec(&vec![0u8; 1 << 20], 6);
+        assert!(bomb.len() < max_size);
+
+        // Announced truthfully, the bomb is rejected from its header alone.
+        match PVSSAggregatedShare::<E>::from_wire_compressed(&wire_with(1 << 20, &bomb), max_size) {
+            Err(PVSSError::WireSizeExceeded(got, max)) => assert_eq!((got, max), (1 << 20, max_size)),
+            _ => panic!("expected WireSizeExceeded"),
+        }
+        match PVSSAggregatedShare::<E>::from_wire_compressed(&wire_with(u64::MAX, &bomb), max_size) {
+            Err(PVSSError::WireSizeExceeded(_, max)) => assert_eq!(max, max_size),
+            _ => panic!("expected WireSizeExceeded"),
+        }
+
+        // Announced with a small length, decompression stops at that length.
+        assert!(matches!(
+            PVSSAggregatedShare::<E>::from_wire_compressed(&wire_with(1024, &bomb), max_size),
+            Err(PVSSError::InvalidWireFormat(_))
+        ));
+    }
+}
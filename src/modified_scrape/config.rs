@@ -1,8 +1,22 @@
-use super::srs::SRS;
+use super::{
+    decomp::{Blake2sDigestHasher, DigestHasher},
+    errors::PVSSError,
+    participant::ParticipantId,
+    srs::SRS,
+};
+use crate::{Digest, Scalar};
+
 use ark_ec::PairingEngine;
+use ark_ff::{FpParameters, PrimeField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use ark_std::{convert::TryFrom, string::ToString};
+use rand::{CryptoRng, Rng};
 
 /* Struct config models the system-wide public parameters that each party
    in the network needs to know in order to generate/verify a PVSS sharing.
+   It also caches srs.g2 in prepared form, as g2 is a fixed input of the verification pairings;
+   hence the SRS must not be changed after construction. The cache is not serialized, and is
+   rebuilt upon deserialization.
 */
 
 #[derive(Clone)]
@@ -10,4 +24,323 @@ pub struct Config<E: PairingEngine> {
     pub srs: SRS<E>,               // the associated SRS
     pub degree: usize,             // polynomial degree (t)
     pub num_participants: usize,   // the total number of participants in the protocol
+    g2_prepared: E::G2Prepared,    // srs.g2 in prepared form
+}
+
+impl<E: PairingEngine> Config<E> {
+    // Constructor of a configuration; prepares srs.g2 once and for all.
+    pub fn new(srs: SRS<E>, degree: usize, num_participants: usize) -> Self {
+        let g2_prepared = srs.g2.into();
+        Self { srs, degree, num_participants, g2_prepared }
+    }
+
+    // Function for starting the step-by-step construction of a configuration (see ConfigBuilder).
+    pub fn builder() -> ConfigBuilder<E> {
+        ConfigBuilder::new()
+    }
+
+    // Accessor for srs.g2 in prepared form.
+    pub fn g2_prepared(&self) -> &E::G2Prepared {
+        &self.g2_prepared
+    }
+
+    // Method returning the evaluation point of the participant at index id (see
+    // ParticipantId::evaluation_point). Fails if the committee does not fit in the scalar field,
+    // as the points 1, ..., n would then wrap around.
+    pub fn point_for(&self, id: usize) -> Result<Scalar<E>, PVSSError<E>> {
+        if !committee_fits_field::<Scalar<E>>(self.num_participants) {
+            return Err(PVSSError::CommitteeTooLargeForField(self.num_participants));
+        }
+
+        if id >= self.num_participants {
+            return Err(PVSSError::InvalidParticipantId(id));
+        }
+
+        let id = ParticipantId::try_from(id).map_err(|_| PVSSError::InvalidParticipantId(id))?;
+
+        Ok(id.evaluation_point::<E>())
+    }
+
+    // Method returning the BLAKE2s digest of the configuration's serialization (SRS, degree and
+    // committee size), which binds shares and aggregated transcripts to the configuration they
+    // were produced under.
+    pub fn digest(&self) -> Digest {
+        let mut bytes = vec![];
+        self.serialize(&mut bytes).unwrap();
+
+        Digest(Blake2sDigestHasher::hash(&bytes))
+    }
+}
+
+
+// Function returning whether the evaluation points 1, ..., num_participants are distinct,
+// non-zero elements of the field F, i.e., whether num_participants < |F|.
+pub fn committee_fits_field<F: PrimeField>(num_participants: usize) -> bool {
+    F::BigInt::from(num_participants as u64) < F::Params::MODULUS
+}
+
+impl<E: PairingEngine> CanonicalSerialize for Config<E> {
+    fn serialize<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        self.srs.serialize(&mut writer)?;
+        self.degree.serialize(&mut writer)?;
+        self.num_participants.serialize(&mut writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.srs.serialized_size() + self.degree.serialized_size() + self.num_participants.serialized_size()
+    }
+}
+
+impl<E: PairingEngine> CanonicalDeserialize for Config<E> {
+    fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let srs = SRS::deserialize(&mut reader)?;
+        let degree = usize::deserialize(&mut reader)?;
+        let num_participants = usize::deserialize(&mut reader)?;
+
+        Ok(Self::new(srs, degree, num_participants))
+    }
+}
+
+
+/* Struct ConfigBuilder assembles a configuration step by step. The SRS is either supplied, e.g.,
+   to share one SRS across several configurations, or generated from an RNG. Nothing is checked
+   until build is called, which then reports the first broken invariant.
+*/
+
+pub struct ConfigBuilder<E: PairingEngine> {
+    srs: Option<Result<SRS<E>, PVSSError<E>>>,   // the supplied or generated SRS, if any
+    degree: Option<usize>,                       // polynomial degree (t)
+    num_participants: Option<usize>,             // the total number of participants in the protocol
+}
+
+impl<E: PairingEngine> Default for ConfigBuilder<E> {
+    fn default() -> Self {
+        Self { srs: None, degree: None, num_participants: None }
+    }
+}
+
+impl<E: PairingEngine> ConfigBuilder<E> {
+    // Constructor of a builder with nothing set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Method setting the polynomial degree (t).
+    pub fn degree(mut self, degree: usize) -> Self {
+        self.degree = Some(degree);
+        self
+    }
+
+    // Method setting the committee size (n).
+    pub fn num_participants(mut self, num_participants: usize) -> Self {
+        self.num_participants = Some(num_participants);
+        self
+    }
+
+    // Method setting the SRS to an existing one.
+    pub fn srs(mut self, srs: SRS<E>) -> Self {
+        self.srs = Some(Ok(srs));
+        self
+    }
+
+    // Method setting the SRS to a fresh one drawn from rng (see SRS::setup). A setup failure is
+    // reported by build.
+    pub fn generate_srs<R: Rng + CryptoRng>(mut self, rng: &mut R) -> Self {
+        self.srs = Some(SRS::setup(rng));
+        self
+    }
+
+    // Method building the configuration. Fails if the degree, the committee size or the SRS is
+    // missing, if the committee is empty, if degree + 1 shares (the number needed for
+    // reconstruction) exceed the committee size, or if the committee does not fit in the scalar
+    // field.
+    pub fn build(self) -> Result<Config<E>, PVSSError<E>> {
+        let degree = self.degree.ok_or_else(|| PVSSError::InvalidConfig("degree is not set".to_string()))?;
+        let num_participants = self.num_participants.ok_or_else(|| PVSSError::InvalidConfig("committee size is not set".to_string()))?;
+        let srs = self.srs.ok_or_else(|| PVSSError::InvalidConfig("SRS is not set".to_string()))??;
+
+        if num_participants == 0 {
+            return Err(PVSSError::InvalidConfig("committee is empty".to_string()));
+        }
+
+        if degree >= num_participants {
+            return Err(PVSSError::InvalidConfig(format!(
+                "degree {} leaves a committee of {} unable to reconstruct", degree, num_participants
+            )));
+        }
+
+        if !committee_fits_field::<Scalar<E>>(num_participants) {
+            return Err(PVSSError::CommitteeTooLargeForField(num_participants));
+        }
+
+        Ok(Config::new(srs, degree, num_participants))
+    }
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{committee_fits_field, Config, ConfigBuilder};
+    use crate::modified_scrape::{errors::PVSSError, pvss::PVSSCore, srs::SRS};
+    use crate::Scalar;
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ec::{PairingEngine, ProjectiveCurve};
+    use ark_ff::{
+        biginteger::BigInteger64 as BigInt,
+        fields::{FftParameters, Fp64, Fp64Parameters, FpParameters},
+    };
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use ark_std::UniformRand;
+
+    use rand::thread_rng;
+
+    // The field of order 17, small enough for committees to outgrow it.
+    struct F17Parameters;
+
+    impl Fp64Parameters for F17Parameters {}
+
+    impl FftParameters for F17Parameters {
+        type BigInt = BigInt;
+
+        const TWO_ADICITY: u32 = 4;
+        const TWO_ADIC_ROOT_OF_UNITY: BigInt = BigInt([3]);
+    }
+
+    impl FpParameters for F17Parameters {
+        const MODULUS: BigInt = BigInt([17]);
+        const MODULUS_BITS: u32 = 5;
+        const CAPACITY: u32 = 4;
+        const REPR_SHAVE_BITS: u32 = 59;
+        const R: BigInt = BigInt([1]);
+        const R2: BigInt = BigInt([1]);
+        const INV: u64 = 1085102592571150095;
+        const GENERATOR: BigInt = BigInt([3]);
+        const T: BigInt = BigInt([1]);
+        const T_MINUS_ONE_DIV_TWO: BigInt = BigInt([0]);
+        const MODULUS_MINUS_ONE_DIV_TWO: BigInt = BigInt([8]);
+    }
+
+    type F17 = Fp64<F17Parameters>;
+
+    // Reference encryption check, with plain pairings.
+    fn reference_check(conf: &Config<E>, core: &PVSSCore<E>, pks: &[<E as PairingEngine>::G1Affine]) -> bool {
+        pks.iter().zip(core.encs.iter().zip(core.comms.iter())).all(|(pk, (enc, comm))| {
+            E::pairing(*pk, *comm) == E::pairing(*enc, conf.srs.g2)
+        })
+    }
+
+    #[test]
+    fn test_prepared_g2() {
+        let rng = &mut thread_rng();
+        let (t, n) = (2, 5);
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+
+        // The cache is rebuilt upon deserialization.
+        let mut bytes = vec![];
+        conf.serialize(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), conf.serialized_size());
+        let deserialized = Config::<E>::deserialize(&bytes[..]).unwrap();
+        assert!(deserialized.srs.g2 == conf.srs.g2 && deserialized.srs.g2_prime == conf.srs.g2_prime);
+        assert_eq!((deserialized.degree, deserialized.num_participants), (t, n));
+
+        let p = <E as PairingEngine>::G1Projective::rand(rng).into_affine();
+        let pairs = [(p.into(), deserialized.g2_prepared().clone())];
+        assert_eq!(E::final_exponentiation(&E::miller_loop(pairs.iter())).unwrap(), E::pairing(p, conf.srs.g2));
+
+        // Both configurations accept and reject exactly as plain pairings do.
+        let pks = (0..n).map(|_| <E as PairingEngine>::G1Projective::rand(rng).into_affine()).collect::<Vec<_>>();
+        let (core, _) = PVSSCore::deal(rng, &conf, &pks, 0).unwrap();
+        let mut bad_core = core.clone();
+        bad_core.encs[3] += &<E as PairingEngine>::G1Projective::rand(rng);
+
+        for c in [&conf, &deserialized].iter() {
+            assert!(reference_check(c, &core, &pks) && core.verify_encryptions(c, &pks));
+            assert!(core.verify_encryptions_batched(rng, c, &pks));
+            assert!(!reference_check(c, &bad_core, &pks) && !bad_core.verify_encryptions(c, &pks));
+            assert!(!bad_core.verify_encryptions_batched(rng, c, &pks));
+        }
+    }
+
+    #[test]
+    fn test_committee_fits_field() {
+        let rng = &mut thread_rng();
+
+        // Points 1, ..., 16 are distinct and non-zero modulo 17; point 17 wraps to zero.
+        assert!(committee_fits_field::<F17>(16));
+        assert!(!committee_fits_field::<F17>(17));
+        assert!(!committee_fits_field::<F17>(100));
+        assert!(F17::from(17u64) == F17::from(0u64));
+
+        // Any committee fits in the BLS12-381 scalar field.
+        assert!(committee_fits_field::<Scalar<E>>(usize::MAX));
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, 2, 5);
+        assert_eq!(conf.point_for(0).unwrap(), Scalar::<E>::from(1u64));
+        assert_eq!(conf.point_for(4).unwrap(), Scalar::<E>::from(5u64));
+        match conf.point_for(5) {
+            Err(PVSSError::InvalidParticipantId(id)) => assert_eq!(id, 5),
+            _ => panic!("expected InvalidParticipantId"),
+        }
+    }
+
+    #[test]
+    fn test_config_digest() {
+        let rng = &mut thread_rng();
+        let (t, n) = (2, 5);
+
+        let conf = Config::new(SRS::<E>::setup(rng).unwrap(), t, n);
+
+        // The digest survives serialization, and changes with the SRS, the degree or the committee size.
+        let mut bytes = vec![];
+        conf.serialize(&mut bytes).unwrap();
+        assert_eq!(Config::<E>::deserialize(&bytes[..]).unwrap().digest(), conf.digest());
+
+        let others = [
+            Config::new(SRS::<E>::setup(rng).unwrap(), t, n),
+            Config::new(conf.srs.clone(), t + 1, n),
+            Config::new(conf.srs.clone(), t, n + 1),
+        ];
+        assert!(others.iter().all(|other| other.digest() != conf.digest()));
+    }
+
+    #[test]
+    fn test_config_builder() {
+        let rng = &mut thread_rng();
+        let (t, n) = (2, 5);
+
+        // A supplied SRS can be shared across configurations, which match those built directly.
+        let srs = SRS::<E>::setup(rng).unwrap();
+        let conf = Config::builder().degree(t).num_participants(n).srs(srs.clone()).build().unwrap();
+        let larger = ConfigBuilder::new().srs(srs.clone()).num_participants(n + 2).degree(t).build().unwrap();
+        assert_eq!(conf.digest(), Config::new(srs.clone(), t, n).digest());
+        assert_eq!(larger.digest(), Config::new(srs, t, n + 2).digest());
+
+        // A generated SRS is fresh, and its g2 is the one prepared.
+        let generated = Config::<E>::builder().generate_srs(rng).degree(t).num_participants(n).build().unwrap();
+        assert_eq!((generated.degree, generated.num_participants), (t, n));
+        assert!(generated.srs.g2 != conf.srs.g2);
+        let p = <E as PairingEngine>::G1Projective::rand(rng).into_affine();
+        let pairs = [(p.into(), generated.g2_prepared().clone())];
+        assert_eq!(E::final_exponentiation(&E::miller_loop(pairs.iter())).unwrap(), E::pairing(p, generated.srs.g2));
+
+        // Missing parameters and broken invariants are reported at build time.
+        let invalid = vec![
+            Config::<E>::builder().num_participants(n).generate_srs(rng),
+            Config::<E>::builder().degree(t).generate_srs(rng),
+            Config::<E>::builder().degree(t).num_participants(n),
+            Config::<E>::builder().degree(0).num_participants(0).generate_srs(rng),
+            Config::<E>::builder().degree(n).num_participants(n).generate_srs(rng),
+        ];
+        for builder in invalid.into_iter() {
+            assert!(matches!(builder.build(), Err(PVSSError::InvalidConfig(_))));
+        }
+        assert!(Config::<E>::builder().degree(n - 1).num_participants(n).generate_srs(rng).build().is_ok());
+    }
 }
@@ -0,0 +1,167 @@
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::{BigInteger, FpParameters, PrimeField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::vec::Vec;
+
+
+// Function choosing the wNAF window for scalars of the given bit length: larger windows cost a
+// bigger table of precomputed odd multiples, which only pays off for longer scalars.
+pub fn wnaf_window(bits: usize) -> usize {
+    match bits {
+        0..=32 => 2,
+        33..=128 => 3,
+        129..=256 => 4,
+        _ => 5,
+    }
+}
+
+
+// Function computing the width-w non-adjacent form of scalar, least significant digit first.
+// Every non-zero digit is odd and lies in (-2^(w-1), 2^(w-1)), and any w consecutive digits
+// hold at most one non-zero digit.
+fn wnaf_digits<F: PrimeField>(scalar: &F, window: usize) -> Vec<i64> {
+    let mut k = scalar.into_repr();
+    let width = 1u64 << window;
+    let half = 1u64 << (window - 1);
+    let mut digits = Vec::with_capacity(F::size_in_bits() + 1);
+
+    while !k.is_zero() {
+        let digit = if k.is_odd() {
+            let low = k.as_ref()[0] % width;
+            if low >= half {
+                // k - (low - 2^w) = k + (2^w - low)
+                k.add_nocarry(&F::BigInt::from(width - low));
+                low as i64 - width as i64
+            } else {
+                k.sub_noborrow(&F::BigInt::from(low));
+                low as i64
+            }
+        } else {
+            0
+        };
+
+        digits.push(digit);
+        k.div2();
+    }
+
+    digits
+}
+
+
+// Function computing base * scalar by double-and-add over the wNAF recoding of scalar, with a
+// table of the odd multiples base, 3 * base, ..., (2^(w-1) - 1) * base. The window must be at
+// least 2 (see wnaf_window).
+pub fn mul_wnaf<G: ProjectiveCurve>(base: &G, scalar: &G::ScalarField, window: usize) -> G {
+    assert!(window >= 2, "wNAF window must be at least 2, got {}", window);
+
+    let double = base.double();
+    let mut table = Vec::with_capacity(1 << (window - 2));
+    table.push(*base);
+    for i in 1..(1 << (window - 2)) {
+        let next = table[i - 1] + double;
+        table.push(next);
+    }
+
+    let mut result = G::zero();
+    for digit in wnaf_digits(scalar, window).iter().rev() {
+        result.double_in_place();
+
+        if *digit > 0 {
+            result += &table[(*digit as usize) / 2];
+        } else if *digit < 0 {
+            result -= &table[(-*digit as usize) / 2];
+        }
+    }
+
+    result
+}
+
+
+// Function returning whether point lies in the prime order subgroup, i.e., whether multiplying
+// it by the subgroup order r yields zero. This is the check of arkworks'
+// is_in_correct_subgroup_assuming_on_curve, which ark-ec only exposes on concrete curve models,
+// and not through ProjectiveCurve. As that check, it is only sound for points on the curve
+// (see is_on_curve).
+pub fn in_prime_subgroup<G: ProjectiveCurve>(point: &G) -> bool {
+    point.mul(<G::ScalarField as PrimeField>::Params::MODULUS).is_zero()
+}
+
+
+// Function returning whether point lies on its curve, e.g., after an unchecked deserialization.
+// ark-ec only exposes is_on_curve on concrete curve models, so the point is instead encoded in
+// compressed form (x and the sign of y) and decoded back: decoding recovers y from the curve
+// equation, hence yields the same point only if it was on the curve. Decoding also rejects
+// points outside the prime order subgroup.
+pub fn is_on_curve<G: AffineCurve>(point: &G) -> bool {
+    let mut bytes = vec![];
+
+    point.serialize(&mut bytes).is_ok() && G::deserialize(&bytes[..]).map_or(false, |decoded| decoded == *point)
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{mul_wnaf, wnaf_digits, wnaf_window};
+    use crate::Scalar;
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ec::{PairingEngine, ProjectiveCurve};
+    use ark_ff::{Field, One, PrimeField, Zero};
+    use ark_std::UniformRand;
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_wnaf_digits() {
+        let rng = &mut thread_rng();
+
+        for window in 2..=6 {
+            let scalar = Scalar::<E>::rand(rng);
+            let digits = wnaf_digits(&scalar, window);
+
+            // The digits recompose to the scalar.
+            let mut recomposed = Scalar::<E>::zero();
+            for digit in digits.iter().rev() {
+                recomposed.double_in_place();
+                if *digit >= 0 {
+                    recomposed += Scalar::<E>::from(*digit as u64);
+                } else {
+                    recomposed -= Scalar::<E>::from((-*digit) as u64);
+                }
+            }
+            assert_eq!(recomposed, scalar);
+
+            // Non-zero digits are odd, bounded, and at least window positions apart.
+            let nonzero = digits.iter().enumerate().filter(|(_, d)| **d != 0).collect::<Vec<_>>();
+            assert!(nonzero.iter().all(|(_, d)| **d % 2 != 0 && d.abs() < (1 << (window - 1))));
+            assert!(nonzero.windows(2).all(|pair| pair[1].0 - pair[0].0 >= window));
+        }
+    }
+
+    #[test]
+    fn test_mul_wnaf_matches_mul() {
+        let rng = &mut thread_rng();
+        let window = wnaf_window(Scalar::<E>::size_in_bits());
+
+        for _ in 0..16 {
+            let g1 = <E as PairingEngine>::G1Projective::rand(rng);
+            let g2 = <E as PairingEngine>::G2Projective::rand(rng);
+            let scalar = Scalar::<E>::rand(rng);
+
+            assert_eq!(mul_wnaf(&g1, &scalar, window), g1.mul(scalar.into_repr()));
+            assert_eq!(mul_wnaf(&g2, &scalar, window), g2.mul(scalar.into_repr()));
+        }
+
+        // Edge scalars, over every window.
+        let g1 = <E as PairingEngine>::G1Projective::rand(rng);
+        for window in 2..=6 {
+            for scalar in [Scalar::<E>::zero(), Scalar::<E>::one(), -Scalar::<E>::one()].iter() {
+                assert_eq!(mul_wnaf(&g1, scalar, window), g1.mul(scalar.into_repr()));
+            }
+        }
+        assert!(mul_wnaf(&<E as PairingEngine>::G1Projective::zero(), &Scalar::<E>::rand(rng), window).is_zero());
+    }
+}
@@ -1,8 +1,11 @@
 use crate::{
     Digest,
-    modified_scrape::{config::Config, errors::PVSSError},
+    modified_scrape::{checked::CountingReader, config::Config, errors::PVSSError, participant::ParticipantId, share::SignedProof},
     nizk::{dlk::{DLKProof, srs::SRS as DLKSRS}, scheme::NIZKProof},
+    PublicKey,
     Scalar,
+    SecretKey,
+    Signature,
 };
 
 use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
@@ -10,18 +13,99 @@ use ark_ff::PrimeField;
 use ark_serialize::*;
 use ark_std::fmt::Debug;
 
-use rand::Rng;
-use std::{
-    collections::hash_map::DefaultHasher,
+use blake2s_simd::{Params, State};
+use rand::{CryptoRng, Rng};
+use ark_std::{
     hash::{Hash, Hasher},
-    io::Cursor,
     marker::PhantomData,
+    string::String,
+    vec::Vec,
 };
+use siphasher::sip::SipHasher13;   // the standard library's DefaultHasher, also available without std
 
 
 pub type ProofGroup<E> = <E as PairingEngine>::G2Affine;   // the group over which the proof is computed
 pub type ProofType<E> = DecompProof<E>;   		   // the type of output decomposition proofs
 
+const DIGEST_PERSONALIZATION: &[u8] = b"OPTRDGST";   // persona for BLAKE2s digests of proofs
+const JOINT_CONTEXT_TAG: &[u8] = b"OPTRAND-JOINT";    // domain separator of joint contexts
+
+
+// Trait DigestHasher selects the hash function used to digest decomposition proofs, i.e., to
+// obtain the message that dealers sign.
+pub trait DigestHasher {
+    // Function hashing the (serialized) proof into a 32-byte digest.
+    fn hash(bytes: &[u8]) -> [u8; 32];
+}
+
+// Struct SipDigestHasher digests with SipHash-1-3 under zero keys, i.e., the standard library's
+// DefaultHasher, zero-padded to 32 bytes. It is not collision-resistant.
+pub struct SipDigestHasher;
+
+impl DigestHasher for SipDigestHasher {
+    fn hash(bytes: &[u8]) -> [u8; 32] {
+        let mut hasher = SipHasher13::new();
+        bytes.hash(&mut hasher);
+        let byte_array = hasher.finish().to_ne_bytes();   // TODO: use cryptographically secure hash
+        let mut arr = [0; 32];
+        arr[..byte_array.len()].copy_from_slice(&byte_array);
+
+        arr
+    }
+}
+
+// Struct Blake2sDigestHasher digests with (personalized) BLAKE2s-256.
+pub struct Blake2sDigestHasher;
+
+impl Blake2sDigestHasher {
+    // Function returning the hashing state that hash starts from, so that data can also be
+    // digested incrementally (and then finalized with finalize).
+    pub fn state() -> State {
+        Params::new()
+            .hash_length(32)
+            .personal(DIGEST_PERSONALIZATION)
+            .to_state()
+    }
+
+    // Function finalizing a state obtained from state into a 32-byte digest.
+    pub fn finalize(state: &State) -> [u8; 32] {
+        let mut arr = [0; 32];
+        arr.copy_from_slice(state.finalize().as_bytes());
+
+        arr
+    }
+}
+
+impl DigestHasher for Blake2sDigestHasher {
+    fn hash(bytes: &[u8]) -> [u8; 32] {
+        Self::finalize(Self::state().update(bytes))
+    }
+}
+
+// Function returning the context that binds decomposition proofs to a whole set of contributions:
+// a digest over the (id, gs) statements of all contributors, by increasing id. Contributors
+// who agreed on the statements beforehand prove with it (see Decomp::generate_with_context), so
+// that no proof verifies once taken out of, or moved into, another set of contributions.
+pub fn joint_context<'a, E, I>(statements: I) -> Vec<u8>
+where
+    E: PairingEngine,
+    I: IntoIterator<Item = (ParticipantId, &'a ProofGroup<E>)>,
+{
+    let mut state = Blake2sDigestHasher::state();
+    state.update(JOINT_CONTEXT_TAG);
+
+    for (id, gs) in statements {
+        let mut bytes = u64::from(id.0).to_le_bytes().to_vec();
+        gs.serialize(&mut bytes).unwrap();
+        state.update(&bytes);
+    }
+
+    Blake2sDigestHasher::finalize(&state).to_vec()
+}
+
+// The hasher used by DecompProof::digest.
+pub type DefaultDigestHasher = SipDigestHasher;
+
 // Struct Decomp models the Decomposition proof system.
 #[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize, PartialEq)]
 pub struct Decomp<E: PairingEngine> {
@@ -38,9 +122,18 @@ pub struct DecompProof<E: PairingEngine> {
 impl<E: PairingEngine> Decomp<E> {
 
     // Associated function for generating decomposition proofs.
-    pub fn generate<R: Rng>(rng: &mut R,
+    pub fn generate<R: Rng + CryptoRng>(rng: &mut R,
                             config: &Config<E>,
 			    p_0: &Scalar<E>) -> Result<ProofType<E>, PVSSError<E>> {
+	Self::generate_with_context(rng, config, p_0, &[])
+    }
+
+    // Associated function for generating decomposition proofs whose challenge is bound to
+    // some context bytes (e.g., the previous epoch's output).
+    pub fn generate_with_context<R: Rng + CryptoRng>(rng: &mut R,
+                                         config: &Config<E>,
+                                         p_0: &Scalar<E>,
+                                         context: &[u8]) -> Result<ProofType<E>, PVSSError<E>> {
 	let secret = p_0;
 	let generator = config.srs.g2;
 	let gs = generator.mul(secret.into_repr()).into_affine();
@@ -48,10 +141,22 @@ impl<E: PairingEngine> Decomp<E> {
 	let dlk_srs = DLKSRS::<ProofGroup::<E>> { g_public_key: generator };
 	let dlk = DLKProof { srs: dlk_srs };   // initialize proof system for DLK NIZKs.
 
-	let proof = dlk.prove(rng, &secret).unwrap();
+	let proof = dlk.prove_with_context(rng, &secret, context).unwrap();
 
 	Ok(DecompProof { proof, gs })
     }
+
+    // Associated function for generating a decomposition proof and signing its digest, as
+    // computed by the hasher H (which verifiers must use as well, see SignedProof::verify_with).
+    pub fn generate_signed<R: Rng + CryptoRng, H: DigestHasher>(rng: &mut R,
+                                                    config: &Config<E>,
+                                                    p_0: &Scalar<E>,
+                                                    private_key_ed: &SecretKey) -> Result<SignedProof<E>, PVSSError<E>> {
+	let decomp_proof = Self::generate(rng, config, p_0)?;
+	let signature_on_decomp = Signature::new(&decomp_proof.digest_with::<H>(), private_key_ed);
+
+	Ok(SignedProof::new(decomp_proof, signature_on_decomp))
+    }
 }
 
 
@@ -67,11 +172,16 @@ impl<E: PairingEngine> DecompProof<E> {
 
     // Method for verifying decomposition proofs under some configuration.
     pub fn verify(&self, config: &Config<E>) -> Result<(), PVSSError<E>> {
+	self.verify_with_context(config, &[])
+    }
+
+    // Method for verifying decomposition proofs bound to some context bytes.
+    pub fn verify_with_context(&self, config: &Config<E>, context: &[u8]) -> Result<(), PVSSError<E>> {
 	// Create a proof system for proving knowledge of discrete log
 	let dlk = DLKProof { srs: DLKSRS::<ProofGroup::<E>> { g_public_key: config.srs.g2 } };
 
 	// If you intercept a NIZKError, return a PVSSError variant.
-	if dlk.verify(&self.gs, &self.proof)
+	if dlk.verify_with_context(&self.gs, &self.proof, context)
 		.is_err() {
 	    return Err(PVSSError::NIZKProofDoesNotVerifyError);
 	}
@@ -79,14 +189,114 @@ impl<E: PairingEngine> DecompProof<E> {
 	Ok(())
     }
 
-    pub fn digest(&mut self) -> Digest {
-        let mut hasher = DefaultHasher::new();
-        self.hash(&mut hasher);
-        let byte_array= hasher.finish().to_ne_bytes();   // TODO: use cryptographically secure hash
-        let mut arr = [0; 32];
-        arr[..byte_array.len()].copy_from_slice(&byte_array);
+    // Deserialization reporting the failing field and byte offset.
+    pub fn deserialize_checked<R: Read>(reader: R) -> Result<Self, PVSSError<E>> {
+        Self::read_checked(&mut CountingReader::new(reader))
+    }
+
+    // Reads a DecompProof off a (possibly shared) counting reader.
+    pub(crate) fn read_checked<R: Read>(reader: &mut CountingReader<R>) -> Result<Self, PVSSError<E>> {
+        let proof = reader.read_field("proof")?;
+        let gs = reader.read_field("gs")?;
+
+        Ok(DecompProof { proof, gs })
+    }
+
+    // Method returning the digest of the proof, under the default hasher.
+    pub fn digest(&self) -> Digest {
+        self.digest_with::<DefaultDigestHasher>()
+    }
+
+    // Method returning the digest of the proof's serialization, under the hasher H.
+    pub fn digest_with<H: DigestHasher>(&self) -> Digest {
+        let mut bytes = vec![];
+        self.serialize(&mut bytes).unwrap();
+
+        Digest(H::hash(&bytes))
+    }
+}
+
+
+/* Struct DeferredVerifier accumulates signed decomposition proofs so that they can be
+   accepted right away and verified later, in a single batch. */
+pub struct DeferredVerifier<E: PairingEngine> {
+    queued: Vec<(DecompProof<E>, PublicKey, Signature)>,   // proofs along with the signer's key and signature
+}
+
+impl<E: PairingEngine> DeferredVerifier<E> {
 
-        Digest(arr)
+    // Function for creating a new (empty) deferred verifier.
+    pub fn new() -> Self {
+        Self { queued: Vec::new() }
+    }
+
+    // Method for queueing a signed decomposition proof for later verification.
+    // Returns the position of the proof in the queue, which is used for reporting failures.
+    pub fn queue(&mut self, proof: DecompProof<E>, pk: PublicKey, sig: Signature) -> usize {
+        self.queued.push((proof, pk, sig));
+        self.queued.len() - 1
+    }
+
+    // Method returning the number of queued proofs.
+    pub fn len(&self) -> usize {
+        self.queued.len()
+    }
+
+    // Method returning whether no proofs are queued.
+    pub fn is_empty(&self) -> bool {
+        self.queued.is_empty()
+    }
+
+    // Method for verifying all queued proofs and signatures, emptying the queue.
+    // The NIZK proofs and the EdDSA signatures are each batch-verified; only if a batch
+    // fails are the entries checked one by one, so that the failing positions can be reported.
+    pub fn flush<R: Rng + CryptoRng>(&mut self, rng: &mut R, config: &Config<E>) -> Result<(), Vec<(usize, PVSSError<E>)>> {
+        let queued = ark_std::mem::replace(&mut self.queued, Vec::new());
+
+        if queued.is_empty() {
+            return Ok(());
+        }
+
+        let dlk = DLKProof { srs: DLKSRS::<ProofGroup::<E>> { g_public_key: config.srs.g2 } };
+
+        // Batch-verify the NIZK proofs.
+        let stmnts = queued.iter().map(|(p, _, _)| p.gs).collect::<Vec<_>>();
+        let proofs = queued.iter().map(|(p, _, _)| p.proof).collect::<Vec<_>>();
+        let proofs_ok = dlk.batch_verify(rng, &stmnts, &proofs).is_ok();
+
+        // Batch-verify the signatures on the proofs' digests.
+        let digests = queued.iter().map(|(p, _, _)| p.clone().digest()).collect::<Vec<_>>();
+        let sigs_ok = Signature::verify_batch_digests(
+            digests.iter().zip(queued.iter()).map(|(d, (_, pk, sig))| (d, pk, sig))
+        ).is_ok();
+
+        if proofs_ok && sigs_ok {
+            return Ok(());
+        }
+
+        // Fall back to individual verification to pinpoint the failures.
+        let failures = queued
+            .iter()
+            .zip(digests.iter())
+            .enumerate()
+            .filter_map(|(i, ((proof, pk, sig), digest))| {
+                if proof.verify(config).is_err() {
+                    Some((i, PVSSError::NIZKProofDoesNotVerifyError))
+                } else if sig.verify(digest, pk).is_err() {
+                    Some((i, PVSSError::EdDSAInvalidSignatureError))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Err(failures)
+    }
+}
+
+impl<E: PairingEngine> Default for DeferredVerifier<E> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -94,9 +304,73 @@ impl<E: PairingEngine> DecompProof<E> {
 // Utility function for buffering a decomposition proof into a buffer and
 // obtaining a reference to said buffer.
 pub fn message_from_pi_i<E: PairingEngine>(pi_i: DecompProof<E>) -> Result<Vec<u8>, PVSSError<E>> {
-    let mut message_writer = Cursor::new(vec![]);
-    pi_i.serialize(&mut message_writer)?;
-    Ok(message_writer.get_ref().to_vec())
+    let mut message = vec![];
+    pi_i.serialize(&mut message)?;
+    Ok(message)
+}
+
+
+/* Module test_vectors derives fixed test vectors, for cross-validation against independent
+   implementations: a seed determines the SRS, the committee's keys and every dealing, hence the
+   bytes of the decomposition proof and PVSS share of node 0. Vectors are emitted in hex. */
+#[cfg(all(feature = "std", any(test, feature = "testing")))]
+pub mod test_vectors {
+
+    use crate::{
+        encode_hex,
+        modified_scrape::errors::PVSSError,
+        testing::fixtures::FixtureBuilder,
+    };
+
+    use ark_ec::PairingEngine;
+    use ark_serialize::CanonicalSerialize;
+
+
+    /* Struct TestVector holds the inputs of a test vector along with the hex encodings of the
+       values they yield. */
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct TestVector {
+        pub seed: u64,                 // seed of the random stream
+        pub degree: usize,             // polynomial degree (t)
+        pub num_participants: usize,   // committee size (n)
+        pub srs: String,               // the SRS drawn from the seed
+        pub decomp_proof: String,      // the decomposition proof of node 0
+        pub share: String,             // the PVSS share of node 0
+    }
+
+    impl TestVector {
+
+        // Function deriving the test vector of the given seed, for a committee of n nodes with
+        // threshold degree t.
+        pub fn generate<E: PairingEngine>(seed: u64, t: usize, n: usize) -> Result<Self, PVSSError<E>> {
+            let fixture = FixtureBuilder::new(t, n).with_seed(seed).build::<E>()?;
+            let share = &fixture.shares[0];
+
+            Ok(TestVector {
+                seed,
+                degree: t,
+                num_participants: n,
+                srs: to_hex(&fixture.config.srs)?,
+                decomp_proof: to_hex(share.signed_proof.decomp_proof())?,
+                share: to_hex(share)?,
+            })
+        }
+
+        // Method checking that re-deriving the vector from its seed and inputs yields the
+        // same bytes.
+        pub fn matches<E: PairingEngine>(&self) -> Result<bool, PVSSError<E>> {
+            Ok(Self::generate::<E>(self.seed, self.degree, self.num_participants)? == *self)
+        }
+    }
+
+
+    // Utility function encoding a value in hex.
+    fn to_hex<T: CanonicalSerialize, E: PairingEngine>(value: &T) -> Result<String, PVSSError<E>> {
+        let mut bytes = vec![];
+        value.serialize(&mut bytes)?;
+
+        Ok(encode_hex(&bytes))
+    }
 }
 
 
@@ -110,10 +384,17 @@ mod test {
     use ark_poly::UVPolynomial;
 
     use crate::signature::utils::tests::check_serialization;
-    use crate::modified_scrape::{decomp::{Decomp, DecompProof}, srs::SRS, poly::Polynomial, config::Config};
+    use crate::modified_scrape::{decomp::{Blake2sDigestHasher, Decomp, DecompProof, DefaultDigestHasher, DeferredVerifier, DigestHasher, SipDigestHasher}, srs::SRS, poly::Polynomial, config::Config, errors::PVSSError, participant::ParticipantId};
+    use crate::{generate_production_keypair, Signature};
 
     use rand::thread_rng;
     use ark_std::UniformRand;
+    use std::{collections::hash_map::DefaultHasher, hash::{Hash, Hasher}};
+
+    use super::test_vectors::TestVector;
+    use crate::decode_hex;
+    use crate::modified_scrape::share::PVSSShare;
+    use ark_serialize::CanonicalDeserialize;
 
 
     #[test]
@@ -123,7 +404,7 @@ mod test {
 
 	let t = 3;
 	let n = 10;
-	let conf = Config { srs, degree: t, num_participants: n };
+	let conf = Config::new(srs, t, n);
 	let poly = Polynomial::<E>::rand(t, rng);
 
 	let dproof = Decomp::<E>::generate(rng, &conf, &poly.coeffs[0]).unwrap();
@@ -139,7 +420,7 @@ mod test {
 
         let t = 3;
         let n = 10;
-        let conf = Config { srs, degree: t, num_participants: n };
+        let conf = Config::new(srs, t, n);
         let poly = Polynomial::<E>::rand(t, rng);
 
         let mut dproof = Decomp::<E>::generate(rng, &conf, &poly.coeffs[0]).unwrap();
@@ -160,7 +441,7 @@ mod test {
 
 	let t = 3;
 	let n = 10;
-	let conf = Config { srs, degree: t, num_participants: n };
+	let conf = Config::new(srs, t, n);
 	let poly = Polynomial::<E>::rand(t, rng);
 
 	let dproof = Decomp::<E>::generate(rng, &conf, &poly.coeffs[0]).unwrap();
@@ -168,4 +449,95 @@ mod test {
         check_serialization(dproof.clone());
     }
 
+    #[test]
+    fn test_deferred_verifier_reports_invalid() {
+        let rng = &mut thread_rng();
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+
+        let t = 3;
+        let n = 10;
+        let conf = Config::new(srs, t, n);
+
+        let mut verifier = DeferredVerifier::<E>::new();
+        for i in 0..5 {
+            let poly = Polynomial::<E>::rand(t, rng);
+            let mut dproof = Decomp::<E>::generate(rng, &conf, &poly.coeffs[0]).unwrap();
+            let (pk, sk) = generate_production_keypair();
+            let sig = Signature::new(&dproof.digest(), &sk);
+
+            // Malform the third proof after signing it.
+            if i == 2 {
+                dproof.proof.1 = <E as PairingEngine>::Fr::rand(rng);
+            }
+            assert_eq!(verifier.queue(dproof, pk, sig), i);
+        }
+        assert_eq!(verifier.len(), 5);
+
+        let failures = verifier.flush(rng, &conf).unwrap_err();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, 2);
+        assert!(matches!(failures[0].1, PVSSError::NIZKProofDoesNotVerifyError));
+
+        // The queue is emptied by flushing.
+        assert!(verifier.is_empty());
+        verifier.flush(rng, &conf).unwrap();
+    }
+
+    #[test]
+    fn test_digest_hashers() {
+        let rng = &mut thread_rng();
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+
+        let t = 3;
+        let n = 10;
+        let conf = Config::new(srs, t, n);
+        let poly = Polynomial::<E>::rand(t, rng);
+        let (pk, sk) = generate_production_keypair();
+
+        let dproof = Decomp::<E>::generate(rng, &conf, &poly.coeffs[0]).unwrap();
+
+        // Different hashers, different digests; each is deterministic.
+        let sip = dproof.digest_with::<SipDigestHasher>();
+        let blake = dproof.digest_with::<Blake2sDigestHasher>();
+        assert_ne!(sip, blake);
+        assert_eq!(sip, dproof.digest_with::<SipDigestHasher>());
+        assert_eq!(blake, dproof.digest_with::<Blake2sDigestHasher>());
+        assert_eq!(dproof.digest(), dproof.digest_with::<DefaultDigestHasher>());
+
+        // The SipHash digest is the one of the standard library's hasher, so that builds with and
+        // without std agree on it.
+        let mut hasher = DefaultHasher::new();
+        b"optrand"[..].hash(&mut hasher);
+        assert_eq!(SipDigestHasher::hash(b"optrand")[..8], hasher.finish().to_ne_bytes());
+
+        // Signing and verifying with the same hasher succeeds; mixing hashers fails.
+        let signed = Decomp::<E>::generate_signed::<_, Blake2sDigestHasher>(rng, &conf, &poly.coeffs[0], &sk).unwrap();
+        signed.verify_with::<Blake2sDigestHasher>(&conf, &pk, ParticipantId(1)).unwrap();
+        assert!(matches!(signed.verify_with::<SipDigestHasher>(&conf, &pk, ParticipantId(1)), Err(PVSSError::SignatureVerificationFailed { participant_id: 1 })));
+
+        let signed = Decomp::<E>::generate_signed::<_, DefaultDigestHasher>(rng, &conf, &poly.coeffs[0], &sk).unwrap();
+        signed.verify(&conf, &pk, ParticipantId(1)).unwrap();
+        assert!(signed.verify_with::<Blake2sDigestHasher>(&conf, &pk, ParticipantId(1)).is_err());
+    }
+
+    #[test]
+    fn test_vectors_are_stable() {
+        const SEED: u64 = 0x5eed;
+        let (t, n) = (3, 7);
+
+        // The vector is re-derived identically from its seed, and another seed yields another one.
+        let vector = TestVector::generate::<E>(SEED, t, n).unwrap();
+        assert!(vector.matches::<E>().unwrap());
+        assert_eq!(TestVector::generate::<E>(SEED, t, n).unwrap(), vector);
+        assert_ne!(TestVector::generate::<E>(SEED + 1, t, n).unwrap().decomp_proof, vector.decomp_proof);
+
+        // The emitted bytes decode to a valid proof, which is the one carried by the share.
+        let srs = SRS::<E>::deserialize(&decode_hex(&vector.srs).unwrap()[..]).unwrap();
+        let conf = Config::new(srs, t, n);
+        let dproof = DecompProof::<E>::deserialize(&decode_hex(&vector.decomp_proof).unwrap()[..]).unwrap();
+        dproof.verify(&conf).unwrap();
+
+        let share = PVSSShare::<E>::deserialize(&decode_hex(&vector.share).unwrap()[..]).unwrap();
+        assert_eq!(*share.signed_proof.decomp_proof(), dproof);
+    }
 }
@@ -1,25 +1,170 @@
-use crate::Scalar;
+use crate::{
+    modified_scrape::{
+        config::Config,
+        curve_utils::{mul_wnaf, wnaf_window},
+        errors::PVSSError,
+        participant::ParticipantId,
+    },
+    nizk::{dleq::{DLEQProof, srs::SRS as DLEQSRS}, scheme::NIZKProof, utils::errors::NIZKError},
+    Scalar,
+};
 use ark_ec::{PairingEngine, AffineCurve, ProjectiveCurve};
-use ark_ff::{Field, PrimeField};
+use ark_ff::{Field, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
 
+use rand::{CryptoRng, Rng};
+
+
+// Proof of correct decryption: a DLEQ proof (over G1) that log_{g1}(pk) = log_{dec}(enc),
+// i.e., that the decryptor's secret key relates the decrypted share to the encrypted one.
+pub type DecryptionProof<E> = <DLEQProof<<E as PairingEngine>::G1Affine, <E as PairingEngine>::G1Affine> as NIZKProof>::Proof;
 
 // Struct DecryptedShare represents a decrypted share obtained when a node cancels out its secret
-// key from some given encrypted share.
-// NOTE: It should be noted that without the use of DLEQs, it is not possible to define verification
-// of decryptions.
-#[derive(Clone)]
+// key from some given encrypted share, along with a proof that the decryption is correct.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct DecryptedShare<E: PairingEngine> {
-    dec: E::G1Affine,   // the decrypted share
-    origin: usize,      // index in the pk_map
+    pub participant_id: ParticipantId,   // id of the decrypting participant
+    pub point: E::G1Affine,              // the decrypted share
+    pub proof: DecryptionProof<E>,       // proof of correct decryption
 }
 
 impl<E: PairingEngine> DecryptedShare<E> {
 
     // Associated function for generating a decrypted share from a given encrypted share.
-    fn generate(enc: &E::G1Affine, sk: &Scalar<E>, my_id: usize) -> DecryptedShare<E> {
-	// dec := enc * sk^{-1}
-	let dec = enc.mul(sk.inverse().unwrap().into_repr()).into_affine();
+    pub fn generate<R: Rng + CryptoRng>(
+        rng: &mut R,
+        conf: &Config<E>,
+        enc: &E::G1Affine,
+        sk: &Scalar<E>,
+        my_id: ParticipantId,
+    ) -> Result<Self, PVSSError<E>> {
+        let point = decrypt_share(enc, sk, my_id.index())?;
+        let proof = prove_decryption(rng, conf, &point, sk)?;
+
+        Ok(DecryptedShare { participant_id: my_id, point, proof })
+    }
+
+    // Method for verifying the decrypted share against the encrypted share it stems from and
+    // the decryptor's public key.
+    pub fn verify(&self, conf: &Config<E>, pk: &E::G1Affine, enc: &E::G1Affine) -> Result<(), PVSSError<E>> {
+        if !verify_decryption(conf, pk, enc, &self.point, &self.proof) {
+            return Err(PVSSError::DecryptionProofVerificationFailed { participant_id: self.participant_id.index() });
+        }
+
+        Ok(())
+    }
+}
+
+
+// Function for decrypting participant i's encrypted share: dec := enc * sk^{-1}.
+// An identity encryption is rejected, as it never encrypts a legitimate (non-trivial) share and
+// would otherwise decrypt to the identity, i.e., to an apparently valid g1^0.
+pub fn decrypt_share<E: PairingEngine>(
+    enc: &E::G1Affine,
+    sk: &Scalar<E>,
+    i: usize,
+) -> Result<E::G1Affine, PVSSError<E>> {
+    if enc.is_zero() {
+        return Err(PVSSError::IdentityEncryption(i));
+    }
+
+    let sk_inv = sk.inverse().ok_or(PVSSError::ZeroSecretKey)?;
+    let window = wnaf_window(Scalar::<E>::size_in_bits());
+
+    Ok(mul_wnaf(&enc.into_projective(), &sk_inv, window).into_affine())
+}
+
+
+// Function for decrypting an encrypted share (dec := enc * sk^{-1}) and proving that the
+// decryption is correct w.r.t. the decryptor's public key pk = g1 * sk.
+pub fn decrypt_with_proof<E: PairingEngine, R: Rng + CryptoRng>(
+    rng: &mut R,
+    conf: &Config<E>,
+    enc: &E::G1Affine,
+    sk: &Scalar<E>,
+) -> Result<(E::G1Affine, DecryptionProof<E>), PVSSError<E>> {
+    let sk_inv = sk.inverse().ok_or(PVSSError::ZeroSecretKey)?;
+    let dec = enc.mul(sk_inv.into_repr()).into_affine();
+    let proof = prove_decryption(rng, conf, &dec, sk)?;
+
+    Ok((dec, proof))
+}
+
+
+// Utility function for proving that dec is the decryption of some encryption under sk.
+fn prove_decryption<E: PairingEngine, R: Rng + CryptoRng>(
+    rng: &mut R,
+    conf: &Config<E>,
+    dec: &E::G1Affine,
+    sk: &Scalar<E>,
+) -> Result<DecryptionProof<E>, PVSSError<E>> {
+    let dleq = DLEQProof { srs: DLEQSRS { g_public_key: conf.srs.g1, h_public_key: *dec } };
+
+    dleq.prove(rng, sk).map_err(|e| match e {
+        NIZKError::SerializationError(e) => PVSSError::SerializationError(e),
+        _ => PVSSError::NIZKProofDoesNotVerifyError,
+    })
+}
+
+
+// Function for verifying a decrypted share against the encrypted share it stems from and the
+// decryptor's public key.
+pub fn verify_decryption<E: PairingEngine>(
+    conf: &Config<E>,
+    pk: &E::G1Affine,
+    enc: &E::G1Affine,
+    dec: &E::G1Affine,
+    proof: &DecryptionProof<E>,
+) -> bool {
+    let dleq = DLEQProof { srs: DLEQSRS { g_public_key: conf.srs.g1, h_public_key: *dec } };
+
+    dleq.verify(&(*pk, *enc), proof).is_ok()
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{decrypt_share, decrypt_with_proof, DecryptedShare};
+    use crate::{
+        modified_scrape::{config::Config, errors::PVSSError, participant::ParticipantId, srs::SRS},
+        Scalar,
+    };
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+    use ark_ff::{PrimeField, UniformRand, Zero};
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_decrypt_share() {
+        let rng = &mut thread_rng();
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, 1, 3);
+
+        let sk = Scalar::<E>::rand(rng);
+        let pk = conf.srs.g1.mul(sk.into_repr()).into_affine();
+        let share = Scalar::<E>::rand(rng);
+
+        // A genuine encryption pk^{share} decrypts to g1^{share}.
+        let enc = pk.mul(share.into_repr()).into_affine();
+        assert_eq!(decrypt_share::<E>(&enc, &sk, 1).unwrap(), conf.srs.g1.mul(share.into_repr()).into_affine());
+
+        let decrypted = DecryptedShare::generate(rng, &conf, &enc, &sk, ParticipantId(1)).unwrap();
+        decrypted.verify(&conf, &pk, &enc).unwrap();
+
+        // An identity encryption is rejected.
+        let identity = <E as PairingEngine>::G1Affine::zero();
+        assert!(matches!(decrypt_share::<E>(&identity, &sk, 2), Err(PVSSError::IdentityEncryption(2))));
+        assert!(matches!(DecryptedShare::generate(rng, &conf, &identity, &sk, ParticipantId(2)), Err(PVSSError::IdentityEncryption(2))));
 
-    	DecryptedShare {dec, origin: my_id}
+        // A zero secret key has no inverse, and is rejected rather than panicking.
+        let zero = Scalar::<E>::zero();
+        assert!(matches!(decrypt_share::<E>(&enc, &zero, 1), Err(PVSSError::ZeroSecretKey)));
+        assert!(matches!(decrypt_with_proof(rng, &conf, &enc, &zero), Err(PVSSError::ZeroSecretKey)));
     }
 }
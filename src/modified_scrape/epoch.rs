@@ -0,0 +1,228 @@
+use crate::{
+    modified_scrape::{
+        config::Config,
+        decryption::DecryptionProof,
+        errors::PVSSError,
+        participant::ParticipantRegistry,
+        pvss::PVSSShareSecrets,
+        roles::OptrandScheme,
+        share::{PVSSAggregatedShare, PVSSShare},
+        snapshot::{open_snapshot, validate_aggregate, SNAPSHOT_VERSION},
+        verifier::EpochTranscript,
+    },
+    read_array,
+    SecretKey,
+};
+
+use ark_ec::PairingEngine;
+use ark_serialize::{CanonicalSerialize, Write};
+use ark_std::{collections::BTreeMap, vec::Vec};
+
+use rand::{CryptoRng, Rng};
+
+
+// Function returning the context bytes that bind the decomposition proofs of an epoch to the
+// epoch number and the previous epoch's output.
+pub fn chain_context(epoch: u64, prev_output: &[u8; 32]) -> Vec<u8> {
+    [&epoch.to_le_bytes()[..], &prev_output[..]].concat()
+}
+
+
+/* Struct EpochState tracks the current epoch of the chain: its number, the output of the
+   previous epoch, and the aggregate being collected. Shares dealt in an epoch are bound to the
+   previous output, so they cannot be precomputed before that output is known. */
+#[derive(Clone)]
+pub struct EpochState<E: PairingEngine> {
+    pub epoch: u64,                            // the current epoch
+    pub prev_output: [u8; 32],                 // the previous epoch's output (or the genesis value)
+    pub aggregate: PVSSAggregatedShare<E>,     // the current epoch's aggregated PVSS share
+}
+
+impl<E: PairingEngine> EpochState<E> {
+
+    // Function for creating the state of epoch 0, chained to a genesis value.
+    pub fn new(config: &Config<E>, genesis: [u8; 32]) -> Self {
+        Self {
+            epoch: 0,
+            prev_output: genesis,
+            aggregate: PVSSAggregatedShare::empty(config.degree, config.num_participants),
+        }
+    }
+
+    // Method returning the context bytes of the current epoch.
+    pub fn context(&self) -> Vec<u8> {
+        chain_context(self.epoch, &self.prev_output)
+    }
+
+    // Method for dealing a PVSS share of the current epoch.
+    pub fn deal<R: Rng + CryptoRng>(
+        &self,
+        rng: &mut R,
+        config: &Config<E>,
+        registry: &ParticipantRegistry<E>,
+        my_id: usize,
+        private_key_ed: &SecretKey,
+    ) -> Result<(PVSSShare<E>, PVSSShareSecrets<E>), PVSSError<E>> {
+        OptrandScheme::new(config.clone()).deal_with_context(rng, registry, my_id, private_key_ed, &self.context())
+    }
+
+    // Method for verifying a PVSS share of the current epoch and folding it into the aggregate.
+    pub fn receive_share<R: Rng + CryptoRng>(
+        &mut self,
+        rng: &mut R,
+        config: &Config<E>,
+        registry: &ParticipantRegistry<E>,
+        share: &PVSSShare<E>,
+    ) -> Result<(), PVSSError<E>> {
+        OptrandScheme::new(config.clone())
+            .aggregator(registry)
+            .share_verify_with_context(rng, share, &self.context())?;
+
+        self.aggregate.fold_share(share)
+    }
+
+    // Method for assembling the current epoch's transcript.
+    pub fn transcript(
+        &self,
+        decryptions: BTreeMap<usize, (E::G1Affine, DecryptionProof<E>)>,
+        beacon: [u8; 32],
+    ) -> EpochTranscript<E> {
+        EpochTranscript {
+            epoch: self.epoch,
+            prev_output: self.prev_output,
+            aggregated: self.aggregate.clone(),
+            decryptions,
+            beacon,
+        }
+    }
+
+    // Method for rolling the state forward, once the current epoch produced its output.
+    pub fn advance(&mut self, output: [u8; 32]) {
+        self.epoch += 1;
+        self.prev_output = output;
+        self.aggregate = PVSSAggregatedShare::empty(self.aggregate.degree, self.aggregate.num_participants);
+    }
+
+
+    // Method for persisting the state: a version byte, followed by the epoch, the previous
+    // output and the aggregate collected so far.
+    pub fn snapshot(&self) -> Vec<u8> {
+        let mut bytes = vec![SNAPSHOT_VERSION];
+
+        self.epoch.serialize(&mut bytes).unwrap();
+        bytes.write_all(&self.prev_output).unwrap();
+        self.aggregate.serialize(&mut bytes).unwrap();
+
+        bytes
+    }
+
+    // Function for restoring the state from a snapshot taken under the given configuration.
+    // The registry is not part of the state, but is needed to re-verify the aggregate, whose
+    // contributions must be bound to the restored epoch and previous output.
+    pub fn restore<R: Rng + CryptoRng>(rng: &mut R, config: &Config<E>, registry: &ParticipantRegistry<E>, bytes: &[u8]) -> Result<Self, PVSSError<E>> {
+        let mut reader = open_snapshot(bytes)?;
+        let epoch = reader.read_field("epoch")?;
+        let prev_output = read_array(&mut reader).map_err(|source| PVSSError::DeserializationFailed {
+            field: "prev_output",
+            offset: reader.offset(),
+            source,
+        })?;
+        let aggregate = reader.read_field("aggregate")?;
+
+        let state = Self { epoch, prev_output, aggregate };
+        validate_aggregate(rng, &mut OptrandScheme::new(config.clone()).aggregator(registry), &state.aggregate, &state.context())?;
+
+        Ok(state)
+    }
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::EpochState;
+    use crate::{
+        modified_scrape::{
+            decryption::decrypt_with_proof,
+            errors::PVSSError,
+            recon::{beacon, reconstruct},
+            snapshot::SNAPSHOT_VERSION,
+            verifier::{verify_chain, verify_epoch},
+        },
+        testing::fixtures::FixtureBuilder,
+    };
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_std::collections::BTreeMap;
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_three_epoch_chain() {
+        let rng = &mut thread_rng();
+        let t = 1;
+        let n = 4;
+
+        // The committee's dealings are bound to each epoch, so only its keys are used.
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let (conf, registry) = (fixture.config, fixture.registry);
+
+        let genesis = [0u8; 32];
+        let mut state = EpochState::new(&conf, genesis);
+        let mut transcripts = vec![];
+
+        for _ in 0..3 {
+            // Everyone deals; the shares are verified and aggregated. Midway, the state is
+            // persisted and restored.
+            for (id, keys) in fixture.keypairs.iter().enumerate() {
+                let (share, _) = state.deal(rng, &conf, &registry, id, &keys.secret_key_ed).unwrap();
+                state.receive_share(rng, &conf, &registry, &share).unwrap();
+
+                if id == n / 2 {
+                    state = EpochState::restore(rng, &conf, &registry, &state.snapshot()).unwrap();
+                }
+            }
+
+            // Everyone decrypts its share; the beacon output is reconstructed.
+            let decryptions = fixture
+                .keypairs
+                .iter()
+                .enumerate()
+                .map(|(id, keys)| (id, decrypt_with_proof(rng, &conf, &state.aggregate.pvss_core.encs[id], &keys.secret_key).unwrap()))
+                .collect::<BTreeMap<_, _>>();
+            let points = decryptions.iter().map(|(id, (dec, _))| (*id, *dec)).collect();
+            let output = beacon(&conf, &reconstruct(&points, &conf).unwrap()).unwrap();
+
+            transcripts.push(state.transcript(decryptions, output));
+            state.advance(output);
+        }
+
+        let outputs = verify_chain(rng, &conf, &registry, genesis, &transcripts).unwrap();
+        assert_eq!(outputs.len(), 3);
+        assert_eq!(state.prev_output, outputs[2]);
+
+        // A snapshot whose aggregate is bound to another epoch's output does not restore.
+        let mut stale = EpochState::new(&conf, outputs[0]);
+        stale.epoch = 1;
+        stale.aggregate = transcripts[2].aggregated.clone();
+        assert!(EpochState::restore(rng, &conf, &registry, &stale.snapshot()).is_err());
+
+        let mut bad_version = state.snapshot();
+        bad_version[0] = SNAPSHOT_VERSION + 1;
+        assert!(matches!(EpochState::restore(rng, &conf, &registry, &bad_version), Err(PVSSError::SnapshotVersionError(_))));
+
+        // Replacing epoch 2's prev_output breaks epoch 2, but not epoch 1.
+        transcripts[1].prev_output = [1u8; 32];
+        assert!(verify_epoch(rng, &conf, &registry, &transcripts[0]).is_ok());
+        match verify_epoch(rng, &conf, &registry, &transcripts[1]) {
+            Err(PVSSError::NIZKProofDoesNotVerify { .. }) => (),
+            _ => panic!("expected NIZKProofDoesNotVerify"),
+        }
+        match verify_chain(rng, &conf, &registry, genesis, &transcripts) {
+            Err(PVSSError::EpochChainError { epoch }) => assert_eq!(epoch, 1),
+            _ => panic!("expected EpochChainError"),
+        }
+    }
+}
@@ -1,15 +1,17 @@
 use crate::signature::utils::errors::SignatureError;
 use ark_ec::PairingEngine;
-use ark_serialize::SerializationError;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
 use thiserror::Error;
 
+use ark_std::{boxed::Box, convert::TryFrom, string::{String, ToString}};
+
 // Enumeration defining appropriate errors for various situations
 #[derive(Error, Debug)]
 pub enum PVSSError<E: PairingEngine> {
-    #[error("Insufficient evaluations")]
-    InsufficientEvaluationsError,
     #[error("Different number of points and evaluations")]
     DifferentPointsEvalsError,
+    #[error("Evaluation points are not distinct")]
+    NonDistinctPointsError,
     #[error("Could not generate decomposition proof")]
     DecompGenerationError,
     #[error("Invalid participant ID: {0}")]
@@ -30,6 +32,8 @@ pub enum PVSSError<E: PairingEngine> {
     InsufficientEncryptionsInShareError(usize, usize),
     #[error("Share's encryptions vector is empty")]
     EmptyEncryptionsVectorError,
+    #[error("Share's commitments vector is empty")]
+    EmptyCommitmentsVectorError,
     #[error("Mismatched commitment vector lengths. First has: {0}, Second has: {1}")]
     MismatchedCommitmentsError(usize, usize),
     #[error("Mismatched encryption vector lengths. First has: {0}, Second has: {1}")]
@@ -42,8 +46,6 @@ pub enum PVSSError<E: PairingEngine> {
     TranscriptDifferentCommitments,
     #[error("Decomposition proof does not verify")]
     DecompProofVerificationError,
-    #[error("Insufficient number of decryptions provided for reconstruction Got: {0}, Expected: >= {1}")]
-    InsufficientDecryptionsError(usize, usize),
     #[error("Length mismatch")]
     LengthMismatchError,
     #[error("Correctness of encryption check failed")]
@@ -71,5 +73,774 @@ pub enum PVSSError<E: PairingEngine> {
     #[error("Signature error: {0}")]
     SignatureError(#[from] SignatureError),
     #[error("Serialization error: {0}")]
-    SerializationError(#[from] SerializationError),
+    SerializationError(#[cfg_attr(feature = "std", from)] SerializationError),
+    #[error("Insufficient shares. Needed: {needed}, Got: {got}")]
+    InsufficientShares { needed: usize, got: usize },
+    #[error("Threshold not met. Threshold: {threshold}, Got: {got}")]
+    ThresholdNotMet { threshold: usize, got: usize },
+    #[error("NIZK proof of participant {participant_id} does not verify")]
+    NIZKProofDoesNotVerify { participant_id: usize },
+    #[error("Signature of participant {participant_id} does not verify")]
+    SignatureVerificationFailed { participant_id: usize },
+    #[error("Deserialization of field {field} failed at byte offset {offset}: {source}")]
+    DeserializationFailed { field: &'static str, offset: usize, source: SerializationError },
+    #[error("A share has already been dealt for epoch {0}")]
+    AlreadyDealtError(u64),
+    #[error("Decryption proof of participant {participant_id} does not verify")]
+    DecryptionProofVerificationFailed { participant_id: usize },
+    #[error("Claimed beacon output does not match the recomputed one")]
+    BeaconMismatchError,
+    #[error("Transcript of epoch {epoch} does not chain to the previous output")]
+    EpochChainError { epoch: u64 },
+    #[error("Unsupported snapshot version: {0}")]
+    SnapshotVersionError(u8),
+    #[error("Restored snapshot is inconsistent")]
+    SnapshotIntegrityError,
+    #[error("Insufficient weight. Threshold: {threshold}, Got: {got}")]
+    InsufficientWeight { threshold: u64, got: u64 },
+    #[error("Complaint against participant {accused_id} does not hold")]
+    InvalidComplaint { accused_id: usize },
+    #[error("Encryption of participant {0} is the identity")]
+    IdentityEncryption(usize),
+    #[error("Wrong core length. Got: {0}, Expected: {1}")]
+    WrongCoreLength(usize, usize),
+    #[error("Draining stopped after {folded} shares")]
+    DrainInterrupted { folded: usize, source: Box<PVSSError<E>> },
+    #[error("Bytes are not a canonical scalar encoding")]
+    NonCanonicalScalar,
+    #[error("Batched cores hold different numbers of sharings. First has: {0}, Second has: {1}")]
+    MismatchedBatchSizes(usize, usize),
+    #[error("Committee of {0} participants does not fit in the scalar field")]
+    CommitteeTooLargeForField(usize),
+    #[error("Invalid JSON transcript: {0}")]
+    InvalidJson(String),
+    #[error("Invalid wire format: {0}")]
+    InvalidWireFormat(String),
+    #[error("Decoded wire payload too large. Got: {0}, Max: {1}")]
+    WireSizeExceeded(usize, usize),
+    #[error("Transcript has no contributions")]
+    EmptyTranscript,
+    #[error("I/O error: {0}")]
+    IoError(String),
+    #[error("Invalid transcript archive: {0}")]
+    InvalidArchive(String),
+    #[error("Too many digests. Got: {0}, Max: {1}")]
+    TooManyDigests(usize, usize),
+    #[error("Invalid message envelope: {0}")]
+    InvalidEnvelope(String),
+    #[error("Transcripts were produced under configurations with different digests")]
+    ConfigMismatch,
+    #[error("Invalid Merkle membership proof for the contribution of participant {0}")]
+    InvalidMembershipProof(usize),
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+    #[error("Point at index {0} is not in the prime order subgroup")]
+    NotInSubgroup(usize),
+    #[error("Recovery share of helper {participant_id} does not verify")]
+    RecoveryShareVerificationFailed { participant_id: usize },
+    #[error("Resharing dealt by participant {participant_id} does not verify")]
+    ReshareVerificationFailed { participant_id: usize },
+    #[error("Secret key is zero, and cannot decrypt")]
+    ZeroSecretKey,
+}
+
+
+// Enumeration PVSSErrorKind mirrors PVSSError without the pairing engine parameter,
+// so that errors can cross engine-agnostic boundaries (e.g., FFI layers or Box<dyn Error>).
+// Engine-specific (and non-cloneable) payloads are carried in stringified form.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PVSSErrorKind {
+    #[error("Different number of points and evaluations")]
+    DifferentPointsEvalsError,
+    #[error("Evaluation points are not distinct")]
+    NonDistinctPointsError,
+    #[error("Could not generate decomposition proof")]
+    DecompGenerationError,
+    #[error("Invalid participant ID: {0}")]
+    InvalidParticipantId(usize),
+    #[error("Mismatch between provided encryptions ({0} given), commitments ({1} given), and participants ({2} given)")]
+    MismatchedCommitsEncryptionsParticipantsError(usize, usize, usize),
+    #[error("Degree check failed. Dual code condition does not hold")]
+    DualCodeError,
+    #[error("gs check failed")]
+    GSCheckError,
+    #[error("Empty shares vector provided")]
+    EmptySharesVectorError,
+    #[error("Insufficient elements in the identities vector")]
+    InsufficientIdsError,
+    #[error("Insufficient commitments in PVSS share. Found: {0}, Expected: {1}")]
+    InsufficientCommitsInShareError(usize, usize),
+    #[error("Insufficient encryptions in PVSS share. Found: {0}, Expected: {1}")]
+    InsufficientEncryptionsInShareError(usize, usize),
+    #[error("Share's encryptions vector is empty")]
+    EmptyEncryptionsVectorError,
+    #[error("Share's commitments vector is empty")]
+    EmptyCommitmentsVectorError,
+    #[error("Mismatched commitment vector lengths. First has: {0}, Second has: {1}")]
+    MismatchedCommitmentsError(usize, usize),
+    #[error("Mismatched encryption vector lengths. First has: {0}, Second has: {1}")]
+    MismatchedEncryptionsError(usize, usize),
+    #[error("Mismatched commitment and encryption vector lengths within share. First has: {0}, Second has: {1}")]
+    MismatchedCommitmentsEncryptionsError(usize, usize),
+    #[error("Transcripts have different degree or number of participants: self.degree={0}, other.degree={1}, self.num_participants={2}, self.num_participants={3}")]
+    TranscriptDifferentConfig(usize, usize, usize, usize),
+    #[error("Transcripts have different commitments")]
+    TranscriptDifferentCommitments,
+    #[error("Decomposition proof does not verify")]
+    DecompProofVerificationError,
+    #[error("Length mismatch")]
+    LengthMismatchError,
+    #[error("Correctness of encryption check failed")]
+    EncryptionCorrectnessError,
+    #[error("Decomposition in transcript is incorrect")]
+    DecompositionInTranscriptError,
+    #[error("Aggregated gs from transcript does not match reconstruction")]
+    AggregationReconstructionMismatchError,
+    #[error("EdDSA signature is invalid")]
+    EdDSAInvalidSignatureError,
+    #[error("EdDSA batch of signatures is invalid")]
+    EdDSAInvalidSignatureBatchError,
+    #[error("NIZK proof does not verify")]
+    NIZKProofDoesNotVerifyError,
+
+
+    #[error("Ratio incorrect")]
+    RatioIncorrect,
+    #[error("Evaluations are wrong: product = {0}")]
+    EvaluationsCheckError(String),
+    #[error("Could not generate evaluation domain")]
+    EvaluationDomainError,
+    #[error("Config, dealer and nodes had different SRSes")]
+    DifferentSRS,
+    #[error("Signature error: {0}")]
+    SignatureError(String),
+    #[error("Serialization error: {0}")]
+    SerializationError(String),
+    #[error("Insufficient shares. Needed: {needed}, Got: {got}")]
+    InsufficientShares { needed: usize, got: usize },
+    #[error("Threshold not met. Threshold: {threshold}, Got: {got}")]
+    ThresholdNotMet { threshold: usize, got: usize },
+    #[error("NIZK proof of participant {participant_id} does not verify")]
+    NIZKProofDoesNotVerify { participant_id: usize },
+    #[error("Signature of participant {participant_id} does not verify")]
+    SignatureVerificationFailed { participant_id: usize },
+    #[error("Deserialization of field {field} failed at byte offset {offset}: {cause}")]
+    DeserializationFailed { field: &'static str, offset: usize, cause: String },
+    #[error("A share has already been dealt for epoch {0}")]
+    AlreadyDealtError(u64),
+    #[error("Decryption proof of participant {participant_id} does not verify")]
+    DecryptionProofVerificationFailed { participant_id: usize },
+    #[error("Claimed beacon output does not match the recomputed one")]
+    BeaconMismatchError,
+    #[error("Transcript of epoch {epoch} does not chain to the previous output")]
+    EpochChainError { epoch: u64 },
+    #[error("Unsupported snapshot version: {0}")]
+    SnapshotVersionError(u8),
+    #[error("Restored snapshot is inconsistent")]
+    SnapshotIntegrityError,
+    #[error("Insufficient weight. Threshold: {threshold}, Got: {got}")]
+    InsufficientWeight { threshold: u64, got: u64 },
+    #[error("Complaint against participant {accused_id} does not hold")]
+    InvalidComplaint { accused_id: usize },
+    #[error("Encryption of participant {0} is the identity")]
+    IdentityEncryption(usize),
+    #[error("Wrong core length. Got: {0}, Expected: {1}")]
+    WrongCoreLength(usize, usize),
+    #[error("Draining stopped after {folded} shares: {cause}")]
+    DrainInterrupted { folded: usize, cause: String },
+    #[error("Bytes are not a canonical scalar encoding")]
+    NonCanonicalScalar,
+    #[error("Batched cores hold different numbers of sharings. First has: {0}, Second has: {1}")]
+    MismatchedBatchSizes(usize, usize),
+    #[error("Committee of {0} participants does not fit in the scalar field")]
+    CommitteeTooLargeForField(usize),
+    #[error("Invalid JSON transcript: {0}")]
+    InvalidJson(String),
+    #[error("Invalid wire format: {0}")]
+    InvalidWireFormat(String),
+    #[error("Decoded wire payload too large. Got: {0}, Max: {1}")]
+    WireSizeExceeded(usize, usize),
+    #[error("Transcript has no contributions")]
+    EmptyTranscript,
+    #[error("I/O error: {0}")]
+    IoError(String),
+    #[error("Invalid transcript archive: {0}")]
+    InvalidArchive(String),
+    #[error("Too many digests. Got: {0}, Max: {1}")]
+    TooManyDigests(usize, usize),
+    #[error("Invalid message envelope: {0}")]
+    InvalidEnvelope(String),
+    #[error("Transcripts were produced under configurations with different digests")]
+    ConfigMismatch,
+    #[error("Invalid Merkle membership proof for the contribution of participant {0}")]
+    InvalidMembershipProof(usize),
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+    #[error("Point at index {0} is not in the prime order subgroup")]
+    NotInSubgroup(usize),
+    #[error("Recovery share of helper {participant_id} does not verify")]
+    RecoveryShareVerificationFailed { participant_id: usize },
+    #[error("Resharing dealt by participant {participant_id} does not verify")]
+    ReshareVerificationFailed { participant_id: usize },
+    #[error("Secret key is zero, and cannot decrypt")]
+    ZeroSecretKey,
+}
+
+
+impl<E: PairingEngine> PVSSError<E> {
+
+    // Method returning the engine-agnostic kind of this error.
+    pub fn kind(&self) -> PVSSErrorKind {
+        match self {
+            PVSSError::DifferentPointsEvalsError => PVSSErrorKind::DifferentPointsEvalsError,
+            PVSSError::NonDistinctPointsError => PVSSErrorKind::NonDistinctPointsError,
+            PVSSError::DecompGenerationError => PVSSErrorKind::DecompGenerationError,
+            PVSSError::InvalidParticipantId(id) => PVSSErrorKind::InvalidParticipantId(*id),
+            PVSSError::MismatchedCommitsEncryptionsParticipantsError(e, c, p) =>
+                PVSSErrorKind::MismatchedCommitsEncryptionsParticipantsError(*e, *c, *p),
+            PVSSError::DualCodeError => PVSSErrorKind::DualCodeError,
+            PVSSError::GSCheckError => PVSSErrorKind::GSCheckError,
+            PVSSError::EmptySharesVectorError => PVSSErrorKind::EmptySharesVectorError,
+            PVSSError::InsufficientIdsError => PVSSErrorKind::InsufficientIdsError,
+            PVSSError::InsufficientCommitsInShareError(f, e) => PVSSErrorKind::InsufficientCommitsInShareError(*f, *e),
+            PVSSError::InsufficientEncryptionsInShareError(f, e) => PVSSErrorKind::InsufficientEncryptionsInShareError(*f, *e),
+            PVSSError::EmptyEncryptionsVectorError => PVSSErrorKind::EmptyEncryptionsVectorError,
+            PVSSError::MismatchedCommitmentsError(a, b) => PVSSErrorKind::MismatchedCommitmentsError(*a, *b),
+            PVSSError::MismatchedEncryptionsError(a, b) => PVSSErrorKind::MismatchedEncryptionsError(*a, *b),
+            PVSSError::MismatchedCommitmentsEncryptionsError(a, b) =>
+                PVSSErrorKind::MismatchedCommitmentsEncryptionsError(*a, *b),
+            PVSSError::TranscriptDifferentConfig(d1, d2, n1, n2) =>
+                PVSSErrorKind::TranscriptDifferentConfig(*d1, *d2, *n1, *n2),
+            PVSSError::TranscriptDifferentCommitments => PVSSErrorKind::TranscriptDifferentCommitments,
+            PVSSError::DecompProofVerificationError => PVSSErrorKind::DecompProofVerificationError,
+            PVSSError::LengthMismatchError => PVSSErrorKind::LengthMismatchError,
+            PVSSError::EncryptionCorrectnessError => PVSSErrorKind::EncryptionCorrectnessError,
+            PVSSError::DecompositionInTranscriptError => PVSSErrorKind::DecompositionInTranscriptError,
+            PVSSError::AggregationReconstructionMismatchError => PVSSErrorKind::AggregationReconstructionMismatchError,
+            PVSSError::EdDSAInvalidSignatureError => PVSSErrorKind::EdDSAInvalidSignatureError,
+            PVSSError::EdDSAInvalidSignatureBatchError => PVSSErrorKind::EdDSAInvalidSignatureBatchError,
+            PVSSError::NIZKProofDoesNotVerifyError => PVSSErrorKind::NIZKProofDoesNotVerifyError,
+            PVSSError::RatioIncorrect => PVSSErrorKind::RatioIncorrect,
+            PVSSError::EvaluationsCheckError(p) => PVSSErrorKind::EvaluationsCheckError(p.to_string()),
+            PVSSError::EvaluationDomainError => PVSSErrorKind::EvaluationDomainError,
+            PVSSError::DifferentSRS => PVSSErrorKind::DifferentSRS,
+            PVSSError::SignatureError(e) => PVSSErrorKind::SignatureError(e.to_string()),
+            PVSSError::SerializationError(e) => PVSSErrorKind::SerializationError(e.to_string()),
+            PVSSError::InsufficientShares { needed, got } => PVSSErrorKind::InsufficientShares { needed: *needed, got: *got },
+            PVSSError::ThresholdNotMet { threshold, got } => PVSSErrorKind::ThresholdNotMet { threshold: *threshold, got: *got },
+            PVSSError::NIZKProofDoesNotVerify { participant_id } => PVSSErrorKind::NIZKProofDoesNotVerify { participant_id: *participant_id },
+            PVSSError::SignatureVerificationFailed { participant_id } => PVSSErrorKind::SignatureVerificationFailed { participant_id: *participant_id },
+            PVSSError::DeserializationFailed { field, offset, source } => PVSSErrorKind::DeserializationFailed { field: *field, offset: *offset, cause: source.to_string() },
+            PVSSError::AlreadyDealtError(epoch) => PVSSErrorKind::AlreadyDealtError(*epoch),
+            PVSSError::DecryptionProofVerificationFailed { participant_id } => PVSSErrorKind::DecryptionProofVerificationFailed { participant_id: *participant_id },
+            PVSSError::BeaconMismatchError => PVSSErrorKind::BeaconMismatchError,
+            PVSSError::EpochChainError { epoch } => PVSSErrorKind::EpochChainError { epoch: *epoch },
+            PVSSError::SnapshotVersionError(version) => PVSSErrorKind::SnapshotVersionError(*version),
+            PVSSError::SnapshotIntegrityError => PVSSErrorKind::SnapshotIntegrityError,
+            PVSSError::InsufficientWeight { threshold, got } => PVSSErrorKind::InsufficientWeight { threshold: *threshold, got: *got },
+            PVSSError::InvalidComplaint { accused_id } => PVSSErrorKind::InvalidComplaint { accused_id: *accused_id },
+            PVSSError::IdentityEncryption(i) => PVSSErrorKind::IdentityEncryption(*i),
+            PVSSError::WrongCoreLength(a, b) => PVSSErrorKind::WrongCoreLength(*a, *b),
+            PVSSError::DrainInterrupted { folded, source } => PVSSErrorKind::DrainInterrupted { folded: *folded, cause: source.to_string() },
+            PVSSError::NonCanonicalScalar => PVSSErrorKind::NonCanonicalScalar,
+            PVSSError::MismatchedBatchSizes(a, b) => PVSSErrorKind::MismatchedBatchSizes(*a, *b),
+            PVSSError::CommitteeTooLargeForField(n) => PVSSErrorKind::CommitteeTooLargeForField(*n),
+            PVSSError::InvalidJson(msg) => PVSSErrorKind::InvalidJson(msg.clone()),
+            PVSSError::InvalidWireFormat(msg) => PVSSErrorKind::InvalidWireFormat(msg.clone()),
+            PVSSError::WireSizeExceeded(got, max) => PVSSErrorKind::WireSizeExceeded(*got, *max),
+            PVSSError::EmptyTranscript => PVSSErrorKind::EmptyTranscript,
+            PVSSError::IoError(msg) => PVSSErrorKind::IoError(msg.clone()),
+            PVSSError::InvalidArchive(msg) => PVSSErrorKind::InvalidArchive(msg.clone()),
+            PVSSError::TooManyDigests(got, max) => PVSSErrorKind::TooManyDigests(*got, *max),
+            PVSSError::InvalidEnvelope(msg) => PVSSErrorKind::InvalidEnvelope(msg.clone()),
+            PVSSError::ConfigMismatch => PVSSErrorKind::ConfigMismatch,
+            PVSSError::EmptyCommitmentsVectorError => PVSSErrorKind::EmptyCommitmentsVectorError,
+            PVSSError::InvalidMembershipProof(id) => PVSSErrorKind::InvalidMembershipProof(*id),
+            PVSSError::InvalidConfig(msg) => PVSSErrorKind::InvalidConfig(msg.clone()),
+            PVSSError::NotInSubgroup(index) => PVSSErrorKind::NotInSubgroup(*index),
+            PVSSError::RecoveryShareVerificationFailed { participant_id } => PVSSErrorKind::RecoveryShareVerificationFailed { participant_id: *participant_id },
+            PVSSError::ReshareVerificationFailed { participant_id } => PVSSErrorKind::ReshareVerificationFailed { participant_id: *participant_id },
+            PVSSError::ZeroSecretKey => PVSSErrorKind::ZeroSecretKey,
+        }
+    }
+}
+
+
+impl<E: PairingEngine> From<PVSSError<E>> for PVSSErrorKind {
+    fn from(err: PVSSError<E>) -> Self {
+        err.kind()
+    }
+}
+
+// Without std, SerializationError does not implement Error, so it cannot be the source of a
+// PVSSError; it still converts into one.
+#[cfg(not(feature = "std"))]
+impl<E: PairingEngine> From<SerializationError> for PVSSError<E> {
+    fn from(err: SerializationError) -> Self {
+        PVSSError::SerializationError(err)
+    }
+}
+
+
+// Enumeration PVSSErrorCode assigns a stable numeric code to every error variant, so that
+// rejections can be reported across the network in a compact, language-agnostic way.
+// NOTE: Codes must never be reused or renumbered; new variants get fresh codes. Codes 1
+// (InsufficientEvaluationsError) and 19 (InsufficientDecryptionsError) are retired.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u16)]
+pub enum PVSSErrorCode {
+    DifferentPointsEvalsError = 2,
+    DecompGenerationError = 3,
+    InvalidParticipantId = 4,
+    MismatchedCommitsEncryptionsParticipantsError = 5,
+    DualCodeError = 6,
+    GSCheckError = 7,
+    EmptySharesVectorError = 8,
+    InsufficientIdsError = 9,
+    InsufficientCommitsInShareError = 10,
+    InsufficientEncryptionsInShareError = 11,
+    EmptyEncryptionsVectorError = 12,
+    MismatchedCommitmentsError = 13,
+    MismatchedEncryptionsError = 14,
+    MismatchedCommitmentsEncryptionsError = 15,
+    TranscriptDifferentConfig = 16,
+    TranscriptDifferentCommitments = 17,
+    DecompProofVerificationError = 18,
+    LengthMismatchError = 20,
+    EncryptionCorrectnessError = 21,
+    DecompositionInTranscriptError = 22,
+    AggregationReconstructionMismatchError = 23,
+    EdDSAInvalidSignatureError = 24,
+    EdDSAInvalidSignatureBatchError = 25,
+    NIZKProofDoesNotVerifyError = 26,
+    RatioIncorrect = 27,
+    EvaluationsCheckError = 28,
+    EvaluationDomainError = 29,
+    DifferentSRS = 30,
+    SignatureError = 31,
+    SerializationError = 32,
+    NonDistinctPointsError = 33,
+    InsufficientShares = 34,
+    ThresholdNotMet = 35,
+    NIZKProofDoesNotVerify = 36,
+    SignatureVerificationFailed = 37,
+    DeserializationFailed = 38,
+    AlreadyDealtError = 39,
+    DecryptionProofVerificationFailed = 40,
+    BeaconMismatchError = 41,
+    EpochChainError = 42,
+    SnapshotVersionError = 43,
+    SnapshotIntegrityError = 44,
+    InsufficientWeight = 45,
+    InvalidComplaint = 46,
+    IdentityEncryption = 47,
+    WrongCoreLength = 48,
+    DrainInterrupted = 49,
+    NonCanonicalScalar = 50,
+    MismatchedBatchSizes = 51,
+    CommitteeTooLargeForField = 52,
+    InvalidJson = 53,
+    InvalidWireFormat = 54,
+    WireSizeExceeded = 55,
+    EmptyTranscript = 56,
+    IoError = 57,
+    InvalidArchive = 58,
+    TooManyDigests = 59,
+    InvalidEnvelope = 60,
+    ConfigMismatch = 61,
+    EmptyCommitmentsVectorError = 62,
+    InvalidMembershipProof = 63,
+    InvalidConfig = 64,
+    NotInSubgroup = 65,
+    RecoveryShareVerificationFailed = 66,
+    ReshareVerificationFailed = 67,
+    ZeroSecretKey = 68,
+}
+
+impl PVSSErrorKind {
+
+    // Method returning the stable error code of this kind.
+    pub fn code(&self) -> PVSSErrorCode {
+        match self {
+            PVSSErrorKind::DifferentPointsEvalsError => PVSSErrorCode::DifferentPointsEvalsError,
+            PVSSErrorKind::DecompGenerationError => PVSSErrorCode::DecompGenerationError,
+            PVSSErrorKind::InvalidParticipantId(_) => PVSSErrorCode::InvalidParticipantId,
+            PVSSErrorKind::MismatchedCommitsEncryptionsParticipantsError(..) => PVSSErrorCode::MismatchedCommitsEncryptionsParticipantsError,
+            PVSSErrorKind::DualCodeError => PVSSErrorCode::DualCodeError,
+            PVSSErrorKind::GSCheckError => PVSSErrorCode::GSCheckError,
+            PVSSErrorKind::EmptySharesVectorError => PVSSErrorCode::EmptySharesVectorError,
+            PVSSErrorKind::InsufficientIdsError => PVSSErrorCode::InsufficientIdsError,
+            PVSSErrorKind::InsufficientCommitsInShareError(..) => PVSSErrorCode::InsufficientCommitsInShareError,
+            PVSSErrorKind::InsufficientEncryptionsInShareError(..) => PVSSErrorCode::InsufficientEncryptionsInShareError,
+            PVSSErrorKind::EmptyEncryptionsVectorError => PVSSErrorCode::EmptyEncryptionsVectorError,
+            PVSSErrorKind::MismatchedCommitmentsError(..) => PVSSErrorCode::MismatchedCommitmentsError,
+            PVSSErrorKind::MismatchedEncryptionsError(..) => PVSSErrorCode::MismatchedEncryptionsError,
+            PVSSErrorKind::MismatchedCommitmentsEncryptionsError(..) => PVSSErrorCode::MismatchedCommitmentsEncryptionsError,
+            PVSSErrorKind::TranscriptDifferentConfig(..) => PVSSErrorCode::TranscriptDifferentConfig,
+            PVSSErrorKind::TranscriptDifferentCommitments => PVSSErrorCode::TranscriptDifferentCommitments,
+            PVSSErrorKind::DecompProofVerificationError => PVSSErrorCode::DecompProofVerificationError,
+            PVSSErrorKind::LengthMismatchError => PVSSErrorCode::LengthMismatchError,
+            PVSSErrorKind::EncryptionCorrectnessError => PVSSErrorCode::EncryptionCorrectnessError,
+            PVSSErrorKind::DecompositionInTranscriptError => PVSSErrorCode::DecompositionInTranscriptError,
+            PVSSErrorKind::AggregationReconstructionMismatchError => PVSSErrorCode::AggregationReconstructionMismatchError,
+            PVSSErrorKind::EdDSAInvalidSignatureError => PVSSErrorCode::EdDSAInvalidSignatureError,
+            PVSSErrorKind::EdDSAInvalidSignatureBatchError => PVSSErrorCode::EdDSAInvalidSignatureBatchError,
+            PVSSErrorKind::NIZKProofDoesNotVerifyError => PVSSErrorCode::NIZKProofDoesNotVerifyError,
+            PVSSErrorKind::RatioIncorrect => PVSSErrorCode::RatioIncorrect,
+            PVSSErrorKind::EvaluationsCheckError(_) => PVSSErrorCode::EvaluationsCheckError,
+            PVSSErrorKind::EvaluationDomainError => PVSSErrorCode::EvaluationDomainError,
+            PVSSErrorKind::DifferentSRS => PVSSErrorCode::DifferentSRS,
+            PVSSErrorKind::SignatureError(_) => PVSSErrorCode::SignatureError,
+            PVSSErrorKind::SerializationError(_) => PVSSErrorCode::SerializationError,
+            PVSSErrorKind::NonDistinctPointsError => PVSSErrorCode::NonDistinctPointsError,
+            PVSSErrorKind::InsufficientShares { .. } => PVSSErrorCode::InsufficientShares,
+            PVSSErrorKind::ThresholdNotMet { .. } => PVSSErrorCode::ThresholdNotMet,
+            PVSSErrorKind::NIZKProofDoesNotVerify { .. } => PVSSErrorCode::NIZKProofDoesNotVerify,
+            PVSSErrorKind::SignatureVerificationFailed { .. } => PVSSErrorCode::SignatureVerificationFailed,
+            PVSSErrorKind::DeserializationFailed { .. } => PVSSErrorCode::DeserializationFailed,
+            PVSSErrorKind::AlreadyDealtError(..) => PVSSErrorCode::AlreadyDealtError,
+            PVSSErrorKind::DecryptionProofVerificationFailed { .. } => PVSSErrorCode::DecryptionProofVerificationFailed,
+            PVSSErrorKind::BeaconMismatchError => PVSSErrorCode::BeaconMismatchError,
+            PVSSErrorKind::EpochChainError { .. } => PVSSErrorCode::EpochChainError,
+            PVSSErrorKind::SnapshotVersionError(..) => PVSSErrorCode::SnapshotVersionError,
+            PVSSErrorKind::SnapshotIntegrityError => PVSSErrorCode::SnapshotIntegrityError,
+            PVSSErrorKind::InsufficientWeight { .. } => PVSSErrorCode::InsufficientWeight,
+            PVSSErrorKind::InvalidComplaint { .. } => PVSSErrorCode::InvalidComplaint,
+            PVSSErrorKind::IdentityEncryption(..) => PVSSErrorCode::IdentityEncryption,
+            PVSSErrorKind::WrongCoreLength(..) => PVSSErrorCode::WrongCoreLength,
+            PVSSErrorKind::DrainInterrupted { .. } => PVSSErrorCode::DrainInterrupted,
+            PVSSErrorKind::NonCanonicalScalar => PVSSErrorCode::NonCanonicalScalar,
+            PVSSErrorKind::MismatchedBatchSizes(..) => PVSSErrorCode::MismatchedBatchSizes,
+            PVSSErrorKind::CommitteeTooLargeForField(..) => PVSSErrorCode::CommitteeTooLargeForField,
+            PVSSErrorKind::InvalidJson(..) => PVSSErrorCode::InvalidJson,
+            PVSSErrorKind::InvalidWireFormat(..) => PVSSErrorCode::InvalidWireFormat,
+            PVSSErrorKind::WireSizeExceeded(..) => PVSSErrorCode::WireSizeExceeded,
+            PVSSErrorKind::EmptyTranscript => PVSSErrorCode::EmptyTranscript,
+            PVSSErrorKind::IoError(..) => PVSSErrorCode::IoError,
+            PVSSErrorKind::InvalidArchive(..) => PVSSErrorCode::InvalidArchive,
+            PVSSErrorKind::TooManyDigests(..) => PVSSErrorCode::TooManyDigests,
+            PVSSErrorKind::InvalidEnvelope(..) => PVSSErrorCode::InvalidEnvelope,
+            PVSSErrorKind::ConfigMismatch => PVSSErrorCode::ConfigMismatch,
+            PVSSErrorKind::EmptyCommitmentsVectorError => PVSSErrorCode::EmptyCommitmentsVectorError,
+            PVSSErrorKind::InvalidMembershipProof(_) => PVSSErrorCode::InvalidMembershipProof,
+            PVSSErrorKind::InvalidConfig(..) => PVSSErrorCode::InvalidConfig,
+            PVSSErrorKind::NotInSubgroup(_) => PVSSErrorCode::NotInSubgroup,
+            PVSSErrorKind::RecoveryShareVerificationFailed { .. } => PVSSErrorCode::RecoveryShareVerificationFailed,
+            PVSSErrorKind::ReshareVerificationFailed { .. } => PVSSErrorCode::ReshareVerificationFailed,
+            PVSSErrorKind::ZeroSecretKey => PVSSErrorCode::ZeroSecretKey,
+        }
+    }
+}
+
+impl<E: PairingEngine> PVSSError<E> {
+
+    // Method returning the stable numeric code of this error.
+    pub fn code(&self) -> u16 {
+        self.kind().code() as u16
+    }
+}
+
+impl TryFrom<u16> for PVSSErrorCode {
+    type Error = SerializationError;
+
+    fn try_from(code: u16) -> Result<Self, Self::Error> {
+        match code {
+            2 => Ok(PVSSErrorCode::DifferentPointsEvalsError),
+            3 => Ok(PVSSErrorCode::DecompGenerationError),
+            4 => Ok(PVSSErrorCode::InvalidParticipantId),
+            5 => Ok(PVSSErrorCode::MismatchedCommitsEncryptionsParticipantsError),
+            6 => Ok(PVSSErrorCode::DualCodeError),
+            7 => Ok(PVSSErrorCode::GSCheckError),
+            8 => Ok(PVSSErrorCode::EmptySharesVectorError),
+            9 => Ok(PVSSErrorCode::InsufficientIdsError),
+            10 => Ok(PVSSErrorCode::InsufficientCommitsInShareError),
+            11 => Ok(PVSSErrorCode::InsufficientEncryptionsInShareError),
+            12 => Ok(PVSSErrorCode::EmptyEncryptionsVectorError),
+            13 => Ok(PVSSErrorCode::MismatchedCommitmentsError),
+            14 => Ok(PVSSErrorCode::MismatchedEncryptionsError),
+            15 => Ok(PVSSErrorCode::MismatchedCommitmentsEncryptionsError),
+            16 => Ok(PVSSErrorCode::TranscriptDifferentConfig),
+            17 => Ok(PVSSErrorCode::TranscriptDifferentCommitments),
+            18 => Ok(PVSSErrorCode::DecompProofVerificationError),
+            20 => Ok(PVSSErrorCode::LengthMismatchError),
+            21 => Ok(PVSSErrorCode::EncryptionCorrectnessError),
+            22 => Ok(PVSSErrorCode::DecompositionInTranscriptError),
+            23 => Ok(PVSSErrorCode::AggregationReconstructionMismatchError),
+            24 => Ok(PVSSErrorCode::EdDSAInvalidSignatureError),
+            25 => Ok(PVSSErrorCode::EdDSAInvalidSignatureBatchError),
+            26 => Ok(PVSSErrorCode::NIZKProofDoesNotVerifyError),
+            27 => Ok(PVSSErrorCode::RatioIncorrect),
+            28 => Ok(PVSSErrorCode::EvaluationsCheckError),
+            29 => Ok(PVSSErrorCode::EvaluationDomainError),
+            30 => Ok(PVSSErrorCode::DifferentSRS),
+            31 => Ok(PVSSErrorCode::SignatureError),
+            32 => Ok(PVSSErrorCode::SerializationError),
+            33 => Ok(PVSSErrorCode::NonDistinctPointsError),
+            34 => Ok(PVSSErrorCode::InsufficientShares),
+            35 => Ok(PVSSErrorCode::ThresholdNotMet),
+            36 => Ok(PVSSErrorCode::NIZKProofDoesNotVerify),
+            37 => Ok(PVSSErrorCode::SignatureVerificationFailed),
+            38 => Ok(PVSSErrorCode::DeserializationFailed),
+            39 => Ok(PVSSErrorCode::AlreadyDealtError),
+            40 => Ok(PVSSErrorCode::DecryptionProofVerificationFailed),
+            41 => Ok(PVSSErrorCode::BeaconMismatchError),
+            42 => Ok(PVSSErrorCode::EpochChainError),
+            43 => Ok(PVSSErrorCode::SnapshotVersionError),
+            44 => Ok(PVSSErrorCode::SnapshotIntegrityError),
+            45 => Ok(PVSSErrorCode::InsufficientWeight),
+            46 => Ok(PVSSErrorCode::InvalidComplaint),
+            47 => Ok(PVSSErrorCode::IdentityEncryption),
+            48 => Ok(PVSSErrorCode::WrongCoreLength),
+            49 => Ok(PVSSErrorCode::DrainInterrupted),
+            50 => Ok(PVSSErrorCode::NonCanonicalScalar),
+            51 => Ok(PVSSErrorCode::MismatchedBatchSizes),
+            52 => Ok(PVSSErrorCode::CommitteeTooLargeForField),
+            53 => Ok(PVSSErrorCode::InvalidJson),
+            54 => Ok(PVSSErrorCode::InvalidWireFormat),
+            55 => Ok(PVSSErrorCode::WireSizeExceeded),
+            56 => Ok(PVSSErrorCode::EmptyTranscript),
+            57 => Ok(PVSSErrorCode::IoError),
+            58 => Ok(PVSSErrorCode::InvalidArchive),
+            59 => Ok(PVSSErrorCode::TooManyDigests),
+            60 => Ok(PVSSErrorCode::InvalidEnvelope),
+            61 => Ok(PVSSErrorCode::ConfigMismatch),
+            62 => Ok(PVSSErrorCode::EmptyCommitmentsVectorError),
+            63 => Ok(PVSSErrorCode::InvalidMembershipProof),
+            64 => Ok(PVSSErrorCode::InvalidConfig),
+            65 => Ok(PVSSErrorCode::NotInSubgroup),
+            66 => Ok(PVSSErrorCode::RecoveryShareVerificationFailed),
+            67 => Ok(PVSSErrorCode::ReshareVerificationFailed),
+            68 => Ok(PVSSErrorCode::ZeroSecretKey),
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+}
+
+
+/* Struct RejectionReason is a compact description of why a peer's message was rejected,
+   intended to be sent back within protocol messages. */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RejectionReason {
+    pub code: PVSSErrorCode,             // the reason for the rejection
+    pub participant_id: Option<u64>,     // the offending participant, if any
+}
+
+impl RejectionReason {
+
+    // Function for creating a rejection reason out of a PVSS error.
+    pub fn from_error<E: PairingEngine>(err: &PVSSError<E>, participant_id: Option<u64>) -> Self {
+        Self {
+            code: err.kind().code(),
+            participant_id,
+        }
+    }
+}
+
+impl CanonicalSerialize for RejectionReason {
+    #[inline]
+    fn serialize<W: Write>(
+    &self,
+    mut writer: W,
+    ) -> Result<(), SerializationError> {
+        (self.code as u16).serialize(&mut writer)?;
+        self.participant_id.is_some().serialize(&mut writer)?;
+        self.participant_id.unwrap_or(0).serialize(&mut writer)?;
+        Ok(())
+    }
+
+    fn serialized_size(&self) -> usize {
+        (self.code as u16).serialized_size()
+            + self.participant_id.is_some().serialized_size()
+            + self.participant_id.unwrap_or(0).serialized_size()
+    }
+}
+
+impl CanonicalDeserialize for RejectionReason {
+    #[inline]
+    fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let code = PVSSErrorCode::try_from(u16::deserialize(&mut reader)?)?;
+        let has_id = bool::deserialize(&mut reader)?;
+        let id = u64::deserialize(&mut reader)?;
+
+        // Without an id, the id field is zero, so that every reason has a single encoding.
+        if !has_id && id != 0 {
+            return Err(SerializationError::InvalidData);
+        }
+
+        Ok(Self {
+            code,
+            participant_id: if has_id { Some(id) } else { None },
+        })
+    }
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{PVSSError, PVSSErrorCode, PVSSErrorKind, RejectionReason};
+    use crate::signature::utils::{errors::SignatureError, tests::check_serialization};
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ec::{AffineCurve, PairingEngine};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+
+    use std::{collections::BTreeSet, convert::TryFrom, error::Error};
+
+    #[test]
+    fn test_kind_of_plain_variants() {
+        assert_eq!(PVSSError::<E>::DualCodeError.kind(), PVSSErrorKind::DualCodeError);
+        assert_eq!(PVSSError::<E>::InvalidParticipantId(7).kind(), PVSSErrorKind::InvalidParticipantId(7));
+        assert_eq!(
+            PVSSError::<E>::TranscriptDifferentConfig(1, 2, 3, 4).kind(),
+            PVSSErrorKind::TranscriptDifferentConfig(1, 2, 3, 4)
+        );
+    }
+
+    #[test]
+    fn test_kind_stringifies_payloads() {
+        let g1 = <E as PairingEngine>::G1Affine::prime_subgroup_generator();
+        let err = PVSSError::<E>::EvaluationsCheckError(g1);
+        let msg = err.to_string();
+
+        match PVSSErrorKind::from(err) {
+            PVSSErrorKind::EvaluationsCheckError(p) => assert_eq!(p, g1.to_string()),
+            kind => panic!("unexpected kind: {:?}", kind),
+        }
+
+        // The kind renders exactly like the generic error.
+        assert_eq!(PVSSErrorKind::EvaluationsCheckError(g1.to_string()).to_string(), msg);
+
+        let kind: PVSSErrorKind = PVSSError::<E>::SignatureError(SignatureError::SchnorrVerify).into();
+        assert_eq!(kind, PVSSErrorKind::SignatureError(SignatureError::SchnorrVerify.to_string()));
+
+        let kind: PVSSErrorKind = PVSSError::<E>::SerializationError(SerializationError::InvalidData).into();
+        assert_eq!(kind, PVSSErrorKind::SerializationError(SerializationError::InvalidData.to_string()));
+    }
+
+    #[test]
+    fn test_kind_as_boxed_error() {
+        fn fails() -> Result<(), Box<dyn Error + Send + Sync>> {
+            Err(PVSSErrorKind::from(PVSSError::<E>::GSCheckError).into())
+        }
+
+        let err = fails().unwrap_err();
+        assert_eq!(err.to_string(), PVSSError::<E>::GSCheckError.to_string());
+        assert_eq!(err.downcast_ref::<PVSSErrorKind>(), Some(&PVSSErrorKind::GSCheckError));
+    }
+
+    // One representative instance of every PVSSError variant.
+    fn all_errors() -> Vec<PVSSError<E>> {
+        vec![
+            PVSSError::DifferentPointsEvalsError,
+            PVSSError::DecompGenerationError,
+            PVSSError::InvalidParticipantId(3),
+            PVSSError::MismatchedCommitsEncryptionsParticipantsError(1, 2, 3),
+            PVSSError::DualCodeError,
+            PVSSError::GSCheckError,
+            PVSSError::EmptySharesVectorError,
+            PVSSError::InsufficientIdsError,
+            PVSSError::InsufficientCommitsInShareError(1, 2),
+            PVSSError::InsufficientEncryptionsInShareError(1, 2),
+            PVSSError::EmptyEncryptionsVectorError,
+            PVSSError::MismatchedCommitmentsError(1, 2),
+            PVSSError::MismatchedEncryptionsError(1, 2),
+            PVSSError::MismatchedCommitmentsEncryptionsError(1, 2),
+            PVSSError::TranscriptDifferentConfig(1, 2, 3, 4),
+            PVSSError::TranscriptDifferentCommitments,
+            PVSSError::DecompProofVerificationError,
+            PVSSError::LengthMismatchError,
+            PVSSError::EncryptionCorrectnessError,
+            PVSSError::DecompositionInTranscriptError,
+            PVSSError::AggregationReconstructionMismatchError,
+            PVSSError::EdDSAInvalidSignatureError,
+            PVSSError::EdDSAInvalidSignatureBatchError,
+            PVSSError::NIZKProofDoesNotVerifyError,
+            PVSSError::RatioIncorrect,
+            PVSSError::EvaluationsCheckError(<E as PairingEngine>::G1Affine::prime_subgroup_generator()),
+            PVSSError::EvaluationDomainError,
+            PVSSError::DifferentSRS,
+            PVSSError::SignatureError(SignatureError::SchnorrVerify),
+            PVSSError::SerializationError(SerializationError::InvalidData),
+            PVSSError::NonDistinctPointsError,
+            PVSSError::InsufficientShares { needed: 4, got: 3 },
+            PVSSError::ThresholdNotMet { threshold: 4, got: 2 },
+            PVSSError::NIZKProofDoesNotVerify { participant_id: 1 },
+            PVSSError::SignatureVerificationFailed { participant_id: 1 },
+            PVSSError::DeserializationFailed { field: "encs", offset: 8, source: SerializationError::InvalidData },
+            PVSSError::AlreadyDealtError(1),
+            PVSSError::DecryptionProofVerificationFailed { participant_id: 1 },
+            PVSSError::BeaconMismatchError,
+            PVSSError::EpochChainError { epoch: 1 },
+            PVSSError::SnapshotVersionError(2),
+            PVSSError::SnapshotIntegrityError,
+            PVSSError::InsufficientWeight { threshold: 10, got: 7 },
+            PVSSError::InvalidComplaint { accused_id: 3 },
+            PVSSError::IdentityEncryption(2),
+            PVSSError::WrongCoreLength(1, 2),
+            PVSSError::DrainInterrupted { folded: 2, source: Box::new(PVSSError::TranscriptDifferentCommitments) },
+            PVSSError::NonCanonicalScalar,
+            PVSSError::MismatchedBatchSizes(1, 2),
+            PVSSError::CommitteeTooLargeForField(17),
+            PVSSError::InvalidJson("format".to_string()),
+            PVSSError::InvalidWireFormat("mode".to_string()),
+            PVSSError::WireSizeExceeded(1, 2),
+            PVSSError::EmptyTranscript,
+            PVSSError::IoError("disk".to_string()),
+            PVSSError::InvalidArchive("magic".to_string()),
+            PVSSError::TooManyDigests(3, 2),
+            PVSSError::InvalidEnvelope("epoch".to_string()),
+            PVSSError::ConfigMismatch,
+            PVSSError::EmptyCommitmentsVectorError,
+            PVSSError::InvalidMembershipProof(4),
+            PVSSError::InvalidConfig("degree".to_string()),
+            PVSSError::NotInSubgroup(3),
+            PVSSError::RecoveryShareVerificationFailed { participant_id: 2 },
+            PVSSError::ReshareVerificationFailed { participant_id: 3 },
+            PVSSError::ZeroSecretKey,
+        ]
+    }
+
+    #[test]
+    fn test_error_codes_unique_and_round_trip() {
+        let errors = all_errors();
+        let codes = errors.iter().map(|e| e.code()).collect::<BTreeSet<_>>();
+
+        // Every variant has its own code.
+        assert_eq!(codes.len(), errors.len());
+
+        for err in errors.iter() {
+            let code = PVSSErrorCode::try_from(err.code()).unwrap();
+            assert_eq!(code as u16, err.code());
+            assert_eq!(code, err.kind().code());
+        }
+
+        // Codes outside the assigned range, and retired codes, are rejected.
+        for code in [0, 1, 19, PVSSErrorCode::ZeroSecretKey as u16 + 1].iter() {
+            assert!(PVSSErrorCode::try_from(*code).is_err());
+        }
+    }
+
+    #[test]
+    fn test_rejection_reason_serialization() {
+        for err in all_errors().iter() {
+            check_serialization(RejectionReason::from_error(err, Some(5)));
+            check_serialization(RejectionReason::from_error(err, None));
+        }
+
+        // An unknown code does not deserialize.
+        let mut bytes = vec![];
+        RejectionReason::from_error(&PVSSError::<E>::DualCodeError, None).serialize(&mut bytes).unwrap();
+        bytes[0] = 0xff;
+        bytes[1] = 0xff;
+        assert!(RejectionReason::deserialize(&bytes[..]).is_err());
+
+        // Neither does a reason without an id, but with a nonzero id field.
+        let mut bytes = vec![];
+        RejectionReason::from_error(&PVSSError::<E>::DualCodeError, None).serialize(&mut bytes).unwrap();
+        bytes[3] = 7;
+        assert!(matches!(RejectionReason::deserialize(&bytes[..]), Err(SerializationError::InvalidData)));
+    }
 }
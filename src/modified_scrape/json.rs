@@ -0,0 +1,361 @@
+use crate::{
+    decode_hex, encode_hex,
+    modified_scrape::{
+        decomp::DecompProof,
+        errors::PVSSError,
+        participant::ParticipantId,
+        pvss::AffinePVSSCore,
+        share::{Contributions, PVSSAggregatedShare, Provenance, SignedProof, MAX_CONTRIBUTOR_ID},
+        verifier::EpochTranscript,
+    },
+    read_array,
+    Digest,
+};
+
+use ark_ec::PairingEngine;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+
+// Version of the JSON transcript format, written to its "format" key. Any change to the field
+// names or to their encodings must bump it.
+pub const JSON_FORMAT: u32 = 2;
+
+
+/* The structs below mirror EpochTranscript field by field. Group elements, scalars and
+   signatures are the lowercase hex of their canonical (compressed) serialization, and the
+   byte strings of the chain (prev_output, beacon) and the configuration digest are plain hex. */
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TranscriptJson {
+    format: u32,                              // the format version, i.e., JSON_FORMAT
+    epoch: u64,                               // the epoch number
+    prev_output: String,                      // the previous epoch's output
+    degree: usize,                            // the aggregated share's degree
+    num_participants: usize,                  // the aggregated share's number of participants
+    config_digest: String,                    // the aggregated share's configuration digest
+    encs: Vec<String>,                        // the aggregated encryptions (in G1)
+    comms: Vec<String>,                       // the aggregated commitments (in G2)
+    contributions: Vec<ContributionJson>,     // the contributions, by increasing id
+    decryptions: Vec<DecryptionJson>,         // the decrypted shares, by increasing id
+    beacon: String,                           // the claimed beacon output
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ContributionJson {
+    id: usize,                  // the contributor's id
+    gs: String,                 // the commitment to the contributor's secret (in G2)
+    proof: DlkProofJson,        // the proof of knowledge of its discrete log
+    signature: String,          // the contributor's EdDSA signature on the decomposition proof
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DlkProofJson {
+    commitment: String,         // commitment to the nonce (in G2)
+    challenge: String,          // challenge scalar
+    response: String,           // response scalar
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DecryptionJson {
+    id: usize,                  // the decryptor's id
+    share: String,              // the decrypted share (in G1)
+    proof: DleqProofJson,       // the proof of correct decryption
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DleqProofJson {
+    commitment_1: String,       // commitment to the nonce over g1 (in G1)
+    commitment_2: String,       // commitment to the nonce over the decrypted share (in G1)
+    challenge: String,          // challenge scalar
+    response: String,           // response scalar
+}
+
+
+// Utility function encoding a value as the hex of its canonical serialization.
+fn to_hex<T: CanonicalSerialize>(value: &T) -> String {
+    let mut bytes = vec![];
+    value.serialize(&mut bytes).unwrap();
+
+    encode_hex(&bytes)
+}
+
+// Utility function decoding a value from the hex of its canonical serialization, which must
+// be consumed entirely.
+fn from_hex<T: CanonicalDeserialize, E: PairingEngine>(field: &str, hex: &str) -> Result<T, PVSSError<E>> {
+    let invalid = || PVSSError::InvalidJson(format!("{} is not a valid encoding", field));
+
+    let bytes = decode_hex(hex).ok_or_else(invalid)?;
+    let mut reader = &bytes[..];
+    let value = T::deserialize(&mut reader).map_err(|_| invalid())?;
+
+    if !reader.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(value)
+}
+
+// Utility function decoding a 32-byte string from hex.
+fn array_from_hex<E: PairingEngine>(field: &str, hex: &str) -> Result<[u8; 32], PVSSError<E>> {
+    let bytes = decode_hex(hex).filter(|bytes| bytes.len() == 32);
+
+    bytes
+        .and_then(|bytes| read_array(&bytes[..]).ok())
+        .ok_or_else(|| PVSSError::InvalidJson(format!("{} is not 32 bytes of hex", field)))
+}
+
+
+impl<E: PairingEngine> EpochTranscript<E> {
+
+    // Method exporting the transcript as JSON for external auditors (see TranscriptJson for
+    // the layout, which is pinned by JSON_FORMAT).
+    pub fn to_json(&self) -> String {
+        let aggregated = &self.aggregated;
+
+        let json = TranscriptJson {
+            format: JSON_FORMAT,
+            epoch: self.epoch,
+            prev_output: encode_hex(&self.prev_output),
+            degree: aggregated.degree,
+            num_participants: aggregated.num_participants,
+            config_digest: aggregated.config_digest.to_hex(),
+            encs: aggregated.pvss_core.encs.iter().map(to_hex).collect(),
+            comms: aggregated.pvss_core.comms.iter().map(to_hex).collect(),
+            contributions: aggregated
+                .contributions
+                .iter()
+                .map(|(id, signed_proof)| {
+                    let decomp_proof = signed_proof.decomp_proof();
+                    let (commitment, challenge, response) = &decomp_proof.proof;
+
+                    ContributionJson {
+                        id: id.index(),
+                        gs: to_hex(&decomp_proof.gs),
+                        proof: DlkProofJson {
+                            commitment: to_hex(commitment),
+                            challenge: to_hex(challenge),
+                            response: to_hex(response),
+                        },
+                        signature: to_hex(&signed_proof.signature_on_decomp),
+                    }
+                })
+                .collect(),
+            decryptions: self
+                .decryptions
+                .iter()
+                .map(|(id, (share, ((commitment_1, commitment_2), challenge, response)))| DecryptionJson {
+                    id: *id,
+                    share: to_hex(share),
+                    proof: DleqProofJson {
+                        commitment_1: to_hex(commitment_1),
+                        commitment_2: to_hex(commitment_2),
+                        challenge: to_hex(challenge),
+                        response: to_hex(response),
+                    },
+                })
+                .collect(),
+            beacon: encode_hex(&self.beacon),
+        };
+
+        serde_json::to_string_pretty(&json).unwrap()
+    }
+
+    // Function importing a transcript exported by to_json. The import is lossless: every
+    // element must be canonically encoded, and ids may not repeat. No verification is done
+    // beyond decoding (see verify_epoch).
+    pub fn from_json(json: &str) -> Result<Self, PVSSError<E>> {
+        let json: TranscriptJson = serde_json::from_str(json).map_err(|e| PVSSError::InvalidJson(e.to_string()))?;
+
+        if json.format != JSON_FORMAT {
+            return Err(PVSSError::InvalidJson(format!("unsupported format {}", json.format)));
+        }
+
+        let mut contributions = Contributions::new();
+        for contribution in json.contributions.iter() {
+            let decomp_proof = DecompProof {
+                proof: (
+                    from_hex("contributions.proof.commitment", &contribution.proof.commitment)?,
+                    from_hex("contributions.proof.challenge", &contribution.proof.challenge)?,
+                    from_hex("contributions.proof.response", &contribution.proof.response)?,
+                ),
+                gs: from_hex("contributions.gs", &contribution.gs)?,
+            };
+            let signature = from_hex("contributions.signature", &contribution.signature)?;
+
+            if contribution.id > MAX_CONTRIBUTOR_ID
+                || !matches!(contributions.insert(ParticipantId::from_index(contribution.id), SignedProof::new(decomp_proof, signature)), Ok(None)) {
+                return Err(PVSSError::InvalidJson(format!("invalid or repeated contributor id {}", contribution.id)));
+            }
+        }
+
+        let mut decryptions = BTreeMap::new();
+        for decryption in json.decryptions.iter() {
+            let share = from_hex("decryptions.share", &decryption.share)?;
+            let proof = (
+                (
+                    from_hex("decryptions.proof.commitment_1", &decryption.proof.commitment_1)?,
+                    from_hex("decryptions.proof.commitment_2", &decryption.proof.commitment_2)?,
+                ),
+                from_hex("decryptions.proof.challenge", &decryption.proof.challenge)?,
+                from_hex("decryptions.proof.response", &decryption.proof.response)?,
+            );
+
+            if decryptions.insert(decryption.id, (share, proof)).is_some() {
+                return Err(PVSSError::InvalidJson(format!("repeated decryptor id {}", decryption.id)));
+            }
+        }
+
+        Ok(EpochTranscript {
+            epoch: json.epoch,
+            prev_output: array_from_hex("prev_output", &json.prev_output)?,
+            aggregated: PVSSAggregatedShare {
+                num_participants: json.num_participants,
+                degree: json.degree,
+                config_digest: Digest(array_from_hex("config_digest", &json.config_digest)?),
+                pvss_core: AffinePVSSCore {
+                    encs: json.encs.iter().map(|enc| from_hex("encs", enc)).collect::<Result<_, _>>()?,
+                    comms: json.comms.iter().map(|comm| from_hex("comms", comm)).collect::<Result<_, _>>()?,
+                },
+                contributions,
+                provenance: Provenance::default(),
+            },
+            decryptions,
+            beacon: array_from_hex("beacon", &json.beacon)?,
+        })
+    }
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::JSON_FORMAT;
+    use crate::{
+        generate_production_keypair,
+        modified_scrape::{
+            config::Config,
+            decomp::Decomp,
+            errors::PVSSError,
+            participant::ParticipantId,
+            poly::Polynomial as Poly,
+            pvss::AffinePVSSCore,
+            share::{PVSSAggregatedShare, SignedProof},
+            srs::SRS,
+            verifier::EpochTranscript,
+        },
+        Signature,
+    };
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ec::{PairingEngine, ProjectiveCurve};
+    use ark_poly::UVPolynomial;
+    use ark_serialize::CanonicalSerialize;
+    use ark_std::UniformRand;
+
+    use rand::thread_rng;
+
+    use std::collections::BTreeMap;
+
+    // Builds a (structurally complete, but not verifying) transcript with random elements.
+    fn random_transcript() -> EpochTranscript<E> {
+        let rng = &mut thread_rng();
+        let t = 2;
+        let n = 5;
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+
+        let mut aggregated = PVSSAggregatedShare::<E>::empty(t, n);
+        aggregated.config_digest = conf.digest();
+        aggregated.pvss_core = AffinePVSSCore {
+            encs:  (0..n).map(|_| <E as PairingEngine>::G1Projective::rand(rng).into_affine()).collect(),
+            comms: (0..n).map(|_| <E as PairingEngine>::G2Projective::rand(rng).into_affine()).collect(),
+        };
+        for id in [0, 2, 3].iter() {
+            let poly = Poly::<E>::rand(t, rng);
+            let (_pk_sig, sk_sig) = generate_production_keypair();
+            let dproof = Decomp::<E>::generate(rng, &conf, &poly[0]).unwrap();
+            let sig = Signature::new(&dproof.digest(), &sk_sig);
+            aggregated.contributions.insert(ParticipantId(*id), SignedProof::new(dproof, sig)).unwrap();
+        }
+
+        let decryptions = [1, 4]
+            .iter()
+            .map(|id| {
+                let mut g1 = || <E as PairingEngine>::G1Projective::rand(rng).into_affine();
+                let (share, commitment_1, commitment_2) = (g1(), g1(), g1());
+                let proof = ((commitment_1, commitment_2), <E as PairingEngine>::Fr::rand(rng), <E as PairingEngine>::Fr::rand(rng));
+                (*id, (share, proof))
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        EpochTranscript { epoch: 7, prev_output: [3u8; 32], aggregated, decryptions, beacon: [9u8; 32] }
+    }
+
+    fn bytes(transcript: &EpochTranscript<E>) -> Vec<u8> {
+        let mut bytes = vec![];
+        transcript.serialize(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let transcript = random_transcript();
+        let json = transcript.to_json();
+
+        let imported = EpochTranscript::<E>::from_json(&json).unwrap();
+        assert_eq!(bytes(&imported), bytes(&transcript));
+        assert_eq!(imported.to_json(), json);
+
+        // Edits that break the encoding or the format are rejected.
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let edits: Vec<(&str, serde_json::Value)> = vec![
+            ("format", serde_json::json!(JSON_FORMAT + 1)),
+            ("beacon", serde_json::json!("09")),
+            ("config_digest", serde_json::json!("00")),
+            ("encs", serde_json::json!(["zz"])),
+            ("unknown", serde_json::json!(0)),
+        ];
+        for (key, edit) in edits {
+            let mut edited = value.clone();
+            edited[key] = edit;
+            match EpochTranscript::<E>::from_json(&edited.to_string()) {
+                Err(PVSSError::InvalidJson(_)) => (),
+                _ => panic!("expected InvalidJson after editing {}", key),
+            }
+        }
+
+        // Repeated ids are rejected.
+        let mut edited = value.clone();
+        let first = edited["contributions"][0].clone();
+        edited["contributions"].as_array_mut().unwrap().push(first);
+        assert!(EpochTranscript::<E>::from_json(&edited.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_json_field_names_are_stable() {
+        let value: serde_json::Value = serde_json::from_str(&random_transcript().to_json()).unwrap();
+
+        let keys = |value: &serde_json::Value| value.as_object().unwrap().keys().cloned().collect::<Vec<_>>();
+
+        assert_eq!(value["format"], serde_json::json!(2));
+        assert_eq!(keys(&value), vec![
+            "beacon", "comms", "config_digest", "contributions", "decryptions", "degree", "encs", "epoch", "format",
+            "num_participants", "prev_output",
+        ]);
+        assert_eq!(keys(&value["contributions"][0]), vec!["gs", "id", "proof", "signature"]);
+        assert_eq!(keys(&value["contributions"][0]["proof"]), vec!["challenge", "commitment", "response"]);
+        assert_eq!(keys(&value["decryptions"][0]), vec!["id", "proof", "share"]);
+        assert_eq!(keys(&value["decryptions"][0]["proof"]), vec!["challenge", "commitment_1", "commitment_2", "response"]);
+    }
+}
@@ -1,6 +1,8 @@
 pub mod errors;
+pub mod checked;
 pub mod srs;
 pub mod poly;
+pub mod curve_utils;
 pub mod config;
 pub mod decomp;
 pub mod decryption;
@@ -9,4 +11,18 @@ pub mod share;
 pub mod participant;
 pub mod dealer;
 pub mod aggregator;
+#[cfg(feature = "std")]
 pub mod node;
+pub mod tbls;
+pub mod recon;
+pub mod recovery;
+pub mod refresh;
+pub mod reshare;
+pub mod roles;
+pub mod verifier;
+pub mod epoch;
+pub mod snapshot;
+#[cfg(feature = "serde")]
+pub mod json;
+#[cfg(feature = "compression")]
+pub mod compression;
@@ -0,0 +1,320 @@
+use crate::{
+    modified_scrape::{
+        aggregator::PVSSAggregator,
+        config::Config,
+        errors::PVSSError,
+        node::message::{Message, SignedMessage},
+        participant::{ParticipantId, ParticipantRegistry},
+        pvss::AffinePVSSCore,
+        share::{Contributions, PVSSAggregatedShare, PVSSShare, Provenance},
+        snapshot::{open_snapshot, validate_aggregate, SNAPSHOT_VERSION},
+    },
+    signature::schnorr::{SchnorrSignature, srs::SRS as SCHSRS},
+    Digest,
+};
+
+use ark_ec::PairingEngine;
+use ark_serialize::CanonicalSerialize;
+use ark_std::convert::TryFrom;
+
+use rand::{CryptoRng, Rng};
+
+
+/* Enumeration ReceiveOutcome reports what became of a share handed to Aggregator::receive_share. */
+pub enum ReceiveOutcome<E: PairingEngine> {
+    Accepted { contributions: usize },    // the share was folded; the threshold is not met yet
+    Duplicate,                            // the same share was already folded; nothing changed
+    Finalized(PVSSAggregatedShare<E>),    // the share met the threshold; the finalized transcript
+    AlreadyFinalized,                     // the transcript was finalized earlier; the share was ignored
+}
+
+
+/* Struct Aggregator drives share collection for a single node: it verifies the shares received
+   from the committee, folds them into a running aggregated transcript, and finalizes the
+   transcript once degree + 1 distinct contributions have been collected. */
+pub struct Aggregator<E: PairingEngine> {
+    inner: PVSSAggregator<E, SchnorrSignature<E::G1Affine>>,   // config, committee and running transcript
+    finalized: bool,                                           // whether the threshold has been met
+}
+
+impl<E: PairingEngine> Aggregator<E> {
+
+    // Function for creating a new aggregator with an empty transcript.
+    pub fn new(config: Config<E>, registry: ParticipantRegistry<E>) -> Self {
+        let scheme_sig = SchnorrSignature { srs: SCHSRS { g_public_key: config.srs.g1 } };
+        let aggregated_tx = PVSSAggregatedShare::empty(config.degree, config.num_participants);
+
+        Self {
+            inner: PVSSAggregator { config, scheme_sig, participants: registry.participants, aggregated_tx },
+            finalized: false,
+        }
+    }
+
+
+    // Method for handling a received PVSS share.
+    // Shares from ids outside the committee and shares failing verification are rejected with an
+    // error; a share equal to an already folded one is reported as a duplicate, whereas a different
+    // share from an id that already contributed is rejected. Once finalized, shares are ignored.
+    pub fn receive_share<R: Rng + CryptoRng>(&mut self, rng: &mut R, share: PVSSShare<E>) -> Result<ReceiveOutcome<E>, PVSSError<E>> {
+        if self.finalized {
+            return Ok(ReceiveOutcome::AlreadyFinalized);
+        }
+
+        let id = share.participant_id;
+        if !self.inner.participants.contains_key(&id) {
+            return Err(PVSSError::InvalidParticipantId(id.index()));
+        }
+
+        if let Some(existing) = self.inner.aggregated_tx.contributions.get(&id) {
+            if *existing == share.signed_proof {
+                return Ok(ReceiveOutcome::Duplicate);
+            }
+            return Err(PVSSError::TranscriptDifferentCommitments);
+        }
+
+        self.inner.share_verify(rng, &share)?;
+
+        // Move the share into the transcript. This cannot fail, as the share verified (hence its
+        // core has the right length) and its issuer has not contributed yet.
+        let placeholder = PVSSAggregatedShare {
+            num_participants: self.inner.config.num_participants,
+            degree: self.inner.config.degree,
+            config_digest: Digest::default(),
+            pvss_core: AffinePVSSCore { encs: vec![], comms: vec![] },
+            contributions: Contributions::new(),
+            provenance: Provenance::default(),
+        };
+        let aggregated_tx = std::mem::replace(&mut self.inner.aggregated_tx, placeholder);
+        self.inner.aggregated_tx = aggregated_tx.aggregate_pvss_share_owned(share)?;
+
+        if !self.inner.is_ready() {
+            return Ok(ReceiveOutcome::Accepted { contributions: self.inner.aggregated_tx.contributions.len() });
+        }
+
+        self.finalized = true;
+
+        Ok(ReceiveOutcome::Finalized(self.inner.aggregated_tx.clone()))
+    }
+
+
+    // Method for handling a PVSS share received from the network: the envelope must verify
+    // against the key of its sender, who may have relayed another dealer's share, and carry a
+    // Share message. The share is then handled as by receive_share.
+    pub fn receive_signed<R: Rng + CryptoRng>(&mut self, rng: &mut R, envelope: SignedMessage<E>) -> Result<ReceiveOutcome<E>, PVSSError<E>> {
+        let sender = ParticipantId::try_from(envelope.sender_id)
+            .ok()
+            .and_then(|id| self.inner.participants.get(&id))
+            .ok_or(PVSSError::InvalidParticipantId(envelope.sender_id))?;
+        envelope.verify_with_key(&sender.public_key_ed)?;
+
+        match envelope.msg {
+            Message::Share { share, .. } => self.receive_share(rng, share),
+            _ => Err(PVSSError::InvalidEnvelope("expected a Share message".to_string())),
+        }
+    }
+
+
+    // Method returning the "global" configuration parameters.
+    pub fn config(&self) -> &Config<E> {
+        &self.inner.config
+    }
+
+    // Method returning the running (or, once finalized, the final) aggregated transcript.
+    pub fn transcript(&self) -> &PVSSAggregatedShare<E> {
+        &self.inner.aggregated_tx
+    }
+
+    // Method returning whether the threshold of distinct contributions has been met.
+    pub fn is_finalized(&self) -> bool {
+        self.finalized
+    }
+
+
+    // Method for persisting the aggregator's state: a version byte, followed by the committee,
+    // the running transcript and the finalization flag.
+    pub fn snapshot(&self) -> Vec<u8> {
+        let mut bytes = vec![SNAPSHOT_VERSION];
+
+        let registry = ParticipantRegistry { participants: self.inner.participants.clone() };
+        registry.serialize(&mut bytes).unwrap();
+        self.inner.aggregated_tx.serialize(&mut bytes).unwrap();
+        self.finalized.serialize(&mut bytes).unwrap();
+
+        bytes
+    }
+
+    // Function for restoring an aggregator from a snapshot taken under the given configuration.
+    // The restored transcript is re-verified, and the finalization flag must agree with it.
+    pub fn restore<R: Rng + CryptoRng>(rng: &mut R, config: Config<E>, bytes: &[u8]) -> Result<Self, PVSSError<E>> {
+        let mut reader = open_snapshot(bytes)?;
+        let registry: ParticipantRegistry<E> = reader.read_field("registry")?;
+        let aggregated_tx: PVSSAggregatedShare<E> = reader.read_field("aggregated_tx")?;
+        let finalized: bool = reader.read_field("finalized")?;
+
+        let mut aggregator = Self::new(config, registry);
+        validate_aggregate(rng, &mut aggregator.inner, &aggregated_tx, &[])?;
+
+        aggregator.inner.aggregated_tx = aggregated_tx;
+        if finalized != aggregator.inner.is_ready() {
+            return Err(PVSSError::SnapshotIntegrityError);
+        }
+        aggregator.finalized = finalized;
+
+        Ok(aggregator)
+    }
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{Aggregator, ReceiveOutcome};
+    use crate::{
+        modified_scrape::{
+            aggregator::PVSSAggregator,
+            errors::PVSSError,
+            node::DealerNode,
+            snapshot::SNAPSHOT_VERSION,
+            participant::ParticipantId,
+            share::PVSSAggregatedShare,
+        },
+        signature::schnorr::{SchnorrSignature, srs::SRS as SCHSRS},
+        testing::fixtures::FixtureBuilder,
+    };
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use rand::{seq::SliceRandom, thread_rng};
+
+    #[test]
+    fn test_receive_shares_until_finalized() {
+        let rng = &mut thread_rng();
+        let t = 3;
+        let n = 7;
+
+        // The committee's dealings are bound to the epoch, so only its keys are used.
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let (conf, registry) = (fixture.config, fixture.registry);
+
+        // Every node deals its share of epoch 0.
+        let mut shares = fixture
+            .keypairs
+            .into_iter()
+            .enumerate()
+            .map(|(id, keys)| {
+                let mut dealer = DealerNode::new(conf.clone(), registry.clone(), keys.secret_key_ed, id).unwrap();
+                dealer.deal(0, rng).unwrap().0
+            })
+            .collect::<Vec<_>>();
+
+        // Out-of-order delivery.
+        shares.shuffle(rng);
+
+        let mut aggregator = Aggregator::new(conf.clone(), registry.clone());
+
+        // An invalid share (its encryption for the issuer is tampered with) is rejected.
+        let mut invalid = shares[0].clone();
+        let issuer = invalid.participant_id.index();
+        invalid.pvss_core.encs[issuer] = invalid.pvss_core.encs[(issuer + 1) % n];
+        assert!(aggregator.receive_share(rng, invalid).is_err());
+
+        // Out-of-committee ids are rejected.
+        let mut outsider = shares[0].clone();
+        outsider.participant_id = ParticipantId::from_index(n);
+        match aggregator.receive_share(rng, outsider) {
+            Err(PVSSError::InvalidParticipantId(id)) => assert_eq!(id, n),
+            _ => panic!("expected InvalidParticipantId"),
+        }
+
+        // The first t shares are accepted, a re-delivered one is a duplicate.
+        for (i, share) in shares.iter().take(t).enumerate() {
+            match aggregator.receive_share(rng, share.clone()).unwrap() {
+                ReceiveOutcome::Accepted { contributions } => assert_eq!(contributions, i + 1),
+                _ => panic!("expected Accepted"),
+            }
+        }
+        assert!(matches!(aggregator.receive_share(rng, shares[1].clone()).unwrap(), ReceiveOutcome::Duplicate));
+
+        // The (t + 1)-th distinct share finalizes the transcript.
+        let transcript = match aggregator.receive_share(rng, shares[t].clone()).unwrap() {
+            ReceiveOutcome::Finalized(transcript) => transcript,
+            _ => panic!("expected Finalized"),
+        };
+        assert!(aggregator.is_finalized());
+        assert_eq!(transcript.contributions.len(), t + 1);
+
+        // Later shares (including duplicates) are ignored.
+        for share in shares.iter() {
+            assert!(matches!(aggregator.receive_share(rng, share.clone()).unwrap(), ReceiveOutcome::AlreadyFinalized));
+        }
+        assert!(*aggregator.transcript() == transcript);
+
+        // The final transcript verifies.
+        let mut verifier = PVSSAggregator {
+            config: conf.clone(),
+            scheme_sig: SchnorrSignature { srs: SCHSRS { g_public_key: conf.srs.g1 } },
+            participants: registry.participants.clone(),
+            aggregated_tx: PVSSAggregatedShare::empty(t, n),
+        };
+        verifier.aggregation_verify(rng, &transcript).unwrap();
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let rng = &mut thread_rng();
+        let t = 3;
+        let n = 5;
+
+        // The committee's dealings are bound to the epoch, so only its keys are used.
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let (conf, registry) = (fixture.config, fixture.registry);
+
+        let shares = fixture
+            .keypairs
+            .into_iter()
+            .enumerate()
+            .map(|(id, keys)| {
+                let mut dealer = DealerNode::new(conf.clone(), registry.clone(), keys.secret_key_ed, id).unwrap();
+                dealer.deal(0, rng).unwrap().0
+            })
+            .collect::<Vec<_>>();
+
+        // Four shares finalize the transcript; snapshot after two of them.
+        let mut aggregator = Aggregator::new(conf.clone(), registry.clone());
+        for share in shares.iter().take(2) {
+            aggregator.receive_share(rng, share.clone()).unwrap();
+        }
+        let snapshot = aggregator.snapshot();
+
+        // Restore into a fresh aggregator; re-delivering a restored share is a duplicate.
+        let mut restored = Aggregator::restore(rng, conf.clone(), &snapshot).unwrap();
+        assert!(!restored.is_finalized());
+        assert!(*restored.transcript() == *aggregator.transcript());
+        assert!(matches!(restored.receive_share(rng, shares[1].clone()).unwrap(), ReceiveOutcome::Duplicate));
+
+        // The remaining shares finalize the restored aggregator.
+        assert!(matches!(restored.receive_share(rng, shares[2].clone()).unwrap(), ReceiveOutcome::Accepted { contributions: 3 }));
+        let transcript = match restored.receive_share(rng, shares[3].clone()).unwrap() {
+            ReceiveOutcome::Finalized(transcript) => transcript,
+            _ => panic!("expected Finalized"),
+        };
+        assert_eq!(transcript.contributions.len(), t + 1);
+
+        // A finalized aggregator survives a round trip as well.
+        assert!(Aggregator::restore(rng, conf.clone(), &restored.snapshot()).unwrap().is_finalized());
+
+        // An unknown version is rejected.
+        let mut bad_version = snapshot.clone();
+        bad_version[0] = SNAPSHOT_VERSION + 1;
+        match Aggregator::restore(rng, conf.clone(), &bad_version) {
+            Err(PVSSError::SnapshotVersionError(version)) => assert_eq!(version, SNAPSHOT_VERSION + 1),
+            _ => panic!("expected SnapshotVersionError"),
+        }
+
+        // A lying finalization flag is rejected.
+        let mut bad_flag = snapshot.clone();
+        *bad_flag.last_mut().unwrap() = 1;
+        assert!(matches!(Aggregator::restore(rng, conf, &bad_flag), Err(PVSSError::SnapshotIntegrityError)));
+    }
+}
@@ -0,0 +1,199 @@
+use crate::modified_scrape::{config::Config, errors::PVSSError, node::message::Message};
+
+use ark_ec::PairingEngine;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{format, marker::PhantomData, string::ToString, vec::Vec};
+
+
+// Version of the framing, written to the first byte following the length prefix. Any change to
+// the frame layout or to the message encoding must bump it.
+pub const FRAME_VERSION: u8 = 2;
+
+// Length of the length prefix: a little-endian u32 counting the version byte and the payload.
+pub const FRAME_PREFIX_LEN: usize = 4;
+
+
+// Function encoding a message as a frame: the length prefix, the version byte, and the
+// message's canonical serialization.
+pub fn encode_frame<E: PairingEngine>(msg: &Message<E>) -> Vec<u8> {
+    let len = 1 + msg.serialized_size();
+
+    let mut bytes = Vec::with_capacity(FRAME_PREFIX_LEN + len);
+    bytes.extend_from_slice(&(len as u32).to_le_bytes());
+    bytes.push(FRAME_VERSION);
+    msg.serialize(&mut bytes).unwrap();
+
+    bytes
+}
+
+
+/* Struct FrameDecoder reassembles the frames written by encode_frame out of a byte stream that
+   arrives in arbitrary chunks (e.g., reads off a TCP socket). Partial frames are buffered until
+   complete. A frame whose length prefix exceeds the largest message under the configuration is
+   rejected as soon as the prefix is read, before its payload is buffered. After any error the
+   stream is out of sync, and the decoder should be discarded along with the connection. */
+pub struct FrameDecoder<E: PairingEngine> {
+    buffer: Vec<u8>,                  // bytes received but not decoded yet
+    max_frame_size: usize,            // largest accepted frame length (as in the length prefix)
+    pairing_engine: PhantomData<E>,   // cache E
+}
+
+impl<E: PairingEngine> FrameDecoder<E> {
+
+    // Function for creating a decoder accepting any message valid under the given configuration.
+    pub fn new(config: &Config<E>) -> Self {
+        Self {
+            buffer: Vec::new(),
+            max_frame_size: 1 + Message::max_size(config),
+            pairing_engine: PhantomData,
+        }
+    }
+
+    // Method for appending a chunk of the stream.
+    pub fn push(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    // Method returning the number of buffered bytes not decoded yet.
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    // Method decoding the next message, if a complete frame has been buffered.
+    pub fn next_message(&mut self) -> Result<Option<Message<E>>, PVSSError<E>> {
+        if self.buffer.len() < FRAME_PREFIX_LEN {
+            return Ok(None);
+        }
+
+        let mut len_bytes = [0u8; FRAME_PREFIX_LEN];
+        len_bytes.copy_from_slice(&self.buffer[..FRAME_PREFIX_LEN]);
+        let len = u32::from_le_bytes(len_bytes) as usize;
+        if len > self.max_frame_size {
+            return Err(PVSSError::WireSizeExceeded(len, self.max_frame_size));
+        }
+        if len == 0 {
+            return Err(PVSSError::InvalidWireFormat("empty frame".to_string()));
+        }
+
+        if self.buffer.len() < FRAME_PREFIX_LEN + len {
+            return Ok(None);
+        }
+
+        let frame = self.buffer.drain(..FRAME_PREFIX_LEN + len).collect::<Vec<_>>();
+        let (version, payload) = (frame[FRAME_PREFIX_LEN], &frame[FRAME_PREFIX_LEN + 1..]);
+        if version != FRAME_VERSION {
+            return Err(PVSSError::InvalidWireFormat(format!("unsupported version {}", version)));
+        }
+
+        let mut reader = payload;
+        let msg = Message::deserialize(&mut reader)?;
+        if !reader.is_empty() {
+            return Err(PVSSError::InvalidWireFormat(format!("{} trailing bytes", reader.len())));
+        }
+
+        Ok(Some(msg))
+    }
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{encode_frame, FrameDecoder, FRAME_PREFIX_LEN, FRAME_VERSION};
+    use crate::{
+        modified_scrape::{errors::PVSSError, node::message::Message},
+        testing::simulation::Simulation,
+    };
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ec::{AffineCurve, PairingEngine};
+
+    use rand::thread_rng;
+
+    // Feeds the stream to a fresh decoder in the given chunks, decoding after every chunk.
+    fn decode_in_chunks(decoder: &mut FrameDecoder<E>, stream: &[u8], cuts: &[usize]) -> Vec<Message<E>> {
+        let mut messages = vec![];
+        let mut start = 0;
+
+        for &end in cuts.iter().chain(Some(&stream.len())) {
+            decoder.push(&stream[start..end]);
+            while let Some(msg) = decoder.next_message().unwrap() {
+                messages.push(msg);
+            }
+            start = end;
+        }
+
+        messages
+    }
+
+    #[test]
+    fn test_frame_reassembly() {
+        let rng = &mut thread_rng();
+        let (t, n) = (2, 5);
+
+        let mut simulation = Simulation::<E>::new(rng, t, n).unwrap();
+        let (share, _) = simulation.nodes[3].dealer.deal(1, rng).unwrap();
+
+        let messages = vec![
+            Message::Have { epoch: 1, digests: vec![share.digest()] },
+            Message::Share { epoch: 1, share },
+            Message::BeaconOutput { epoch: 1, value: [5u8; 32], proof: <E as PairingEngine>::G1Affine::prime_subgroup_generator() },
+            Message::Want { epoch: 2, digests: vec![] },
+        ];
+        let frames = messages.iter().map(encode_frame).collect::<Vec<_>>();
+        assert!(frames.iter().all(|frame| frame[FRAME_PREFIX_LEN] == FRAME_VERSION));
+        let stream = frames.concat();
+
+        // Cuts in the middle of a length prefix, of a payload, and right after a version byte.
+        let first = frames[0].len();
+        let second = first + frames[1].len();
+        let cuts = [2, first + 3, first + FRAME_PREFIX_LEN + 1, second - 100, second + 1];
+
+        let mut decoder = FrameDecoder::new(&simulation.config);
+        let decoded = decode_in_chunks(&mut decoder, &stream, &cuts);
+        assert!(decoded == messages);
+        assert_eq!(decoder.buffered(), 0);
+
+        // Byte by byte, and all at once.
+        let cuts = (1..stream.len()).collect::<Vec<_>>();
+        assert!(decode_in_chunks(&mut FrameDecoder::new(&simulation.config), &stream, &cuts) == messages);
+        assert!(decode_in_chunks(&mut FrameDecoder::new(&simulation.config), &stream, &[]) == messages);
+
+        // A trailing partial frame stays buffered.
+        let mut decoder = FrameDecoder::new(&simulation.config);
+        let decoded = decode_in_chunks(&mut decoder, &stream[..second + 3], &[]);
+        assert!(decoded == messages[..2]);
+        assert_eq!(decoder.buffered(), 3);
+    }
+
+    #[test]
+    fn test_frame_rejection() {
+        let rng = &mut thread_rng();
+        let simulation = Simulation::<E>::new(rng, 1, 3).unwrap();
+        let frame = encode_frame(&Message::<E>::Want { epoch: 0, digests: vec![[1u8; 32]] });
+
+        // An oversize frame is rejected from its length prefix alone.
+        let max = 1 + Message::max_size(&simulation.config);
+        let mut decoder = FrameDecoder::<E>::new(&simulation.config);
+        decoder.push(&(max as u32 + 1).to_le_bytes());
+        match decoder.next_message() {
+            Err(PVSSError::WireSizeExceeded(got, limit)) => assert_eq!((got, limit), (max + 1, max)),
+            _ => panic!("expected WireSizeExceeded"),
+        }
+
+        // Empty frames, unknown versions and payloads with trailing bytes are rejected.
+        let mut bad_version = frame.clone();
+        bad_version[FRAME_PREFIX_LEN] = FRAME_VERSION + 1;
+        let mut trailing = frame.clone();
+        trailing[0] += 1;
+        trailing.push(0);
+
+        for stream in [vec![0u8; FRAME_PREFIX_LEN], bad_version, trailing].iter() {
+            let mut decoder = FrameDecoder::<E>::new(&simulation.config);
+            decoder.push(stream);
+            assert!(matches!(decoder.next_message(), Err(PVSSError::InvalidWireFormat(_))));
+        }
+    }
+}
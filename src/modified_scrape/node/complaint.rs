@@ -0,0 +1,185 @@
+use crate::modified_scrape::{
+    config::Config,
+    errors::{PVSSError, RejectionReason},
+    participant::{ParticipantId, ParticipantRegistry},
+    roles::OptrandScheme,
+    share::{PVSSShare, SignedProof},
+};
+
+use ark_ec::PairingEngine;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+
+use rand::{CryptoRng, Rng};
+
+
+/* Struct Complaint is a self-contained accusation against the issuer of a PVSS share that
+   fails verification: it carries the offending share itself, so that any node can re-run the
+   verification without having received the share beforehand. */
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, PartialEq)]
+pub struct Complaint<E: PairingEngine> {
+    pub accuser_id: usize,                  // id of the complaining participant
+    pub offending_share: PVSSShare<E>,      // the share failing verification
+    pub failure: RejectionReason,           // the check it fails, and the issuer's id
+}
+
+impl<E: PairingEngine> Complaint<E> {
+
+    // Function for complaining, as participant accuser_id, about a share.
+    // The share is verified first; a complaint can only be created if verification fails, in
+    // which case it records the failed check. A valid share yields InvalidComplaint.
+    pub fn generate<R: Rng + CryptoRng>(
+        rng: &mut R,
+        conf: &Config<E>,
+        registry: &ParticipantRegistry<E>,
+        accuser_id: usize,
+        share: PVSSShare<E>,
+    ) -> Result<Self, PVSSError<E>> {
+        registry.get_by_index(accuser_id)?;
+
+        let accused_id = share.participant_id;
+        match Self::check(rng, conf, registry, &share) {
+            Ok(()) => Err(PVSSError::InvalidComplaint { accused_id: accused_id.index() }),
+            Err(err) => Ok(Self {
+                accuser_id,
+                failure: RejectionReason::from_error(&err, Some(u64::from(accused_id.0))),
+                offending_share: share,
+            }),
+        }
+    }
+
+
+    // Method for checking that a (received) complaint holds: the accuser must belong to the
+    // committee, and verifying the offending share must fail with the recorded reason.
+    // Since verification performs its checks in a fixed order, the first failing check (which
+    // is the reported one) is exactly the one recorded when the complaint was generated.
+    pub fn verify<R: Rng + CryptoRng>(&self, rng: &mut R, conf: &Config<E>, registry: &ParticipantRegistry<E>) -> Result<(), PVSSError<E>> {
+        registry.get_by_index(self.accuser_id)?;
+
+        let accused_id = self.offending_share.participant_id.index();
+        if self.failure.participant_id != Some(accused_id as u64) {
+            return Err(PVSSError::InvalidComplaint { accused_id });
+        }
+
+        match Self::check(rng, conf, registry, &self.offending_share) {
+            Err(err) if err.kind().code() == self.failure.code => Ok(()),
+            _ => Err(PVSSError::InvalidComplaint { accused_id }),
+        }
+    }
+
+
+    // Utility function running share verification.
+    fn check<R: Rng + CryptoRng>(
+        rng: &mut R,
+        conf: &Config<E>,
+        registry: &ParticipantRegistry<E>,
+        share: &PVSSShare<E>,
+    ) -> Result<(), PVSSError<E>> {
+        OptrandScheme::new(conf.clone())
+            .aggregator(registry)
+            .share_verify(rng, share)
+    }
+}
+
+
+/* Struct Equivocation is evidence that a dealer issued two different signed decomposition
+   proofs (hence, two different shares) in place of a single one. */
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, PartialEq)]
+pub struct Equivocation<E: PairingEngine> {
+    pub participant_id: ParticipantId,      // id of the equivocating dealer
+    pub first: SignedProof<E>,              // one of the signed proofs
+    pub second: SignedProof<E>,             // a different signed proof, from the same dealer
+}
+
+impl<E: PairingEngine> Equivocation<E> {
+
+    // Method for checking the evidence: the proofs must differ, and both signatures must verify
+    // against the dealer's EdDSA public key.
+    pub fn verify(&self, registry: &ParticipantRegistry<E>) -> Result<(), PVSSError<E>> {
+        let participant = registry.get(self.participant_id)?;
+
+        let signed = |proof: &SignedProof<E>| {
+            proof.signature_on_decomp.verify(proof.digest(), &participant.public_key_ed).is_ok()
+        };
+
+        if self.first.decomp_proof() == self.second.decomp_proof() || !signed(&self.first) || !signed(&self.second) {
+            return Err(PVSSError::InvalidComplaint { accused_id: self.participant_id.index() });
+        }
+
+        Ok(())
+    }
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::Complaint;
+    use crate::{
+        modified_scrape::errors::{PVSSError, PVSSErrorCode, RejectionReason},
+        testing::fixtures::FixtureBuilder,
+        Signature,
+    };
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ec::ProjectiveCurve;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_complaints() {
+        let rng = &mut thread_rng();
+        let t = 2;
+        let n = 5;
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let (conf, registry) = (&fixture.config, &fixture.registry);
+        let valid = fixture.shares[1].clone();
+
+        // A bad signature: participant 1's share, signed with participant 2's key.
+        let mut bad_signature = valid.clone();
+        bad_signature.signed_proof.signature_on_decomp =
+            Signature::new(bad_signature.signed_proof.digest(), &fixture.keypairs[2].secret_key_ed);
+        let complaint = Complaint::generate(rng, conf, registry, 0, bad_signature).unwrap();
+        assert_eq!(complaint.failure.code, PVSSErrorCode::SignatureVerificationFailed);
+        assert_eq!(complaint.failure.participant_id, Some(1));
+
+        // Others check the complaint from its serialization alone.
+        let mut bytes = vec![];
+        complaint.serialize(&mut bytes).unwrap();
+        let received = Complaint::<E>::deserialize(&bytes[..]).unwrap();
+        assert!(received == complaint);
+        received.verify(rng, conf, registry).unwrap();
+
+        // A bad degree: a commitment (other than the issuer's own) is shifted off the polynomial.
+        let mut bad_degree = valid.clone();
+        bad_degree.pvss_core.comms[4].add_assign_mixed(&conf.srs.g2);
+        let complaint = Complaint::generate(rng, conf, registry, 3, bad_degree).unwrap();
+        assert_eq!(complaint.failure.code, PVSSErrorCode::DualCodeError);
+        complaint.verify(rng, conf, registry).unwrap();
+
+        // Recording another reason than the failing check does not hold.
+        let mut wrong_reason = complaint.clone();
+        wrong_reason.failure.code = PVSSErrorCode::SignatureVerificationFailed;
+        assert!(matches!(wrong_reason.verify(rng, conf, registry), Err(PVSSError::InvalidComplaint { accused_id: 1 })));
+
+        // No complaint can be generated about a valid share, and a forged one does not hold.
+        assert!(matches!(
+            Complaint::generate(rng, conf, registry, 0, valid.clone()),
+            Err(PVSSError::InvalidComplaint { accused_id: 1 })
+        ));
+        let forged = Complaint {
+            accuser_id: 0,
+            offending_share: valid,
+            failure: RejectionReason::from_error(&PVSSError::<E>::DualCodeError, Some(1)),
+        };
+        assert!(matches!(forged.verify(rng, conf, registry), Err(PVSSError::InvalidComplaint { accused_id: 1 })));
+
+        // Accusers must belong to the committee.
+        let mut outsider = complaint;
+        outsider.accuser_id = n;
+        assert!(matches!(outsider.verify(rng, conf, registry), Err(PVSSError::InvalidParticipantId(id)) if id == n));
+    }
+}
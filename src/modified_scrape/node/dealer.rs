@@ -0,0 +1,138 @@
+use crate::{
+    modified_scrape::{
+        config::Config,
+        errors::PVSSError,
+        participant::ParticipantRegistry,
+        node::message::{Message, SignedMessage},
+        pvss::PVSSShareSecrets,
+        roles::{Dealer as _, OptrandScheme},
+        share::PVSSShare,
+    },
+    SecretKey,
+};
+
+use ark_ec::PairingEngine;
+use ark_std::collections::BTreeMap;
+
+use rand::{CryptoRng, Rng};
+
+
+/* Struct DealerNode drives share generation for a single node: it holds the node's keys and id,
+   deals one PVSS share per epoch, and keeps the secrets of the shares it has dealt. */
+pub struct DealerNode<E: PairingEngine> {
+    pub config: Config<E>,                       // the "global" configuration parameters
+    pub registry: ParticipantRegistry<E>,        // the committee's public keys
+    pub id: usize,                               // this node's participant id
+    private_key_ed: SecretKey,                   // EdDSA secret (signing) key
+    dealt: BTreeMap<u64, PVSSShareSecrets<E>>,   // secrets of the dealt shares, by epoch
+}
+
+impl<E: PairingEngine> DealerNode<E> {
+
+    // Function for creating a new dealer. The id must belong to the registry.
+    pub fn new(
+        config: Config<E>,
+        registry: ParticipantRegistry<E>,
+        private_key_ed: SecretKey,
+        id: usize,
+    ) -> Result<Self, PVSSError<E>> {
+        registry.get_by_index(id)?;
+
+        Ok(Self { config, registry, id, private_key_ed, dealt: BTreeMap::new() })
+    }
+
+
+    // Method for dealing the PVSS share of the given epoch.
+    // Dealing twice in the same epoch would amount to equivocation, hence it is refused.
+    pub fn deal<R: Rng + CryptoRng>(
+        &mut self,
+        epoch: u64,
+        rng: &mut R,
+    ) -> Result<(PVSSShare<E>, PVSSShareSecrets<E>), PVSSError<E>> {
+        if self.dealt.contains_key(&epoch) {
+            return Err(PVSSError::AlreadyDealtError(epoch));
+        }
+
+        self.deal_override(epoch, rng)
+    }
+
+
+    // Method for dealing the PVSS share of the given epoch, even if one has already been dealt.
+    // The stored secrets of the epoch are replaced by the new ones.
+    pub fn deal_override<R: Rng + CryptoRng>(
+        &mut self,
+        epoch: u64,
+        rng: &mut R,
+    ) -> Result<(PVSSShare<E>, PVSSShareSecrets<E>), PVSSError<E>> {
+        let scheme = OptrandScheme::new(self.config.clone());
+        let (share, secrets) = scheme.deal(rng, &self.registry, self.id, &self.private_key_ed)?;
+
+        self.dealt.insert(epoch, secrets.clone());
+
+        Ok((share, secrets))
+    }
+
+
+    // Method returning the secrets of the share dealt in the given epoch, if any.
+    pub fn secrets(&self, epoch: u64) -> Option<&PVSSShareSecrets<E>> {
+        self.dealt.get(&epoch)
+    }
+
+    // Method for enveloping a message sent by this node, signed with its EdDSA key.
+    pub fn sign(&self, msg: Message<E>) -> SignedMessage<E> {
+        SignedMessage::sign(&self.private_key_ed, self.id, msg)
+    }
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::DealerNode;
+    use crate::{
+        modified_scrape::{aggregator::PVSSAggregator, errors::PVSSError, share::PVSSAggregatedShare},
+        signature::schnorr::{SchnorrSignature, srs::SRS as SCHSRS},
+        testing::fixtures::FixtureBuilder,
+    };
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use rand::thread_rng;
+
+    #[test]
+    fn test_deal_per_epoch() {
+        let rng = &mut thread_rng();
+        let t = 2;
+        let n = 5;
+
+        let mut fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let (conf, registry) = (fixture.config, fixture.registry);
+
+        let id = 3;
+        let mut dealer = DealerNode::new(conf.clone(), registry.clone(), fixture.keypairs.remove(id).secret_key_ed, id).unwrap();
+
+        let mut aggregator = PVSSAggregator {
+            config: conf.clone(),
+            scheme_sig: SchnorrSignature { srs: SCHSRS { g_public_key: conf.srs.g1 } },
+            participants: registry.participants.clone(),
+            aggregated_tx: PVSSAggregatedShare::empty(t, n),
+        };
+
+        // Two epochs produce distinct shares, which both verify.
+        let (share1, secrets1) = dealer.deal(1, rng).unwrap();
+        let (share2, _) = dealer.deal(2, rng).unwrap();
+        assert!(share1 != share2);
+        aggregator.share_verify(rng, &share1).unwrap();
+        aggregator.share_verify(rng, &share2).unwrap();
+        assert_eq!(dealer.secrets(1).unwrap().p_0, secrets1.p_0);
+
+        // Dealing again in the same epoch is refused, unless overridden.
+        match dealer.deal(1, rng) {
+            Err(PVSSError::AlreadyDealtError(epoch)) => assert_eq!(epoch, 1),
+            _ => panic!("expected AlreadyDealtError"),
+        }
+        let (_, secrets1_new) = dealer.deal_override(1, rng).unwrap();
+        assert_eq!(dealer.secrets(1).unwrap().p_0, secrets1_new.p_0);
+    }
+}
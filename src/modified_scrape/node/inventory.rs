@@ -0,0 +1,187 @@
+use crate::modified_scrape::{
+    errors::PVSSError,
+    node::message::{Message, MAX_DIGESTS},
+    share::{PVSSAggregatedShare, PVSSShare},
+};
+
+use ark_ec::PairingEngine;
+use ark_std::collections::BTreeMap;
+
+
+/* Struct InventoryTracker indexes the shares and aggregated transcripts a node holds for an
+   epoch by their digests, so that nodes can advertise what they hold (Message::Have) and only
+   request what they miss (Message::Want), instead of re-sending full shares. Messages of other
+   epochs are ignored. */
+pub struct InventoryTracker<E: PairingEngine> {
+    pub epoch: u64,                                            // the epoch whose inventory is tracked
+    shares: BTreeMap<[u8; 32], PVSSShare<E>>,                  // shares held, by digest
+    aggregates: BTreeMap<[u8; 32], PVSSAggregatedShare<E>>,    // aggregated transcripts held, by digest
+}
+
+impl<E: PairingEngine> InventoryTracker<E> {
+
+    // Function for creating an empty inventory for the given epoch.
+    pub fn new(epoch: u64) -> Self {
+        Self { epoch, shares: BTreeMap::new(), aggregates: BTreeMap::new() }
+    }
+
+
+    // Method for storing a share; returns its digest.
+    pub fn insert_share(&mut self, share: PVSSShare<E>) -> [u8; 32] {
+        let digest = share.digest();
+        self.shares.insert(digest, share);
+
+        digest
+    }
+
+    // Method for storing an aggregated transcript; returns its digest.
+    pub fn insert_aggregate(&mut self, share: PVSSAggregatedShare<E>) -> [u8; 32] {
+        let digest = share.digest();
+        self.aggregates.insert(digest, share);
+
+        digest
+    }
+
+    // Method returning whether a share or an aggregated transcript with the given digest is held.
+    pub fn contains(&self, digest: &[u8; 32]) -> bool {
+        self.shares.contains_key(digest) || self.aggregates.contains_key(digest)
+    }
+
+    // Method returning the number of shares and aggregated transcripts held.
+    pub fn len(&self) -> usize {
+        self.shares.len() + self.aggregates.len()
+    }
+
+    // Method returning whether nothing is held.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+
+    // Method returning the Have messages advertising the whole inventory, each carrying at most
+    // MAX_DIGESTS digests. An empty inventory is not advertised.
+    pub fn have(&self) -> Vec<Message<E>> {
+        let digests = self.shares.keys().chain(self.aggregates.keys()).copied().collect::<Vec<_>>();
+
+        digests
+            .chunks(MAX_DIGESTS)
+            .map(|chunk| Message::Have { epoch: self.epoch, digests: chunk.to_vec() })
+            .collect()
+    }
+
+    // Method for handling a received Have message: returns the Want message requesting the
+    // advertised digests that are not held, if any.
+    pub fn receive_have(&self, epoch: u64, digests: &[[u8; 32]]) -> Result<Option<Message<E>>, PVSSError<E>> {
+        check_digests::<E>(digests)?;
+        if epoch != self.epoch {
+            return Ok(None);
+        }
+
+        let mut missing = digests.iter().filter(|digest| !self.contains(digest)).copied().collect::<Vec<_>>();
+        missing.sort_unstable();
+        missing.dedup();
+
+        if missing.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(Message::Want { epoch, digests: missing }))
+    }
+
+    // Method for handling a received Want message: returns a Share or Aggregated message for
+    // every requested digest that is held. Unknown digests are skipped.
+    pub fn receive_want(&self, epoch: u64, digests: &[[u8; 32]]) -> Result<Vec<Message<E>>, PVSSError<E>> {
+        check_digests::<E>(digests)?;
+        if epoch != self.epoch {
+            return Ok(vec![]);
+        }
+
+        let messages = digests
+            .iter()
+            .filter_map(|digest| {
+                if let Some(share) = self.shares.get(digest) {
+                    return Some(Message::Share { epoch, share: share.clone() });
+                }
+                self.aggregates.get(digest).map(|share| Message::Aggregated { epoch, share: share.clone() })
+            })
+            .collect();
+
+        Ok(messages)
+    }
+}
+
+
+// Utility function for bounding the digest lists handed to an InventoryTracker.
+fn check_digests<E: PairingEngine>(digests: &[[u8; 32]]) -> Result<(), PVSSError<E>> {
+    if digests.len() > MAX_DIGESTS {
+        return Err(PVSSError::TooManyDigests(digests.len(), MAX_DIGESTS));
+    }
+
+    Ok(())
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::InventoryTracker;
+    use crate::{
+        modified_scrape::{
+            errors::PVSSError,
+            node::message::{Message, MAX_DIGESTS},
+            share::PVSSAggregatedShare,
+        },
+        testing::simulation::Simulation,
+    };
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_inventory_tracker() {
+        let rng = &mut thread_rng();
+        let (t, n) = (1, 3);
+
+        let mut simulation = Simulation::<E>::new(rng, t, n).unwrap();
+        let (share, _) = simulation.nodes[0].dealer.deal(4, rng).unwrap();
+        let (other, _) = simulation.nodes[1].dealer.deal(4, rng).unwrap();
+        let aggregate = PVSSAggregatedShare::empty(t, n).aggregate_pvss_share(&share).unwrap();
+
+        let mut inventory = InventoryTracker::<E>::new(4);
+        assert!(inventory.is_empty() && inventory.have().is_empty());
+
+        let share_digest = inventory.insert_share(share.clone());
+        let aggregate_digest = inventory.insert_aggregate(aggregate.clone());
+        assert_ne!(share_digest, aggregate_digest);
+        assert_eq!(inventory.len(), 2);
+
+        match &inventory.have()[..] {
+            [Message::Have { epoch: 4, digests }] => assert_eq!(digests.len(), 2),
+            _ => panic!("expected a single Have"),
+        }
+
+        // Only the missing digest is requested, once.
+        let advertised = [share_digest, other.digest(), other.digest()];
+        match inventory.receive_have(4, &advertised).unwrap() {
+            Some(Message::Want { epoch: 4, digests }) => assert_eq!(digests, vec![other.digest()]),
+            _ => panic!("expected a Want"),
+        }
+        assert!(inventory.receive_have(4, &[share_digest, aggregate_digest]).unwrap().is_none());
+        assert!(inventory.receive_have(5, &advertised).unwrap().is_none());
+
+        // Requests are answered for the digests held.
+        let answers = inventory.receive_want(4, &[aggregate_digest, other.digest(), share_digest]).unwrap();
+        assert_eq!(answers.len(), 2);
+        assert!(answers[0] == Message::Aggregated { epoch: 4, share: aggregate });
+        assert!(answers[1] == Message::Share { epoch: 4, share });
+        assert!(inventory.receive_want(3, &[share_digest]).unwrap().is_empty());
+
+        // Digest lists over the bound are rejected.
+        let long = vec![share_digest; MAX_DIGESTS + 1];
+        assert!(matches!(inventory.receive_have(4, &long), Err(PVSSError::TooManyDigests(got, MAX_DIGESTS)) if got == MAX_DIGESTS + 1));
+        assert!(matches!(inventory.receive_want(4, &long), Err(PVSSError::TooManyDigests(..))));
+    }
+}
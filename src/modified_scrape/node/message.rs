@@ -0,0 +1,450 @@
+use crate::{
+    Digest,
+    modified_scrape::{
+        config::Config,
+        decomp::{Blake2sDigestHasher, DecompProof, DigestHasher},
+        decryption::DecryptionProof,
+        errors::PVSSError,
+        participant::ParticipantRegistry,
+        pvss::PVSSCore,
+        share::{PVSSAggregatedShare, PVSSShare, SignedProof},
+    },
+    PublicKey,
+    read_array,
+    Scalar,
+    SecretKey,
+    Signature,
+};
+
+use ark_ec::PairingEngine;
+use ark_ff::Zero;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+
+
+// One-byte discriminants identifying the message variants on the wire.
+const SHARE: u8 = 0;
+const AGGREGATED: u8 = 1;
+const DECRYPTION: u8 = 2;
+const BEACON_OUTPUT: u8 = 3;
+const HAVE: u8 = 4;
+const WANT: u8 = 5;
+
+// Domain separator of the bytes signed by message envelopes.
+const ENVELOPE_TAG: &[u8] = b"OPTRAND-ENVELOPE";
+
+// Largest number of digests carried by a single Have or Want message; longer lists are rejected
+// on deserialization, before anything is allocated for them.
+pub const MAX_DIGESTS: usize = 1024;
+
+
+/* Enumeration Message defines the envelopes exchanged by nodes. Every message carries the
+   epoch it belongs to, and is serialized as a one-byte discriminant followed by its fields. */
+#[derive(Clone, PartialEq)]
+pub enum Message<E: PairingEngine> {
+    Share { epoch: u64, share: PVSSShare<E> },                   // a dealt PVSS share
+    Aggregated { epoch: u64, share: PVSSAggregatedShare<E> },    // an aggregated transcript
+    Decryption {                                                 // a decrypted share
+        epoch: u64,
+        id: usize,                                               // id of the decrypting participant
+        point: E::G1Affine,                                      // the decrypted share g1^{p(id + 1)}
+        proof: DecryptionProof<E>,                               // proof of correct decryption
+    },
+    BeaconOutput {                                               // the epoch's beacon output
+        epoch: u64,
+        value: [u8; 32],                                         // the beacon value
+        proof: E::G1Affine,                                      // the reconstructed secret g1^{p(0)}
+    },
+    Have { epoch: u64, digests: Vec<[u8; 32]> },                 // digests of the shares/aggregates held
+    Want { epoch: u64, digests: Vec<[u8; 32]> },                 // digests of the shares/aggregates requested
+}
+
+impl<E: PairingEngine> Message<E> {
+
+    // Method returning the epoch the message belongs to.
+    pub fn epoch(&self) -> u64 {
+        match self {
+            Message::Share { epoch, .. }
+            | Message::Aggregated { epoch, .. }
+            | Message::Decryption { epoch, .. }
+            | Message::BeaconOutput { epoch, .. }
+            | Message::Have { epoch, .. }
+            | Message::Want { epoch, .. } => *epoch,
+        }
+    }
+
+    // Function returning an upper bound on the serialized size of any message under the given
+    // configuration (the largest variant being the aggregated transcript), for use in framing.
+    pub fn max_size(config: &Config<E>) -> usize {
+        let n = config.num_participants;
+        let g1 = E::G1Affine::zero().serialized_size();
+        let fr = Scalar::<E>::zero().serialized_size();
+        let header = SHARE.serialized_size() + 0u64.serialized_size();
+        let id = 0usize.serialized_size();
+
+        let signed_proof = SignedProof::<E>::new(
+            DecompProof {
+                proof: (E::G2Affine::zero(), Scalar::<E>::zero(), Scalar::<E>::zero()),
+                gs: E::G2Affine::zero(),
+            },
+            Signature::default(),
+        )
+        .serialized_size();
+        let core = PVSSCore::<E>::empty(n).serialized_size();
+
+        let digest = Digest::default().serialized_size();
+
+        let share = 3 * id + digest + core + signed_proof;
+        let aggregated = 2 * id + digest + core + 0u64.serialized_size() + n * (id + signed_proof);
+        let decryption = id + g1 + 2 * g1 + 2 * fr;
+        let beacon_output = 32 + g1;
+        let inventory = 0u64.serialized_size() + MAX_DIGESTS * 32;
+
+        header + *[share, aggregated, decryption, beacon_output, inventory].iter().max().unwrap()
+    }
+}
+
+impl<E: PairingEngine> CanonicalSerialize for Message<E> {
+    #[inline]
+    fn serialize<W: Write>(
+    &self,
+    mut writer: W,
+    ) -> Result<(), SerializationError> {
+        match self {
+            Message::Share { epoch, share } => {
+                SHARE.serialize(&mut writer)?;
+                epoch.serialize(&mut writer)?;
+                share.serialize(&mut writer)?;
+            }
+            Message::Aggregated { epoch, share } => {
+                AGGREGATED.serialize(&mut writer)?;
+                epoch.serialize(&mut writer)?;
+                share.serialize(&mut writer)?;
+            }
+            Message::Decryption { epoch, id, point, proof } => {
+                DECRYPTION.serialize(&mut writer)?;
+                epoch.serialize(&mut writer)?;
+                id.serialize(&mut writer)?;
+                point.serialize(&mut writer)?;
+                proof.serialize(&mut writer)?;
+            }
+            Message::BeaconOutput { epoch, value, proof } => {
+                BEACON_OUTPUT.serialize(&mut writer)?;
+                epoch.serialize(&mut writer)?;
+                writer.write_all(value)?;
+                proof.serialize(&mut writer)?;
+            }
+            Message::Have { epoch, digests } => {
+                HAVE.serialize(&mut writer)?;
+                epoch.serialize(&mut writer)?;
+                write_digests(&mut writer, digests)?;
+            }
+            Message::Want { epoch, digests } => {
+                WANT.serialize(&mut writer)?;
+                epoch.serialize(&mut writer)?;
+                write_digests(&mut writer, digests)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn serialized_size(&self) -> usize {
+        let body = match self {
+            Message::Share { share, .. } => share.serialized_size(),
+            Message::Aggregated { share, .. } => share.serialized_size(),
+            Message::Decryption { id, point, proof, .. } => {
+                id.serialized_size() + point.serialized_size() + proof.serialized_size()
+            }
+            Message::BeaconOutput { value, proof, .. } => value.len() + proof.serialized_size(),
+            Message::Have { digests, .. } | Message::Want { digests, .. } => {
+                0u64.serialized_size() + digests.len() * 32
+            }
+        };
+
+        SHARE.serialized_size() + self.epoch().serialized_size() + body
+    }
+}
+
+impl<E: PairingEngine> CanonicalDeserialize for Message<E> {
+    #[inline]
+    fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let discriminant = u8::deserialize(&mut reader)?;
+        let epoch = u64::deserialize(&mut reader)?;
+
+        match discriminant {
+            SHARE => Ok(Message::Share { epoch, share: PVSSShare::deserialize(&mut reader)? }),
+            AGGREGATED => Ok(Message::Aggregated { epoch, share: PVSSAggregatedShare::deserialize(&mut reader)? }),
+            DECRYPTION => Ok(Message::Decryption {
+                epoch,
+                id: usize::deserialize(&mut reader)?,
+                point: E::G1Affine::deserialize(&mut reader)?,
+                proof: DecryptionProof::<E>::deserialize(&mut reader)?,
+            }),
+            BEACON_OUTPUT => Ok(Message::BeaconOutput {
+                epoch,
+                value: read_array(&mut reader)?,
+                proof: E::G1Affine::deserialize(&mut reader)?,
+            }),
+            HAVE => Ok(Message::Have { epoch, digests: read_digests(&mut reader)? }),
+            WANT => Ok(Message::Want { epoch, digests: read_digests(&mut reader)? }),
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+}
+
+
+/* Struct SignedMessage authenticates a message at the envelope level: its sender signs, with
+   its EdDSA key, the digest of a domain tag followed by the canonical bytes of
+   (sender_id, epoch, msg). Unlike the signed proofs inside shares, which only cover the
+   decomposition proofs, the signature covers the whole message and binds it to its sender. */
+#[derive(Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SignedMessage<E: PairingEngine> {
+    pub sender_id: usize,       // id of the signing participant
+    pub epoch: u64,             // the epoch of the message
+    pub msg: Message<E>,        // the enveloped message
+    pub signature: Signature,   // sender's EdDSA signature over the envelope
+}
+
+impl<E: PairingEngine> SignedMessage<E> {
+
+    // Function for enveloping a message of sender_id, signed with its EdDSA secret key.
+    pub fn sign(eddsa_sk: &SecretKey, sender_id: usize, msg: Message<E>) -> Self {
+        let epoch = msg.epoch();
+        let signature = Signature::new(&envelope_digest(sender_id, epoch, &msg), eddsa_sk);
+
+        Self { sender_id, epoch, msg, signature }
+    }
+
+    // Method for verifying the envelope against the sender's EdDSA public key, as found in the
+    // registry. The envelope's epoch must also be that of the enveloped message.
+    pub fn verify(&self, registry: &ParticipantRegistry<E>) -> Result<(), PVSSError<E>> {
+        self.verify_with_key(&registry.get_by_index(self.sender_id)?.public_key_ed)
+    }
+
+    // Method for verifying the envelope against the given EdDSA public key of the sender.
+    pub fn verify_with_key(&self, pk_ed: &PublicKey) -> Result<(), PVSSError<E>> {
+        if self.epoch != self.msg.epoch() {
+            return Err(PVSSError::InvalidEnvelope(format!(
+                "envelope of epoch {} carries a message of epoch {}", self.epoch, self.msg.epoch()
+            )));
+        }
+
+        self.signature
+            .verify(&envelope_digest(self.sender_id, self.epoch, &self.msg), pk_ed)
+            .map_err(|_| PVSSError::SignatureVerificationFailed { participant_id: self.sender_id })
+    }
+}
+
+// Utility function returning the digest signed by envelopes (see SignedMessage).
+fn envelope_digest<E: PairingEngine>(sender_id: usize, epoch: u64, msg: &Message<E>) -> Digest {
+    let mut bytes = ENVELOPE_TAG.to_vec();
+    sender_id.serialize(&mut bytes).unwrap();
+    epoch.serialize(&mut bytes).unwrap();
+    msg.serialize(&mut bytes).unwrap();
+
+    Digest(Blake2sDigestHasher::hash(&bytes))
+}
+
+
+// Utility function for writing a digest list as its length followed by the raw digests.
+fn write_digests<W: Write>(mut writer: W, digests: &[[u8; 32]]) -> Result<(), SerializationError> {
+    if digests.len() > MAX_DIGESTS {
+        return Err(SerializationError::InvalidData);
+    }
+
+    (digests.len() as u64).serialize(&mut writer)?;
+    for digest in digests.iter() {
+        writer.write_all(digest)?;
+    }
+    Ok(())
+}
+
+// Utility function for reading a digest list written by write_digests; lists longer than
+// MAX_DIGESTS are rejected.
+fn read_digests<R: Read>(mut reader: R) -> Result<Vec<[u8; 32]>, SerializationError> {
+    let len = u64::deserialize(&mut reader)?;
+    if len > MAX_DIGESTS as u64 {
+        return Err(SerializationError::InvalidData);
+    }
+
+    (0..len).map(|_| read_array(&mut reader)).collect()
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{Message, SignedMessage, MAX_DIGESTS};
+    use crate::{
+        generate_production_keypair,
+        modified_scrape::{
+            config::Config,
+            decomp::Decomp,
+            errors::PVSSError,
+            node::{Aggregator, ReceiveOutcome},
+            participant::ParticipantId,
+            poly::Polynomial,
+            pvss::PVSSCore,
+            share::{PVSSAggregatedShare, PVSSShare, SignedProof},
+            srs::SRS,
+        },
+        nizk::{dleq::{DLEQProof, srs::SRS as DLEQSRS}, scheme::NIZKProof},
+        testing::simulation::Simulation,
+        Scalar,
+        Signature,
+    };
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+    use ark_ff::PrimeField;
+    use ark_poly::UVPolynomial;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+    use ark_std::UniformRand;
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_message_round_trip() {
+        let rng = &mut thread_rng();
+        let t = 2;
+        let n = 5;
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+        let (_pk_sig, sk_sig) = generate_production_keypair();
+
+        // A share with a random core but a genuine signed proof.
+        let poly = Polynomial::<E>::rand(t, rng);
+        let dproof = Decomp::<E>::generate(rng, &conf, &poly[0]).unwrap();
+        let sig = Signature::new(&dproof.digest(), &sk_sig);
+        let share = PVSSShare {
+            participant_id: ParticipantId(1),
+            degree: t,
+            num_participants: n,
+            config_digest: conf.digest(),
+            pvss_core: PVSSCore::<E>::new(
+                (0..n).map(|_| <E as PairingEngine>::G1Projective::rand(rng)).collect(),
+                (0..n).map(|_| <E as PairingEngine>::G2Projective::rand(rng)).collect(),
+            ).unwrap(),
+            signed_proof: SignedProof::new(dproof, sig),
+        };
+        let agg_share = PVSSAggregatedShare::empty(t, n).aggregate_pvss_share(&share).unwrap();
+
+        // A decryption proof for dec = enc^{sk^{-1}}.
+        let sk = Scalar::<E>::rand(rng);
+        let dec = <E as PairingEngine>::G1Projective::rand(rng).into_affine();
+        let dleq = DLEQProof { srs: DLEQSRS { g_public_key: conf.srs.g1, h_public_key: dec } };
+        let proof = dleq.prove(rng, &sk).unwrap();
+
+        let messages = vec![
+            Message::Share { epoch: 1, share },
+            Message::Aggregated { epoch: 2, share: agg_share },
+            Message::Decryption { epoch: 3, id: 4, point: dec, proof },
+            Message::BeaconOutput { epoch: 4, value: [9u8; 32], proof: conf.srs.g1.mul(poly[0].into_repr()).into_affine() },
+            Message::Have { epoch: 5, digests: vec![[1u8; 32], [2u8; 32]] },
+            Message::Want { epoch: 6, digests: vec![] },
+        ];
+
+        for (i, msg) in messages.iter().enumerate() {
+            let mut bytes = vec![];
+            msg.serialize(&mut bytes).unwrap();
+
+            assert_eq!(bytes[0] as usize, i);
+            assert_eq!(bytes.len(), msg.serialized_size());
+            assert!(bytes.len() <= Message::<E>::max_size(&conf));
+
+            let decoded = Message::<E>::deserialize(&bytes[..]).unwrap();
+            assert!(decoded == *msg);
+            assert_eq!(decoded.epoch(), (i + 1) as u64);
+        }
+    }
+
+    #[test]
+    fn test_unknown_discriminant_rejected() {
+        let msg = Message::<E>::BeaconOutput {
+            epoch: 7,
+            value: [1u8; 32],
+            proof: <E as PairingEngine>::G1Affine::prime_subgroup_generator(),
+        };
+
+        let mut bytes = vec![];
+        msg.serialize(&mut bytes).unwrap();
+        bytes[0] = 6;
+
+        match Message::<E>::deserialize(&bytes[..]) {
+            Err(SerializationError::InvalidData) => (),
+            _ => panic!("expected InvalidData"),
+        }
+    }
+
+    #[test]
+    fn test_digest_list_bound() {
+        let msg = Message::<E>::Have { epoch: 1, digests: vec![[3u8; 32]; MAX_DIGESTS] };
+        let mut bytes = vec![];
+        msg.serialize(&mut bytes).unwrap();
+        assert!(Message::<E>::deserialize(&bytes[..]).unwrap() == msg);
+
+        // Lists over the bound are neither written nor read.
+        let long = Message::<E>::Want { epoch: 1, digests: vec![[3u8; 32]; MAX_DIGESTS + 1] };
+        assert!(long.serialize(&mut vec![]).is_err());
+
+        let offset = 1 + 8;   // past the discriminant and the epoch
+        bytes[offset..offset + 8].copy_from_slice(&(MAX_DIGESTS as u64 + 1).to_le_bytes());
+        match Message::<E>::deserialize(&bytes[..]) {
+            Err(SerializationError::InvalidData) => (),
+            _ => panic!("expected InvalidData"),
+        }
+    }
+
+    #[test]
+    fn test_signed_message() {
+        let rng = &mut thread_rng();
+        let (t, n) = (1, 3);
+
+        let mut simulation = Simulation::<E>::new(rng, t, n).unwrap();
+        let registry = simulation.registry.clone();
+        let (share, _) = simulation.nodes[0].dealer.deal(2, rng).unwrap();
+
+        // Node 1 relays node 0's share; the envelope verifies, also after serialization.
+        let envelope = simulation.nodes[1].dealer.sign(Message::Share { epoch: 2, share: share.clone() });
+        assert_eq!((envelope.sender_id, envelope.epoch), (1, 2));
+        envelope.verify(&registry).unwrap();
+
+        let mut bytes = vec![];
+        envelope.serialize(&mut bytes).unwrap();
+        let decoded = SignedMessage::<E>::deserialize(&bytes[..]).unwrap();
+        assert!(decoded == envelope);
+        decoded.verify(&registry).unwrap();
+
+        // The envelope replayed under another (or an unknown) sender id is rejected.
+        let mut replayed = envelope.clone();
+        replayed.sender_id = 2;
+        assert!(matches!(replayed.verify(&registry), Err(PVSSError::SignatureVerificationFailed { participant_id: 2 })));
+        replayed.sender_id = n;
+        assert!(matches!(replayed.verify(&registry), Err(PVSSError::InvalidParticipantId(id)) if id == n));
+
+        // So are mutated payloads and epochs.
+        let mut mutated = envelope.clone();
+        if let Message::Share { share, .. } = &mut mutated.msg {
+            share.participant_id = ParticipantId(2);
+        }
+        assert!(matches!(mutated.verify(&registry), Err(PVSSError::SignatureVerificationFailed { participant_id: 1 })));
+
+        let mut mutated = envelope.clone();
+        mutated.epoch = 3;
+        assert!(matches!(mutated.verify(&registry), Err(PVSSError::InvalidEnvelope(_))));
+        mutated.msg = Message::Share { epoch: 3, share };
+        assert!(matches!(mutated.verify(&registry), Err(PVSSError::SignatureVerificationFailed { participant_id: 1 })));
+
+        // The aggregator only accepts verified envelopes of shares.
+        let mut aggregator = Aggregator::new(simulation.config.clone(), registry);
+        assert!(aggregator.receive_signed(rng, mutated).is_err());
+        assert!(aggregator.receive_signed(rng, replayed).is_err());
+        assert!(aggregator.transcript().contributions.is_empty());
+
+        let beacon = simulation.nodes[1].dealer.sign(Message::BeaconOutput { epoch: 2, value: [0u8; 32], proof: simulation.config.srs.g1 });
+        assert!(matches!(aggregator.receive_signed(rng, beacon), Err(PVSSError::InvalidEnvelope(_))));
+        assert!(matches!(aggregator.receive_signed(rng, envelope).unwrap(), ReceiveOutcome::Accepted { contributions: 1 }));
+    }
+}
@@ -4,24 +4,36 @@ use crate::{
         config::Config,
         dealer::Dealer,
         errors::PVSSError,
-        participant::Participant,
+        participant::{Participant, ParticipantId},
         pvss::{PVSSCore, PVSSShareSecrets},
 	    share::{PVSSAggregatedShare, PVSSShare, SignedProof},
 	decomp::Decomp,
-	poly::{Polynomial as Poly}
     },
-    Scalar,
     Signature,
     signature::scheme::BatchVerifiableSignatureScheme,
 };
 
-use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
-use ark_ff::PrimeField;
-use ark_poly::{Polynomial, UVPolynomial};
+use ark_ec::PairingEngine;
 
-use rand::Rng;
+use rand::{CryptoRng, Rng};
 use std::collections::BTreeMap;
 
+pub mod aggregator;
+pub mod codec;
+pub mod complaint;
+pub mod dealer;
+pub mod inventory;
+pub mod message;
+pub mod reconstruction;
+
+pub use aggregator::{Aggregator, ReceiveOutcome};
+pub use codec::{encode_frame, FrameDecoder};
+pub use complaint::{Complaint, Equivocation};
+pub use dealer::DealerNode;
+pub use inventory::InventoryTracker;
+pub use message::{Message, SignedMessage};
+pub use reconstruction::{ReconstructionOutcome, ReconstructionTracker};
+
 
 /* Struct Node models the individual nodes participating in the PVSS sharing
 *  protocol. Nodes can act as both dealers, as well as aggregators of share
@@ -50,7 +62,7 @@ where
         config: Config<E>,
         scheme_sig: SSIG,
         dealer: Dealer<E, SSIG>,
-        participants: BTreeMap<usize, Participant<E, SSIG>>,
+        participants: BTreeMap<ParticipantId, Participant<E, SSIG>>,
     ) -> Result<Self, PVSSError<E>> {
         let degree = config.degree;
         let num_participants = participants.len();
@@ -69,89 +81,48 @@ where
 
 
     // Utility method for generating a core of a PVSS share.
-    pub fn share_pvss<R: Rng>(
+    pub fn share_pvss<R: Rng + CryptoRng>(
         &mut self,
         rng: &mut R,
     ) -> Result<(PVSSCore<E>, PVSSShareSecrets<E>), PVSSError<E>> {
-	// Retrieve scheme parameters
-        let t = self.aggregator.config.degree;
-	let n = self.aggregator.config.num_participants;
-
-	// Sample a random degree t polynomial
-	let poly = Poly::<E>::rand(t, rng);
-
-	// Evaluate poly(j) for all j in {1, ..., n}
-	let evals = (1..n+1)
-	        .map(|j| poly.evaluate(&Scalar::<E>::from(j as u64)))
-	        .collect::<Vec<_>>();
-
-	// Compute commitments for all nodes in {0, ..., n-1}
-        // Recall that G2 is the commitment group.
-	let comms = (0..n)
-	        .map(|j| self.aggregator.config.srs.g2.mul(evals[j].into_repr()))
-	        .collect::<Vec<_>>();
-
-	// Compute encryptions for all nodes in {0, ..., n-1}
-	let encs = (0..n)
-	        .map::<Result<E::G1Projective, PVSSError<E>>, _>(|j| {
+	// Retrieve the public (encryption) keys of all nodes in {0, ..., n-1}
+	let public_keys = (0..self.aggregator.config.num_participants)
+	        .map(|j| {
                     Ok(self
                         .aggregator
                         .participants
-                        .get(&j)
+                        .get(&ParticipantId::from_index(j))
                         .ok_or(PVSSError::<E>::InvalidParticipantId(j))?
-                        .public_key_sig   // obtain participant's public (encryption) key
-                        .mul(evals[j].into_repr())
-                        )   // .into_affine()
+                        .public_key_sig)   // obtain participant's public (encryption) key
                     })
-                .collect::<Result<_, _>>()?;
+                .collect::<Result<Vec<_>, PVSSError<E>>>()?;
 
-	// Compose PVSS core
-	let pvss_core = PVSSCore {
-            comms,
-	    encs,
-        };
-
-	// Generate my_secret
-        let my_secret = self
-            .aggregator
-            .config
-            .srs
-            .g1
-            .mul(evals[self.dealer.participant.id].into_repr())
-            .into_affine();
-
-	// Create PVSSShareSecrets
-        let pvss_share_secrets = PVSSShareSecrets {
-            p_0: poly.coeffs[0],
-            my_secret,
-        };
-
-	// Return the result
-	Ok((pvss_core, pvss_share_secrets))
+	// Sample a polynomial, then commit to and encrypt its evaluations
+	PVSSCore::deal(rng, &self.aggregator.config, &public_keys, self.dealer.participant.id.index())
     }
 
 
     // Method for creating a PVSSShare instance for secret sharing.
-    pub fn share<R: Rng>(&mut self, rng: &mut R) -> Result<PVSSShare<E>, PVSSError<E>> {
+    pub fn share<R: Rng + CryptoRng>(&mut self, rng: &mut R) -> Result<PVSSShare<E>, PVSSError<E>> {
         // Create the core PVSSCore first.
 	let (pvss_core, pvss_share_secrets) = self.share_pvss(rng)?;
 
 	// Generate decomposition proof.
-	let mut decomp_proof = Decomp::<E>::generate(rng, &self.aggregator.config, &pvss_share_secrets.p_0).unwrap();
+	let decomp_proof = Decomp::<E>::generate(rng, &self.aggregator.config, &pvss_share_secrets.p_0).unwrap();
 
         let digest = decomp_proof.digest();
 
         // Sign the decomposition proof using EdDSA
 	let signature_on_decomp = Signature::new(&digest, &self.dealer.private_key_ed);
 
-    let signed_proof = SignedProof::<E> {
-        decomp_proof,
-        signature_on_decomp,
-    };
+    let signed_proof = SignedProof::<E>::new(decomp_proof, signature_on_decomp);
 
 	// Create the PVSS share.
 	let share = PVSSShare {
             participant_id: self.dealer.participant.id,
+            degree: self.aggregator.config.degree,
+            num_participants: self.aggregator.config.num_participants,
+            config_digest: self.aggregator.config.digest(),
             pvss_core,
 	        signed_proof,
     };
@@ -175,7 +146,7 @@ mod test {
             aggregator::PVSSAggregator,
             config::Config,
             dealer::Dealer,
-            participant::Participant,
+            participant::{Participant, ParticipantId},
 	    share::PVSSAggregatedShare,
 	    srs::SRS,
 	    node::Node,
@@ -213,18 +184,14 @@ mod test {
     	    private_key_ed: eddsa_keypair.1,
             participant: Participant {
                 pairing_type: PhantomData,
-                id: 0,
+                id: ParticipantId(0),
                 public_key_sig: dealer_keypair_sig.1,
 		public_key_ed: eddsa_keypair.0,
             },
         };
 
 	// set global configuration parameters
-	let config = Config {
-            srs: srs.clone(),
-            degree: 1,
-	    num_participants: 1,
-        };
+	let config = Config::new(srs.clone(), 1, 1);
 
         let participants = vec![dealer.participant.clone()];
 	let num_participants = participants.len();
@@ -235,7 +202,7 @@ mod test {
 			   SchnorrSignature<<Bls12_381 as PairingEngine>::G1Affine>> = PVSSAggregator {
                 config: config.clone(),
                 scheme_sig: schnorr_sig.clone(),
-                participants: participants.clone().into_iter().enumerate().collect(),
+                participants: participants.clone().into_iter().map(|p| (p.id, p)).collect(),
                 aggregated_tx: PVSSAggregatedShare::empty(degree, num_participants),
         };
         
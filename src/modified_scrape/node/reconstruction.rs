@@ -0,0 +1,259 @@
+use crate::modified_scrape::{
+    config::Config,
+    decryption::DecryptedShare,
+    errors::PVSSError,
+    node::message::{Message, SignedMessage},
+    participant::{ParticipantId, ParticipantRegistry},
+    pvss::AffinePVSSCore,
+    recon::{beacon, reconstruct},
+    snapshot::{open_snapshot, SNAPSHOT_VERSION},
+};
+
+use ark_ec::PairingEngine;
+use ark_serialize::CanonicalSerialize;
+use ark_std::{collections::BTreeMap, convert::TryFrom};
+
+
+/* Enumeration ReconstructionOutcome reports what became of a decrypted share handed to
+   ReconstructionTracker::receive. */
+pub enum ReconstructionOutcome<E: PairingEngine> {
+    Accepted { shares: usize },                                   // the share was kept; more are needed
+    Duplicate,                                                    // a share from that id was already kept
+    Reconstructed { secret: E::G1Affine, beacon: [u8; 32] },      // the share completed reconstruction
+    AlreadyReconstructed,                                         // reconstruction happened earlier; the share was ignored
+}
+
+
+/* Struct ReconstructionTracker collects the decrypted shares of a finalized aggregated core,
+   verifies each of them, and reconstructs the secret (and the beacon output) once degree + 1
+   valid shares are present. */
+pub struct ReconstructionTracker<E: PairingEngine> {
+    pub config: Config<E>,                             // the "global" configuration parameters
+    pub registry: ParticipantRegistry<E>,              // the committee's public keys
+    pub core: AffinePVSSCore<E>,                       // the finalized aggregated PVSS core
+    shares: BTreeMap<usize, DecryptedShare<E>>,        // valid decrypted shares, by participant id
+    output: Option<(E::G1Affine, [u8; 32])>,           // the reconstructed secret and beacon output
+}
+
+impl<E: PairingEngine> ReconstructionTracker<E> {
+
+    // Function for creating a tracker for the given finalized core.
+    pub fn new(config: Config<E>, registry: ParticipantRegistry<E>, core: AffinePVSSCore<E>) -> Self {
+        Self { config, registry, core, shares: BTreeMap::new(), output: None }
+    }
+
+
+    // Method for handling a received decrypted share.
+    // Shares from ids outside the committee and shares whose proof does not verify are rejected
+    // with an error; a second share from the same id is reported as a duplicate.
+    pub fn receive(&mut self, share: DecryptedShare<E>) -> Result<ReconstructionOutcome<E>, PVSSError<E>> {
+        if self.output.is_some() {
+            return Ok(ReconstructionOutcome::AlreadyReconstructed);
+        }
+
+        let id = share.participant_id.index();
+        if self.shares.contains_key(&id) {
+            return Ok(ReconstructionOutcome::Duplicate);
+        }
+
+        let participant = self.registry.get(share.participant_id)?;
+        let enc = *self.core.encs.get(id).ok_or(PVSSError::InvalidParticipantId(id))?;
+        share.verify(&self.config, &participant.public_key_sig, &enc)?;
+
+        self.shares.insert(id, share);
+        if self.shares.len() < self.config.degree + 1 {
+            return Ok(ReconstructionOutcome::Accepted { shares: self.shares.len() });
+        }
+
+        let points = self.shares.iter().map(|(id, share)| (*id, share.point)).collect();
+        let secret = reconstruct(&points, &self.config)?;
+        let value = beacon(&self.config, &secret)?;
+        self.output = Some((secret, value));
+
+        Ok(ReconstructionOutcome::Reconstructed { secret, beacon: value })
+    }
+
+
+    // Method for handling a decrypted share received from the network: the envelope must verify
+    // against the key of its sender and carry a Decryption message, whose share is then handled
+    // as by receive.
+    pub fn receive_signed(&mut self, envelope: SignedMessage<E>) -> Result<ReconstructionOutcome<E>, PVSSError<E>> {
+        envelope.verify(&self.registry)?;
+
+        match envelope.msg {
+            Message::Decryption { id, point, proof, .. } => {
+                let participant_id = ParticipantId::try_from(id).map_err(|_| PVSSError::InvalidParticipantId(id))?;
+                self.receive(DecryptedShare { participant_id, point, proof })
+            }
+            _ => Err(PVSSError::InvalidEnvelope("expected a Decryption message".to_string())),
+        }
+    }
+
+
+    // Method returning the reconstructed secret and beacon output, if any.
+    pub fn output(&self) -> Option<&(E::G1Affine, [u8; 32])> {
+        self.output.as_ref()
+    }
+
+
+    // Method for persisting the tracker's state: a version byte, followed by the committee, the
+    // core and the decrypted shares (along with their proofs) kept so far.
+    pub fn snapshot(&self) -> Vec<u8> {
+        let mut bytes = vec![SNAPSHOT_VERSION];
+
+        self.registry.serialize(&mut bytes).unwrap();
+        self.core.serialize(&mut bytes).unwrap();
+        self.shares.values().cloned().collect::<Vec<_>>().serialize(&mut bytes).unwrap();
+
+        bytes
+    }
+
+    // Function for restoring a tracker from a snapshot taken under the given configuration.
+    // Every share is received anew, hence its proof is re-verified and the output recomputed.
+    pub fn restore(config: Config<E>, bytes: &[u8]) -> Result<Self, PVSSError<E>> {
+        let mut reader = open_snapshot(bytes)?;
+        let registry = reader.read_field("registry")?;
+        let core = reader.read_field("core")?;
+        let shares: Vec<DecryptedShare<E>> = reader.read_vec("shares")?;
+
+        let mut tracker = Self::new(config, registry, core);
+        for share in shares {
+            match tracker.receive(share)? {
+                ReconstructionOutcome::Duplicate | ReconstructionOutcome::AlreadyReconstructed => {
+                    return Err(PVSSError::SnapshotIntegrityError);
+                }
+                _ => (),
+            }
+        }
+
+        Ok(tracker)
+    }
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{ReconstructionOutcome, ReconstructionTracker};
+    use crate::{
+        modified_scrape::{
+            decryption::DecryptedShare,
+            errors::PVSSError,
+            node::message::Message,
+            participant::ParticipantId,
+            recon::beacon,
+            share::PVSSAggregatedShare,
+        },
+        testing::{fixtures::FixtureBuilder, simulation::Simulation},
+        Scalar,
+    };
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ec::{AffineCurve, ProjectiveCurve};
+    use ark_ff::PrimeField;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_reconstruction_tracker() {
+        let rng = &mut thread_rng();
+        let t = 2;
+        let n = 5;
+
+        // Everyone deals; the shares are aggregated.
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let (conf, registry, aggregated) = (fixture.config, fixture.registry, fixture.transcript);
+        let secret = fixture.secrets.iter().map(|secrets| secrets.p_0).sum::<Scalar<E>>();
+
+        // Everyone decrypts its share.
+        let decrypted = fixture
+            .keypairs
+            .iter()
+            .enumerate()
+            .map(|(id, keys)| DecryptedShare::generate(rng, &conf, &aggregated.pvss_core.encs[id], &keys.secret_key, ParticipantId::from_index(id)).unwrap())
+            .collect::<Vec<_>>();
+
+        // Decrypted shares survive serialization.
+        let mut bytes = vec![];
+        decrypted[0].serialize(&mut bytes).unwrap();
+        assert_eq!(DecryptedShare::<E>::deserialize(&bytes[..]).unwrap(), decrypted[0]);
+
+        let mut tracker = ReconstructionTracker::new(conf.clone(), registry, aggregated.pvss_core.clone());
+
+        // An invalid share: participant 1 claims participant 3's decryption.
+        let mut invalid = decrypted[3].clone();
+        invalid.participant_id = ParticipantId(1);
+        match tracker.receive(invalid) {
+            Err(PVSSError::DecryptionProofVerificationFailed { participant_id }) => assert_eq!(participant_id, 1),
+            _ => panic!("expected DecryptionProofVerificationFailed"),
+        }
+
+        // A duplicate submission is ignored.
+        assert!(matches!(tracker.receive(decrypted[4].clone()).unwrap(), ReconstructionOutcome::Accepted { shares: 1 }));
+        assert!(matches!(tracker.receive(decrypted[4].clone()).unwrap(), ReconstructionOutcome::Duplicate));
+        assert!(matches!(tracker.receive(decrypted[1].clone()).unwrap(), ReconstructionOutcome::Accepted { shares: 2 }));
+
+        // The (t + 1)-th valid share completes reconstruction.
+        let expected = conf.srs.g1.mul(secret.into_repr()).into_affine();
+        match tracker.receive(decrypted[2].clone()).unwrap() {
+            ReconstructionOutcome::Reconstructed { secret, beacon: value } => {
+                assert_eq!(secret, expected);
+                assert_eq!(value, beacon(&conf, &expected).unwrap());
+            }
+            _ => panic!("expected Reconstructed"),
+        }
+        assert!(matches!(tracker.receive(decrypted[0].clone()).unwrap(), ReconstructionOutcome::AlreadyReconstructed));
+        assert_eq!(tracker.output().unwrap().0, expected);
+
+        // Snapshots of a partial and of a completed tracker restore to the same state.
+        let mut partial = ReconstructionTracker::new(conf.clone(), tracker.registry.clone(), tracker.core.clone());
+        partial.receive(decrypted[0].clone()).unwrap();
+        let mut restored = ReconstructionTracker::restore(conf.clone(), &partial.snapshot()).unwrap();
+        assert!(restored.output().is_none());
+        assert!(matches!(restored.receive(decrypted[0].clone()).unwrap(), ReconstructionOutcome::Duplicate));
+        restored.receive(decrypted[1].clone()).unwrap();
+        assert!(matches!(restored.receive(decrypted[2].clone()).unwrap(), ReconstructionOutcome::Reconstructed { .. }));
+
+        let restored = ReconstructionTracker::restore(conf, &tracker.snapshot()).unwrap();
+        assert_eq!(restored.output(), tracker.output());
+    }
+
+    #[test]
+    fn test_receive_signed() {
+        let rng = &mut thread_rng();
+        let (t, n) = (1, 3);
+
+        let mut simulation = Simulation::<E>::new(rng, t, n).unwrap();
+        let mut aggregated = PVSSAggregatedShare::empty(t, n);
+        for node in simulation.nodes.iter_mut() {
+            let (share, _) = node.dealer.deal(0, rng).unwrap();
+            aggregated.fold_share(&share).unwrap();
+        }
+
+        // Every node envelopes its decrypted share.
+        let envelopes = simulation
+            .nodes
+            .iter()
+            .map(|node| {
+                let enc = &aggregated.pvss_core.encs[node.id];
+                let share = DecryptedShare::generate(rng, &simulation.config, enc, &node.secret_key, ParticipantId::from_index(node.id)).unwrap();
+                node.dealer.sign(Message::Decryption { epoch: 0, id: share.participant_id.index(), point: share.point, proof: share.proof })
+            })
+            .collect::<Vec<_>>();
+
+        let mut tracker = ReconstructionTracker::new(simulation.config.clone(), simulation.registry.clone(), aggregated.pvss_core.clone());
+
+        // Envelopes replayed under another sender id, or of another message, are rejected.
+        let mut replayed = envelopes[0].clone();
+        replayed.sender_id = 1;
+        assert!(matches!(tracker.receive_signed(replayed), Err(PVSSError::SignatureVerificationFailed { participant_id: 1 })));
+        let other = simulation.nodes[0].dealer.sign(Message::Want { epoch: 0, digests: vec![] });
+        assert!(matches!(tracker.receive_signed(other), Err(PVSSError::InvalidEnvelope(_))));
+
+        assert!(matches!(tracker.receive_signed(envelopes[0].clone()).unwrap(), ReconstructionOutcome::Accepted { shares: 1 }));
+        assert!(matches!(tracker.receive_signed(envelopes[2].clone()).unwrap(), ReconstructionOutcome::Reconstructed { .. }));
+    }
+}
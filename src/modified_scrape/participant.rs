@@ -1,12 +1,80 @@
 use crate::{
-    PublicKey,
-    signature::scheme::BatchVerifiableSignatureScheme,
+    modified_scrape::errors::PVSSError,
+    PublicKey, Scalar,
+    signature::{scheme::BatchVerifiableSignatureScheme, schnorr::SchnorrSignature},
 };
 
 use ark_ec::PairingEngine;
 use ark_serialize::{CanonicalSerialize, CanonicalDeserialize, SerializationError, Read, Write};
+use ark_std::collections::BTreeMap;
+use ark_std::{convert::TryFrom, fmt, num::TryFromIntError, vec::Vec};
 
-use std::marker::PhantomData;
+use ark_std::marker::PhantomData;
+
+
+/* Struct ParticipantId identifies a participant of the committee. Ids are 0-based: they index
+   the vectors of encryptions, commitments and public keys, whereas the share of participant
+   id is the evaluation of the dealt polynomial at id + 1 (see evaluation_point). It serializes
+   exactly like the usize it replaces, i.e., as a u64. */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ParticipantId(pub u32);
+
+impl ParticipantId {
+    // Constructor from a vector index. Panics if index does not fit in 32 bits.
+    pub fn from_index(index: usize) -> Self {
+        Self::try_from(index).expect("participant index exceeds u32::MAX")
+    }
+
+    // Method returning the position of the participant in the vectors of encryptions,
+    // commitments and public keys.
+    pub fn index(&self) -> usize {
+        self.0 as usize
+    }
+
+    // Method returning the evaluation point id + 1 of the participant, at which the dealt
+    // polynomials are evaluated for it (see Config::point_for).
+    pub fn evaluation_point<E: PairingEngine>(&self) -> Scalar<E> {
+        Scalar::<E>::from(u64::from(self.0) + 1)
+    }
+}
+
+impl From<u32> for ParticipantId {
+    fn from(id: u32) -> Self {
+        Self(id)
+    }
+}
+
+impl TryFrom<usize> for ParticipantId {
+    type Error = TryFromIntError;
+
+    fn try_from(index: usize) -> Result<Self, Self::Error> {
+        u32::try_from(index).map(Self)
+    }
+}
+
+impl fmt::Display for ParticipantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl CanonicalSerialize for ParticipantId {
+    fn serialize<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+        u64::from(self.0).serialize(writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        u64::from(self.0).serialized_size()
+    }
+}
+
+impl CanonicalDeserialize for ParticipantId {
+    fn deserialize<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        let id = u64::deserialize(reader)?;
+
+        u32::try_from(id).map(Self).map_err(|_| SerializationError::InvalidData)
+    }
+}
 
 
 // Struct ParticipantState models the states that each participant in the PVSS
@@ -26,8 +94,86 @@ pub struct Participant<
     SSIG: BatchVerifiableSignatureScheme<PublicKey = E::G1Affine, Secret = E::Fr>,   // G1 is the encryption group
 > {
     pub pairing_type: PhantomData<E>,
-    pub id: usize,                         // participant id
+    pub id: ParticipantId,                 // participant id
     pub public_key_sig: SSIG::PublicKey,   // public (encryption) key (in G1)
     pub public_key_ed: PublicKey,          // EdDSA public (verification) key
     // pub state: ParticipantState,        // participant current state
 }
+
+
+// Participants whose encryption keys are Schnorr keys over G1.
+pub type SchnorrParticipant<E> = Participant<E, SchnorrSignature<<E as PairingEngine>::G1Affine>>;
+
+/* Struct ParticipantRegistry models the committee: it maps participant ids to the
+   participants' public (encryption and EdDSA) keys. */
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ParticipantRegistry<E: PairingEngine> {
+    pub participants: BTreeMap<ParticipantId, SchnorrParticipant<E>>,   // maps ids to Participant instances
+}
+
+impl<E: PairingEngine> ParticipantRegistry<E> {
+
+    // Function for creating a registry out of a list of participants, keyed by their ids.
+    pub fn new(participants: Vec<SchnorrParticipant<E>>) -> Self {
+        Self {
+            participants: participants.into_iter().map(|p| (p.id, p)).collect(),
+        }
+    }
+
+    // Method for retrieving a participant, failing for ids outside the committee.
+    pub fn get(&self, id: ParticipantId) -> Result<&SchnorrParticipant<E>, PVSSError<E>> {
+        self.participants.get(&id).ok_or(PVSSError::InvalidParticipantId(id.index()))
+    }
+
+    // Same as get, for callers holding the participant's index (see ParticipantId::index).
+    pub fn get_by_index(&self, index: usize) -> Result<&SchnorrParticipant<E>, PVSSError<E>> {
+        let id = ParticipantId::try_from(index).map_err(|_| PVSSError::InvalidParticipantId(index))?;
+
+        self.get(id)
+    }
+
+    // Method returning whether id belongs to the committee.
+    pub fn contains(&self, id: ParticipantId) -> bool {
+        self.participants.contains_key(&id)
+    }
+
+    // Method returning the number of registered participants.
+    pub fn len(&self) -> usize {
+        self.participants.len()
+    }
+
+    // Method returning whether the registry is empty.
+    pub fn is_empty(&self) -> bool {
+        self.participants.is_empty()
+    }
+
+    // Method returning the participants' encryption keys, indexed by id (the key of participant
+    // i is at index i). Fails on a gap, i.e., unless the ids run from 0 to len - 1.
+    pub fn public_keys_sig(&self) -> Result<Vec<E::G1Affine>, PVSSError<E>> {
+        (0..self.len()).map(|index| self.get_by_index(index).map(|p| p.public_key_sig)).collect()
+    }
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{ParticipantId, ParticipantRegistry};
+    use crate::{modified_scrape::errors::PVSSError, testing::fixtures::FixtureBuilder};
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+
+    #[test]
+    fn test_public_keys_sig() {
+        let fixture = FixtureBuilder::new(1, 4).build::<E>().unwrap();
+        assert_eq!(fixture.registry.public_keys_sig().unwrap(), fixture.public_keys());
+
+        // Without participant 2, the ids have a gap, rather than the keys shifting down.
+        let mut participants = fixture.registry.participants.clone();
+        participants.remove(&ParticipantId(2));
+        let registry = ParticipantRegistry::<E>::new(participants.into_iter().map(|(_, p)| p).collect());
+        assert!(matches!(registry.public_keys_sig(), Err(PVSSError::InvalidParticipantId(2))));
+    }
+}
@@ -3,12 +3,13 @@ use crate::{
     Scalar,
 };
 
-use ark_ec::{PairingEngine, ProjectiveCurve};
+use ark_ec::{msm::VariableBaseMSM, PairingEngine, ProjectiveCurve};
 use ark_ff::{Field, Zero, One, PrimeField};
 use ark_poly::{UVPolynomial, Polynomial as Poly, polynomial::univariate::DensePolynomial};
 //use ark_std::ops::AddAssign;
+use ark_std::vec::Vec;
 
-use rand::Rng;
+use rand::{CryptoRng, Rng};
 
 
 // A polynomial with the various coefficients in the Scalar Group
@@ -23,12 +24,12 @@ pub fn ensure_degree<E, R>(rng: &mut R,
 where
 	E: PairingEngine,
 	//E::G2Projective: AddAssign,
-	R: Rng
+	R: Rng + CryptoRng
 {
     let num = evaluations.len() as u64;
 
     if num < degree {
-        return Err(PVSSError::InsufficientEvaluationsError);
+        return Err(PVSSError::InsufficientShares { needed: degree as usize, got: num as usize });
     }
 
     // Sample a random polynomial of appropriate degree
@@ -66,11 +67,11 @@ where
 	//E::G2Projective: AddAssign,
 {
     if evals.len() < (degree + 1) as usize {
-        return Err(PVSSError::InsufficientEvaluationsError);
+        return Err(PVSSError::InsufficientShares { needed: (degree + 1) as usize, got: evals.len() });
     }
 
-    let mut sum = E::G2Projective::zero();
-    
+    let mut coeffs = Vec::with_capacity((degree + 1) as usize);
+
     for j in 0..=degree {
         let x_j = Scalar::<E>::from(j + 1);
         let mut prod = Scalar::<E>::one();
@@ -80,12 +81,11 @@ where
                 prod *= x_k * (x_k - x_j).inverse().unwrap();
             }
         }
-
-        // Recovery formula
-        sum += evals[j as usize].mul(prod.into_repr());
+        coeffs.push(prod);
     }
 
-    Ok(sum)
+    // Recovery formula
+    Ok(msm(&evals[..=degree as usize], &coeffs))
 }
 
 
@@ -99,14 +99,14 @@ where
 	Scalar<E>: From<u64>
 {
     if evals.len() < (degree + 1) as usize {
-        return Err(PVSSError::InsufficientEvaluationsError);
+        return Err(PVSSError::InsufficientShares { needed: (degree + 1) as usize, got: evals.len() });
     }
 
     if evals.len() != points.len() {
 	    return Err(PVSSError::DifferentPointsEvalsError);
     }
 
-    let mut sum = E::G2Projective::zero();
+    let mut coeffs = Vec::with_capacity((degree + 1) as usize);
 
     for j in 0..=degree {
         let x_j = points[j as usize];
@@ -117,12 +117,47 @@ where
                 prod *= x_k * (x_k - x_j).inverse().unwrap();
             }
         }
+        coeffs.push(prod);
+    }
+
+    // Recovery formula
+    Ok(msm(&evals[..=degree as usize], &coeffs))
+}
+
+
+// Utility function computing sum_i scalars[i] * bases[i] with a single variable-base
+// multi-scalar multiplication, normalizing the bases to affine form (all at once) first.
+pub fn msm<G: ProjectiveCurve>(bases: &[G], scalars: &[G::ScalarField]) -> G {
+    debug_assert_eq!(bases.len(), scalars.len());
+
+    let bases = G::batch_normalization_into_affine(bases);
+    let scalars = scalars.iter().map(|s| s.into_repr()).collect::<Vec<_>>();
+
+    VariableBaseMSM::multi_scalar_mul(&bases, &scalars)
+}
+
+
+// Utility function for computing the Lagrange coefficients at zero for a given list of
+// (distinct) points, i.e., lambda_j = prod_{k != j} x_k / (x_k - x_j).
+pub fn lagrange_coefficients<E>(points: &Vec<Scalar<E>>) -> Result<Vec<Scalar<E>>, PVSSError<E>>
+where
+	E: PairingEngine,
+{
+    let mut coeffs = Vec::with_capacity(points.len());
 
-        // Recovery formula
-        sum += evals[j as usize].mul(prod.into_repr());
+    for (j, x_j) in points.iter().enumerate() {
+        let mut prod = Scalar::<E>::one();
+        for (k, x_k) in points.iter().enumerate() {
+            if j != k {
+                // Repeated points make the interpolation ill-defined.
+                let denom = (*x_k - x_j).inverse().ok_or(PVSSError::NonDistinctPointsError)?;
+                prod *= *x_k * denom;
+            }
+        }
+        coeffs.push(prod);
     }
 
-    Ok(sum)
+    Ok(coeffs)
 }
 
 
@@ -133,15 +168,15 @@ where
 mod test {
     use crate::{
         modified_scrape::{
-            poly::{Polynomial, ensure_degree, lagrange_interpolation_simple, lagrange_interpolation},
+            poly::{Polynomial, ensure_degree, lagrange_interpolation_simple, lagrange_interpolation, msm},
             srs::SRS,
         },
         Scalar,
     };
 
     use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
-    use ark_ec::{PairingEngine, AffineCurve};
-    use ark_ff::PrimeField;
+    use ark_ec::{PairingEngine, AffineCurve, ProjectiveCurve};
+    use ark_ff::{PrimeField, Zero};
     use ark_poly::{UVPolynomial, Polynomial as Poly};
     use ark_std::UniformRand;
 
@@ -284,4 +319,29 @@ mod test {
 	assert_eq!(reconstructed_secret, shared_secret);
     }
 
+
+
+    #[test]
+    fn test_msm_matches_naive() {
+	let rng = &mut thread_rng();
+	let size = 64;
+
+	let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+
+	let scalars = (0..size).map(|_| Scalar::<E>::rand(rng)).collect::<Vec<_>>();
+	let g1s = (0..size).map(|_| srs.g1.mul(Scalar::<E>::rand(rng).into_repr())).collect::<Vec<_>>();
+	let g2s = (0..size).map(|_| srs.g2.mul(Scalar::<E>::rand(rng).into_repr())).collect::<Vec<_>>();
+
+	let mut naive_g1 = <E as PairingEngine>::G1Projective::zero();
+	let mut naive_g2 = <E as PairingEngine>::G2Projective::zero();
+	for ((g1, g2), s) in g1s.iter().zip(g2s.iter()).zip(scalars.iter()) {
+	    naive_g1 += g1.mul(s.into_repr());
+	    naive_g2 += g2.mul(s.into_repr());
+	}
+
+	assert_eq!(msm(&g1s, &scalars), naive_g1);
+	assert_eq!(msm(&g2s, &scalars), naive_g2);
+	assert!(msm::<<E as PairingEngine>::G1Projective>(&[], &[]).is_zero());
+    }
+
 }
@@ -1,42 +1,420 @@
-use crate::{modified_scrape::errors::PVSSError, Scalar};
+use crate::{
+    modified_scrape::{
+        checked::CountingReader,
+        config::Config,
+        curve_utils::{in_prime_subgroup, is_on_curve, mul_wnaf, wnaf_window},
+        errors::PVSSError,
+        participant::ParticipantId,
+        poly::{msm, Polynomial},
+        recon::LagrangeCache,
+    },
+    fingerprint,
+    Scalar,
+};
 
-use ark_ec::PairingEngine;
-use ark_ff::Zero;
+use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+use ark_ff::{One, PrimeField, Zero};
+use ark_poly::{Polynomial as Poly, UVPolynomial};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
 
+use rand::{CryptoRng, Rng};
 
-/* Struct PVSSShare models the "core" of a PVSS sharing generated by the a participant when acting as dealer */
+use ark_std::{fmt, ops::Neg, vec::Vec};
 
-#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Debug, PartialEq)]
+
+// Default minimum number of participants handled by each task when dealing in parallel,
+// so that small committees do not pay the thread overhead.
+pub const DEFAULT_MIN_CHUNK: usize = 16;
+
+
+// Utility function mapping f over items; under the parallel feature the work is split into
+// tasks of at least min_chunk items. The output order always matches the input order.
+fn map_chunked<T, U, F>(items: &[T], min_chunk: usize, f: F) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> U + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+
+        items.par_iter().with_min_len(min_chunk.max(1)).map(f).collect()
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    {
+        let _ = min_chunk;
+        items.iter().map(f).collect()
+    }
+}
+
+
+// Function computing the encryption pk_i^{p(i)} of an evaluation eval = p(i) under the public key
+// pk_i, exactly as done when dealing; e.g., for recomputing an expected encryption from a
+// disclosed evaluation.
+pub fn encrypt_eval<E: PairingEngine>(pk_i: &E::G1Affine, eval: &Scalar<E>) -> E::G1Projective {
+    mul_wnaf(&pk_i.into_projective(), eval, wnaf_window(Scalar::<E>::size_in_bits()))
+}
+
+
+/* Struct PVSSShare models the "core" of a PVSS sharing generated by the a participant when acting as dealer.
+   Cores are built with PVSSCore::new, which checks that both vectors are non-empty and of equal
+   length; the vectors can be read through the encs and comms accessors.
+   Cores created by empty() are flagged as such, which lets aggregation skip the additions with
+   zero. As the vectors can still be modified within the crate, the flag is only a hint, and is
+   confirmed against the points before use. It is not serialized, and does not take part in
+   comparisons. */
+
+#[derive(Clone)]
 pub struct PVSSCore<E>
 where
     E: PairingEngine,
 {
-    pub encs: Vec<E::G1Projective>,    // vector of encryptions c
-    pub comms: Vec<E::G2Projective>,   // vector of commitments v
+    pub(crate) encs: Vec<E::G1Projective>,    // vector of encryptions c
+    pub(crate) comms: Vec<E::G2Projective>,   // vector of commitments v
+    is_empty: bool,                           // whether the core was created by empty()
+}
+
+impl<E: PairingEngine> PartialEq for PVSSCore<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.encs == other.encs && self.comms == other.comms
+    }
+}
+
+// Debug prints the lengths of the vectors along with fingerprints of their contents, rather
+// than the points' coordinates.
+impl<E: PairingEngine> fmt::Debug for PVSSCore<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PVSSCore")
+            .field("encs", &format_args!("{} x G1 #{}", self.encs.len(), fingerprint(&self.encs)))
+            .field("comms", &format_args!("{} x G2 #{}", self.comms.len(), fingerprint(&self.comms)))
+            .finish()
+    }
+}
+
+impl<E: PairingEngine> CanonicalSerialize for PVSSCore<E> {
+    fn serialize<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        self.encs.serialize(&mut writer)?;
+        self.comms.serialize(&mut writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.encs.serialized_size() + self.comms.serialized_size()
+    }
+
+    fn serialize_uncompressed<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        self.encs.serialize_uncompressed(&mut writer)?;
+        self.comms.serialize_uncompressed(&mut writer)
+    }
+
+    fn uncompressed_size(&self) -> usize {
+        self.encs.uncompressed_size() + self.comms.uncompressed_size()
+    }
+}
+
+impl<E: PairingEngine> CanonicalDeserialize for PVSSCore<E> {
+    fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let encs = Vec::deserialize(&mut reader)?;
+        let comms = Vec::deserialize(&mut reader)?;
+
+        Ok(Self::from_parts(encs, comms))
+    }
+
+    fn deserialize_uncompressed<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let encs = Vec::deserialize_uncompressed(&mut reader)?;
+        let comms = Vec::deserialize_uncompressed(&mut reader)?;
+
+        Ok(Self::from_parts(encs, comms))
+    }
+
+    fn deserialize_unchecked<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let encs = Vec::deserialize_unchecked(&mut reader)?;
+        let comms = Vec::deserialize_unchecked(&mut reader)?;
+
+        Ok(Self::from_parts(encs, comms))
+    }
 }
 
 impl<E> PVSSCore<E>
 where
     E: PairingEngine,
 {
+    // Constructor of a PVSS core from its encryptions and commitments, which must be non-empty
+    // vectors of equal length. Empty encryptions are reported first, then empty commitments.
+    pub fn new(encs: Vec<E::G1Projective>, comms: Vec<E::G2Projective>) -> Result<Self, PVSSError<E>> {
+        if encs.is_empty() {
+            return Err(PVSSError::EmptyEncryptionsVectorError);
+        }
+        if comms.is_empty() {
+            return Err(PVSSError::EmptyCommitmentsVectorError);
+        }
+        if encs.len() != comms.len() {
+            return Err(PVSSError::MismatchedCommitmentsEncryptionsError(comms.len(), encs.len()));
+        }
+
+        Ok(Self::from_parts(encs, comms))
+    }
+
+    // Unchecked constructor, for vectors whose lengths are known to match (e.g., sums of checked
+    // cores), and for deserialization, after which the lengths are checked by the methods using them.
+    pub(crate) fn from_parts(encs: Vec<E::G1Projective>, comms: Vec<E::G2Projective>) -> Self {
+        PVSSCore { encs, comms, is_empty: false }
+    }
+
+    // Accessor for the vector of encryptions.
+    pub fn encs(&self) -> &[E::G1Projective] {
+        &self.encs
+    }
+
+    // Accessor for the vector of commitments.
+    pub fn comms(&self) -> &[E::G2Projective] {
+        &self.comms
+    }
+
+    // Method interpolating the commitment g2^{p(0)} to the shared secret from the commitments of
+    // the given participants. The commitments of a valid core lie on a degree t polynomial, so
+    // any t + 1 distinct participants yield the same value.
+    pub fn interpolate_commitment_at_zero(&self, ids: &[ParticipantId]) -> Result<E::G2Projective, PVSSError<E>> {
+        let indices = ids.iter().map(|id| id.index()).collect::<Vec<_>>();
+        let comms = indices
+            .iter()
+            .map(|&index| self.comms.get(index).copied().ok_or(PVSSError::InvalidParticipantId(index)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let cache = LagrangeCache::<E>::new(&indices, Scalar::<E>::zero())?;
+
+        Ok(msm(&comms, cache.coefficients()))
+    }
+
     // Create a new "empty" PVSS core, where all fields are set to "zero" values.
     pub fn empty(num_participants: usize) -> Self {
         PVSSCore {
-	        encs:  vec![E::G1Projective::zero(); num_participants],
-	        comms: vec![E::G2Projective::zero(); num_participants],
+            encs:  vec![E::G1Projective::zero(); num_participants],
+            comms: vec![E::G2Projective::zero(); num_participants],
+            is_empty: true,
         }
     }
 
+    // Method returning whether the core is known to be all zero, i.e., it was created by empty()
+    // and its points are still zero. Zero points are recognized by their coordinates, so the
+    // confirmation costs no group operations; cores not created by empty() are rejected at once.
+    fn known_empty(&self) -> bool {
+        self.is_empty && self.encs.iter().all(Zero::is_zero) && self.comms.iter().all(Zero::is_zero)
+    }
 
-    // Aggregation of two PVSSCore instances.
-    pub fn aggregate(&self, other: &Self) -> Result<Self, PVSSError<E>> {
-        // Perform some basic checks:
 
-        // Commitment vector must be non-empty
-        if self.comms.len() == 0 {
+    // Function for dealing a fresh PVSS core: samples a random degree t polynomial p, commits to
+    // p(j+1) in G2 and encrypts it under the j-th public key, for all j in {0, ..., n-1}.
+    // Also returns the secrets underlying the core, where my_id is the dealer's id.
+    pub fn deal<R: Rng + CryptoRng>(
+        rng: &mut R,
+        config: &Config<E>,
+        public_keys: &[E::G1Affine],
+        my_id: usize,
+    ) -> Result<(Self, PVSSShareSecrets<E>), PVSSError<E>> {
+        Self::deal_with_min_chunk(rng, config, public_keys, my_id, DEFAULT_MIN_CHUNK)
+    }
+
+
+    // Same as deal, with the minimum number of participants handled by each parallel task
+    // (under the parallel feature) set by the caller.
+    pub fn deal_with_min_chunk<R: Rng + CryptoRng>(
+        rng: &mut R,
+        config: &Config<E>,
+        public_keys: &[E::G1Affine],
+        my_id: usize,
+        min_chunk: usize,
+    ) -> Result<(Self, PVSSShareSecrets<E>), PVSSError<E>> {
+        // Sample a random degree t polynomial. All randomness is drawn here, so that the
+        // (possibly parallel) rest of the dealing is deterministic.
+        let poly = Polynomial::<E>::rand(config.degree, rng);
+
+        Self::deal_polynomial(&poly, config, public_keys, my_id, min_chunk)
+    }
+
+
+    // Deterministic part of dealing: evaluates, commits to and encrypts the given polynomial.
+    pub fn deal_polynomial(
+        poly: &Polynomial<E>,
+        config: &Config<E>,
+        public_keys: &[E::G1Affine],
+        my_id: usize,
+        min_chunk: usize,
+    ) -> Result<(Self, PVSSShareSecrets<E>), PVSSError<E>> {
+        let n = config.num_participants;
+
+        if public_keys.len() != n {
+            return Err(PVSSError::LengthMismatchError);
+        }
+
+        if my_id >= n {
+            return Err(PVSSError::InvalidParticipantId(my_id));
+        }
+
+        // Evaluate poly(j) for all j in {1, ..., n}
+        let points = (0..n).map(|id| config.point_for(id)).collect::<Result<Vec<_>, _>>()?;
+        let evals = map_chunked(&points, min_chunk, |x| poly.evaluate(x));
+
+        // Compute commitments for all nodes in {0, ..., n-1}.
+        // Recall that G2 is the commitment group.
+        let comms = map_chunked(&evals, min_chunk, |e| config.srs.g2.mul(e.into_repr()));
+
+        // Compute encryptions for all nodes in {0, ..., n-1}.
+        // Each public key is a distinct base, so the multiplications use wNAF recoding.
+        let pairs = public_keys.iter().zip(evals.iter()).collect::<Vec<_>>();
+        let encs = map_chunked(&pairs, min_chunk, |(pk, e)| encrypt_eval::<E>(pk, e));
+
+        let secrets = PVSSShareSecrets {
+            p_0: poly.coeffs[0],
+            my_secret: config.srs.g1.mul(evals[my_id].into_repr()).into_affine(),
+        };
+
+        Ok((PVSSCore::new(encs, comms)?, secrets))
+    }
+
+
+    // Deserialization reporting the failing field and byte offset.
+    pub fn deserialize_checked<R: Read>(reader: R) -> Result<Self, PVSSError<E>> {
+        Self::read_checked(&mut CountingReader::new(reader))
+    }
+
+
+    // Reads a PVSSCore off a (possibly shared) counting reader.
+    pub(crate) fn read_checked<R: Read>(reader: &mut CountingReader<R>) -> Result<Self, PVSSError<E>> {
+        let encs = reader.read_vec("encs")?;
+        let comms = reader.read_vec("comms")?;
+
+        Ok(PVSSCore::from_parts(encs, comms))
+    }
+
+
+    // Deserialization of a core in uncompressed form (see serialize_uncompressed), checking that
+    // every encryption and commitment lies on its curve and in the prime order subgroup. Unlike
+    // deserialize_unchecked, which skips these checks, off-curve and off-subgroup points (which
+    // break the soundness of the pairing checks) are rejected; the points being read unchecked
+    // first, the checks are done here once (the subgroup check only being sound on the curve),
+    // and the first offending index (its encryption checked before its commitment) is reported
+    // with NotInSubgroup.
+    pub fn deserialize_subgroup_checked<R: Read>(reader: R) -> Result<Self, PVSSError<E>> {
+        let core = Self::deserialize_unchecked(reader)?;
+
+        fn valid<G: ProjectiveCurve>(point: &G) -> bool {
+            is_on_curve(&point.into_affine()) && in_prime_subgroup(point)
+        }
+
+        let len = core.encs.len().max(core.comms.len());
+        let in_subgroup = |j: usize| core.encs.get(j).map_or(true, valid) && core.comms.get(j).map_or(true, valid);
+
+        match (0..len).find(|&j| !in_subgroup(j)) {
+            Some(j) => Err(PVSSError::NotInSubgroup(j)),
+            None => Ok(core),
+        }
+    }
+
+
+    // Method checking that the core holds expected encryptions and expected commitments, neither
+    // vector being empty. Empty encryptions are reported first, then empty commitments.
+    pub fn validate_lengths(&self, expected: usize) -> Result<(), PVSSError<E>> {
+        if self.encs.is_empty() {
             return Err(PVSSError::EmptyEncryptionsVectorError);
         }
+        if self.comms.is_empty() {
+            return Err(PVSSError::EmptyCommitmentsVectorError);
+        }
+        if self.encs.len() != expected || self.comms.len() != expected {
+            return Err(PVSSError::MismatchedCommitsEncryptionsParticipantsError(self.encs.len(), self.comms.len(), expected));
+        }
+
+        Ok(())
+    }
+
+
+    // Method checking the consistency of every encryption with its commitment, i.e., that
+    // e(pk_i, comm_i) = e(enc_i, g2) for all i, one pairing product per index (2n Miller loops).
+    pub fn verify_encryptions(&self, config: &Config<E>, public_keys: &[E::G1Affine]) -> bool {
+        // The vectors are zipped below, so their lengths must all match.
+        if self.validate_lengths(public_keys.len()).is_err() {
+            return false;
+        }
+
+        public_keys.iter().zip(self.encs.iter().zip(self.comms.iter())).all(|(pk, (enc, comm))| {
+            let pairs = [
+                ((*pk).into(), comm.into_affine().into()),
+                (enc.neg().into_affine().into(), config.g2_prepared().clone()),
+            ];
+
+            E::final_exponentiation(&E::miller_loop(pairs.iter())).map_or(false, |p| p.is_one())
+        })
+    }
+
+
+    // Method performing the same check as verify_encryptions against public keys given in
+    // prepared form. Along with the prepared g2 cached by the configuration, this lets a verifier
+    // checking many transcripts under the same committee prepare the keys only once.
+    pub fn verify_encryptions_prepared(&self, config: &Config<E>, prepared_keys: &[E::G1Prepared]) -> bool {
+        // The vectors are zipped below, so their lengths must all match.
+        if self.validate_lengths(prepared_keys.len()).is_err() {
+            return false;
+        }
+
+        prepared_keys.iter().zip(self.encs.iter().zip(self.comms.iter())).all(|(pk, (enc, comm))| {
+            let pairs = [
+                (pk.clone(), comm.into_affine().into()),
+                (enc.neg().into_affine().into(), config.g2_prepared().clone()),
+            ];
+
+            E::final_exponentiation(&E::miller_loop(pairs.iter())).map_or(false, |p| p.is_one())
+        })
+    }
+
+
+    // Method performing the same check as verify_encryptions, batched into a single pairing
+    // product: for random 128-bit r_i, prod_i e(r_i * pk_i, comm_i) * e(-sum_i r_i * enc_i, g2) = 1.
+    // A core with some inconsistent encryption passes with probability at most 2^-128.
+    // NOTE: The n keys cannot be replaced by a single aggregate key (see aggregate_pubkey): with
+    // pk_i = g1^{x_i} and comm_i = g2^{a_i}, the encryptions only fix the products x_i * a_i,
+    // and no pairing relation between g1^{sum_i x_i} and the core holds for every valid core
+    // without knowing the a_i. Any check against the aggregate alone either rejects valid cores
+    // or accepts arbitrary encryptions, so this n + 1 Miller loop product is the succinct form.
+    pub fn verify_encryptions_batched<R: Rng + CryptoRng>(&self, rng: &mut R, config: &Config<E>, public_keys: &[E::G1Affine]) -> bool {
+        match self.batched_encryption_pairs(rng, config, public_keys) {
+            Ok(pairs) => E::final_exponentiation(&E::miller_loop(pairs.iter())).map_or(false, |p| p.is_one()),
+            Err(_) => false,
+        }
+    }
+
+
+    // Utility method building the terms of the batched encryption check: one per index, plus a
+    // single one for g2 (n + 1 Miller loops). Fails unless there are as many encryptions and
+    // commitments as public keys.
+    pub(crate) fn batched_encryption_pairs<R: Rng + CryptoRng>(
+        &self,
+        rng: &mut R,
+        config: &Config<E>,
+        public_keys: &[E::G1Affine],
+    ) -> Result<Vec<(E::G1Prepared, E::G2Prepared)>, PVSSError<E>> {
+        self.validate_lengths(public_keys.len())?;
+
+        let mut pairs = Vec::with_capacity(public_keys.len() + 1);
+        let mut encs_sum = E::G1Projective::zero();
+
+        for (pk, (enc, comm)) in public_keys.iter().zip(self.encs.iter().zip(self.comms.iter())) {
+            let r = Scalar::<E>::from(rng.gen::<u128>()).into_repr();
+
+            pairs.push((pk.mul(r).into_affine().into(), comm.into_affine().into()));
+            encs_sum += enc.into_affine().mul(r);
+        }
+        pairs.push((encs_sum.neg().into_affine().into(), config.g2_prepared().clone()));
+
+        Ok(pairs)
+    }
+
+
+    // Aggregation of two PVSSCore instances.
+    pub fn aggregate(&self, other: &Self) -> Result<Self, PVSSError<E>> {
+        // Both vectors of self must be non-empty and of equal length.
+        self.validate_lengths(self.comms.len())?;
 
         // Commitment vector lengths must match
         if self.comms.len() != other.comms.len() {
@@ -48,61 +426,566 @@ where
             return Err(PVSSError::MismatchedEncryptionsError(self.encs.len(), other.encs.len()));
         }
 
-        // Commitment and encryption vector lengths must match
-        if self.comms.len() != self.encs.len() {
-            return Err(PVSSError::MismatchedCommitmentsEncryptionsError(self.comms.len(), other.encs.len()));
+        // Adding zero is skipped.
+        if self.known_empty() {
+            return Ok(other.clone());
+        }
+        if other.known_empty() {
+            return Ok(self.clone());
         }
-
-        // At this point, the following also hold:
-        // self.encs.len() != 0
-        // other.comms.len() == other.encs.len()
-        // other.comms.len(), other.encs.len() != 0
 
         // Aggregate PVSS cores
-        let result = Self {
-                encs: self
+        let result = Self::from_parts(
+                self
                     .encs
                     .iter()
                     .zip(other.encs.iter())
                     .map(|(e1, e2)| *e1 + *e2)
                     .collect::<Vec<_>>(),
-                comms: self
+                self
                     .comms
                     .iter()
                     .zip(other.comms.iter())
                     .map(|(c1, c2)| *c1 + *c2)
                     .collect::<Vec<_>>(),
-        };
+        );
+
+        Ok(result)
+    }
+
+
+    // In-place aggregation of another PVSSCore instance into self.
+    // Performs the same checks as aggregate, but avoids allocating a fresh core.
+    pub fn add_assign(&mut self, other: &Self) -> Result<(), PVSSError<E>> {
+        // Both vectors of self must be non-empty and of equal length.
+        self.validate_lengths(self.comms.len())?;
+
+        // Commitment vector lengths must match
+        if self.comms.len() != other.comms.len() {
+            return Err(PVSSError::MismatchedCommitmentsError(self.comms.len(), other.comms.len()));
+        }
+
+        // Encryption vector lengths must match
+        if self.encs.len() != other.encs.len() {
+            return Err(PVSSError::MismatchedEncryptionsError(self.encs.len(), other.encs.len()));
+        }
+
+        // Adding zero is skipped; an empty self takes on other's points.
+        if other.known_empty() {
+            return Ok(());
+        }
+        if self.known_empty() {
+            self.encs.copy_from_slice(&other.encs);
+            self.comms.copy_from_slice(&other.comms);
+            self.is_empty = false;
+            return Ok(());
+        }
+
+        for (e1, e2) in self.encs.iter_mut().zip(other.encs.iter()) {
+            *e1 += e2;
+        }
+
+        for (c1, c2) in self.comms.iter_mut().zip(other.comms.iter()) {
+            *c1 += c2;
+        }
+
+        Ok(())
+    }
+
+
+    // Trusted test oracle: given the decryptions g1^{p(j)} of all encryptions (which requires
+    // knowledge of every secret key), check that the commitments are consistent with them,
+    // i.e., e(g1, comms[j]) = e(g1^{p(j)}, g2) for every j. Detects a dealer that committed
+    // to different values than it encrypted.
+    pub fn check_decrypted(
+        &self,
+        decrypted: &[E::G1Projective],
+        g1: &E::G1Affine,
+        g2: &E::G2Affine,
+    ) -> Result<(), PVSSError<E>> {
+        if decrypted.len() != self.comms.len() {
+            return Err(PVSSError::LengthMismatchError);
+        }
+
+        let expected = expected_comms_from_decrypted::<E>(decrypted, g2);
+
+        if self.comms
+            .iter()
+            .zip(expected.iter())
+            .any(|(comm, target)| E::pairing(*g1, comm.into_affine()) != *target) {
+            return Err(PVSSError::EncryptionCorrectnessError);
+        }
+
+        Ok(())
+    }
+
+}
+
+
+/* Struct BatchedPVSSCore models L independent PVSS sharings dealt in a single transcript:
+   encs[l] and comms[l] are the encryptions and commitments of the l-th sharing */
+
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Debug, PartialEq)]
+pub struct BatchedPVSSCore<E>
+where
+    E: PairingEngine,
+{
+    pub encs: Vec<Vec<E::G1Projective>>,    // encs[l][j]: encryption of p_l(j+1) for node j
+    pub comms: Vec<Vec<E::G2Projective>>,   // comms[l][j]: commitment to p_l(j+1)
+}
+
+impl<E> BatchedPVSSCore<E>
+where
+    E: PairingEngine,
+{
+    // Create a new "empty" batched core of num_secrets sharings among num_participants nodes.
+    pub fn empty(num_secrets: usize, num_participants: usize) -> Self {
+        BatchedPVSSCore {
+            encs: vec![vec![E::G1Projective::zero(); num_participants]; num_secrets],
+            comms: vec![vec![E::G2Projective::zero(); num_participants]; num_secrets],
+        }
+    }
+
+
+    // Function for dealing num_secrets fresh sharings at once, one independent random polynomial
+    // per sharing. Returns the secrets underlying each sharing, in order.
+    pub fn deal<R: Rng + CryptoRng>(
+        rng: &mut R,
+        config: &Config<E>,
+        public_keys: &[E::G1Affine],
+        my_id: usize,
+        num_secrets: usize,
+    ) -> Result<(Self, Vec<PVSSShareSecrets<E>>), PVSSError<E>> {
+        let mut batch = BatchedPVSSCore { encs: Vec::with_capacity(num_secrets), comms: Vec::with_capacity(num_secrets) };
+        let mut secrets = Vec::with_capacity(num_secrets);
+
+        for _ in 0..num_secrets {
+            let (core, secret) = PVSSCore::deal(rng, config, public_keys, my_id)?;
+            batch.encs.push(core.encs);
+            batch.comms.push(core.comms);
+            secrets.push(secret);
+        }
+
+        Ok((batch, secrets))
+    }
+
+
+    // Number of sharings L held in the batch.
+    pub fn num_secrets(&self) -> usize {
+        self.encs.len()
+    }
+
+
+    // Returns the l-th sharing as a standalone PVSSCore, if present.
+    pub fn sharing(&self, l: usize) -> Option<PVSSCore<E>> {
+        Some(PVSSCore::from_parts(
+            self.encs.get(l)?.clone(),
+            self.comms.get(l)?.clone(),
+        ))
+    }
+
+
+    // Aggregation of two batched cores, summing corresponding sharings.
+    pub fn aggregate(&self, other: &Self) -> Result<Self, PVSSError<E>> {
+        let mut result = self.clone();
+        result.add_assign(other)?;
 
         Ok(result)
     }
 
+
+    // In-place aggregation of another batched core into self. Every sharing is subject to the
+    // same checks as PVSSCore::aggregate; self is left untouched if any of them fails.
+    pub fn add_assign(&mut self, other: &Self) -> Result<(), PVSSError<E>> {
+        if self.num_secrets() != other.num_secrets() || self.comms.len() != other.comms.len() {
+            return Err(PVSSError::MismatchedBatchSizes(self.num_secrets(), other.num_secrets()));
+        }
+
+        if self.comms.len() != self.encs.len() {
+            return Err(PVSSError::MismatchedBatchSizes(self.encs.len(), self.comms.len()));
+        }
+
+        for l in 0..self.num_secrets() {
+            let (encs, comms) = (&self.encs[l], &self.comms[l]);
+
+            if comms.len() == 0 {
+                return Err(PVSSError::EmptyEncryptionsVectorError);
+            }
+
+            if comms.len() != other.comms[l].len() {
+                return Err(PVSSError::MismatchedCommitmentsError(comms.len(), other.comms[l].len()));
+            }
+
+            if encs.len() != other.encs[l].len() {
+                return Err(PVSSError::MismatchedEncryptionsError(encs.len(), other.encs[l].len()));
+            }
+
+            if comms.len() != encs.len() {
+                return Err(PVSSError::MismatchedCommitmentsEncryptionsError(comms.len(), encs.len()));
+            }
+        }
+
+        for (encs, other_encs) in self.encs.iter_mut().zip(other.encs.iter()) {
+            for (e1, e2) in encs.iter_mut().zip(other_encs.iter()) {
+                *e1 += e2;
+            }
+        }
+
+        for (comms, other_comms) in self.comms.iter_mut().zip(other.comms.iter()) {
+            for (c1, c2) in comms.iter_mut().zip(other_comms.iter()) {
+                *c1 += c2;
+            }
+        }
+
+        Ok(())
+    }
+
+
+    // Method checking the consistency of all encryptions with their commitments across the L
+    // sharings in a single pairing product: for random 128-bit r_{l,j},
+    // prod_j e(pk_j, sum_l r_{l,j} * comm_{l,j}) * e(-sum_{l,j} r_{l,j} * enc_{l,j}, g2) = 1.
+    // Each public key is paired once for the whole batch, i.e., n + 1 Miller loops regardless of L.
+    pub fn verify_encryptions_batched<R: Rng + CryptoRng>(&self, rng: &mut R, config: &Config<E>, public_keys: &[E::G1Affine]) -> bool {
+        let n = public_keys.len();
+
+        if self.encs.len() != self.comms.len()
+            || self.encs.iter().any(|encs| encs.len() != n)
+            || self.comms.iter().any(|comms| comms.len() != n) {
+            return false;
+        }
+
+        let mut comm_sums = vec![E::G2Projective::zero(); n];
+        let mut encs_sum = E::G1Projective::zero();
+
+        for (encs, comms) in self.encs.iter().zip(self.comms.iter()) {
+            for (j, (enc, comm)) in encs.iter().zip(comms.iter()).enumerate() {
+                let r = Scalar::<E>::from(rng.gen::<u128>()).into_repr();
+
+                comm_sums[j] += comm.into_affine().mul(r);
+                encs_sum += enc.into_affine().mul(r);
+            }
+        }
+
+        let mut pairs = public_keys
+            .iter()
+            .zip(E::G2Projective::batch_normalization_into_affine(&comm_sums).into_iter())
+            .map(|(pk, comm)| ((*pk).into(), comm.into()))
+            .collect::<Vec<(E::G1Prepared, E::G2Prepared)>>();
+        pairs.push((encs_sum.neg().into_affine().into(), config.g2_prepared().clone()));
+
+        E::final_exponentiation(&E::miller_loop(pairs.iter())).map_or(false, |p| p.is_one())
+    }
+}
+
+
+// Utility function mapping each decrypted share g1^{p(j)} to the pairing image e(g1^{p(j)}, g2).
+// The commitment g2^{p(j)} cannot be computed without p(j), but it is the unique element
+// comm of G2 satisfying e(g1, comm) = e(g1^{p(j)}, g2), so the image serves as its expected value.
+pub fn expected_comms_from_decrypted<E: PairingEngine>(
+    decrypted: &[E::G1Projective],
+    g2: &E::G2Affine,
+) -> Vec<E::Fqk> {
+    decrypted
+        .iter()
+        .map(|dec| E::pairing(dec.into_affine(), *g2))
+        .collect()
+}
+
+
+// Function computing the decrypted shares g1^{p(j)}, j in {1, ..., n}, of a sharing of poly, in
+// participant id order; i.e., what the committee would jointly decrypt. Meant as a reconstruction
+// oracle for tests, as it requires the polynomial.
+pub fn all_decrypted_shares<E: PairingEngine>(poly: &Polynomial<E>, conf: &Config<E>) -> Vec<E::G1Projective> {
+    (0..conf.num_participants)
+        .map(|id| {
+            let eval = poly.evaluate(&ParticipantId::from_index(id).evaluation_point::<E>());
+            conf.srs.g1.mul(eval.into_repr())
+        })
+        .collect()
 }
 
 
 // PVSSShareSecrets models the secret parts underlying each share.
+#[derive(Clone)]
 pub struct PVSSShareSecrets<E: PairingEngine> {
     pub p_0: Scalar<E>,           // secret polynomial's free term s s.t.: p_i(0) = s
     pub my_secret: E::G1Affine,   // partial secret
 }
 
 
+/* Struct SparsePVSSCore models the part of a PVSS core within a contiguous index window
+   [start, start + encs.len()); encryptions and commitments outside the window are implicitly zero. */
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Debug, PartialEq)]
+pub struct SparsePVSSCore<E>
+where
+    E: PairingEngine,
+{
+    pub start: usize,                  // first index of the window
+    pub encs: Vec<E::G1Projective>,    // encryptions of indices start, start + 1, ...
+    pub comms: Vec<E::G2Projective>,   // commitments of indices start, start + 1, ...
+}
+
+impl<E> SparsePVSSCore<E>
+where
+    E: PairingEngine,
+{
+    // Function for extracting the window [start, end) of a dense PVSS core.
+    pub fn from_dense(core: &PVSSCore<E>, start: usize, end: usize) -> Result<Self, PVSSError<E>> {
+        if start > end || end > core.encs.len() || end > core.comms.len() {
+            return Err(PVSSError::LengthMismatchError);
+        }
+
+        Ok(Self {
+            start,
+            encs: core.encs[start..end].to_vec(),
+            comms: core.comms[start..end].to_vec(),
+        })
+    }
+
+    // Method returning the (exclusive) end of the window.
+    pub fn end(&self) -> usize {
+        self.start + self.encs.len()
+    }
+
+    // Method for aggregating two sparse PVSS cores. The resulting window is the smallest one
+    // covering both windows; overlapping indices are summed.
+    pub fn aggregate(&self, other: &Self) -> Result<Self, PVSSError<E>> {
+        // Commitment and encryption vector lengths must match within each core
+        for core in [self, other].iter() {
+            if core.comms.len() != core.encs.len() {
+                return Err(PVSSError::MismatchedCommitmentsEncryptionsError(core.comms.len(), core.encs.len()));
+            }
+        }
+
+        let start = self.start.min(other.start);
+        let end = self.end().max(other.end());
+
+        let mut result = Self {
+            start,
+            encs: vec![E::G1Projective::zero(); end - start],
+            comms: vec![E::G2Projective::zero(); end - start],
+        };
+
+        for core in [self, other].iter() {
+            let offset = core.start - start;
+            for (i, (enc, comm)) in core.encs.iter().zip(core.comms.iter()).enumerate() {
+                result.encs[offset + i] += enc;
+                result.comms[offset + i] += comm;
+            }
+        }
+
+        Ok(result)
+    }
+
+    // Method for expanding the sparse core into a dense one over num_participants indices.
+    pub fn to_dense(&self, num_participants: usize) -> Result<PVSSCore<E>, PVSSError<E>> {
+        if self.comms.len() != self.encs.len() {
+            return Err(PVSSError::MismatchedCommitmentsEncryptionsError(self.comms.len(), self.encs.len()));
+        }
+        if self.end() > num_participants {
+            return Err(PVSSError::LengthMismatchError);
+        }
+
+        let mut core = PVSSCore::empty(num_participants);
+        core.encs[self.start..self.end()].copy_from_slice(&self.encs);
+        core.comms[self.start..self.end()].copy_from_slice(&self.comms);
+
+        Ok(core)
+    }
+}
+
+
+/* Struct AffinePVSSCore models a PVSS core held in affine form, which is how aggregated shares
+   keep theirs in memory (affine points are about two thirds the size of projective ones).
+   It serializes exactly like the corresponding PVSSCore. */
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, PartialEq)]
+pub struct AffinePVSSCore<E>
+where
+    E: PairingEngine,
+{
+    pub encs: Vec<E::G1Affine>,    // vector of encryptions c
+    pub comms: Vec<E::G2Affine>,   // vector of commitments v
+}
+
+// As for PVSSCore; both forms of a core have the same fingerprints.
+impl<E: PairingEngine> fmt::Debug for AffinePVSSCore<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AffinePVSSCore")
+            .field("encs", &format_args!("{} x G1 #{}", self.encs.len(), fingerprint(&self.encs)))
+            .field("comms", &format_args!("{} x G2 #{}", self.comms.len(), fingerprint(&self.comms)))
+            .finish()
+    }
+}
+
+impl<E> AffinePVSSCore<E>
+where
+    E: PairingEngine,
+{
+    // Create a new "empty" affine PVSS core, where all fields are set to "zero" values.
+    pub fn empty(num_participants: usize) -> Self {
+        AffinePVSSCore {
+            encs:  vec![E::G1Affine::zero(); num_participants],
+            comms: vec![E::G2Affine::zero(); num_participants],
+        }
+    }
+
+    // Method returning whether all encryptions and commitments are zero (e.g., the core was
+    // created by empty()). Zero affine points are flagged as such, so no group operations are needed.
+    pub fn is_zero(&self) -> bool {
+        self.encs.iter().all(Zero::is_zero) && self.comms.iter().all(Zero::is_zero)
+    }
+
+    // Function for normalizing a PVSS core (one batched inversion per vector).
+    pub fn from_projective(core: &PVSSCore<E>) -> Self {
+        AffinePVSSCore {
+            encs: E::G1Projective::batch_normalization_into_affine(&core.encs),
+            comms: E::G2Projective::batch_normalization_into_affine(&core.comms),
+        }
+    }
+
+    // Method for converting back into a (projective) PVSS core, e.g., for verification.
+    pub fn to_projective(&self) -> PVSSCore<E> {
+        PVSSCore::from_parts(
+            self.encs.iter().map(|enc| enc.into_projective()).collect(),
+            self.comms.iter().map(|comm| comm.into_projective()).collect(),
+        )
+    }
+
+
+    // Reads an AffinePVSSCore off a (possibly shared) counting reader.
+    pub(crate) fn read_checked<R: Read>(reader: &mut CountingReader<R>) -> Result<Self, PVSSError<E>> {
+        let encs = reader.read_vec("encs")?;
+        let comms = reader.read_vec("comms")?;
+
+        Ok(AffinePVSSCore { encs, comms })
+    }
+
+
+    // Aggregation of two AffinePVSSCore instances. If self is zero, the sum is other itself,
+    // which is cloned without first cloning self.
+    pub fn aggregate(&self, other: &Self) -> Result<Self, PVSSError<E>> {
+        if self.is_zero() {
+            check_aggregation_lengths(self.encs.len(), self.comms.len(), other.encs.len(), other.comms.len())?;
+
+            return Ok(other.clone());
+        }
+
+        let mut result = self.clone();
+        result.add_assign(other)?;
+
+        Ok(result)
+    }
+
+    // In-place aggregation of another affine PVSS core into self. Performs the same checks as
+    // PVSSCore::add_assign; the sums are normalized in a batch and written back into self's vectors.
+    // Adding zero is skipped, and a zero self takes on other's points.
+    pub fn add_assign(&mut self, other: &Self) -> Result<(), PVSSError<E>> {
+        check_aggregation_lengths(self.encs.len(), self.comms.len(), other.encs.len(), other.comms.len())?;
+
+        if other.is_zero() {
+            return Ok(());
+        }
+        if self.is_zero() {
+            self.encs.copy_from_slice(&other.encs);
+            self.comms.copy_from_slice(&other.comms);
+            return Ok(());
+        }
+
+        add_into_affine(&mut self.encs, other.encs.iter().map(|enc| enc.into_projective()));
+        add_into_affine(&mut self.comms, other.comms.iter().map(|comm| comm.into_projective()));
+
+        Ok(())
+    }
+
+    // In-place aggregation of a (projective) PVSS core into self. A zero self takes on other's
+    // points, normalized in a batch, without the additions.
+    pub fn add_assign_projective(&mut self, other: &PVSSCore<E>) -> Result<(), PVSSError<E>> {
+        check_aggregation_lengths(self.encs.len(), self.comms.len(), other.encs.len(), other.comms.len())?;
+
+        if self.is_zero() {
+            self.encs.copy_from_slice(&E::G1Projective::batch_normalization_into_affine(&other.encs));
+            self.comms.copy_from_slice(&E::G2Projective::batch_normalization_into_affine(&other.comms));
+            return Ok(());
+        }
+
+        add_into_affine(&mut self.encs, other.encs.iter().cloned());
+        add_into_affine(&mut self.comms, other.comms.iter().cloned());
+
+        Ok(())
+    }
+}
+
+
+// Utility function performing the checks of PVSSCore::add_assign on the vector lengths.
+fn check_aggregation_lengths<E: PairingEngine>(
+    encs: usize,
+    comms: usize,
+    other_encs: usize,
+    other_comms: usize,
+) -> Result<(), PVSSError<E>> {
+    // Commitment vector must be non-empty
+    if comms == 0 {
+        return Err(PVSSError::EmptyEncryptionsVectorError);
+    }
+
+    // Commitment vector lengths must match
+    if comms != other_comms {
+        return Err(PVSSError::MismatchedCommitmentsError(comms, other_comms));
+    }
+
+    // Encryption vector lengths must match
+    if encs != other_encs {
+        return Err(PVSSError::MismatchedEncryptionsError(encs, other_encs));
+    }
+
+    // Commitment and encryption vector lengths must match
+    if comms != encs {
+        return Err(PVSSError::MismatchedCommitmentsEncryptionsError(comms, other_encs));
+    }
+
+    Ok(())
+}
+
+
+// Utility function adding each of the given terms to the corresponding affine point.
+fn add_into_affine<G: ProjectiveCurve>(points: &mut [G::Affine], terms: impl Iterator<Item = G>) {
+    let sums = points
+        .iter()
+        .zip(terms)
+        .map(|(point, mut term)| {
+            term.add_assign_mixed(point);
+            term
+        })
+        .collect::<Vec<_>>();
+
+    points.copy_from_slice(&G::batch_normalization_into_affine(&sums));
+}
+
+
 /* Unit tests: */
 
 #[cfg(test)]
 mod test {
 
-    use super::PVSSCore;
+    use super::{all_decrypted_shares, encrypt_eval, AffinePVSSCore, BatchedPVSSCore, PVSSCore, SparsePVSSCore, DEFAULT_MIN_CHUNK};
+    use crate::modified_scrape::{
+        config::Config, decryption::decrypt_share, errors::PVSSError, participant::ParticipantId, poly::Polynomial, recon::reconstruct,
+        srs::SRS,
+    };
     use crate::signature::utils::tests::check_serialization;
+    use crate::Scalar;
 
-    use ark_ff::Zero;
-    use ark_ec::PairingEngine;
-    use ark_std::UniformRand;
+    use ark_ff::{Field, One, PrimeField, Zero};
+    use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+    use ark_poly::{Polynomial as Poly, UVPolynomial};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use ark_std::{collections::BTreeMap, UniformRand};
     use ark_bls12_381::{
 	    Bls12_381 as E,   // type Bls12_381 = Bls12<Parameters> (Bls12 implements PairingEngine)
+	    Fq, Fq2, G1Affine, G2Affine,
     };
 
-    use rand::thread_rng;
+    use rand::{seq::index::sample, thread_rng};
     use std::ops::Neg;
 
     #[test]
@@ -136,8 +1019,8 @@ mod test {
         let comms1 = vec![<E as PairingEngine>::G2Projective::rand(rng); size];
         let comms2 = vec![<E as PairingEngine>::G2Projective::rand(rng); size];
 
-        let core1 = PVSSCore::<E> {encs: encs1, comms: comms1};
-        let core2 = PVSSCore::<E> {encs: encs2, comms: comms2};
+        let core1 = PVSSCore::<E>::new(encs1, comms1).unwrap();
+        let core2 = PVSSCore::<E>::new(encs2, comms2).unwrap();
 
         assert!(core1 != core2)
     }
@@ -160,15 +1043,15 @@ mod test {
         let encs = vec![<E as PairingEngine>::G1Projective::rand(rng); size];
         let comms = vec![<E as PairingEngine>::G2Projective::rand(rng); size];
 
-	let core1 = PVSSCore::<E> {
-            encs:  encs.clone(),
-            comms: comms.clone(),
-        };
+	let core1 = PVSSCore::<E>::new(
+            encs.clone(),
+            comms.clone(),
+        ).unwrap();
 
-        let core2 = PVSSCore::<E> {
-            encs:  encs.iter().map(|&x| x.neg()).collect(),
-            comms: comms.iter().map(|&x| x.neg()).collect(),
-        };
+        let core2 = PVSSCore::<E>::new(
+            encs.iter().map(|&x| x.neg()).collect(),
+            comms.iter().map(|&x| x.neg()).collect(),
+        ).unwrap();
 
         let result = core1.aggregate(&core2).unwrap();
 
@@ -177,39 +1060,28 @@ mod test {
     }
 
     #[test]
-    #[should_panic]
-    fn test_aggregate_empty_encs() {
+    fn test_new_empty_encs() {
         let size = 10;
 
-        let core1 = PVSSCore::<E> {
-	        encs:  vec![],
-	        comms: vec![<E as PairingEngine>::G2Projective::zero(); size],
-        };
+        let core = PVSSCore::<E>::new(
+            vec![],
+            vec![<E as PairingEngine>::G2Projective::zero(); size],
+        );
 
-        let core2 = PVSSCore::<E> {
-	        encs:  vec![<E as PairingEngine>::G1Projective::zero(); size],
-	        comms: vec![<E as PairingEngine>::G2Projective::zero(); size],
-        };
-
-        core1.aggregate(&core2).unwrap();
+        assert!(matches!(core, Err(PVSSError::EmptyEncryptionsVectorError)));
+        assert!(matches!(PVSSCore::<E>::new(vec![], vec![]), Err(PVSSError::EmptyEncryptionsVectorError)));
     }
 
     #[test]
-    #[should_panic]
-    fn test_aggregate_empty_comms() {
+    fn test_new_empty_comms() {
         let size = 10;
 
-        let core1 = PVSSCore::<E> {
-	        encs:  vec![<E as PairingEngine>::G1Projective::zero(); size],
-	        comms: vec![],
-        };
-
-        let core2 = PVSSCore::<E> {
-	        encs:  vec![<E as PairingEngine>::G1Projective::zero(); size],
-	        comms: vec![<E as PairingEngine>::G2Projective::zero(); size],
-        };
+        let core = PVSSCore::<E>::new(
+            vec![<E as PairingEngine>::G1Projective::zero(); size],
+            vec![],
+        );
 
-        core1.aggregate(&core2).unwrap();
+        assert!(matches!(core, Err(PVSSError::EmptyCommitmentsVectorError)));
     }
 
     #[test]
@@ -218,35 +1090,124 @@ mod test {
         let size1 = 10;
         let size2 = 20;
 
-        let core1 = PVSSCore::<E> {
-	        encs:  vec![<E as PairingEngine>::G1Projective::zero(); size1],
-	        comms: vec![<E as PairingEngine>::G2Projective::zero(); size1],
-        };
+        let core1 = PVSSCore::<E>::new(
+            vec![<E as PairingEngine>::G1Projective::zero(); size1],
+            vec![<E as PairingEngine>::G2Projective::zero(); size1],
+        ).unwrap();
 
-        let core2 = PVSSCore::<E> {
-	        encs:  vec![<E as PairingEngine>::G1Projective::zero(); size2],
-	        comms: vec![<E as PairingEngine>::G2Projective::zero(); size2],
-        };
+        let core2 = PVSSCore::<E>::new(
+            vec![<E as PairingEngine>::G1Projective::zero(); size2],
+            vec![<E as PairingEngine>::G2Projective::zero(); size2],
+        ).unwrap();
 
         core1.aggregate(&core2).unwrap();
     }
 
     #[test]
-    #[should_panic]
-    fn test_aggregate_mismatching_encs_comms_lengths() {
+    fn test_new_mismatching_encs_comms_lengths() {
         let size = 10;
 
-        let core1 = PVSSCore::<E> {
-	        encs:  vec![<E as PairingEngine>::G1Projective::zero(); size],
-	        comms: vec![<E as PairingEngine>::G2Projective::zero(); size+1],   // mismatch with enc's length
-        };
+        let core = PVSSCore::<E>::new(
+            vec![<E as PairingEngine>::G1Projective::zero(); size],
+            vec![<E as PairingEngine>::G2Projective::zero(); size+1],   // mismatch with enc's length
+        );
+
+        match core {
+            Err(PVSSError::MismatchedCommitmentsEncryptionsError(comms, encs)) => assert_eq!((comms, encs), (size + 1, size)),
+            _ => panic!("expected MismatchedCommitmentsEncryptionsError"),
+        }
+    }
+
+    #[test]
+    fn test_validate_lengths() {
+        let size = 10;
 
-        let core2 = PVSSCore::<E> {
-	        encs:  vec![<E as PairingEngine>::G1Projective::zero(); size],
-	        comms: vec![<E as PairingEngine>::G2Projective::zero(); size],
+        // Inconsistent cores cannot be built with new, but can still be deserialized.
+        let core = |encs: usize, comms: usize| {
+            let mut bytes = vec![];
+            vec![<E as PairingEngine>::G1Projective::zero(); encs].serialize(&mut bytes).unwrap();
+            vec![<E as PairingEngine>::G2Projective::zero(); comms].serialize(&mut bytes).unwrap();
+
+            PVSSCore::<E>::deserialize(&bytes[..]).unwrap()
         };
 
-        core1.aggregate(&core2).unwrap();
+        core(size, size).validate_lengths(size).unwrap();
+
+        // Empty encryptions are reported before empty commitments.
+        assert!(matches!(core(0, 0).validate_lengths(size), Err(PVSSError::EmptyEncryptionsVectorError)));
+        assert!(matches!(core(0, size).validate_lengths(size), Err(PVSSError::EmptyEncryptionsVectorError)));
+        assert!(matches!(core(size, 0).validate_lengths(size), Err(PVSSError::EmptyCommitmentsVectorError)));
+
+        for &(encs, comms) in [(size + 1, size), (size, size - 1), (size + 1, size + 1)].iter() {
+            match core(encs, comms).validate_lengths(size) {
+                Err(PVSSError::MismatchedCommitsEncryptionsParticipantsError(e, c, n)) => assert_eq!((e, c, n), (encs, comms, size)),
+                _ => panic!("expected MismatchedCommitsEncryptionsParticipantsError"),
+            }
+        }
+
+        // Aggregation rejects an empty or inconsistent core up front, with the same errors.
+        let valid = core(size, size);
+        assert!(matches!(core(0, size).aggregate(&valid), Err(PVSSError::EmptyEncryptionsVectorError)));
+        assert!(matches!(core(size, 0).aggregate(&valid), Err(PVSSError::EmptyCommitmentsVectorError)));
+        assert!(matches!(core(size, size + 1).aggregate(&valid), Err(PVSSError::MismatchedCommitsEncryptionsParticipantsError(..))));
+        assert!(matches!(valid.aggregate(&core(size, size + 1)), Err(PVSSError::MismatchedCommitmentsError(..))));
+    }
+
+    #[test]
+    fn test_aggregate_empty_fast_path() {
+        let rng = &mut thread_rng();
+        let n = 8;
+
+        let core = PVSSCore::<E>::new(
+            (0..n).map(|_| <E as PairingEngine>::G1Projective::rand(rng)).collect(),
+            (0..n).map(|_| <E as PairingEngine>::G2Projective::rand(rng)).collect(),
+        ).unwrap();
+        let empty = PVSSCore::<E>::empty(n);
+
+        // A round trip drops the flag: the deserialized core is equal, but takes the slow path.
+        let mut bytes = vec![];
+        empty.serialize(&mut bytes).unwrap();
+        let zero = PVSSCore::<E>::deserialize(&bytes[..]).unwrap();
+        assert!(zero == empty && empty.known_empty() && !zero.known_empty());
+
+        let slow = zero.aggregate(&core).unwrap();
+        assert!(slow == core);
+        assert!(empty.aggregate(&core).unwrap() == slow);
+        assert!(core.aggregate(&empty).unwrap() == core.aggregate(&zero).unwrap());
+
+        let mut fast = PVSSCore::<E>::empty(n);
+        fast.add_assign(&core).unwrap();
+        let mut slow = zero.clone();
+        slow.add_assign(&core).unwrap();
+        assert!(fast == slow && !fast.known_empty());
+
+        // An empty core whose points were modified since is no longer taken for empty.
+        let mut modified = PVSSCore::<E>::empty(n);
+        modified.encs[2] = core.encs[5];
+        assert!(!modified.known_empty());
+        let sum = modified.aggregate(&core).unwrap();
+        assert!(sum.encs[2] == core.encs[2] + core.encs[5] && sum.comms == core.comms);
+
+        // Affine cores are recognized as zero from their points alone, before and after a round trip.
+        let affine = AffinePVSSCore::from_projective(&core);
+        let mut bytes = vec![];
+        AffinePVSSCore::<E>::empty(n).serialize(&mut bytes).unwrap();
+        let affine_zero = AffinePVSSCore::<E>::deserialize(&bytes[..]).unwrap();
+        assert!(affine_zero.is_zero() && !affine.is_zero());
+
+        for start in [AffinePVSSCore::<E>::empty(n), affine_zero].iter() {
+            assert!(start.aggregate(&affine).unwrap() == affine);
+
+            let mut folded = start.clone();
+            folded.add_assign_projective(&core).unwrap();
+            assert!(folded == affine);
+
+            let mut folded = start.clone();
+            folded.add_assign(&affine).unwrap();
+            assert!(folded == affine);
+        }
+        assert!(affine.aggregate(&AffinePVSSCore::empty(n)).unwrap() == affine);
+        assert!(AffinePVSSCore::<E>::empty(n).aggregate(&AffinePVSSCore::empty(n + 1)).is_err());
     }
 
     #[test]
@@ -254,12 +1215,393 @@ mod test {
         let rng = &mut thread_rng();
         let size = 10;
 
-	    let core = PVSSCore::<E> {
-            encs:  vec![<E as PairingEngine>::G1Projective::rand(rng); size],
-            comms: vec![<E as PairingEngine>::G2Projective::rand(rng); size],
-        };
+	    let core = PVSSCore::<E>::new(
+            vec![<E as PairingEngine>::G1Projective::rand(rng); size],
+            vec![<E as PairingEngine>::G2Projective::rand(rng); size],
+        ).unwrap();
 
         check_serialization(core.clone());
     }
 
+    #[test]
+    fn test_check_decrypted() {
+        let rng = &mut thread_rng();
+        let t = 3;
+        let n = 10;
+
+        let srs = SRS::<E>::setup(rng).unwrap();
+        let poly = Polynomial::<E>::rand(t, rng);
+        let sks = (0..n).map(|_| Scalar::<E>::rand(rng)).collect::<Vec<_>>();
+        let evals = (0..n)
+            .map(|j| poly.evaluate(&Scalar::<E>::from((j + 1) as u64)))
+            .collect::<Vec<_>>();
+
+        // A genuine core: enc_j = pk_j^{p(j)} with pk_j = g1^{sk_j}, comm_j = g2^{p(j)}.
+        let mut core = PVSSCore::<E>::new(
+            (0..n)
+                .map(|j| srs.g1.mul((sks[j] * evals[j]).into_repr()))
+                .collect(),
+            evals.iter().map(|e| srs.g2.mul(e.into_repr())).collect(),
+        ).unwrap();
+
+        // The oracle holds all secret keys, so it can decrypt every share.
+        let decrypted = core.encs
+            .iter()
+            .zip(sks.iter())
+            .map(|(enc, sk)| enc.mul(sk.inverse().unwrap().into_repr()))
+            .collect::<Vec<_>>();
+
+        core.check_decrypted(&decrypted, &srs.g1, &srs.g2).unwrap();
+
+        // A commitment that does not match the encrypted value is detected.
+        core.comms[2] = srs.g2.mul(Scalar::<E>::rand(rng).into_repr());
+        assert!(core.check_decrypted(&decrypted, &srs.g1, &srs.g2).is_err());
+
+        // Mismatching lengths are rejected.
+        assert!(core.check_decrypted(&decrypted[1..], &srs.g1, &srs.g2).is_err());
+    }
+
+
+    #[test]
+    fn test_sparse_core_to_dense() {
+        let rng = &mut thread_rng();
+        let n = 8;
+
+        let sparse = SparsePVSSCore::<E> {
+            start: 2,
+            encs:  (0..3).map(|_| <E as PairingEngine>::G1Projective::rand(rng)).collect(),
+            comms: (0..3).map(|_| <E as PairingEngine>::G2Projective::rand(rng)).collect(),
+        };
+
+        // The dense core with zeros outside [2, 5).
+        let mut expected = PVSSCore::<E>::empty(n);
+        for i in 0..3 {
+            expected.encs[2 + i] = sparse.encs[i];
+            expected.comms[2 + i] = sparse.comms[i];
+        }
+
+        let dense = sparse.to_dense(n).unwrap();
+        assert!(dense == expected);
+        assert!(SparsePVSSCore::from_dense(&dense, 2, 5).unwrap() == sparse);
+
+        // The window does not fit in fewer participants.
+        assert!(sparse.to_dense(4).is_err());
+    }
+
+    #[test]
+    fn test_sparse_core_aggregation_with_overlap() {
+        let rng = &mut thread_rng();
+        let n = 8;
+
+        let random_core = |rng: &mut _| PVSSCore::<E>::new(
+            (0..n).map(|_| <E as PairingEngine>::G1Projective::rand(rng)).collect(),
+            (0..n).map(|_| <E as PairingEngine>::G2Projective::rand(rng)).collect(),
+        ).unwrap();
+
+        // Windows [1, 4) and [3, 7) overlap at index 3.
+        let sparse1 = SparsePVSSCore::from_dense(&random_core(rng), 1, 4).unwrap();
+        let sparse2 = SparsePVSSCore::from_dense(&random_core(rng), 3, 7).unwrap();
+
+        let aggregated = sparse1.aggregate(&sparse2).unwrap();
+        assert_eq!((aggregated.start, aggregated.end()), (1, 7));
+
+        let expected = sparse1.to_dense(n).unwrap().aggregate(&sparse2.to_dense(n).unwrap()).unwrap();
+        assert!(aggregated.to_dense(n).unwrap() == expected);
+    }
+
+    #[test]
+    fn test_batched_encryption_check() {
+        let rng = &mut thread_rng();
+        let t = 3;
+        let n = 10;
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+
+        let pks = (0..n)
+            .map(|_| conf.srs.g1.mul(Scalar::<E>::rand(rng).into_repr()).into_affine())
+            .collect::<Vec<_>>();
+        let (core, _) = PVSSCore::<E>::deal(rng, &conf, &pks, 0).unwrap();
+
+        // Both checks accept a valid core.
+        assert!(core.verify_encryptions(&conf, &pks));
+        assert!(core.verify_encryptions_batched(rng, &conf, &pks));
+
+        // Both checks reject a core with a single inconsistent encryption.
+        for i in [0, n / 2, n - 1].iter() {
+            let mut faulty = core.clone();
+            faulty.encs[*i] += conf.srs.g1.into_projective();
+            assert!(!faulty.verify_encryptions(&conf, &pks));
+            assert!(!faulty.verify_encryptions_batched(rng, &conf, &pks));
+        }
+
+        // ... and a core with swapped encryptions.
+        let mut swapped = core.clone();
+        swapped.encs.swap(1, 2);
+        assert!(!swapped.verify_encryptions(&conf, &pks));
+        assert!(!swapped.verify_encryptions_batched(rng, &conf, &pks));
+
+        // The batched check takes n + 1 Miller loops, against 2n.
+        assert_eq!(core.batched_encryption_pairs(rng, &conf, &pks).unwrap().len(), n + 1);
+        assert!(!core.verify_encryptions_batched(rng, &conf, &pks[1..]));
+    }
+
+    #[test]
+    fn test_prepared_encryption_check() {
+        let rng = &mut thread_rng();
+        let t = 3;
+        let n = 10;
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+
+        let pks = (0..n)
+            .map(|_| conf.srs.g1.mul(Scalar::<E>::rand(rng).into_repr()).into_affine())
+            .collect::<Vec<_>>();
+        let prepared = pks.iter().map(|pk| (*pk).into()).collect::<Vec<<E as PairingEngine>::G1Prepared>>();
+
+        // The keys are prepared once, and reused across transcripts.
+        for _ in 0..3 {
+            let (core, _) = PVSSCore::<E>::deal(rng, &conf, &pks, 0).unwrap();
+            assert!(core.verify_encryptions(&conf, &pks));
+            assert!(core.verify_encryptions_prepared(&conf, &prepared));
+
+            let mut faulty = core.clone();
+            faulty.encs[n - 1] += conf.srs.g1.into_projective();
+            assert!(!faulty.verify_encryptions(&conf, &pks));
+            assert!(!faulty.verify_encryptions_prepared(&conf, &prepared));
+
+            let mut swapped = core.clone();
+            swapped.encs.swap(1, 2);
+            assert!(!swapped.verify_encryptions_prepared(&conf, &prepared));
+
+            assert!(!core.verify_encryptions_prepared(&conf, &prepared[1..]));
+        }
+    }
+
+    #[test]
+    fn test_deal_polynomial_deterministic() {
+        let rng = &mut thread_rng();
+        let t = 3;
+        let n = 40;
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+
+        let pks = (0..n)
+            .map(|_| conf.srs.g1.mul(Scalar::<E>::rand(rng).into_repr()).into_affine())
+            .collect::<Vec<_>>();
+        let poly = Polynomial::<E>::rand(t, rng);
+
+        // Serial reference dealing of the same polynomial.
+        let evals = (1..=n)
+            .map(|j| poly.evaluate(&Scalar::<E>::from(j as u64)))
+            .collect::<Vec<_>>();
+        let expected = PVSSCore::<E>::new(
+            pks.iter().zip(evals.iter()).map(|(pk, e)| pk.mul(e.into_repr())).collect(),
+            evals.iter().map(|e| conf.srs.g2.mul(e.into_repr())).collect(),
+        ).unwrap();
+
+        // One task per participant, the default chunking, and a single task all agree.
+        for min_chunk in [1, DEFAULT_MIN_CHUNK, usize::MAX].iter() {
+            let (core, secrets) = PVSSCore::<E>::deal_polynomial(&poly, &conf, &pks, 2, *min_chunk).unwrap();
+            assert!(core == expected);
+            assert_eq!(secrets.p_0, poly.coeffs[0]);
+            assert_eq!(secrets.my_secret, conf.srs.g1.mul(evals[2].into_repr()).into_affine());
+        }
+
+        assert!(PVSSCore::<E>::deal_polynomial(&poly, &conf, &pks[1..], 0, 1).is_err());
+        assert!(PVSSCore::<E>::deal_polynomial(&poly, &conf, &pks, n, 1).is_err());
+    }
+
+    #[test]
+    fn test_encrypt_eval() {
+        let rng = &mut thread_rng();
+        let t = 2;
+        let n = 6;
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+
+        let pks = (0..n)
+            .map(|_| conf.srs.g1.mul(Scalar::<E>::rand(rng).into_repr()).into_affine())
+            .collect::<Vec<_>>();
+        let poly = Polynomial::<E>::rand(t, rng);
+        let (core, _) = PVSSCore::<E>::deal_polynomial(&poly, &conf, &pks, 0, DEFAULT_MIN_CHUNK).unwrap();
+
+        // The encryptions of the dealt core, recomputed from the disclosed evaluations.
+        for (j, pk) in pks.iter().enumerate() {
+            let eval = poly.evaluate(&Scalar::<E>::from(j as u64 + 1));
+            assert_eq!(encrypt_eval::<E>(pk, &eval), pk.mul(eval.into_repr()));
+            assert_eq!(encrypt_eval::<E>(pk, &eval), core.encs[j]);
+        }
+        assert!(encrypt_eval::<E>(&pks[0], &Scalar::<E>::zero()).is_zero());
+    }
+
+    #[test]
+    fn test_evaluation_point() {
+        let rng = &mut thread_rng();
+        let t = 2;
+        let n = 6;
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+
+        let pks = (0..n)
+            .map(|_| conf.srs.g1.mul(Scalar::<E>::rand(rng).into_repr()).into_affine())
+            .collect::<Vec<_>>();
+        let poly = Polynomial::<E>::rand(t, rng);
+        let (core, secrets) = PVSSCore::<E>::deal_polynomial(&poly, &conf, &pks, 3, DEFAULT_MIN_CHUNK).unwrap();
+
+        // The entries of participant j are those of the evaluation at its evaluation point j + 1.
+        for (j, pk) in pks.iter().enumerate() {
+            let id = ParticipantId::from_index(j);
+            assert_eq!(id.evaluation_point::<E>(), conf.point_for(j).unwrap());
+
+            let eval = poly.evaluate(&id.evaluation_point::<E>());
+            assert_eq!(core.encs[j], encrypt_eval::<E>(pk, &eval));
+            assert_eq!(core.comms[j], conf.srs.g2.mul(eval.into_repr()));
+        }
+        let eval = poly.evaluate(&ParticipantId(3).evaluation_point::<E>());
+        assert_eq!(secrets.my_secret, conf.srs.g1.mul(eval.into_repr()).into_affine());
+
+        // No participant is assigned the point 0, which would disclose the secret p(0).
+        assert_eq!(ParticipantId(0).evaluation_point::<E>(), Scalar::<E>::from(1u64));
+        assert!(!ParticipantId(u32::MAX).evaluation_point::<E>().is_zero());
+    }
+
+    #[test]
+    fn test_batched_core() {
+        let rng = &mut thread_rng();
+        let t = 2;
+        let n = 6;
+        let num_secrets = 3;
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+
+        let sks = (0..n).map(|_| Scalar::<E>::rand(rng)).collect::<Vec<_>>();
+        let pks = sks
+            .iter()
+            .map(|sk| conf.srs.g1.mul(sk.into_repr()).into_affine())
+            .collect::<Vec<_>>();
+
+        let (batch_a, secrets_a) = BatchedPVSSCore::<E>::deal(rng, &conf, &pks, 0, num_secrets).unwrap();
+        let (batch_b, secrets_b) = BatchedPVSSCore::<E>::deal(rng, &conf, &pks, 1, num_secrets).unwrap();
+        assert_eq!(batch_a.num_secrets(), num_secrets);
+        assert!(batch_a.verify_encryptions_batched(rng, &conf, &pks));
+
+        let aggregated = batch_a.aggregate(&batch_b).unwrap();
+        assert!(aggregated.verify_encryptions_batched(rng, &conf, &pks));
+
+        // Each sharing is reconstructed independently from t + 1 decrypted shares.
+        for l in 0..num_secrets {
+            let sharing = aggregated.sharing(l).unwrap();
+            let shares = [1_usize, 3, 4]
+                .iter()
+                .map(|&j| (j, decrypt_share::<E>(&sharing.encs[j].into_affine(), &sks[j], j).unwrap()))
+                .collect::<BTreeMap<_, _>>();
+
+            let expected = conf.srs.g1.mul((secrets_a[l].p_0 + secrets_b[l].p_0).into_repr()).into_affine();
+            assert_eq!(reconstruct(&shares, &conf).unwrap(), expected);
+        }
+        assert!(aggregated.sharing(num_secrets).is_none());
+
+        // A single inconsistent encryption in any sharing is caught.
+        let mut faulty = aggregated.clone();
+        faulty.encs[2][n - 1] += conf.srs.g1.into_projective();
+        assert!(!faulty.verify_encryptions_batched(rng, &conf, &pks));
+        assert!(!aggregated.verify_encryptions_batched(rng, &conf, &pks[1..]));
+
+        // Batches of different sizes are not aggregated.
+        let (small, _) = BatchedPVSSCore::<E>::deal(rng, &conf, &pks, 0, num_secrets - 1).unwrap();
+        match batch_a.aggregate(&small) {
+            Err(PVSSError::MismatchedBatchSizes(a, b)) => assert_eq!((a, b), (num_secrets, num_secrets - 1)),
+            _ => panic!("expected MismatchedBatchSizes"),
+        }
+
+        check_serialization(aggregated.clone());
+        assert!(BatchedPVSSCore::<E>::empty(num_secrets, n).aggregate(&aggregated).unwrap() == aggregated);
+    }
+
+    #[test]
+    fn test_all_decrypted_shares() {
+        let rng = &mut thread_rng();
+        let (t, n) = (3, 10);
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+
+        // A known polynomial p(x) = 5 + 4x + 3x^2 + 2x^3.
+        let poly = Polynomial::<E>::from_coefficients_vec((2..=5u64).rev().map(Scalar::<E>::from).collect());
+        let shares = all_decrypted_shares(&poly, &conf);
+        assert_eq!(shares.len(), n);
+        assert_eq!(shares[0], conf.srs.g1.mul(Scalar::<E>::from(14u64).into_repr()));
+
+        // The shares are those decrypted by honest participants from a dealing of the polynomial.
+        let sks = (0..n).map(|_| Scalar::<E>::rand(rng)).collect::<Vec<_>>();
+        let pks = sks.iter().map(|sk| conf.srs.g1.mul(sk.into_repr()).into_affine()).collect::<Vec<_>>();
+        let (core, _) = PVSSCore::<E>::deal_polynomial(&poly, &conf, &pks, 0, DEFAULT_MIN_CHUNK).unwrap();
+        for (j, share) in shares.iter().enumerate() {
+            assert_eq!(decrypt_share::<E>(&core.encs[j].into_affine(), &sks[j], j).unwrap(), share.into_affine());
+        }
+
+        // Any t + 1 of them reconstruct g1^{p(0)}.
+        let subset = sample(rng, n, t + 1)
+            .into_iter()
+            .map(|j| (j, shares[j].into_affine()))
+            .collect::<BTreeMap<_, _>>();
+        let expected = conf.srs.g1.mul(Scalar::<E>::from(5u64).into_repr()).into_affine();
+        assert_eq!(reconstruct(&subset, &conf).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_deserialize_subgroup_checked() {
+        let rng = &mut thread_rng();
+        let (t, n) = (2, 5);
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+
+        let pks = (0..n).map(|_| <E as PairingEngine>::G1Projective::rand(rng).into_affine()).collect::<Vec<_>>();
+        let (core, _) = PVSSCore::<E>::deal(rng, &conf, &pks, 0).unwrap();
+
+        let encode = |core: &PVSSCore<E>| {
+            let mut bytes = vec![];
+            core.serialize_uncompressed(&mut bytes).unwrap();
+            bytes
+        };
+        assert!(PVSSCore::<E>::deserialize_subgroup_checked(&encode(&core)[..]).unwrap() == core);
+
+        // Points on the curves, but outside the prime order subgroups: the cofactors of both
+        // groups of BLS12-381 are large, so the first point found on each curve will do.
+        let g1_point = (1u64..).find_map(|x| G1Affine::get_point_from_x(Fq::from(x), false)).unwrap();
+        let g2_point = (1u64..).find_map(|x| G2Affine::get_point_from_x(Fq2::from(x), false)).unwrap();
+        assert!(!g1_point.is_in_correct_subgroup_assuming_on_curve());
+        assert!(!g2_point.is_in_correct_subgroup_assuming_on_curve());
+
+        let mut bad_enc = core.clone();
+        bad_enc.encs[3] = g1_point.into_projective();
+        let mut bad_comm = core.clone();
+        bad_comm.comms[1] = g2_point.into_projective();
+        let mut bad_both = bad_enc.clone();
+        bad_both.comms[1] = g2_point.into_projective();
+
+        // A point off the curve, whose unchecked decoding succeeds as well.
+        let off_curve = G1Affine::new(Fq::one(), Fq::one(), false);
+        assert!(!off_curve.is_on_curve());
+        let mut bad_curve = core.clone();
+        bad_curve.encs[2] = off_curve.into_projective();
+
+        for (bad, index) in [(&bad_enc, 3), (&bad_comm, 1), (&bad_both, 1), (&bad_curve, 2)].iter() {
+            let bytes = encode(bad);
+
+            // The unchecked path lets the point through; the checked path reports its index.
+            assert!(PVSSCore::<E>::deserialize_unchecked(&bytes[..]).unwrap() == **bad);
+            match PVSSCore::<E>::deserialize_subgroup_checked(&bytes[..]) {
+                Err(PVSSError::NotInSubgroup(j)) => assert_eq!(j, *index),
+                _ => panic!("expected NotInSubgroup"),
+            }
+        }
+    }
 }
@@ -0,0 +1,409 @@
+use crate::{
+    modified_scrape::{
+        config::Config,
+        errors::PVSSError,
+        poly::{lagrange_coefficients, msm},
+    },
+    Scalar,
+};
+
+use ark_ec::{msm::VariableBaseMSM, PairingEngine, ProjectiveCurve};
+use ark_ff::{batch_inversion, One, PrimeField, Zero};
+use ark_serialize::CanonicalSerialize;
+use ark_std::{collections::BTreeMap, vec::Vec};
+use blake2s_simd::Params;
+
+use ark_std::ops::Neg;
+
+
+const BEACON_PERSONALIZATION: &[u8] = b"OPTRBEAC";   // persona for hashing beacon outputs
+
+
+// Function for reconstructing g1^{p(0)} from decrypted shares g1^{p(j+1)}, keyed by participant id.
+// At least degree + 1 shares are required; the first degree + 1 of them are used.
+pub fn reconstruct<E: PairingEngine>(
+    shares: &BTreeMap<usize, E::G1Affine>,
+    conf: &Config<E>,
+) -> Result<E::G1Affine, PVSSError<E>> {
+    let needed = conf.degree + 1;
+
+    if shares.len() < needed {
+        return Err(PVSSError::InsufficientShares { needed, got: shares.len() });
+    }
+
+    let selected = shares.iter().take(needed).collect::<Vec<_>>();
+
+    let points = selected
+        .iter()
+        .map(|(id, _)| Scalar::<E>::from((**id as u64) + 1))
+        .collect::<Vec<_>>();
+    let coeffs = lagrange_coefficients::<E>(&points)?;
+
+    let bases = selected.iter().map(|(_, share)| **share).collect::<Vec<_>>();
+    let scalars = coeffs.iter().map(|lambda| lambda.into_repr()).collect::<Vec<_>>();
+
+    Ok(VariableBaseMSM::multi_scalar_mul(&bases, &scalars).into_affine())
+}
+
+
+// Function for reconstructing the secret p(0) from scalar shares p(j+1), keyed by participant
+// id, under a weighted threshold: the weights (keyed by participant id) of the supplied shares
+// must add up to at least total_threshold. The weights only gate reconstruction; since p is of
+// degree conf.degree, interpolation still needs degree + 1 distinct shares regardless of their
+// weight, so the weighted threshold should be set such that any set of shares meeting it holds
+// at least degree + 1 of them (otherwise InsufficientShares is returned).
+pub fn reconstruct_weighted<E: PairingEngine>(
+    shares: &BTreeMap<usize, Scalar<E>>,
+    weights: &BTreeMap<usize, u64>,
+    total_threshold: u64,
+    conf: &Config<E>,
+) -> Result<Scalar<E>, PVSSError<E>> {
+    let mut total = 0u64;
+    for id in shares.keys() {
+        let weight = weights.get(id).ok_or(PVSSError::InvalidParticipantId(*id))?;
+        total = total.saturating_add(*weight);
+    }
+
+    if total < total_threshold {
+        return Err(PVSSError::InsufficientWeight { threshold: total_threshold, got: total });
+    }
+
+    let needed = conf.degree + 1;
+    if shares.len() < needed {
+        return Err(PVSSError::InsufficientShares { needed, got: shares.len() });
+    }
+
+    let selected = shares.iter().take(needed).collect::<Vec<_>>();
+
+    let points = selected
+        .iter()
+        .map(|(id, _)| Scalar::<E>::from((**id as u64) + 1))
+        .collect::<Vec<_>>();
+    let coeffs = lagrange_coefficients::<E>(&points)?;
+
+    Ok(selected.iter().zip(coeffs.iter()).map(|((_, share), lambda)| **share * lambda).sum())
+}
+
+
+// Function for reconstructing g1^{p(0)} while discarding invalid decrypted shares.
+// A share from participant j is valid if e(share, g2) = e(g1, comms[j]); shares failing the
+// check (or with an id outside the commitment vector) are dropped before reconstruction, so
+// an InsufficientShares error reports the number of valid shares.
+pub fn reconstruct_robust<E: PairingEngine>(
+    shares: &BTreeMap<usize, E::G1Affine>,
+    comms: &Vec<E::G2Projective>,
+    conf: &Config<E>,
+) -> Result<E::G1Affine, PVSSError<E>> {
+    let valid = shares
+        .iter()
+        .filter(|(id, share)| {
+            comms.get(**id).map_or(false, |comm| {
+                let pairs = [
+                    ((**share).into(), conf.g2_prepared().clone()),
+                    (conf.srs.g1.neg().into(), comm.into_affine().into()),
+                ];
+
+                E::final_exponentiation(&E::miller_loop(pairs.iter())).map_or(false, |p| p.is_one())
+            })
+        })
+        .map(|(id, share)| (*id, *share))
+        .collect::<BTreeMap<_, _>>();
+
+    reconstruct(&valid, conf)
+}
+
+
+/* Struct LagrangeCache holds the Lagrange coefficients of a fixed set of participant ids
+   (i.e., of the evaluation points id + 1) at a fixed point, so that repeated interpolations
+   over the same committee do not recompute them. */
+#[derive(Clone, Debug)]
+pub struct LagrangeCache<E: PairingEngine> {
+    pub indices: Vec<usize>,         // the participant ids
+    pub point: Scalar<E>,            // the point interpolated at
+    coeffs: Vec<Scalar<E>>,          // lambda_j(point), in the order of the ids
+}
+
+impl<E: PairingEngine> LagrangeCache<E> {
+
+    // Function computing the coefficients lambda_j(x) = prod_{k != j} (x - x_k) / (x_j - x_k)
+    // at x = point, with all denominators inverted at once.
+    pub fn new(indices: &[usize], point: Scalar<E>) -> Result<Self, PVSSError<E>> {
+        let xs = indices.iter().map(|id| Scalar::<E>::from((*id as u64) + 1)).collect::<Vec<_>>();
+
+        let mut nums = Vec::with_capacity(xs.len());
+        let mut denoms = Vec::with_capacity(xs.len());
+        for (j, x_j) in xs.iter().enumerate() {
+            let mut num = Scalar::<E>::one();
+            let mut denom = Scalar::<E>::one();
+            for (k, x_k) in xs.iter().enumerate() {
+                if j != k {
+                    num *= point - x_k;
+                    denom *= *x_j - x_k;
+                }
+            }
+
+            // Repeated ids make the interpolation ill-defined.
+            if denom.is_zero() {
+                return Err(PVSSError::NonDistinctPointsError);
+            }
+            nums.push(num);
+            denoms.push(denom);
+        }
+
+        batch_inversion(&mut denoms);
+        let coeffs = nums.iter().zip(denoms.iter()).map(|(num, denom)| *num * denom).collect();
+
+        Ok(Self { indices: indices.to_vec(), point, coeffs })
+    }
+
+    // Method interpolating, at the cached point, the values g^{p(id + 1)} given in the order of
+    // the cached ids: returns g^{p(point)}.
+    pub fn combine(&self, values: &[E::G1Projective]) -> E::G1Projective {
+        debug_assert_eq!(values.len(), self.coeffs.len());
+
+        msm(values, &self.coeffs)
+    }
+
+    // Method returning the coefficients, in the order of the cached ids, for interpolating
+    // values of other groups.
+    pub fn coefficients(&self) -> &[Scalar<E>] {
+        &self.coeffs
+    }
+}
+
+
+// Function deriving the beacon output from the reconstructed secret g1^{p(0)}, as the hash
+// of e(g1^{p(0)}, g2_prime).
+pub fn beacon<E: PairingEngine>(
+    conf: &Config<E>,
+    secret: &E::G1Affine,
+) -> Result<[u8; 32], PVSSError<E>> {
+    let mut bytes = vec![];
+    E::pairing(*secret, conf.srs.g2_prime).serialize(&mut bytes)?;
+
+    let hash = Params::new()
+        .hash_length(32)
+        .personal(BEACON_PERSONALIZATION)
+        .to_state()
+        .update(&bytes)
+        .finalize();
+    let mut value = [0u8; 32];
+    value.copy_from_slice(hash.as_bytes());
+
+    Ok(value)
+}
+
+
+// Function checking a reconstructed secret g1^{p(0)} against the commitment g2^{p(0)}
+// interpolated from a core's commitments (see PVSSCore::interpolate_commitment_at_zero), i.e.,
+// that e(secret, g2) = e(g1, commitment). This lets verifiers that never see decrypted shares
+// check a claimed secret (and hence the beacon output derived from it).
+pub fn verify_secret_against_commitment<E: PairingEngine>(
+    conf: &Config<E>,
+    secret: &E::G1Affine,
+    commitment: &E::G2Projective,
+) -> bool {
+    E::pairing(*secret, conf.srs.g2) == E::pairing(conf.srs.g1, commitment.into_affine())
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{reconstruct, reconstruct_robust, reconstruct_weighted, verify_secret_against_commitment, LagrangeCache};
+    use crate::modified_scrape::{
+        config::Config,
+        decryption::decrypt_share,
+        errors::PVSSError,
+        participant::ParticipantId,
+        poly::Polynomial,
+        pvss::PVSSCore,
+        srs::SRS,
+    };
+    use crate::testing::fixtures::FixtureBuilder;
+    use crate::Scalar;
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+    use ark_ff::{PrimeField, Zero};
+    use ark_poly::{Polynomial as Poly, UVPolynomial};
+    use ark_std::collections::BTreeMap;
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_reconstruct() {
+        let rng = &mut thread_rng();
+        let t = 3;
+        let n = 10;
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+        let poly = Polynomial::<E>::rand(t, rng);
+
+        let shares = [0_usize, 3, 5, 7]
+            .iter()
+            .map(|&j| (j, conf.srs.g1.mul(poly.evaluate(&Scalar::<E>::from((j + 1) as u64)).into_repr()).into_affine()))
+            .collect::<BTreeMap<_, _>>();
+
+        let secret = reconstruct(&shares, &conf).unwrap();
+        assert_eq!(secret, conf.srs.g1.mul(poly[0].into_repr()).into_affine());
+
+        // Raw shortage: t shares are not enough.
+        let mut few = shares.clone();
+        few.remove(&7);
+        match reconstruct(&few, &conf) {
+            Err(PVSSError::InsufficientShares { needed, got }) => assert_eq!((needed, got), (t + 1, t)),
+            _ => panic!("expected InsufficientShares"),
+        }
+    }
+
+    #[test]
+    fn test_reconstruct_robust_filters_invalid() {
+        let rng = &mut thread_rng();
+        let t = 3;
+        let n = 10;
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+        let poly = Polynomial::<E>::rand(t, rng);
+
+        let evals = (0..n)
+            .map(|j| poly.evaluate(&Scalar::<E>::from((j + 1) as u64)))
+            .collect::<Vec<_>>();
+        let comms = evals.iter().map(|e| conf.srs.g2.mul(e.into_repr())).collect::<Vec<_>>();
+
+        let mut shares = (0..t + 2)
+            .map(|j| (j, conf.srs.g1.mul(evals[j].into_repr()).into_affine()))
+            .collect::<BTreeMap<_, _>>();
+
+        // Corrupt one share: t + 1 valid ones remain.
+        shares.insert(1, conf.srs.g1.mul(Scalar::<E>::from(7u64).into_repr()).into_affine());
+        let secret = reconstruct_robust(&shares, &comms, &conf).unwrap();
+        assert_eq!(secret, conf.srs.g1.mul(poly[0].into_repr()).into_affine());
+
+        // Filtered shortage: t + 2 shares supplied, but only t are valid.
+        shares.insert(2, conf.srs.g1.mul(Scalar::<E>::from(9u64).into_repr()).into_affine());
+        match reconstruct_robust(&shares, &comms, &conf) {
+            Err(PVSSError::InsufficientShares { needed, got }) => assert_eq!((needed, got), (t + 1, t)),
+            _ => panic!("expected InsufficientShares"),
+        }
+    }
+
+    #[test]
+    fn test_reconstruct_weighted() {
+        let rng = &mut thread_rng();
+        let t = 2;
+        let n = 6;
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+        let poly = Polynomial::<E>::rand(t, rng);
+
+        let weights = [5u64, 1, 1, 1, 4, 3].iter().cloned().enumerate().collect::<BTreeMap<_, _>>();
+        let threshold = 8;
+        let shares_of = |ids: &[usize]| {
+            ids.iter()
+                .map(|&j| (j, poly.evaluate(&Scalar::<E>::from((j + 1) as u64))))
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        // Sufficient weight: 5 + 1 + 3 >= 8.
+        assert_eq!(reconstruct_weighted(&shares_of(&[0, 2, 5]), &weights, threshold, &conf).unwrap(), poly[0]);
+
+        // Insufficient weight, despite enough shares: 1 + 1 + 1 + 4 < 8.
+        match reconstruct_weighted(&shares_of(&[1, 2, 3, 4]), &weights, threshold, &conf) {
+            Err(PVSSError::InsufficientWeight { threshold: w, got }) => assert_eq!((w, got), (threshold, 7)),
+            _ => panic!("expected InsufficientWeight"),
+        }
+
+        // Sufficient weight, but too few shares to interpolate: 5 + 4 >= 8.
+        match reconstruct_weighted(&shares_of(&[0, 4]), &weights, threshold, &conf) {
+            Err(PVSSError::InsufficientShares { needed, got }) => assert_eq!((needed, got), (t + 1, 2)),
+            _ => panic!("expected InsufficientShares"),
+        }
+
+        // Shares without a weight are rejected.
+        let mut unweighted = weights.clone();
+        unweighted.remove(&5);
+        match reconstruct_weighted(&shares_of(&[0, 2, 5]), &unweighted, threshold, &conf) {
+            Err(PVSSError::InvalidParticipantId(id)) => assert_eq!(id, 5),
+            _ => panic!("expected InvalidParticipantId"),
+        }
+    }
+
+    #[test]
+    fn test_lagrange_cache() {
+        let rng = &mut thread_rng();
+        let t = 3;
+        let n = 10;
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+
+        let ids = [1_usize, 4, 6, 9];
+        let at_zero = LagrangeCache::<E>::new(&ids, Scalar::<E>::from(0u64)).unwrap();
+        let at_five = LagrangeCache::<E>::new(&ids, Scalar::<E>::from(5u64)).unwrap();
+
+        // The cache is reused across several polynomials.
+        for _ in 0..3 {
+            let poly = Polynomial::<E>::rand(t, rng);
+            let shares = ids
+                .iter()
+                .map(|&j| (j, conf.srs.g1.mul(poly.evaluate(&Scalar::<E>::from((j + 1) as u64)).into_repr()).into_affine()))
+                .collect::<BTreeMap<_, _>>();
+            let values = shares.values().map(|share| share.into_projective()).collect::<Vec<_>>();
+
+            assert_eq!(at_zero.combine(&values).into_affine(), reconstruct(&shares, &conf).unwrap());
+            assert_eq!(at_five.combine(&values), conf.srs.g1.mul(poly.evaluate(&Scalar::<E>::from(5u64)).into_repr()));
+        }
+
+        // Repeated ids are rejected.
+        assert!(matches!(LagrangeCache::<E>::new(&[1, 4, 4], Scalar::<E>::from(0u64)), Err(PVSSError::NonDistinctPointsError)));
+    }
+
+    #[test]
+    fn test_commitment_at_zero() {
+        let t = 3;
+        let n = 10;
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let (conf, transcript) = (&fixture.config, &fixture.transcript);
+        let core = PVSSCore::<E>::new(
+            transcript.pvss_core.encs.iter().map(|enc| enc.into_projective()).collect(),
+            transcript.pvss_core.comms.iter().map(|comm| comm.into_projective()).collect(),
+        )
+        .unwrap();
+
+        // The interpolated commitment is the sum of the contributors' gs, whichever t + 1
+        // participants it is interpolated from.
+        let ids = |indices: &[u32]| indices.iter().map(|&index| ParticipantId(index)).collect::<Vec<_>>();
+        let commitment = core.interpolate_commitment_at_zero(&ids(&[0, 1, 2, 3])).unwrap();
+        assert_eq!(core.interpolate_commitment_at_zero(&ids(&[9, 2, 6, 4])).unwrap(), commitment);
+        let gs_total = transcript
+            .contributions
+            .values()
+            .fold(<E as PairingEngine>::G2Projective::zero(), |acc, proof| acc + proof.decomp_proof().gs.into_projective());
+        assert_eq!(commitment, gs_total);
+
+        // Ids outside the committee, and repeated ids, are rejected.
+        assert!(matches!(core.interpolate_commitment_at_zero(&ids(&[0, 1, 2, 10])), Err(PVSSError::InvalidParticipantId(10))));
+        assert!(matches!(core.interpolate_commitment_at_zero(&ids(&[0, 1, 2, 2])), Err(PVSSError::NonDistinctPointsError)));
+
+        // The secret reconstructed from the decrypted shares passes the cross-check.
+        let shares = fixture
+            .keypairs
+            .iter()
+            .enumerate()
+            .map(|(id, keys)| (id, decrypt_share(&transcript.pvss_core.encs[id], &keys.secret_key, id).unwrap()))
+            .collect::<BTreeMap<_, _>>();
+        let secret = reconstruct(&shares, conf).unwrap();
+        assert!(verify_secret_against_commitment(conf, &secret, &commitment));
+
+        // The secret of a single contributor, swapped in for the aggregated one, does not.
+        let swapped = conf.srs.g1.mul(fixture.secrets[0].p_0.into_repr()).into_affine();
+        assert!(!verify_secret_against_commitment(conf, &swapped, &commitment));
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{
+    modified_scrape::{
+        config::Config,
+        decryption::decrypt_share,
+        errors::PVSSError,
+        participant::ParticipantId,
+        pvss::AffinePVSSCore,
+        recon::LagrangeCache,
+    },
+    nizk::{dleq::{DLEQProof, srs::SRS as DLEQSRS}, scheme::NIZKProof, utils::errors::NIZKError},
+    Scalar,
+};
+use ark_ec::{PairingEngine, AffineCurve, ProjectiveCurve};
+use ark_ff::{PrimeField, UniformRand};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use ark_std::{collections::BTreeMap, vec::Vec};
+
+use rand::{CryptoRng, Rng};
+
+
+// Proof of a well-formed recovery mask: a DLEQ proof (across G1 and G2) that
+// log_{g1}(mask_g1) = log_{g2}(mask_g2).
+pub type RecoveryProof<E> = <DLEQProof<<E as PairingEngine>::G1Affine, <E as PairingEngine>::G2Affine> as NIZKProof>::Proof;
+
+// Struct MaskedPoint represents a G1 point ElGamal-encrypted under some public key pk with a
+// fresh mask r, i.e., (g1^r, g2^r, point * pk^r), along with a proof that both masks share r.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MaskedPoint<E: PairingEngine> {
+    pub mask_g1: E::G1Affine,       // g1^r
+    pub mask_g2: E::G2Affine,       // g2^r
+    pub point: E::G1Affine,         // the encrypted point, masked by pk^r
+    pub proof: RecoveryProof<E>,    // proof that mask_g1 and mask_g2 share their exponent
+}
+
+impl<E: PairingEngine> MaskedPoint<E> {
+
+    // Associated function for encrypting point under pk.
+    pub fn generate<R: Rng + CryptoRng>(
+        rng: &mut R,
+        conf: &Config<E>,
+        point: &E::G1Affine,
+        pk: &E::G1Affine,
+    ) -> Result<Self, PVSSError<E>> {
+        let r = Scalar::<E>::rand(rng);
+        let mask_g1 = conf.srs.g1.mul(r.into_repr()).into_affine();
+        let mask_g2 = conf.srs.g2.mul(r.into_repr()).into_affine();
+        let masked = (point.into_projective() + pk.mul(r.into_repr())).into_affine();
+
+        let dleq = DLEQProof { srs: DLEQSRS { g_public_key: conf.srs.g1, h_public_key: conf.srs.g2 } };
+        let proof = dleq.prove(rng, &r).map_err(|e| match e {
+            NIZKError::SerializationError(e) => PVSSError::SerializationError(e),
+            _ => PVSSError::NIZKProofDoesNotVerifyError,
+        })?;
+
+        Ok(MaskedPoint { mask_g1, mask_g2, point: masked, proof })
+    }
+
+    // Method checking that the encrypted point is g1^x, given the commitment g2^x: the masks
+    // must share their exponent r, and e(point, g2) = e(g1, comm) * e(pk, g2^r).
+    pub fn verify(&self, conf: &Config<E>, comm: &E::G2Affine, pk: &E::G1Affine) -> bool {
+        let dleq = DLEQProof { srs: DLEQSRS { g_public_key: conf.srs.g1, h_public_key: conf.srs.g2 } };
+
+        dleq.verify(&(self.mask_g1, self.mask_g2), &self.proof).is_ok()
+            && E::pairing(self.point, conf.srs.g2) == E::pairing(conf.srs.g1, *comm) * E::pairing(*pk, self.mask_g2)
+    }
+
+    // Method for decrypting the point with the secret key sk matching pk.
+    pub fn unmask(&self, sk: &Scalar<E>) -> E::G1Projective {
+        self.point.into_projective() - self.mask_g1.mul(sk.into_repr())
+    }
+}
+
+
+// Struct RecoveryShare represents the help of a node towards a target node that missed the
+// decryption phase: the helper's decrypted share g1^{p(j + 1)}, encrypted under the target's
+// public key.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct RecoveryShare<E: PairingEngine> {
+    pub helper_id: ParticipantId,   // id of the helping participant
+    pub masked: MaskedPoint<E>,     // the helper's decrypted share, encrypted under the target's key
+}
+
+impl<E: PairingEngine> RecoveryShare<E> {
+
+    // Associated function for generating the recovery share of helper_id towards the target
+    // whose public key is target_pk, from the helper's encrypted share in the given core.
+    pub fn generate<R: Rng + CryptoRng>(
+        rng: &mut R,
+        conf: &Config<E>,
+        core: &AffinePVSSCore<E>,
+        helper_id: ParticipantId,
+        helper_sk: &Scalar<E>,
+        target_pk: &E::G1Affine,
+    ) -> Result<Self, PVSSError<E>> {
+        let id = helper_id.index();
+        let enc = core.encs.get(id).ok_or(PVSSError::InvalidParticipantId(id))?;
+        let dec = decrypt_share(enc, helper_sk, id)?;
+        let masked = MaskedPoint::generate(rng, conf, &dec, target_pk)?;
+
+        Ok(RecoveryShare { helper_id, masked })
+    }
+
+    // Method for verifying the recovery share against the core and the target's public key.
+    pub fn verify(&self, conf: &Config<E>, core: &AffinePVSSCore<E>, target_pk: &E::G1Affine) -> Result<(), PVSSError<E>> {
+        verify_recovery_share(conf, core, target_pk, self)
+    }
+}
+
+
+// Function for verifying a recovery share: the masked share must open, under the target's key,
+// to the helper's committed share comm_j (see MaskedPoint::verify).
+pub fn verify_recovery_share<E: PairingEngine>(
+    conf: &Config<E>,
+    core: &AffinePVSSCore<E>,
+    target_pk: &E::G1Affine,
+    share: &RecoveryShare<E>,
+) -> Result<(), PVSSError<E>> {
+    let id = share.helper_id.index();
+    let comm = core.comms.get(id).ok_or(PVSSError::InvalidParticipantId(id))?;
+
+    if !share.masked.verify(conf, comm, target_pk) {
+        return Err(PVSSError::RecoveryShareVerificationFailed { participant_id: id });
+    }
+
+    Ok(())
+}
+
+
+// Function for recovering the decrypted share g1^{p(i + 1)} of the target node i from (verified)
+// recovery shares of degree + 1 distinct helpers: each helper's share is unmasked with the
+// target's secret key, and the results are interpolated at the target's evaluation point.
+// Shares beyond the first degree + 1 distinct helpers are ignored.
+pub fn recover<E: PairingEngine>(
+    conf: &Config<E>,
+    target_id: ParticipantId,
+    target_sk: &Scalar<E>,
+    shares: &[RecoveryShare<E>],
+) -> Result<E::G1Affine, PVSSError<E>> {
+    let needed = conf.degree + 1;
+
+    let unmasked = shares
+        .iter()
+        .map(|share| (share.helper_id.index(), share.masked.unmask(target_sk)))
+        .collect::<BTreeMap<_, _>>();
+    if unmasked.len() < needed {
+        return Err(PVSSError::InsufficientShares { needed, got: unmasked.len() });
+    }
+
+    let (ids, values): (Vec<_>, Vec<_>) = unmasked.into_iter().take(needed).unzip();
+    let cache = LagrangeCache::<E>::new(&ids, target_id.evaluation_point::<E>())?;
+
+    Ok(cache.combine(&values).into_affine())
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{recover, RecoveryShare};
+    use crate::modified_scrape::{decryption::decrypt_share, errors::PVSSErrorKind, participant::ParticipantId};
+    use crate::testing::fixtures::FixtureBuilder;
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ec::{AffineCurve, ProjectiveCurve};
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_recovery() {
+        const TARGET: usize = 5;   // the node that missed the decryption phase
+
+        let rng = &mut thread_rng();
+        let (t, n) = (3, 7);
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let (conf, core) = (&fixture.config, &fixture.transcript.pvss_core);
+        let target = &fixture.keypairs[TARGET];
+
+        // degree + 1 helpers (other than the target) help the target recover its share.
+        let helpers = [0, 1, 3, 6];
+        let shares = helpers
+            .iter()
+            .map(|id| {
+                let share = RecoveryShare::generate(
+                    rng,
+                    conf,
+                    core,
+                    ParticipantId::from_index(*id),
+                    &fixture.keypairs[*id].secret_key,
+                    &target.public_key,
+                )
+                .unwrap();
+                share.verify(conf, core, &target.public_key).unwrap();
+                share
+            })
+            .collect::<Vec<_>>();
+
+        let recovered = recover(conf, ParticipantId::from_index(TARGET), &target.secret_key, &shares).unwrap();
+        let direct = decrypt_share(&core.encs[TARGET], &target.secret_key, TARGET).unwrap();
+        assert_eq!(recovered, direct);
+
+        // A tampered share does not verify.
+        let mut tampered = shares[1].clone();
+        tampered.masked.point = (tampered.masked.point.into_projective() + conf.srs.g1.into_projective()).into_affine();
+        match tampered.verify(conf, core, &target.public_key) {
+            Err(err) => assert_eq!(err.kind(), PVSSErrorKind::RecoveryShareVerificationFailed { participant_id: 1 }),
+            Ok(()) => panic!("tampered recovery share went unnoticed"),
+        }
+
+        // Neither does a share meant for another target.
+        let other_pk = fixture.keypairs[2].public_key;
+        assert!(shares[0].verify(conf, core, &other_pk).is_err());
+
+        // degree shares, or a repeated helper, do not suffice.
+        let repeated = vec![shares[0].clone(), shares[0].clone(), shares[1].clone(), shares[2].clone()];
+        for few in [&shares[..t], &repeated[..]].iter() {
+            match recover(conf, ParticipantId::from_index(TARGET), &target.secret_key, few) {
+                Err(err) => assert_eq!(err.kind(), PVSSErrorKind::InsufficientShares { needed: t + 1, got: t }),
+                Ok(_) => panic!("recovered from {} distinct helpers", t),
+            }
+        }
+    }
+}
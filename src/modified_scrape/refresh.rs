@@ -0,0 +1,163 @@
+use crate::{
+    modified_scrape::{
+        config::Config,
+        decomp::Decomp,
+        decryption::decrypt_share,
+        errors::PVSSError,
+        participant::{ParticipantId, ParticipantRegistry},
+        poly::Polynomial,
+        pvss::{PVSSCore, DEFAULT_MIN_CHUNK},
+        share::{PVSSAggregatedShare, PVSSShare, SignedProof},
+    },
+    Scalar,
+    SecretKey,
+    Signature,
+};
+
+use ark_ec::{PairingEngine, AffineCurve, ProjectiveCurve};
+use ark_ff::Zero;
+use ark_poly::UVPolynomial;
+
+use rand::{CryptoRng, Rng};
+
+
+// Function for dealing a refresh share: a standard PVSS share of a random degree t polynomial
+// whose free term is zero. Aggregating the refresh shares of a committee yields a sharing of
+// zero, which re-randomizes every node's share without changing the shared secret.
+pub fn deal_refresh<E: PairingEngine, R: Rng + CryptoRng>(
+    rng: &mut R,
+    conf: &Config<E>,
+    registry: &ParticipantRegistry<E>,
+    my_id: usize,
+    private_key_ed: &SecretKey,
+) -> Result<PVSSShare<E>, PVSSError<E>> {
+    let mut poly = Polynomial::<E>::rand(conf.degree, rng);
+    poly.coeffs[0] = Scalar::<E>::zero();
+
+    let (pvss_core, _) = PVSSCore::deal_polynomial(&poly, conf, &registry.public_keys_sig()?, my_id, DEFAULT_MIN_CHUNK)?;
+
+    // The decomposition proof is on the zero free term, i.e., its gs is the identity.
+    let decomp_proof = Decomp::<E>::generate(rng, conf, &poly.coeffs[0])?;
+    let signature_on_decomp = Signature::new(&decomp_proof.digest(), private_key_ed);
+
+    Ok(PVSSShare {
+        participant_id: ParticipantId::from_index(my_id),   // my_id < n, as checked by deal_polynomial
+        degree: conf.degree,
+        num_participants: conf.num_participants,
+        config_digest: conf.digest(),
+        pvss_core,
+        signed_proof: SignedProof::new(decomp_proof, signature_on_decomp),
+    })
+}
+
+
+// Function for applying a refresh transcript to the stored (decrypted) share g1^{p(i + 1)} of
+// node my_id: the node's refresh share is decrypted and added to it. The transcript must have
+// been verified beforehand (e.g., with Verifier::verify_aggregated); here it is only checked to
+// share zero, i.e., that the gs of its contributions sum to the identity (GSCheckError).
+pub fn apply_refresh<E: PairingEngine>(
+    old_share: &E::G1Affine,
+    refresh_transcript: &PVSSAggregatedShare<E>,
+    my_id: ParticipantId,
+    sk: &Scalar<E>,
+) -> Result<E::G1Affine, PVSSError<E>> {
+    let gs_total = refresh_transcript
+        .contributions
+        .values()
+        .fold(E::G2Projective::zero(), |mut acc, proof| {
+            acc.add_assign_mixed(&proof.decomp_proof().gs);
+            acc
+        });
+    if !gs_total.is_zero() {
+        return Err(PVSSError::GSCheckError);
+    }
+
+    let id = my_id.index();
+    let enc = refresh_transcript.pvss_core.encs.get(id).ok_or(PVSSError::InvalidParticipantId(id))?;
+    let refresh = decrypt_share(enc, sk, id)?;
+
+    Ok((old_share.into_projective() + refresh.into_projective()).into_affine())
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{apply_refresh, deal_refresh};
+    use crate::modified_scrape::{
+        decryption::decrypt_share,
+        errors::PVSSErrorKind,
+        participant::ParticipantId,
+        recon::reconstruct,
+        roles::{OptrandScheme, Verifier},
+        share::PVSSAggregatedShare,
+    };
+    use crate::testing::fixtures::FixtureBuilder;
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_std::collections::BTreeMap;
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_refresh() {
+        let rng = &mut thread_rng();
+        let (t, n) = (3, 7);
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let (conf, core) = (&fixture.config, &fixture.transcript.pvss_core);
+        let scheme = OptrandScheme::new(conf.clone());
+
+        let old_shares = fixture
+            .keypairs
+            .iter()
+            .enumerate()
+            .map(|(id, keys)| (id, decrypt_share(&core.encs[id], &keys.secret_key, id).unwrap()))
+            .collect::<BTreeMap<_, _>>();
+        let secret = reconstruct(&old_shares, conf).unwrap();
+
+        // Every node deals a refresh share; each verifies as a standard share, and so does
+        // their aggregation.
+        let refresh_shares = fixture
+            .keypairs
+            .iter()
+            .enumerate()
+            .map(|(id, keys)| deal_refresh(rng, conf, &fixture.registry, id, &keys.secret_key_ed).unwrap())
+            .collect::<Vec<_>>();
+        for share in refresh_shares.iter() {
+            scheme.verify_share(rng, &fixture.registry, share).unwrap();
+        }
+        let refresh_transcript = PVSSAggregatedShare::aggregate_from_iter(t, n, refresh_shares.iter()).unwrap();
+        scheme.verify_aggregated(rng, &fixture.registry, &refresh_transcript).unwrap();
+
+        let new_shares = fixture
+            .keypairs
+            .iter()
+            .enumerate()
+            .map(|(id, keys)| {
+                let new_share = apply_refresh(&old_shares[&id], &refresh_transcript, ParticipantId::from_index(id), &keys.secret_key).unwrap();
+                assert_ne!(new_share, old_shares[&id]);
+                (id, new_share)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        // Any degree + 1 refreshed shares reconstruct the original secret.
+        assert_eq!(reconstruct(&new_shares, conf).unwrap(), secret);
+        let last = new_shares.iter().skip(n - t - 1).map(|(id, share)| (*id, *share)).collect();
+        assert_eq!(reconstruct(&last, conf).unwrap(), secret);
+
+        // Old shares are useless alongside refreshed ones.
+        let mixed = (0..=t)
+            .map(|id| (id, if id < 2 { old_shares[&id] } else { new_shares[&id] }))
+            .collect();
+        assert_ne!(reconstruct(&mixed, conf).unwrap(), secret);
+
+        // A transcript sharing a non-zero secret is not a refresh.
+        match apply_refresh(&old_shares[&0], &fixture.transcript, ParticipantId::from_index(0), &fixture.keypairs[0].secret_key) {
+            Err(err) => assert_eq!(err.kind(), PVSSErrorKind::GSCheckError),
+            Ok(_) => panic!("applied a non-refresh transcript"),
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use crate::{
+    modified_scrape::{
+        config::Config,
+        errors::PVSSError,
+        participant::ParticipantId,
+        poly::{ensure_degree, lagrange_interpolation_simple, msm, Polynomial},
+        pvss::AffinePVSSCore,
+        recon::LagrangeCache,
+        recovery::MaskedPoint,
+    },
+    Scalar,
+};
+
+use ark_ec::{PairingEngine, AffineCurve, ProjectiveCurve};
+use ark_ff::{PrimeField, Zero};
+use ark_poly::{Polynomial as Poly, UVPolynomial};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use ark_std::{collections::BTreeMap, vec::Vec};
+
+use rand::{CryptoRng, Rng};
+
+
+/* Struct ReshareDealing represents the resharing of the decrypted share g1^{p(i + 1)} of member
+   i of the old committee A to the new committee B: for a random polynomial r of B's degree
+   with r(0) = 0, member j of B receives g1^{p(i + 1) + r(j + 1)}, encrypted under its key, and
+   the dealing commits to r. */
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ReshareDealing<E: PairingEngine> {
+    pub dealer_id: ParticipantId,      // id of the dealer in committee A
+    pub comms: Vec<E::G2Affine>,       // commitments g2^{r(j + 1)}, for every member j of B
+    pub shares: Vec<MaskedPoint<E>>,   // the sub-shares, encrypted under the keys of B
+}
+
+impl<E: PairingEngine> ReshareDealing<E> {
+
+    // Method for verifying the dealing against the finalized core of A (whose commitment
+    // g2^{p(i + 1)} binds the dealer's share) and the public keys of B: the commitments must lie
+    // on a polynomial of B's degree that vanishes at zero, and every sub-share must match the
+    // dealer's commitment in A along with its own commitment.
+    pub fn verify<R: Rng + CryptoRng>(
+        &self,
+        rng: &mut R,
+        core_a: &AffinePVSSCore<E>,
+        conf_b: &Config<E>,
+        pks_b: &[E::G1Affine],
+    ) -> Result<(), PVSSError<E>> {
+        let id = self.dealer_id.index();
+        let comm_a = core_a.comms.get(id).ok_or(PVSSError::InvalidParticipantId(id))?.into_projective();
+
+        let n = conf_b.num_participants;
+        if self.shares.len() != n || self.comms.len() != n || pks_b.len() != n {
+            return Err(PVSSError::MismatchedCommitsEncryptionsParticipantsError(self.shares.len(), self.comms.len(), n));
+        }
+
+        let comms = self.comms.iter().map(|comm| comm.into_projective()).collect::<Vec<_>>();
+        if ensure_degree::<E, _>(rng, &comms, conf_b.degree as u64).is_err()
+            || !lagrange_interpolation_simple::<E>(&comms, conf_b.degree as u64)?.is_zero()
+            || self
+                .shares
+                .iter()
+                .zip(comms.iter())
+                .zip(pks_b.iter())
+                .any(|((share, comm), pk)| !share.verify(conf_b, &(*comm + comm_a).into_affine(), pk)) {
+            return Err(PVSSError::ReshareVerificationFailed { participant_id: id });
+        }
+
+        Ok(())
+    }
+}
+
+
+/* Struct ResharedCore is the outcome of a resharing to committee B: member j of B holds the
+   share g1^{q(j + 1)} of a polynomial q of B's degree with q(0) = p(0), encrypted under its key,
+   and comms commit to q as the commitments of a PVSS core do. */
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ResharedCore<E: PairingEngine> {
+    pub masks: Vec<E::G1Affine>,   // the combined masks g1^r of the encryptions
+    pub encs: Vec<E::G1Affine>,    // the encrypted shares g1^{q(j + 1)} * pk_j^r
+    pub comms: Vec<E::G2Affine>,   // commitments g2^{q(j + 1)}
+}
+
+impl<E: PairingEngine> ResharedCore<E> {
+
+    // Method for decrypting the share g1^{q(j + 1)} of member j of B with its secret key. The
+    // share is checked against its commitment, which rejects a wrong key.
+    pub fn decrypt(&self, conf_b: &Config<E>, my_id: ParticipantId, sk: &Scalar<E>) -> Result<E::G1Affine, PVSSError<E>> {
+        let id = my_id.index();
+        let (mask, enc, comm) = match (self.masks.get(id), self.encs.get(id), self.comms.get(id)) {
+            (Some(mask), Some(enc), Some(comm)) => (mask, enc, comm),
+            _ => return Err(PVSSError::InvalidParticipantId(id)),
+        };
+
+        let share = (enc.into_projective() - mask.mul(sk.into_repr())).into_affine();
+        if E::pairing(share, conf_b.srs.g2) != E::pairing(conf_b.srs.g1, *comm) {
+            return Err(PVSSError::DecryptionProofVerificationFailed { participant_id: id });
+        }
+
+        Ok(share)
+    }
+}
+
+
+// Function for resharing the decrypted share my_share = g1^{p(i + 1)} of member my_id of A to
+// the committee B, whose public keys are pks_b.
+pub fn reshare_deal<E: PairingEngine, R: Rng + CryptoRng>(
+    rng: &mut R,
+    conf_b: &Config<E>,
+    pks_b: &[E::G1Affine],
+    my_id: ParticipantId,
+    my_share: &E::G1Affine,
+) -> Result<ReshareDealing<E>, PVSSError<E>> {
+    if pks_b.len() != conf_b.num_participants {
+        return Err(PVSSError::LengthMismatchError);
+    }
+
+    let mut poly = Polynomial::<E>::rand(conf_b.degree, rng);
+    poly.coeffs[0] = Scalar::<E>::zero();
+
+    let mut comms = Vec::with_capacity(pks_b.len());
+    let mut shares = Vec::with_capacity(pks_b.len());
+    for (j, pk) in pks_b.iter().enumerate() {
+        let eval = poly.evaluate(&conf_b.point_for(j)?);
+        let sub_share = (my_share.into_projective() + conf_b.srs.g1.mul(eval.into_repr())).into_affine();
+
+        comms.push(conf_b.srs.g2.mul(eval.into_repr()).into_affine());
+        shares.push(MaskedPoint::generate(rng, conf_b, &sub_share, pk)?);
+    }
+
+    Ok(ReshareDealing { dealer_id: my_id, comms, shares })
+}
+
+
+// Function for combining (verified) dealings of degree_a + 1 distinct members of A into the
+// reshared core of B: the dealings are weighted by their dealers' Lagrange coefficients at zero
+// (w.r.t. A), so that the shares of B interpolate to A's secret g1^{p(0)}. Dealings beyond
+// the first degree_a + 1 distinct dealers are ignored.
+pub fn finalize_reshare<E: PairingEngine>(
+    conf_a: &Config<E>,
+    core_a: &AffinePVSSCore<E>,
+    conf_b: &Config<E>,
+    dealings: &[ReshareDealing<E>],
+) -> Result<ResharedCore<E>, PVSSError<E>> {
+    let needed = conf_a.degree + 1;
+
+    let mut by_dealer = BTreeMap::new();
+    for dealing in dealings {
+        by_dealer.entry(dealing.dealer_id.index()).or_insert(dealing);
+    }
+    if by_dealer.len() < needed {
+        return Err(PVSSError::InsufficientShares { needed, got: by_dealer.len() });
+    }
+
+    let (ids, dealings): (Vec<_>, Vec<_>) = by_dealer.into_iter().take(needed).unzip();
+    let cache = LagrangeCache::<E>::new(&ids, Scalar::<E>::zero())?;
+    let comms_a = ids
+        .iter()
+        .map(|id| core_a.comms.get(*id).map(|comm| comm.into_projective()).ok_or(PVSSError::InvalidParticipantId(*id)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let n = conf_b.num_participants;
+    for dealing in dealings.iter() {
+        if dealing.shares.len() != n || dealing.comms.len() != n {
+            return Err(PVSSError::MismatchedCommitsEncryptionsParticipantsError(dealing.shares.len(), dealing.comms.len(), n));
+        }
+    }
+
+    let mut core = ResharedCore { masks: Vec::with_capacity(n), encs: Vec::with_capacity(n), comms: Vec::with_capacity(n) };
+    for j in 0..n {
+        let masks = dealings.iter().map(|dealing| dealing.shares[j].mask_g1.into_projective()).collect::<Vec<_>>();
+        let encs = dealings.iter().map(|dealing| dealing.shares[j].point.into_projective()).collect::<Vec<_>>();
+        let comms = dealings
+            .iter()
+            .zip(comms_a.iter())
+            .map(|(dealing, comm_a)| dealing.comms[j].into_projective() + comm_a)
+            .collect::<Vec<_>>();
+
+        core.masks.push(msm(&masks, cache.coefficients()).into_affine());
+        core.encs.push(msm(&encs, cache.coefficients()).into_affine());
+        core.comms.push(msm(&comms, cache.coefficients()).into_affine());
+    }
+
+    Ok(core)
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{finalize_reshare, reshare_deal};
+    use crate::{
+        modified_scrape::{
+            config::Config,
+            decryption::decrypt_share,
+            errors::PVSSErrorKind,
+            participant::ParticipantId,
+            poly::lagrange_interpolation_simple,
+            recon::{beacon, reconstruct},
+        },
+        Scalar,
+    };
+    use crate::testing::fixtures::FixtureBuilder;
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+    use ark_ff::{PrimeField, UniformRand};
+    use ark_std::collections::BTreeMap;
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_reshare() {
+        let rng = &mut thread_rng();
+        let (t_a, n_a) = (2, 5);
+        let (t_b, n_b) = (3, 7);
+
+        // Committee A holds a finalized transcript, along with the decrypted shares of its members.
+        let fixture = FixtureBuilder::new(t_a, n_a).build::<E>().unwrap();
+        let (conf_a, core_a) = (&fixture.config, &fixture.transcript.pvss_core);
+        let shares_a = fixture
+            .keypairs
+            .iter()
+            .enumerate()
+            .map(|(id, keys)| (id, decrypt_share(&core_a.encs[id], &keys.secret_key, id).unwrap()))
+            .collect::<BTreeMap<_, _>>();
+        let secret = reconstruct(&shares_a, conf_a).unwrap();
+        let beacon_a = beacon(conf_a, &secret).unwrap();
+
+        // Committee B shares the SRS, but neither the size nor the threshold of A.
+        let conf_b = Config::new(conf_a.srs.clone(), t_b, n_b);
+        let sks_b = (0..n_b).map(|_| Scalar::<E>::rand(rng)).collect::<Vec<_>>();
+        let pks_b = sks_b.iter().map(|sk| conf_b.srs.g1.mul(sk.into_repr()).into_affine()).collect::<Vec<_>>();
+
+        let dealings = shares_a
+            .iter()
+            .map(|(id, share)| reshare_deal(rng, &conf_b, &pks_b, ParticipantId::from_index(*id), share).unwrap())
+            .collect::<Vec<_>>();
+        for dealing in dealings.iter() {
+            dealing.verify(rng, core_a, &conf_b, &pks_b).unwrap();
+        }
+
+        // A dealer resharing a share other than its own is caught.
+        let forged = reshare_deal(rng, &conf_b, &pks_b, ParticipantId::from_index(1), &shares_a[&0]).unwrap();
+        match forged.verify(rng, core_a, &conf_b, &pks_b) {
+            Err(err) => assert_eq!(err.kind(), PVSSErrorKind::ReshareVerificationFailed { participant_id: 1 }),
+            Ok(()) => panic!("forged resharing went unnoticed"),
+        }
+
+        // The reshared core commits to the secret committed to by A's core.
+        let core_b = finalize_reshare(conf_a, core_a, &conf_b, &dealings).unwrap();
+        let implied = |comms: &Vec<<E as PairingEngine>::G2Affine>, degree: usize| {
+            let comms = comms.iter().map(|comm| comm.into_projective()).collect();
+            lagrange_interpolation_simple::<E>(&comms, degree as u64).unwrap()
+        };
+        assert_eq!(implied(&core_b.comms, t_b), implied(&core_a.comms, t_a));
+
+        // Members of B decrypt their shares, from which B reconstructs A's secret and beacon.
+        let shares_b = sks_b
+            .iter()
+            .enumerate()
+            .map(|(id, sk)| (id, core_b.decrypt(&conf_b, ParticipantId::from_index(id), sk).unwrap()))
+            .collect::<BTreeMap<_, _>>();
+        let secret_b = reconstruct(&shares_b, &conf_b).unwrap();
+        assert_eq!(secret_b, secret);
+        assert_eq!(beacon(&conf_b, &secret_b).unwrap(), beacon_a);
+        let last = shares_b.iter().skip(n_b - t_b - 1).map(|(id, share)| (*id, *share)).collect();
+        assert_eq!(reconstruct(&last, &conf_b).unwrap(), secret);
+
+        // A wrong key does not decrypt, and degree_a dealings do not suffice.
+        assert!(core_b.decrypt(&conf_b, ParticipantId::from_index(0), &sks_b[1]).is_err());
+        match finalize_reshare(conf_a, core_a, &conf_b, &dealings[..t_a]) {
+            Err(err) => assert_eq!(err.kind(), PVSSErrorKind::InsufficientShares { needed: t_a + 1, got: t_a }),
+            Ok(_) => panic!("reshared from {} dealings", t_a),
+        }
+
+        // A truncated (unverified) dealing is rejected rather than indexed out of bounds.
+        let mut truncated = dealings.clone();
+        truncated[0].shares.pop();
+        match finalize_reshare(conf_a, core_a, &conf_b, &truncated) {
+            Err(err) => assert_eq!(err.kind(), PVSSErrorKind::MismatchedCommitsEncryptionsParticipantsError(n_b - 1, n_b, n_b)),
+            Ok(_) => panic!("reshared from a truncated dealing"),
+        }
+    }
+}
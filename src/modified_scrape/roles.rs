@@ -0,0 +1,229 @@
+use crate::{
+    modified_scrape::{
+        aggregator::PVSSAggregator,
+        config::Config,
+        decomp::Decomp,
+        errors::PVSSError,
+        participant::{ParticipantId, ParticipantRegistry},
+        pvss::{PVSSCore, PVSSShareSecrets},
+        share::{PVSSAggregatedShare, PVSSShare, SignedProof},
+    },
+    signature::schnorr::{SchnorrSignature, srs::SRS as SCHSRS},
+    SecretKey,
+    Signature,
+};
+
+use ark_ec::PairingEngine;
+
+use rand::{CryptoRng, Rng};
+
+
+// Trait Dealer captures the dealing side of a PVSS scheme.
+pub trait Dealer<E: PairingEngine> {
+    // Method for dealing a signed PVSS share, as participant my_id, to the registry's committee.
+    fn deal<R: Rng + CryptoRng>(
+        &self,
+        rng: &mut R,
+        registry: &ParticipantRegistry<E>,
+        my_id: usize,
+        private_key_ed: &SecretKey,
+    ) -> Result<(PVSSShare<E>, PVSSShareSecrets<E>), PVSSError<E>>;
+}
+
+// Trait Verifier captures the (public) verification side of a PVSS scheme.
+pub trait Verifier<E: PairingEngine> {
+    // Method for verifying a PVSS share issued by a member of the registry's committee.
+    fn verify_share<R: Rng + CryptoRng>(
+        &self,
+        rng: &mut R,
+        registry: &ParticipantRegistry<E>,
+        share: &PVSSShare<E>,
+    ) -> Result<(), PVSSError<E>>;
+
+    // Method for verifying an aggregated PVSS share against the registry's committee.
+    fn verify_aggregated<R: Rng + CryptoRng>(
+        &self,
+        rng: &mut R,
+        registry: &ParticipantRegistry<E>,
+        agg_share: &PVSSAggregatedShare<E>,
+    ) -> Result<(), PVSSError<E>>;
+}
+
+
+/* Struct OptrandScheme is the concrete (modified SCRAPE) PVSS scheme, parameterized by the
+   "global" configuration; it implements both the Dealer and the Verifier role. */
+#[derive(Clone)]
+pub struct OptrandScheme<E: PairingEngine> {
+    pub config: Config<E>,   // the "global" configuration parameters
+}
+
+impl<E: PairingEngine> OptrandScheme<E> {
+
+    // Function for creating the scheme under a given configuration.
+    pub fn new(config: Config<E>) -> Self {
+        Self { config }
+    }
+
+    // Method for dealing a signed PVSS share whose decomposition proof is bound to some
+    // context bytes (e.g., the previous epoch's output).
+    pub fn deal_with_context<R: Rng + CryptoRng>(
+        &self,
+        rng: &mut R,
+        registry: &ParticipantRegistry<E>,
+        my_id: usize,
+        private_key_ed: &SecretKey,
+        context: &[u8],
+    ) -> Result<(PVSSShare<E>, PVSSShareSecrets<E>), PVSSError<E>> {
+        // Create the core PVSSCore first.
+        let (pvss_core, secrets) = PVSSCore::deal(rng, &self.config, &registry.public_keys_sig()?, my_id)?;
+
+        // Generate and sign the decomposition proof.
+        let decomp_proof = Decomp::<E>::generate_with_context(rng, &self.config, &secrets.p_0, context)?;
+        let signature_on_decomp = Signature::new(&decomp_proof.digest(), private_key_ed);
+
+        let share = PVSSShare {
+            participant_id: ParticipantId::from_index(my_id),   // my_id < n, as checked by PVSSCore::deal
+            degree: self.config.degree,
+            num_participants: self.config.num_participants,
+            config_digest: self.config.digest(),
+            pvss_core,
+            signed_proof: SignedProof::new(decomp_proof, signature_on_decomp),
+        };
+
+        Ok((share, secrets))
+    }
+
+    // Utility method for creating an aggregator (with an empty transcript) to verify against.
+    pub(crate) fn aggregator(&self, registry: &ParticipantRegistry<E>) -> PVSSAggregator<E, SchnorrSignature<E::G1Affine>> {
+        PVSSAggregator {
+            config: self.config.clone(),
+            scheme_sig: SchnorrSignature { srs: SCHSRS { g_public_key: self.config.srs.g1 } },
+            participants: registry.participants.clone(),
+            aggregated_tx: PVSSAggregatedShare::empty(self.config.degree, self.config.num_participants),
+        }
+    }
+}
+
+impl<E: PairingEngine> Dealer<E> for OptrandScheme<E> {
+    fn deal<R: Rng + CryptoRng>(
+        &self,
+        rng: &mut R,
+        registry: &ParticipantRegistry<E>,
+        my_id: usize,
+        private_key_ed: &SecretKey,
+    ) -> Result<(PVSSShare<E>, PVSSShareSecrets<E>), PVSSError<E>> {
+        self.deal_with_context(rng, registry, my_id, private_key_ed, &[])
+    }
+}
+
+impl<E: PairingEngine> Verifier<E> for OptrandScheme<E> {
+    fn verify_share<R: Rng + CryptoRng>(
+        &self,
+        rng: &mut R,
+        registry: &ParticipantRegistry<E>,
+        share: &PVSSShare<E>,
+    ) -> Result<(), PVSSError<E>> {
+        self.aggregator(registry).share_verify(rng, share)
+    }
+
+    fn verify_aggregated<R: Rng + CryptoRng>(
+        &self,
+        rng: &mut R,
+        registry: &ParticipantRegistry<E>,
+        agg_share: &PVSSAggregatedShare<E>,
+    ) -> Result<(), PVSSError<E>> {
+        self.aggregator(registry).aggregation_verify(rng, agg_share)
+    }
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{Dealer, OptrandScheme, Verifier};
+    use crate::{
+        modified_scrape::{errors::PVSSError, participant::ParticipantRegistry, share::PVSSAggregatedShare},
+        testing::fixtures::FixtureBuilder,
+        SecretKey,
+    };
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use rand::{thread_rng, CryptoRng, Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
+
+    // A deal -> verify cycle written against the roles only.
+    fn deal_and_verify<S, R>(
+        scheme: &S,
+        rng: &mut R,
+        registry: &ParticipantRegistry<E>,
+        eddsa_sks: &[SecretKey],
+        degree: usize,
+    ) -> Result<PVSSAggregatedShare<E>, PVSSError<E>>
+    where
+        S: Dealer<E> + Verifier<E>,
+        R: Rng + CryptoRng,
+    {
+        let mut agg_share = PVSSAggregatedShare::empty(degree, registry.len());
+
+        for (id, sk_ed) in eddsa_sks.iter().enumerate() {
+            let (share, _) = scheme.deal(rng, registry, id, sk_ed)?;
+            scheme.verify_share(rng, registry, &share)?;
+            agg_share.fold_share(&share)?;
+        }
+
+        scheme.verify_aggregated(rng, registry, &agg_share)?;
+
+        Ok(agg_share)
+    }
+
+    #[test]
+    fn test_deal_verify_cycle() {
+        let rng = &mut thread_rng();
+        let t = 2;
+        let n = 5;
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let registry = fixture.registry;
+        let eddsa_sks = fixture.keypairs.into_iter().map(|keys| keys.secret_key_ed).collect::<Vec<_>>();
+
+        let scheme = OptrandScheme::new(fixture.config);
+        let agg_share = deal_and_verify(&scheme, rng, &registry, &eddsa_sks, t).unwrap();
+        assert_eq!(agg_share.contributions.len(), n);
+
+        // A share signed with someone else's key does not verify.
+        let (share, _) = scheme.deal(rng, &registry, 0, &eddsa_sks[1]).unwrap();
+        match scheme.verify_share(rng, &registry, &share) {
+            Err(PVSSError::SignatureVerificationFailed { participant_id }) => assert_eq!(participant_id, 0),
+            _ => panic!("expected SignatureVerificationFailed"),
+        }
+
+        // A fresh empty aggregate is rejected, rather than passing vacuously.
+        let empty = PVSSAggregatedShare::<E>::empty(t, n);
+        assert!(matches!(scheme.verify_aggregated(rng, &registry, &empty), Err(PVSSError::EmptyTranscript)));
+        assert!(matches!(empty.verify_contributions_bound(&scheme.config, &registry), Err(PVSSError::EmptyTranscript)));
+    }
+
+    #[test]
+    fn test_deal_with_seeded_rng() {
+        let t = 2;
+        let n = 5;
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let (registry, sk_ed) = (&fixture.registry, &fixture.keypairs[1].secret_key_ed);
+        let scheme = OptrandScheme::new(fixture.config.clone());
+
+        // Dealing draws all of its randomness from the injected rng: equal seeds give equal
+        // shares (and secrets), which verify as any other.
+        let deal = |seed: [u8; 32]| scheme.deal(&mut ChaChaRng::from_seed(seed), registry, 1, sk_ed).unwrap();
+        let (share, secrets) = deal([7u8; 32]);
+        let (replayed, replayed_secrets) = deal([7u8; 32]);
+        assert!(share == replayed);
+        assert_eq!(secrets.p_0, replayed_secrets.p_0);
+        scheme.verify_share(&mut ChaChaRng::from_seed([8u8; 32]), registry, &share).unwrap();
+
+        let (other, _) = deal([9u8; 32]);
+        assert!(share != other);
+    }
+}
@@ -1,58 +1,629 @@
 use crate::{
     modified_scrape::{
+        checked::CountingReader,
         config::Config,
         errors::PVSSError,
-        pvss::PVSSCore,
-        decomp::DecompProof,
+        participant::{ParticipantId, ParticipantRegistry},
+        pvss::{AffinePVSSCore, PVSSCore},
+        decomp::{joint_context, Blake2sDigestHasher, DecompProof, DefaultDigestHasher, DigestHasher},
     },
+    Digest,
+    fingerprint,
     PublicKey,
+    SecretKey,
     Signature,
 };
 
-use ark_ec::PairingEngine;
+use ark_ec::{AffineCurve, PairingEngine};
+use ark_ff::Zero;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError, Read, Write};
-use ark_std::collections::BTreeMap;
+#[cfg(feature = "std")]
+use once_cell::sync::OnceCell;
+#[cfg(not(feature = "std"))]
+use once_cell::unsync::OnceCell;   // without std, the digest cache (hence SignedProof) is not Sync
 
-use std::io::Cursor;
+use ark_std::{boxed::Box, collections::BTreeMap, convert::TryFrom, fmt, io, mem::size_of, string::{String, ToString}, vec::Vec};
+use blake2s_simd::State;
 
 
 /* Struct SignedProof represents a pair consisting of a decomposition proof along with
-   a signature on it. */
-#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, PartialEq)]
+   a signature on it. The digest of the decomposition proof (i.e., the signed message) is
+   computed once and cached; the proof is therefore only reachable through SignedProof::new
+   and the decomp_proof accessor, so that it cannot change after the digest is cached. The
+   cache is not serialized, and does not take part in comparisons. */
+#[derive(Clone)]
 pub struct SignedProof<E>
 where
     E: PairingEngine,
 {
-    pub decomp_proof: DecompProof<E>,     // proof of knowledge of shared secret
+    decomp_proof: DecompProof<E>,         // proof of knowledge of shared secret
     pub signature_on_decomp: Signature,   // EdDSA-signed knowledge proof
+    digest: OnceCell<Digest>,             // cached digest of decomp_proof
+}
+
+impl<E: PairingEngine> PartialEq for SignedProof<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.decomp_proof == other.decomp_proof && self.signature_on_decomp == other.signature_on_decomp
+    }
+}
+
+// Debug prints fingerprints of the proof and the signature.
+impl<E: PairingEngine> fmt::Debug for SignedProof<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SignedProof")
+            .field("decomp_proof", &format_args!("#{}", fingerprint(&self.decomp_proof)))
+            .field("signature_on_decomp", &format_args!("#{}", fingerprint(&self.signature_on_decomp)))
+            .finish()
+    }
+}
+
+impl<E: PairingEngine> CanonicalSerialize for SignedProof<E> {
+    fn serialize<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        self.decomp_proof.serialize(&mut writer)?;
+        self.signature_on_decomp.serialize(&mut writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.decomp_proof.serialized_size() + self.signature_on_decomp.serialized_size()
+    }
+
+    fn serialize_uncompressed<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        self.decomp_proof.serialize_uncompressed(&mut writer)?;
+        self.signature_on_decomp.serialize_uncompressed(&mut writer)
+    }
+
+    fn uncompressed_size(&self) -> usize {
+        self.decomp_proof.uncompressed_size() + self.signature_on_decomp.uncompressed_size()
+    }
+}
+
+impl<E: PairingEngine> CanonicalDeserialize for SignedProof<E> {
+    fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let decomp_proof = DecompProof::deserialize(&mut reader)?;
+        let signature_on_decomp = Signature::deserialize(&mut reader)?;
+
+        Ok(Self::new(decomp_proof, signature_on_decomp))
+    }
+
+    fn deserialize_uncompressed<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let decomp_proof = DecompProof::deserialize_uncompressed(&mut reader)?;
+        let signature_on_decomp = Signature::deserialize_uncompressed(&mut reader)?;
+
+        Ok(Self::new(decomp_proof, signature_on_decomp))
+    }
+
+    fn deserialize_unchecked<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let decomp_proof = DecompProof::deserialize_unchecked(&mut reader)?;
+        let signature_on_decomp = Signature::deserialize_unchecked(&mut reader)?;
+
+        Ok(Self::new(decomp_proof, signature_on_decomp))
+    }
 }
 
 impl<E: PairingEngine> SignedProof<E> {
-    // Method enabling verification of signed proofs.
-    fn verify(&mut self, conf: &Config<E>, pk_sig: &PublicKey) -> Result<(), PVSSError<E>> {
-        // Verify the NIZK proof
-        self.decomp_proof.verify(&conf).unwrap();
+    // Constructor of a signed proof; the digest is computed on first use.
+    pub fn new(decomp_proof: DecompProof<E>, signature_on_decomp: Signature) -> Self {
+        Self { decomp_proof, signature_on_decomp, digest: OnceCell::new() }
+    }
+
+    // Accessor for the (immutable) decomposition proof.
+    pub fn decomp_proof(&self) -> &DecompProof<E> {
+        &self.decomp_proof
+    }
+
+    // Method returning the cached digest of the decomposition proof.
+    pub fn digest(&self) -> &Digest {
+        self.digest.get_or_init(|| self.decomp_proof.digest_with::<DefaultDigestHasher>())
+    }
+
+    // Method returning the message covered by the signature, i.e., the cached digest.
+    pub fn message(&self) -> &[u8] {
+        self.digest().as_ref()
+    }
+
+    // Method enabling verification of signed proofs issued by participant_id.
+    // A faulty NIZK and a faulty signature are reported as distinct errors.
+    pub fn verify(&self, conf: &Config<E>, pk_sig: &PublicKey, participant_id: ParticipantId) -> Result<(), PVSSError<E>> {
+        self.verify_nizk(conf, participant_id)?;
+        self.verify_signature(self.message(), pk_sig, participant_id)
+    }
+
+    // Method enabling verification of signed proofs whose signature covers the digest computed
+    // by the hasher H (i.e., proofs generated by Decomp::generate_signed::<_, H>).
+    pub fn verify_with<H: DigestHasher>(&self, conf: &Config<E>, pk_sig: &PublicKey, participant_id: ParticipantId) -> Result<(), PVSSError<E>> {
+        self.verify_nizk(conf, participant_id)?;
 
         // Verify the signature on the NIZK proof
-        self.signature_on_decomp.verify(&mut self.decomp_proof.digest(), &pk_sig).unwrap();
+        let message = self.decomp_proof.digest_with::<H>();
+        self.verify_signature(message.as_ref(), pk_sig, participant_id)
+    }
+
+    // Method returning the exact bytes that the signature is expected to cover,
+    // i.e., the digest of the decomposition proof.
+    pub fn signed_message(&self) -> Result<Vec<u8>, PVSSError<E>> {
+        Ok(self.message().to_vec())
+    }
+
+    // Method for verifying the signature over the given message, after asserting that the
+    // message is exactly the one returned by signed_message.
+    pub fn verify_signed_message(&self, message: &[u8], pk_sig: &PublicKey, participant_id: ParticipantId) -> Result<(), PVSSError<E>> {
+        if message != &self.signed_message()?[..] {
+            return Err(PVSSError::SignatureVerificationFailed { participant_id: participant_id.index() });
+        }
+
+        self.verify_signature(message, pk_sig, participant_id)
+    }
+
+    // Utility method for verifying the NIZK proof.
+    fn verify_nizk(&self, conf: &Config<E>, participant_id: ParticipantId) -> Result<(), PVSSError<E>> {
+        if self.decomp_proof.verify(&conf).is_err() {
+            return Err(PVSSError::NIZKProofDoesNotVerify { participant_id: participant_id.index() });
+        }
+
+        Ok(())
+    }
+
+    // Utility method for verifying the signature over the given message.
+    fn verify_signature(&self, message: &[u8], pk_sig: &PublicKey, participant_id: ParticipantId) -> Result<(), PVSSError<E>> {
+        let participant_id = participant_id.index();
+        let digest = Digest::try_from(message)
+            .map_err(|_| PVSSError::SignatureVerificationFailed { participant_id })?;
+
+        if self.signature_on_decomp.verify(&digest, &pk_sig).is_err() {
+            return Err(PVSSError::SignatureVerificationFailed { participant_id });
+        }
 
         Ok(())
     }
+
+    // Deserialization reporting the failing field and byte offset.
+    pub fn deserialize_checked<R: Read>(reader: R) -> Result<Self, PVSSError<E>> {
+        Self::read_checked(&mut CountingReader::new(reader))
+    }
+
+    // Reads a SignedProof off a (possibly shared) counting reader.
+    pub(crate) fn read_checked<R: Read>(reader: &mut CountingReader<R>) -> Result<Self, PVSSError<E>> {
+        let decomp_proof = DecompProof::read_checked(reader)?;
+        let signature_on_decomp = reader.read_field("signature_on_decomp")?;
+
+        Ok(SignedProof::new(decomp_proof, signature_on_decomp))
+    }
 }
 
 /* PVSSShare represents a PVSSCore instance that has been augmented to include the origin's id,
-   as well as a signature on the decomposition proof included in the core PVSS share. */
-#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, PartialEq)]
+   as well as a signature on the decomposition proof included in the core PVSS share.
+   Note that the commitments cannot be dropped from the wire format: comm_j = g2^{p(j+1)} lives
+   in G2, whereas the encryptions pk_j^{p(j+1)} live in G1 and the decomposition proof only
+   fixes g2^{p(0)}, so verifiers have no way of recomputing them. Moreover, the commitments are
+   the pairing inputs of both the encryption check and the degree (dual code) check.
+   The share also carries the degree and committee size it was dealt under, as well as the
+   digest of that configuration (see Config::digest), so that it is self-describing and cannot
+   be pooled into an aggregate of a different configuration, even one with equal (t, n). */
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Debug, PartialEq)]
 pub struct PVSSShare<E>
 where
     E: PairingEngine,
 {
-    pub participant_id: usize,            // issuer of this PVSS share
+    pub participant_id: ParticipantId,    // issuer of this PVSS share
+    pub degree: usize,                    // polynomial degree (t) the share was dealt under
+    pub num_participants: usize,          // committee size (n) the share was dealt under
+    pub config_digest: Digest,            // digest of the configuration the share was dealt under
     pub pvss_core: PVSSCore<E>,           // "core" of the PVSS share
     pub signed_proof: SignedProof<E>,     // signed proof of decomposition
 }
 
-/* Struct PVSSAggregatedShare represents an aggregation of PVSS shares. */
+impl<E: PairingEngine> PVSSShare<E> {
+    // Method for verifying the share's core length and its signed proof against the issuer's
+    // EdDSA public key.
+    pub fn verify(&self, conf: &Config<E>, pk_sig: &PublicKey) -> Result<(), PVSSError<E>> {
+        if self.degree != conf.degree || self.num_participants != conf.num_participants {
+            return Err(PVSSError::TranscriptDifferentConfig(
+                conf.degree,
+                self.degree,
+                conf.num_participants,
+                self.num_participants,
+            ));
+        }
+        if self.config_digest != conf.digest() {
+            return Err(PVSSError::ConfigMismatch);
+        }
+
+        self.check_core_length(conf.num_participants)?;
+        self.signed_proof.verify(conf, pk_sig, self.participant_id)
+    }
+
+    // Method checking that the core holds exactly one encryption and one commitment per
+    // participant, so that it can be safely indexed.
+    pub fn check_core_length(&self, num_participants: usize) -> Result<(), PVSSError<E>> {
+        for len in [self.pvss_core.encs.len(), self.pvss_core.comms.len()].iter() {
+            if *len != num_participants {
+                return Err(PVSSError::WrongCoreLength(*len, num_participants));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Method returning the encryption at index 0 of the core (None for an empty core). Note
+    // that this is participant 0's encryption of p(1): the free term p(0) is never encrypted,
+    // only committed to by the gs of the decomposition proof.
+    pub fn first_encryption(&self) -> Option<E::G1Projective> {
+        self.pvss_core.encs.first().copied()
+    }
+
+    // Method returning the BLAKE2s digest of the share's serialization, which identifies the
+    // share when advertising or requesting it (see Message::Have and Message::Want).
+    pub fn digest(&self) -> [u8; 32] {
+        let mut bytes = vec![];
+        self.serialize(&mut bytes).unwrap();
+
+        Blake2sDigestHasher::hash(&bytes)
+    }
+
+    // Method returning a one-line summary of the share for structured logs, as key=value pairs
+    // with fingerprints of the group elements.
+    pub fn to_hex_summary(&self) -> String {
+        format!(
+            "participant_id={} encs={}#{} comms={}#{} signed_proof=#{}",
+            self.participant_id,
+            self.pvss_core.encs.len(),
+            fingerprint(&self.pvss_core.encs),
+            self.pvss_core.comms.len(),
+            fingerprint(&self.pvss_core.comms),
+            fingerprint(&self.signed_proof),
+        )
+    }
+
+    // Deserialization reporting the failing field and byte offset.
+    pub fn deserialize_checked<R: Read>(reader: R) -> Result<Self, PVSSError<E>> {
+        let mut reader = CountingReader::new(reader);
+
+        let participant_id = reader.read_field("participant_id")?;
+        let degree = reader.read_field("degree")?;
+        let num_participants = reader.read_field("num_participants")?;
+        let config_digest = reader.read_field("config_digest")?;
+        let pvss_core = PVSSCore::read_checked(&mut reader)?;
+        let signed_proof = SignedProof::read_checked(&mut reader)?;
+
+        Ok(PVSSShare { participant_id, degree, num_participants, config_digest, pvss_core, signed_proof })
+    }
+}
+
+// Largest supported contributor id, as ids are stored in 16 bits.
+pub const MAX_CONTRIBUTOR_ID: usize = u16::MAX as usize;
+
+
+/* Struct Contributions maps contributor ids to their signed proofs. Entries are kept in a vector
+   sorted by id, which is much more compact than a BTreeMap; it serializes exactly like a
+   BTreeMap<ParticipantId, SignedProof<E>>, i.e., as len || id_1 || proof_1 || ... || id_len || proof_len. */
+#[derive(Clone, PartialEq)]
+pub struct Contributions<E>
+where
+    E: PairingEngine,
+{
+    entries: Vec<(u16, SignedProof<E>)>,   // (id, signed proof) pairs, sorted by id
+}
+
+impl<E: PairingEngine> Default for Contributions<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: PairingEngine> Contributions<E> {
+    // Create a new empty map.
+    pub fn new() -> Self {
+        Contributions { entries: vec![] }
+    }
+
+    // Method returning the number of contributions.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    // Method returning whether there is no contribution.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // Utility method locating id: Ok(position) if present, Err(insertion point) otherwise.
+    fn search(&self, id: ParticipantId) -> Result<usize, usize> {
+        if id.index() > MAX_CONTRIBUTOR_ID {
+            return Err(self.entries.len());
+        }
+
+        self.entries.binary_search_by_key(&(id.0 as u16), |(key, _)| *key)
+    }
+
+    // Method returning the signed proof of contributor id, if any.
+    pub fn get(&self, id: &ParticipantId) -> Option<&SignedProof<E>> {
+        self.search(*id).ok().map(|i| &self.entries[i].1)
+    }
+
+    // Method returning a mutable reference to the signed proof of contributor id, if any.
+    pub fn get_mut(&mut self, id: &ParticipantId) -> Option<&mut SignedProof<E>> {
+        let i = self.search(*id).ok()?;
+        Some(&mut self.entries[i].1)
+    }
+
+    // Method returning whether contributor id is present.
+    pub fn contains_key(&self, id: &ParticipantId) -> bool {
+        self.search(*id).is_ok()
+    }
+
+    // Method inserting the signed proof of contributor id; returns the replaced proof, if any.
+    // Ids exceeding MAX_CONTRIBUTOR_ID are rejected (InvalidParticipantId).
+    pub fn insert(&mut self, id: ParticipantId, proof: SignedProof<E>) -> Result<Option<SignedProof<E>>, PVSSError<E>> {
+        if id.index() > MAX_CONTRIBUTOR_ID {
+            return Err(PVSSError::InvalidParticipantId(id.index()));
+        }
+
+        match self.search(id) {
+            Ok(i) => Ok(Some(ark_std::mem::replace(&mut self.entries[i].1, proof))),
+            Err(i) => {
+                self.entries.insert(i, (id.0 as u16, proof));
+                Ok(None)
+            }
+        }
+    }
+
+    // Method removing the signed proof of contributor id, if any.
+    pub fn remove(&mut self, id: &ParticipantId) -> Option<SignedProof<E>> {
+        let i = self.search(*id).ok()?;
+        Some(self.entries.remove(i).1)
+    }
+
+    // Method iterating over the contributor ids, in increasing order.
+    pub fn keys(&self) -> impl Iterator<Item = ParticipantId> + '_ {
+        self.entries.iter().map(|(id, _)| ParticipantId::from(u32::from(*id)))
+    }
+
+    // Method iterating over the signed proofs, by increasing contributor id.
+    pub fn values(&self) -> impl Iterator<Item = &SignedProof<E>> {
+        self.entries.iter().map(|(_, proof)| proof)
+    }
+
+    // Method iterating over (id, signed proof) pairs, by increasing contributor id.
+    pub fn iter(&self) -> impl Iterator<Item = (ParticipantId, &SignedProof<E>)> {
+        self.entries.iter().map(|(id, proof)| (ParticipantId::from(u32::from(*id)), proof))
+    }
+
+    // Method merging two maps in linear time; on overlapping ids, the preferred entry is kept
+    // (see prefer_other), so that merging is commutative.
+    pub fn merge(self, other: Self) -> Self {
+        let mut entries = Vec::with_capacity(self.entries.len() + other.entries.len());
+        let mut other = other.entries.into_iter().peekable();
+
+        for (id, mut proof) in self.entries {
+            while let Some((other_id, _)) = other.peek() {
+                if *other_id > id {
+                    break;
+                }
+                let (other_id, other_proof) = other.next().unwrap();
+                if other_id < id {
+                    entries.push((other_id, other_proof));
+                } else if prefer_other(&proof, &other_proof) {
+                    proof = other_proof;
+                }
+            }
+            entries.push((id, proof));
+        }
+        entries.extend(other);
+
+        Contributions { entries }
+    }
+
+    // Method returning an estimate (in bytes) of the memory held by the map's entries.
+    pub fn memory_estimate(&self) -> usize {
+        self.entries.capacity() * size_of::<(u16, SignedProof<E>)>()
+    }
+}
+
+impl<E: PairingEngine> CanonicalSerialize for Contributions<E> {
+    fn serialize<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        (self.entries.len() as u64).serialize(&mut writer)?;
+        for (id, proof) in self.entries.iter() {
+            (*id as usize).serialize(&mut writer)?;
+            proof.serialize(&mut writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn serialized_size(&self) -> usize {
+        8 + self.entries.iter().map(|(_, proof)| 8 + proof.serialized_size()).sum::<usize>()
+    }
+
+    fn serialize_uncompressed<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        (self.entries.len() as u64).serialize_uncompressed(&mut writer)?;
+        for (id, proof) in self.entries.iter() {
+            (*id as usize).serialize_uncompressed(&mut writer)?;
+            proof.serialize_uncompressed(&mut writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn uncompressed_size(&self) -> usize {
+        8 + self.entries.iter().map(|(_, proof)| 8 + proof.uncompressed_size()).sum::<usize>()
+    }
+}
+
+impl<E: PairingEngine> CanonicalDeserialize for Contributions<E> {
+    fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let len = u64::deserialize(&mut reader)?;
+
+        let mut contributions = Self::new();
+        for _ in 0..len {
+            let id = ParticipantId::deserialize(&mut reader)?;
+            contributions
+                .insert(id, SignedProof::deserialize(&mut reader)?)
+                .map_err(|_| SerializationError::InvalidData)?;
+        }
+
+        Ok(contributions)
+    }
+
+    fn deserialize_uncompressed<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let len = u64::deserialize_uncompressed(&mut reader)?;
+
+        let mut contributions = Self::new();
+        for _ in 0..len {
+            let id = ParticipantId::deserialize_uncompressed(&mut reader)?;
+            contributions
+                .insert(id, SignedProof::deserialize_uncompressed(&mut reader)?)
+                .map_err(|_| SerializationError::InvalidData)?;
+        }
+
+        Ok(contributions)
+    }
+}
+
+
+const MERKLE_LEAF_TAG: &[u8] = b"OPTRAND-MERKLE-LEAF";   // domain separator of contribution leaves
+const MERKLE_NODE_TAG: &[u8] = b"OPTRAND-MERKLE-NODE";   // domain separator of inner nodes
+
+/* Struct MerkleProof models the path from the leaf of a contribution to the root of the Merkle
+   tree over the contributions of an aggregated PVSS share (see contributions_root). Leaves are
+   ordered by contributor id, and a node without a sibling is carried up to the next level as is. */
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Debug, PartialEq)]
+pub struct MerkleProof {
+    pub index: usize,             // position of the leaf, by increasing contributor id
+    pub num_leaves: usize,        // number of contributions
+    pub siblings: Vec<Digest>,    // hashes of the siblings on the path, from the leaf up
+}
+
+impl MerkleProof {
+    // Method computing the root that the path leads to from leaf. Returns None if the path does
+    // not fit the position of the leaf (too few or too many siblings, or an index out of range).
+    pub fn root_from(&self, leaf: &Digest) -> Option<Digest> {
+        if self.index >= self.num_leaves {
+            return None;
+        }
+
+        let (mut node, mut index, mut width) = (leaf.clone(), self.index, self.num_leaves);
+        let mut siblings = self.siblings.iter();
+        while width > 1 {
+            if index ^ 1 < width {
+                let sibling = siblings.next()?;
+                node = if index % 2 == 0 { merkle_node(&node, sibling) } else { merkle_node(sibling, &node) };
+            }
+            index /= 2;
+            width = (width + 1) / 2;
+        }
+
+        if siblings.next().is_some() {
+            return None;
+        }
+
+        Some(node)
+    }
+}
+
+// Utility function hashing the contribution of participant id into its leaf.
+fn merkle_leaf<E: PairingEngine>(id: ParticipantId, signed_proof: &SignedProof<E>) -> Digest {
+    let mut bytes = MERKLE_LEAF_TAG.to_vec();
+    bytes.extend_from_slice(&u64::from(id.0).to_le_bytes());
+    signed_proof.serialize(&mut bytes).unwrap();
+
+    Digest(Blake2sDigestHasher::hash(&bytes))
+}
+
+// Utility function hashing two sibling nodes into their parent.
+fn merkle_node(left: &Digest, right: &Digest) -> Digest {
+    let mut state = Blake2sDigestHasher::state();
+    state.update(MERKLE_NODE_TAG);
+    state.update(&left.0);
+    state.update(&right.0);
+
+    Digest(Blake2sDigestHasher::finalize(&state))
+}
+
+// Utility function computing the root of the Merkle tree over leaves, along with the siblings on
+// the path of the leaf at index, if any. No leaves yield the zero digest.
+fn merkle_root_and_path(leaves: Vec<Digest>, index: Option<usize>) -> (Digest, Vec<Digest>) {
+    let (mut level, mut index, mut siblings) = (leaves, index, vec![]);
+    if level.is_empty() {
+        return (Digest::default(), siblings);
+    }
+
+    while level.len() > 1 {
+        if let Some(i) = index {
+            if let Some(sibling) = level.get(i ^ 1) {
+                siblings.push(sibling.clone());
+            }
+            index = Some(i / 2);
+        }
+
+        level = level
+            .chunks(2)
+            .map(|pair| if pair.len() == 2 { merkle_node(&pair[0], &pair[1]) } else { pair[0].clone() })
+            .collect();
+    }
+
+    (level.pop().unwrap(), siblings)
+}
+
+// Function for light clients auditing a single dealer: checks that the contribution (id,
+// signed_proof) is a member of the aggregated PVSS share whose contributions_root is root, and
+// that the signed proof is valid (NIZK and signature under pk_sig), without the whole share.
+pub fn verify_membership_and_proof<E: PairingEngine>(
+    root: &[u8; 32],
+    id: ParticipantId,
+    signed_proof: &SignedProof<E>,
+    merkle_proof: &MerkleProof,
+    conf: &Config<E>,
+    pk_sig: &PublicKey,
+) -> Result<(), PVSSError<E>> {
+    if merkle_proof.root_from(&merkle_leaf(id, signed_proof)) != Some(Digest(*root)) {
+        return Err(PVSSError::InvalidMembershipProof(id.index()));
+    }
+
+    signed_proof.verify(conf, pk_sig, id)
+}
+
+
+/* Struct PeerId identifies a peer of the network (e.g., by the hash of its transport key). It
+   names the relayer of a contribution in a Provenance record. */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PeerId(pub [u8; 32]);
+
+/* Struct Provenance records, for the contributions of an aggregated PVSS share, the peer that
+   relayed each of them, so that relayers can be held accountable if the transcript later proves
+   faulty. It is local metadata rather than part of the cryptographic object: it serializes to
+   nothing (hence does not affect transcript_id), deserializes as empty, and does not take part
+   in comparisons. */
+#[derive(Clone, Debug, Default)]
+pub struct Provenance(pub BTreeMap<ParticipantId, PeerId>);
+
+impl PartialEq for Provenance {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl CanonicalSerialize for Provenance {
+    fn serialize<W: Write>(&self, _writer: W) -> Result<(), SerializationError> {
+        Ok(())
+    }
+
+    fn serialized_size(&self) -> usize {
+        0
+    }
+}
+
+impl CanonicalDeserialize for Provenance {
+    fn deserialize<R: Read>(_reader: R) -> Result<Self, SerializationError> {
+        Ok(Self::default())
+    }
+}
+
+
+/* Struct PVSSAggregatedShare represents an aggregation of PVSS shares. It carries the digest of
+   the configuration its contributions were dealt under, so that transcripts of different
+   configurations (e.g., SRSs of different chains) with equal (t, n) cannot be merged. An
+   aggregate without contributions is not bound to any configuration yet, and adopts the digest
+   of the first transcript merged into it. */
 #[derive(CanonicalSerialize, CanonicalDeserialize, Clone, PartialEq)]
 pub struct PVSSAggregatedShare<E>
 where
@@ -60,40 +631,158 @@ where
 {
     pub num_participants: usize,
     pub degree: usize,
-    pub pvss_core: PVSSCore<E>,                           // "core" of the aggregated PVSS sharing
-    pub contributions: BTreeMap<usize, SignedProof<E>>,   // combination of the three following fields
+    pub config_digest: Digest,                 // digest of the configuration of the contributions
+    pub pvss_core: AffinePVSSCore<E>,          // "core" of the aggregated PVSS sharing
+    pub contributions: Contributions<E>,       // combination of the three following fields
+    pub provenance: Provenance,                // relayers of the contributions (not serialized)
 
-    // Using a BTreeMap saves us from having to manually manage three vectors instead:
+    // Using a Contributions map saves us from having to manually manage three vectors instead:
     // pub id_vec: Vec<usize>,                     // vector of participant ids whose shares have been pooled together
     // pub decomp_proofs: Vec<DecompProof<E>>,     // accumulation of decomposition proofs
     // pub signatures_on_decomps: Vec<Signature>,  // accumulation of signatures on decomposition proofs
 }
 
 
+/* Struct HashingWriter forwards writes to an inner writer, and feeds the bytes that the inner
+   writer accepted to a hashing state. */
+struct HashingWriter<'a, W: Write> {
+    inner: &'a mut W,   // the underlying writer
+    state: State,       // the state hashing the bytes written so far
+}
+
+impl<'a, W: Write> Write for HashingWriter<'a, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.state.update(&buf[..written]);
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+
+// Debug prints the contributor ids instead of the contributions, and a compact core.
+impl<E: PairingEngine> fmt::Debug for PVSSAggregatedShare<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PVSSAggregatedShare")
+            .field("num_participants", &self.num_participants)
+            .field("degree", &self.degree)
+            .field("pvss_core", &self.pvss_core)
+            .field("contributors", &self.contributor_ids())
+            .finish()
+    }
+}
+
+
 // Utility function for buffering a decomposition proof into a buffer and obtaining a reference
 // to said buffer.
 pub fn message_from_pi_i<E: PairingEngine>(pi_i: DecompProof<E>) -> Result<Vec<u8>, PVSSError<E>> {
-    let mut message_writer = Cursor::new(vec![]);
-    pi_i.serialize(&mut message_writer)?;
-    Ok(message_writer.get_ref().to_vec())
+    let mut message = vec![];
+    pi_i.serialize(&mut message)?;
+    Ok(message)
+}
+
+
+// Utility function resolving a conflict between two signed proofs for the same contributor
+// (with equal gs, e.g., the same proof signed twice): the one whose serialization is
+// lexicographically smaller is preferred, so that aggregation does not depend on the order in
+// which transcripts are combined. Returns whether b is preferred over a.
+fn prefer_other<E: PairingEngine>(a: &SignedProof<E>, b: &SignedProof<E>) -> bool {
+    let bytes = |proof: &SignedProof<E>| {
+        let mut bytes = vec![];
+        proof.serialize(&mut bytes).unwrap();
+        bytes
+    };
+
+    bytes(b) < bytes(a)
 }
 
 
 impl<E: PairingEngine> PVSSAggregatedShare<E>
 {
     // Function for generating a new (empty) PVSSAggregatedShare instance.
+    // An empty aggregate is structurally valid, but never has a quorum (is_recoverable() is false).
+    // Its configuration digest is left zero until a transcript is merged into it.
     pub fn empty(degree: usize, num_participants: usize) -> Self {
         Self {
 	    num_participants,
 	    degree,
-	    pvss_core: PVSSCore::empty(num_participants),
-	    contributions: BTreeMap::new(),
+	    config_digest: Digest::default(),
+	    pvss_core: AffinePVSSCore::empty(num_participants),
+	    contributions: Contributions::new(),
+	    provenance: Provenance::default(),
+        }
+    }
+
+    // Function for aggregating the shares yielded by shares, starting from an empty aggregate.
+    // No shares yield PVSSAggregatedShare::empty(degree, num_participants) rather than an error.
+    pub fn aggregate_from_iter<'a, I>(degree: usize, num_participants: usize, shares: I) -> Result<Self, PVSSError<E>>
+    where
+        I: IntoIterator<Item = &'a PVSSShare<E>>,
+    {
+        let mut aggregated = Self::empty(degree, num_participants);
+
+        for share in shares {
+            aggregated.fold_share(share)?;
         }
+
+        Ok(aggregated)
+    }
+
+    // Deserialization reporting the failing field and byte offset.
+    pub fn deserialize_checked<R: Read>(reader: R) -> Result<Self, PVSSError<E>> {
+        let mut reader = CountingReader::new(reader);
+
+        let num_participants = reader.read_field("num_participants")?;
+        let degree = reader.read_field("degree")?;
+        let config_digest = reader.read_field("config_digest")?;
+        let pvss_core = AffinePVSSCore::read_checked(&mut reader)?;
+
+        // Contributions are encoded as len || id_1 || proof_1 || ... || id_len || proof_len.
+        // There is at most one contribution per participant.
+        let offset = reader.offset();
+        let len: u64 = reader.read_field("contributions")?;
+        if len > num_participants as u64 {
+            return Err(PVSSError::DeserializationFailed {
+                field: "contributions",
+                offset,
+                source: SerializationError::InvalidData,
+            });
+        }
+
+        let mut contributions = Contributions::new();
+        for _ in 0..len {
+            let offset = reader.offset();
+            let id: ParticipantId = reader.read_field("contributions")?;
+            if id.index() > MAX_CONTRIBUTOR_ID {
+                return Err(PVSSError::DeserializationFailed {
+                    field: "contributions",
+                    offset,
+                    source: SerializationError::InvalidData,
+                });
+            }
+            contributions.insert(id, SignedProof::read_checked(&mut reader)?)?;
+        }
+
+        Ok(PVSSAggregatedShare { num_participants, degree, config_digest, pvss_core, contributions, provenance: Provenance::default() })
     }
 
     // Method for aggregating two PVSS aggregated shares.
     // Returns the resulting aggregated PVSS share.
     pub fn aggregate(&self, other: &Self) -> Result<Self, PVSSError<E>> {
+        self.aggregate_opts(other, false)
+    }
+
+    // Method for aggregating two PVSS aggregated shares, optionally skipping the commitment
+    // equality check on contributions present in both.
+    // Safety contract for fast_merge = true: the caller guarantees that the contributor id sets
+    // of self and other are disjoint (as is the case when collecting fresh shares). Overlapping
+    // ids are then only caught by a debug assertion; in release builds the preferred signed proof
+    // is kept unchecked, and the cores of both contributions are added.
+    pub fn aggregate_opts(&self, other: &Self, fast_merge: bool) -> Result<Self, PVSSError<E>> {
 	// Ensure that both PVSS aggregated shares are under a common configuration.
         if self.degree != other.degree || self.num_participants != other.num_participants {
             return Err(PVSSError::TranscriptDifferentConfig(
@@ -103,37 +792,53 @@ impl<E: PairingEngine> PVSSAggregatedShare<E>
                 other.num_participants,
             ));
         }
+        let config_digest = self.merged_config_digest(&other.config_digest, other.contributions.is_empty())?;
+
+        // A fresh aggregate adds nothing: other's core and contributions are adopted as they are.
+        if self.is_fresh() {
+            return Ok(Self {
+                num_participants: self.num_participants,
+                degree: self.degree,
+                config_digest,
+                pvss_core: self.pvss_core.aggregate(&other.pvss_core)?,   // other's core, after the length checks
+                contributions: other.contributions.clone(),
+                provenance: other.provenance.clone(),
+            });
+        }
 
-	// Combine contributions of self and other into a single BTreeMap.
+	// Combine contributions of self and other into a single map.
         let contributions = (0..self.num_participants)   // this is: n x amortized O(1)
+            .map(ParticipantId::from_index)
             .map(
                 |i| match (self.contributions.get(&i), other.contributions.get(&i)) {
                     (Some(a), Some(b)) => {
-                        if a.decomp_proof.gs != b.decomp_proof.gs {
+                        if fast_merge {
+                            debug_assert!(false, "fast_merge requires disjoint contributor ids, found {} in both", i);
+                        } else if a.decomp_proof.gs != b.decomp_proof.gs {
                             return Err(PVSSError::TranscriptDifferentCommitments);
                         }
-                        // Only keep a's signed proof
-                        let signed_proof = SignedProof {
-                            decomp_proof: a.decomp_proof,
-                            signature_on_decomp: a.signature_on_decomp.clone(),
-                        };
-                        Ok(Some((i, signed_proof)))
+                        // Keep the preferred of the two signed proofs
+                        Ok(Some((i, if prefer_other(a, b) { b.clone() } else { a.clone() })))
                     }
                     (Some(a), None) => Ok(Some((i, a.clone()))),
                     (None, Some(b)) => Ok(Some((i, b.clone()))),
                     (None, None) => Ok(None),
                 },
             )
-            .collect::<Result<Vec<_>, _>>()?
-            .into_iter()
-            .filter_map(|e| e)
-            .collect::<Vec<_>>();
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut merged = Contributions::new();
+        for (id, proof) in contributions.into_iter().flatten() {
+            merged.insert(id, proof)?;
+        }
 
         let aggregated_share = Self {
             num_participants: self.num_participants,
 	    degree: self.degree,
-            pvss_core: self.pvss_core.aggregate(&other.pvss_core).unwrap(),   // aggregate the two cores of PVSS shares
-            contributions: contributions.into_iter().collect(),
+            config_digest,
+            pvss_core: self.pvss_core.aggregate(&other.pvss_core)?,   // aggregate the two cores of PVSS shares
+            contributions: merged,
+            provenance: self.merged_provenance(other),
         };
 
         // Return the aggregate of the two aggregated PVSS shares.
@@ -143,456 +848,2141 @@ impl<E: PairingEngine> PVSSAggregatedShare<E>
     // Method for aggregating a PVSS share to an aggregated PVSS share.
     // Returns the resulting aggregated PVSS share.
     pub fn aggregate_pvss_share(&self, other: &PVSSShare<E>) -> Result<Self, PVSSError<E>> {
+	    // Reject contributor ids outside the committee before building on them.
+	    self.check_participant_id(other.participant_id)?;
+
 	    // Convert other from a PVSSShare instance into a PVSSAggregatedShare instance.
-	    let mut contribs = BTreeMap::new();
-	    contribs.insert(other.participant_id, SignedProof{ decomp_proof: other.signed_proof.decomp_proof,
-							   signature_on_decomp: other.signed_proof.signature_on_decomp});
+	    let mut contribs = Contributions::new();
+	    contribs.insert(other.participant_id, other.signed_proof.clone())?;
 
+	    // The share's own configuration is kept, so that aggregate rejects a mismatch.
 	    let other_agg_share = Self {
-            num_participants: self.num_participants,
-            degree: self.degree,
-            pvss_core: other.pvss_core.clone(),
+            num_participants: other.num_participants,
+            degree: other.degree,
+            config_digest: other.config_digest.clone(),
+            pvss_core: AffinePVSSCore::from_projective(&other.pvss_core),
             contributions: contribs,
+            provenance: Provenance::default(),
         };
 
 	    // Return the aggregate of the two aggregated PVSS shares.
 	    self.aggregate(&other_agg_share)
     }
-}
-
 
-/* Unit tests: */
+    // Same as aggregate_pvss_share, additionally recording peer as the relayer of the share's
+    // contribution (see Provenance). A contribution already relayed by another peer keeps its
+    // first relayer, unless the share's signed proof is the one kept in its place.
+    pub fn aggregate_pvss_share_with_peer(&self, other: &PVSSShare<E>, peer: PeerId) -> Result<Self, PVSSError<E>> {
+        let mut aggregated = self.aggregate_pvss_share(other)?;
 
-#[cfg(test)]
-mod test {
+        if aggregated.contributions.get(&other.participant_id) == Some(&other.signed_proof) {
+            aggregated.provenance.0.entry(other.participant_id).or_insert(peer);
+        }
 
-    use crate::signature::scheme::SignatureScheme;
-    use crate::signature::utils::tests::check_serialization;
+        Ok(aggregated)
+    }
 
-    use crate::{modified_scrape::share::PVSSCore, generate_production_keypair};
-    use crate::modified_scrape::{srs::SRS, config::Config, share::SignedProof};
-    use crate::modified_scrape::{decomp::Decomp, poly::Polynomial as Poly};
-    use crate::Scalar;
-    use crate::Signature;
-    use crate::signature::schnorr::{SchnorrSignature, srs::SRS as SCHSRS};
-    use crate::modified_scrape::share::PVSSAggregatedShare;
+    // Method returning the peer recorded as the relayer of the contribution of id, if any.
+    pub fn relayer(&self, id: ParticipantId) -> Option<&PeerId> {
+        self.provenance.0.get(&id)
+    }
 
-    use ark_ec::{PairingEngine, AffineCurve, ProjectiveCurve};
-    use ark_ff::{PrimeField, Zero};
-    use ark_poly::{Polynomial, UVPolynomial};
-    use ark_std::UniformRand;
-    use ark_std::collections::BTreeMap;
+    // Consuming variant of aggregate: contributions are moved rather than cloned, and the sum is
+    // accumulated into whichever of the two cores has the larger allocation. On overlapping ids,
+    // the preferred signed proof is kept, as in aggregate.
+    pub fn aggregate_owned(self, other: Self) -> Result<Self, PVSSError<E>> {
+        if self.degree != other.degree || self.num_participants != other.num_participants {
+            return Err(PVSSError::TranscriptDifferentConfig(
+                self.degree,
+                other.degree,
+                self.num_participants,
+                other.num_participants,
+            ));
+        }
+        let config_digest = self.merged_config_digest(&other.config_digest, other.contributions.is_empty())?;
+        let provenance = self.merged_provenance(&other);
+
+        for (id, b) in other.contributions.iter() {
+            if let Some(a) = self.contributions.get(&id) {
+                if a.decomp_proof.gs != b.decomp_proof.gs {
+                    return Err(PVSSError::TranscriptDifferentCommitments);
+                }
+            }
+        }
+
+        // A fresh aggregate adds nothing: other's core and contributions are adopted as they are.
+        if self.is_fresh() {
+            let mut pvss_core = other.pvss_core;
+            pvss_core.add_assign(&self.pvss_core)?;   // only checks the lengths, as self's core is zero
+
+            return Ok(Self {
+                num_participants: self.num_participants,
+                degree: self.degree,
+                config_digest,
+                pvss_core,
+                contributions: other.contributions,
+                provenance,
+            });
+        }
+
+        let capacity = |core: &AffinePVSSCore<E>| core.encs.capacity() + core.comms.capacity();
+        let pvss_core = if capacity(&self.pvss_core) >= capacity(&other.pvss_core) {
+            let mut core = self.pvss_core;
+            core.add_assign(&other.pvss_core)?;
+            core
+        } else {
+            let mut core = other.pvss_core;
+            core.add_assign(&self.pvss_core)?;
+            core
+        };
+
+        // Merge the two maps, keeping the preferred entry on overlapping ids.
+        let contributions = self.contributions.merge(other.contributions);
+
+        Ok(Self { num_participants: self.num_participants, degree: self.degree, config_digest, pvss_core, contributions, provenance })
+    }
+
+    // Consuming variant of aggregate_pvss_share: the share's core is added into self's core in
+    // place, and its signed proof is moved into the aggregate.
+    pub fn aggregate_pvss_share_owned(mut self, share: PVSSShare<E>) -> Result<Self, PVSSError<E>> {
+        let config_digest = self.check_share_config(&share)?;
+
+        let PVSSShare { participant_id, pvss_core, signed_proof, .. } = share;
+
+        if let Some(existing) = self.contributions.get(&participant_id) {
+            if existing.decomp_proof.gs != signed_proof.decomp_proof.gs {
+                return Err(PVSSError::TranscriptDifferentCommitments);
+            }
+        }
+
+        self.pvss_core.add_assign_projective(&pvss_core)?;
+        self.config_digest = config_digest;
+
+        // As in aggregate, the preferred of the two signed proofs is kept.
+        if self.contributions.get(&participant_id).map_or(true, |existing| prefer_other(existing, &signed_proof)) {
+            self.contributions.insert(participant_id, signed_proof)?;
+            self.provenance.0.remove(&participant_id);
+        }
+
+        Ok(self)
+    }
+
+    // Method for removing a contribution (e.g., of a dealer later proven faulty) from the aggregate.
+    // The caller must supply the original PVSS share, whose core is subtracted from the aggregated
+    // core; the share must match the signed proof stored for id.
+    // Returns the resulting aggregated PVSS share.
+    pub fn remove_contribution(&self, id: ParticipantId, share: &PVSSShare<E>) -> Result<Self, PVSSError<E>> {
+        let stored = self.contributions
+            .get(&id)
+            .ok_or(PVSSError::InvalidParticipantId(id.index()))?;
+
+        if share.participant_id != id || *stored != share.signed_proof {
+            return Err(PVSSError::TranscriptDifferentCommitments);
+        }
+
+        let negated_core = PVSSCore::from_parts(
+            share.pvss_core.encs.iter().map(|e| -*e).collect(),
+            share.pvss_core.comms.iter().map(|c| -*c).collect(),
+        );
+
+        let mut pvss_core = self.pvss_core.clone();
+        pvss_core.add_assign_projective(&negated_core)?;
+
+        let mut contributions = self.contributions.clone();
+        contributions.remove(&id);
+
+        let mut provenance = self.provenance.clone();
+        provenance.0.remove(&id);
+
+        Ok(Self {
+            num_participants: self.num_participants,
+            degree: self.degree,
+            config_digest: self.config_digest.clone(),
+            pvss_core,
+            contributions,
+            provenance,
+        })
+    }
+
+    // Method for folding a PVSS share into this aggregated PVSS share in place.
+    // Unlike aggregate_pvss_share, the share's core is added directly into self's core
+    // and only its signed proof is cloned, so no temporary aggregated share is built.
+    pub fn fold_share(&mut self, other: &PVSSShare<E>) -> Result<(), PVSSError<E>> {
+        // Reject shares dealt under another configuration, and conflicting contributions,
+        // before mutating self.
+        let config_digest = self.check_share_config(other)?;
+
+        if let Some(existing) = self.contributions.get(&other.participant_id) {
+            if existing.decomp_proof.gs != other.signed_proof.decomp_proof.gs {
+                return Err(PVSSError::TranscriptDifferentCommitments);
+            }
+        }
+
+        // Add the incoming core into ours.
+        self.pvss_core.add_assign_projective(&other.pvss_core)?;
+        self.config_digest = config_digest;
+
+        // As in aggregate, the preferred of the two signed proofs is kept.
+        if self.contributions.get(&other.participant_id).map_or(true, |existing| prefer_other(existing, &other.signed_proof)) {
+            self.contributions.insert(other.participant_id, other.signed_proof.clone())?;
+            self.provenance.0.remove(&other.participant_id);
+        }
+
+        Ok(())
+    }
+
+    // Method returning the provenance of the aggregate of self with other: each contribution
+    // keeps the relayer recorded on the side whose signed proof is kept (see prefer_other).
+    fn merged_provenance(&self, other: &Self) -> Provenance {
+        if self.provenance.0.is_empty() && other.provenance.0.is_empty() {
+            return Provenance::default();
+        }
+
+        let mut provenance = self.provenance.clone();
+        for (id, b) in other.contributions.iter() {
+            if self.contributions.get(&id).map_or(true, |a| prefer_other(a, b)) {
+                match other.provenance.0.get(&id) {
+                    Some(peer) => provenance.0.insert(id, *peer),
+                    None => provenance.0.remove(&id),
+                };
+            }
+        }
+
+        provenance
+    }
+
+    // Method returning whether self is a fresh aggregate, i.e., one without contributions whose
+    // core is the canonical empty core (as created by empty()).
+    fn is_fresh(&self) -> bool {
+        self.contributions.is_empty() && self.pvss_core.is_zero()
+    }
+
+    // Method checking that id is the id of a participant of the committee, i.e., id < n.
+    fn check_participant_id(&self, id: ParticipantId) -> Result<(), PVSSError<E>> {
+        if id.index() >= self.num_participants {
+            return Err(PVSSError::InvalidParticipantId(id.index()));
+        }
+
+        Ok(())
+    }
+
+    // Method checking that a PVSS share was dealt under the same configuration as this aggregated
+    // PVSS share. Returns the configuration digest of the aggregate once the share is folded in.
+    fn check_share_config(&self, share: &PVSSShare<E>) -> Result<Digest, PVSSError<E>> {
+        self.check_participant_id(share.participant_id)?;
+        if self.degree != share.degree || self.num_participants != share.num_participants {
+            return Err(PVSSError::TranscriptDifferentConfig(
+                self.degree,
+                share.degree,
+                self.num_participants,
+                share.num_participants,
+            ));
+        }
+
+        self.merged_config_digest(&share.config_digest, false)
+    }
+
+    // Method returning the configuration digest of the aggregate of self with a transcript
+    // produced under config_digest. An aggregate without contributions is not bound to any
+    // configuration, and takes on the digest of the other transcript.
+    fn merged_config_digest(&self, config_digest: &Digest, other_is_empty: bool) -> Result<Digest, PVSSError<E>> {
+        if self.contributions.is_empty() {
+            return Ok(config_digest.clone());
+        }
+        if !other_is_empty && *config_digest != self.config_digest {
+            return Err(PVSSError::ConfigMismatch);
+        }
+
+        Ok(self.config_digest.clone())
+    }
+
+    // Method for folding every share yielded by iter (e.g., a channel's backlog) via fold_share.
+    // Returns the number of shares folded (0 for an empty iter, leaving self untouched); stops at the first share that fails to fold, reporting
+    // its error along with the number of shares folded before it.
+    pub fn aggregate_drain<I: Iterator<Item = PVSSShare<E>>>(&mut self, iter: I) -> Result<usize, PVSSError<E>> {
+        let mut folded = 0;
+
+        for share in iter {
+            self.fold_share(&share)
+                .map_err(|e| PVSSError::DrainInterrupted { folded, source: Box::new(e) })?;
+            folded += 1;
+        }
+
+        Ok(folded)
+    }
+
+    // Method returning an estimate (in bytes) of the memory held by the aggregated PVSS share.
+    pub fn memory_estimate(&self) -> usize {
+        size_of::<Self>()
+            + self.pvss_core.encs.capacity() * size_of::<E::G1Affine>()
+            + self.pvss_core.comms.capacity() * size_of::<E::G2Affine>()
+            + self.contributions.memory_estimate()
+    }
+
+    // Method returning the ids of the contributors to the aggregated PVSS share, in ascending order.
+    pub fn contributor_ids(&self) -> Vec<ParticipantId> {
+        self.contributions.keys().collect()
+    }
+
+    // Method returning an identifier of the aggregated PVSS share: the BLAKE2s digest of its
+    // serialization, so any change to the core or the contributions changes it.
+    pub fn transcript_id(&self) -> Digest {
+        let mut bytes = vec![];
+        self.serialize(&mut bytes).unwrap();
+
+        Digest(Blake2sDigestHasher::hash(&bytes))
+    }
+
+    // Method returning the root of the Merkle tree over the contributions, against which a single
+    // contribution can be checked with a membership_proof. Unlike transcript_id, it does not
+    // cover the core; no contributions yield the zero digest.
+    pub fn contributions_root(&self) -> Digest {
+        let leaves = self.contributions.iter().map(|(id, proof)| merkle_leaf(id, proof)).collect();
+
+        merkle_root_and_path(leaves, None).0
+    }
+
+    // Method returning the Merkle proof that the contribution of participant id is a member of
+    // the aggregated PVSS share (see verify_membership_and_proof), if id contributed.
+    pub fn membership_proof(&self, id: ParticipantId) -> Option<MerkleProof> {
+        let index = self.contributions.keys().position(|key| key == id)?;
+        let leaves = self.contributions.iter().map(|(key, proof)| merkle_leaf(key, proof)).collect();
+
+        Some(MerkleProof {
+            index,
+            num_leaves: self.contributions.len(),
+            siblings: merkle_root_and_path(leaves, Some(index)).1,
+        })
+    }
+
+    // Method returning the digest identifying the aggregated PVSS share when advertising or
+    // requesting it, i.e., its transcript_id.
+    pub fn digest(&self) -> [u8; 32] {
+        self.transcript_id().0
+    }
+
+    // Method returning a one-line summary of the aggregated PVSS share for structured logs, as
+    // key=value pairs with fingerprints of the group elements and the (truncated) transcript_id.
+    pub fn to_hex_summary(&self) -> String {
+        format!(
+            "degree={} num_participants={} contributions={} encs={}#{} comms={}#{} transcript_id={}",
+            self.degree,
+            self.num_participants,
+            self.contributions.len(),
+            self.pvss_core.encs.len(),
+            fingerprint(&self.pvss_core.encs),
+            self.pvss_core.comms.len(),
+            fingerprint(&self.pvss_core.comms),
+            &self.transcript_id().to_hex()[..16],
+        )
+    }
+
+    // Method returning a human-readable one-line summary of the aggregated PVSS share for
+    // operational logs: its configuration, contributor ids, whether degree + 1 contributions
+    // have been aggregated, and the (truncated) transcript_id. Not meant for parsing.
+    pub fn summary(&self) -> String {
+        let contributors = self
+            .contributor_ids()
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!(
+            "transcript n={} t={} contributors=[{}] quorum={} id={}",
+            self.num_participants,
+            self.degree,
+            contributors,
+            self.contributions.len() > self.degree,
+            &self.transcript_id().to_hex()[..8],
+        )
+    }
+
+    // Method for verifying the contributions in bound mode: every decomposition proof must have
+    // been generated against the joint context of all contributions (see joint_context), and
+    // be signed by its contributor. Proofs generated independently are rejected, and so are
+    // jointly generated ones once a contribution is added or removed, and so is an aggregate
+    // without contributions.
+    pub fn verify_contributions_bound(&self, conf: &Config<E>, registry: &ParticipantRegistry<E>) -> Result<(), PVSSError<E>> {
+        if self.contributions.is_empty() {
+            return Err(PVSSError::EmptyTranscript);
+        }
+
+        let context = joint_context::<E, _>(self.contributions.iter().map(|(id, proof)| (id, &proof.decomp_proof().gs)));
+
+        for (id, signed_proof) in self.contributions.iter() {
+            let participant = registry.get(id)?;
+
+            if signed_proof.decomp_proof().verify_with_context(conf, &context).is_err() {
+                return Err(PVSSError::NIZKProofDoesNotVerify { participant_id: id.index() });
+            }
+            signed_proof.verify_signature(signed_proof.message(), &participant.public_key_ed, id)?;
+        }
+
+        Ok(())
+    }
+
+    // Method for verifying every contribution: its signed proof must verify against the EdDSA
+    // public key of its contributor. Contributions are checked by increasing id, and the error of
+    // the first faulty one is returned; an aggregate without contributions is rejected.
+    pub fn verify(&self, conf: &Config<E>, registry: &ParticipantRegistry<E>) -> Result<(), PVSSError<E>> {
+        self.check_config(conf)?;
+
+        self.contributions
+            .iter()
+            .try_for_each(|(id, signed_proof)| verify_contribution(conf, registry, id, signed_proof))
+    }
+
+    // Same as verify, with the contributions checked across the rayon thread pool. The error
+    // returned is still that of the faulty contribution with the smallest id.
+    #[cfg(feature = "parallel")]
+    pub fn verify_par(&self, conf: &Config<E>, registry: &ParticipantRegistry<E>) -> Result<(), PVSSError<E>> {
+        use rayon::prelude::*;
+
+        self.check_config(conf)?;
+
+        let contributions = self.contributions.iter().collect::<Vec<_>>();
+        match contributions
+            .par_iter()
+            .find_map_first(|(id, signed_proof)| verify_contribution(conf, registry, *id, signed_proof).err())
+        {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    // Method checking that the aggregated PVSS share has contributions, all dealt under conf.
+    fn check_config(&self, conf: &Config<E>) -> Result<(), PVSSError<E>> {
+        if self.contributions.is_empty() {
+            return Err(PVSSError::EmptyTranscript);
+        }
+        if self.config_digest != conf.digest() {
+            return Err(PVSSError::ConfigMismatch);
+        }
+
+        Ok(())
+    }
+
+    // Method serializing the aggregated PVSS share into writer while hashing the written bytes,
+    // so that persisting a transcript and computing its transcript_id take a single pass.
+    // Returns the digest, which equals transcript_id.
+    pub fn serialize_and_hash<W: Write>(&self, writer: &mut W) -> Result<[u8; 32], PVSSError<E>> {
+        let mut tee = HashingWriter { inner: writer, state: Blake2sDigestHasher::state() };
+        self.serialize(&mut tee)?;
+
+        Ok(Blake2sDigestHasher::finalize(&tee.state))
+    }
+
+    // Method for attesting to the aggregated PVSS share (e.g., by the collector that assembled
+    // it, or a relayer forwarding it): signs its transcript_id.
+    pub fn sign_transcript(&self, sk: &SecretKey) -> Signature {
+        Signature::new(&self.transcript_id(), sk)
+    }
+
+    // Method for verifying an attestation produced by sign_transcript against the signer's key.
+    pub fn verify_transcript_signature(&self, sig: &Signature, pk: &PublicKey) -> Result<(), PVSSError<E>> {
+        sig.verify(&self.transcript_id(), pk)
+            .map_err(|_| PVSSError::EdDSAInvalidSignatureError)
+    }
+
+    // Method for computing a structured diff against another aggregated PVSS share, for
+    // debugging disagreements between nodes.
+    pub fn diff(&self, other: &Self) -> TranscriptDiff {
+        // Indices where two vectors differ; indices present in only one of them count as well.
+        fn differing<T: PartialEq>(a: &[T], b: &[T]) -> Vec<usize> {
+            (0..a.len().max(b.len())).filter(|&i| a.get(i) != b.get(i)).collect()
+        }
+
+        TranscriptDiff {
+            config: self.degree != other.degree
+                || self.num_participants != other.num_participants
+                || self.config_digest != other.config_digest,
+            encs: differing(&self.pvss_core.encs, &other.pvss_core.encs),
+            comms: differing(&self.pvss_core.comms, &other.pvss_core.comms),
+            only_in_self: self.contributions.keys().filter(|id| !other.contributions.contains_key(id)).collect(),
+            only_in_other: other.contributions.keys().filter(|id| !self.contributions.contains_key(id)).collect(),
+            differing_proofs: self
+                .contributions
+                .iter()
+                .filter(|(id, proof)| other.contributions.get(id).map_or(false, |other_proof| other_proof != *proof))
+                .map(|(id, _)| id)
+                .collect(),
+        }
+    }
+
+    // Method returning whether two aggregated PVSS shares are semantically equal: same
+    // configuration, core, contributor ids and committed gs, whichever signed proofs were kept
+    // (e.g., when the same proof was signed twice). Aggregation keeps the preferred of two such
+    // proofs (see prefer_other), so honest nodes already end up byte-equal; this also covers
+    // transcripts assembled elsewhere, which may have kept the other one.
+    pub fn equivalent(&self, other: &Self) -> bool {
+        self.degree == other.degree
+            && self.num_participants == other.num_participants
+            && self.config_digest == other.config_digest
+            && self.pvss_core == other.pvss_core
+            && self.contributions.len() == other.contributions.len()
+            && self
+                .contributions
+                .iter()
+                .zip(other.contributions.iter())
+                .all(|((id, a), (other_id, b))| id == other_id && a.decomp_proof.gs == b.decomp_proof.gs)
+    }
+
+    // Method returning whether the contributions still guarantee reconstruction when f of them
+    // are faulty, i.e., whether contributions.len() - f >= degree + 1.
+    pub fn tolerates_faults(&self, f: usize) -> bool {
+        f <= self.max_tolerable_faults() && self.contributions.len() > self.degree
+    }
+
+    // Method returning the largest number of faulty contributions that still leaves degree + 1
+    // honest ones (zero if there are not even degree + 1 contributions).
+    pub fn max_tolerable_faults(&self) -> usize {
+        self.contributions.len().saturating_sub(self.degree + 1)
+    }
+
+    // Method returning whether the aggregated PVSS share meets all structural preconditions for
+    // recovering the secret (see recoverability_report).
+    pub fn is_recoverable(&self) -> bool {
+        self.recoverability_report().is_recoverable()
+    }
+
+    // Method reporting on each structural precondition for recovering the secret: one
+    // encryption and one commitment per participant, contributor ids within range, and at
+    // least degree + 1 contributions.
+    pub fn recoverability_report(&self) -> RecoverabilityReport {
+        RecoverabilityReport {
+            num_participants: self.num_participants,
+            encs_len: self.pvss_core.encs.len(),
+            comms_len: self.pvss_core.comms.len(),
+            out_of_range: self.contributions.keys().filter(|id| id.index() >= self.num_participants).collect(),
+            contributions: self.contributions.len(),
+            threshold: self.degree + 1,
+        }
+    }
+
+    // Method checking that the core holds exactly one encryption and one commitment per
+    // participant, so that it can be safely indexed.
+    pub fn check_core_length(&self) -> Result<(), PVSSError<E>> {
+        for len in [self.pvss_core.encs.len(), self.pvss_core.comms.len()].iter() {
+            if *len != self.num_participants {
+                return Err(PVSSError::WrongCoreLength(*len, self.num_participants));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Method returning the aggregated encryption at index 0 of the core, i.e., the sum of the
+    // contributions' encryptions at index 0 (see PVSSShare::first_encryption). Contributions'
+    // cores are not retained, so the individual terms are only available from the shares.
+    pub fn first_encryption(&self) -> Option<E::G1Projective> {
+        self.pvss_core.encs.first().map(|enc| enc.into_projective())
+    }
+
+    // Method for growing the committee to the conf.num_participants participants of the new
+    // configuration conf: the new slots get identity encryptions and commitments, while existing
+    // contributions keep their ids, and the aggregate is rebound to conf, so that shares dealt
+    // under conf can be merged into it. The committee cannot shrink, as that would drop the slots
+    // of existing participants, and the degree cannot change.
+    // Note that the padded core is not a degree t sharing among the new committee on its own:
+    // verification under conf fails until the new slots stem from shares dealt under conf only.
+    pub fn resize(&mut self, conf: &Config<E>) -> Result<(), PVSSError<E>> {
+        let new_num_participants = conf.num_participants;
+        if conf.degree != self.degree {
+            return Err(PVSSError::TranscriptDifferentConfig(
+                self.degree,
+                conf.degree,
+                self.num_participants,
+                new_num_participants,
+            ));
+        }
+        if new_num_participants < self.num_participants {
+            return Err(PVSSError::InvalidConfig(format!(
+                "cannot shrink the committee from {} to {} participants",
+                self.num_participants, new_num_participants,
+            )));
+        }
+
+        self.pvss_core.encs.resize(new_num_participants, E::G1Affine::zero());
+        self.pvss_core.comms.resize(new_num_participants, E::G2Affine::zero());
+        self.num_participants = new_num_participants;
+        if !self.contributions.is_empty() {
+            self.config_digest = conf.digest();
+        }
+
+        Ok(())
+    }
+
+    // Method checking that every contributor id is within range. The error names the smallest
+    // id that is not.
+    pub fn validate_contribution_ids(&self) -> Result<(), PVSSError<E>> {
+        match self.contributions.keys().find(|id| id.index() >= self.num_participants) {
+            Some(id) => Err(PVSSError::InvalidParticipantId(id.index())),
+            None => Ok(()),
+        }
+    }
+}
+
+
+// Utility function verifying the signed proof of contributor id against its EdDSA public key.
+fn verify_contribution<E: PairingEngine>(
+    conf: &Config<E>,
+    registry: &ParticipantRegistry<E>,
+    id: ParticipantId,
+    signed_proof: &SignedProof<E>,
+) -> Result<(), PVSSError<E>> {
+    let participant = registry.get(id)?;
+    signed_proof.verify(conf, &participant.public_key_ed, id)
+}
+
+
+// Function deserializing an aggregated PVSS share (see PVSSAggregatedShare::deserialize_checked)
+// and running its structural checks right away: the committee must hold at most
+// max_participants participants, contributor ids must be within range, and the core must hold
+// one encryption and one commitment per participant. The first failing check is returned, so
+// that a malformed blob is rejected before any pairing is computed on it.
+pub fn deserialize_checked<E: PairingEngine, R: Read>(reader: &mut R, max_participants: usize) -> Result<PVSSAggregatedShare<E>, PVSSError<E>> {
+    let share = PVSSAggregatedShare::<E>::deserialize_checked(reader)?;
+
+    if share.num_participants > max_participants {
+        return Err(PVSSError::DeserializationFailed {
+            field: "num_participants",
+            offset: 0,
+            source: SerializationError::InvalidData,
+        });
+    }
+    share.validate_contribution_ids()?;
+    share.check_core_length()?;
+
+    Ok(share)
+}
+
+
+/* Struct TranscriptDiff describes how two aggregated PVSS shares differ. */
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TranscriptDiff {
+    pub config: bool,                           // whether the degree, number of participants or configuration digest differ
+    pub encs: Vec<usize>,                       // indices whose encryptions differ
+    pub comms: Vec<usize>,                      // indices whose commitments differ
+    pub only_in_self: Vec<ParticipantId>,       // contributor ids only present in the first share
+    pub only_in_other: Vec<ParticipantId>,      // contributor ids only present in the second share
+    pub differing_proofs: Vec<ParticipantId>,   // contributor ids present in both, with differing signed proofs
+}
+
+impl TranscriptDiff {
+    // Method returning whether the two aggregated PVSS shares are identical.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+
+/* Struct RecoverabilityReport describes which structural preconditions for recovering the
+   secret from an aggregated PVSS share hold. */
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecoverabilityReport {
+    pub num_participants: usize,           // expected length of the core
+    pub encs_len: usize,                   // number of encryptions in the core
+    pub comms_len: usize,                  // number of commitments in the core
+    pub out_of_range: Vec<ParticipantId>,  // contributor ids that are not below num_participants
+    pub contributions: usize,              // number of contributions
+    pub threshold: usize,                  // required number of contributions, i.e., degree + 1
+}
+
+impl RecoverabilityReport {
+    // Method returning whether the core holds one encryption and one commitment per participant.
+    pub fn core_length_ok(&self) -> bool {
+        self.encs_len == self.num_participants && self.comms_len == self.num_participants
+    }
+
+    // Method returning whether all contributor ids are valid participant ids.
+    pub fn ids_in_range(&self) -> bool {
+        self.out_of_range.is_empty()
+    }
+
+    // Method returning whether enough contributions have been aggregated.
+    pub fn threshold_met(&self) -> bool {
+        self.contributions >= self.threshold
+    }
+
+    // Method returning whether all preconditions hold.
+    pub fn is_recoverable(&self) -> bool {
+        self.core_length_ok() && self.ids_in_range() && self.threshold_met()
+    }
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use crate::signature::utils::tests::check_serialization;
+
+    use crate::{modified_scrape::share::PVSSCore, generate_production_keypair};
+    use crate::modified_scrape::{srs::SRS, config::Config, errors::PVSSError, share::SignedProof};
+    use crate::modified_scrape::{decomp::Decomp, poly::Polynomial as Poly};
+    use crate::Scalar;
+    use crate::{Digest, Signature};
+    use crate::modified_scrape::pvss::AffinePVSSCore;
+    use crate::modified_scrape::share::{deserialize_checked, verify_membership_and_proof, Contributions, MerkleProof, PVSSAggregatedShare, PeerId, Provenance, MAX_CONTRIBUTOR_ID};
+
+    use ark_ec::{PairingEngine, AffineCurve, ProjectiveCurve};
+    use ark_ff::{PrimeField, Zero};
+    use ark_poly::UVPolynomial;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use std::mem::size_of;
+    use ark_std::UniformRand;
+    use ark_std::collections::BTreeMap;
+    use std::convert::TryFrom;
     use ark_bls12_381::{
 	    Bls12_381 as E,   // type Bls12_381 = Bls12<Parameters> (Bls12 implements PairingEngine)
     };
     use rand::thread_rng;
 
     use super::PVSSShare;
+    use crate::testing::{fixtures::{Fixture, FixtureBuilder}, simulation::Simulation};
+    use crate::modified_scrape::{decomp::{joint_context, DecompProof}, participant::ParticipantId};
 
     #[test]
     fn test_generate_valid_signed_proof() {
         let rng = &mut thread_rng();
+        let fixture = FixtureBuilder::new(3, 10).build::<E>().unwrap();
+        let keys = &fixture.keypairs[0];
+
+        // generate decomposition proof
+        let dproof = Decomp::<E>::generate(rng, &fixture.config, &fixture.secrets[0].p_0).unwrap();
+
+        // sign the proof
+        let sig = Signature::new(&dproof.digest(), &keys.secret_key_ed);
+
+        let sproof = SignedProof::new(dproof, sig);
+
+        // Verify SignedProof instance, as well as the one dealt along with the share
+        sproof.verify(&fixture.config, &keys.public_key_ed, ParticipantId(0)).unwrap();
+        fixture.shares[0].signed_proof.verify(&fixture.config, &keys.public_key_ed, ParticipantId(0)).unwrap();
+    }
+
+
+    #[test]
+    fn test_signed_message() {
+        let fixture = FixtureBuilder::new(3, 10).build::<E>().unwrap();
+        let (conf, keys) = (&fixture.config, &fixture.keypairs[0]);
+
+        let dproof = *fixture.shares[0].signed_proof.decomp_proof();
+        let mut sproof = SignedProof::new(dproof, Signature::default());
+
+        // Sign exactly the bytes reported by signed_message.
+        let message = sproof.signed_message().unwrap();
+        sproof.signature_on_decomp = Signature::new(&Digest::try_from(&message[..]).unwrap(), &keys.secret_key_ed);
+
+        sproof.verify(conf, &keys.public_key_ed, ParticipantId(0)).unwrap();
+        sproof.verify_signed_message(&message, &keys.public_key_ed, ParticipantId(0)).unwrap();
+
+        // A truncated digest is rejected.
+        assert!(sproof.verify_signed_message(&message[..message.len() - 1], &keys.public_key_ed, ParticipantId(0)).is_err());
+    }
+
+
+    #[test]
+    fn test_signed_proof_cached_digest() {
+        let fixture = FixtureBuilder::new(3, 10).build::<E>().unwrap();
+        let (conf, pk_sig) = (&fixture.config, &fixture.keypairs[0].public_key_ed);
+
+        let dproof = *fixture.shares[0].signed_proof.decomp_proof();
+        let sproof = SignedProof::new(dproof, fixture.shares[0].signed_proof.signature_on_decomp.clone());
+
+        let mut uncached = vec![];
+        sproof.serialize(&mut uncached).unwrap();
+
+        // The cached digest matches a fresh recomputation, before and after caching.
+        assert!(sproof.digest.get().is_none());
+        assert_eq!(sproof.message(), dproof.digest().as_ref());
+        assert!(sproof.digest.get().is_some());
+        assert_eq!(sproof.message(), dproof.digest().as_ref());
+        sproof.verify(conf, pk_sig, ParticipantId(0)).unwrap();
+
+        // The cache is not serialized...
+        let mut cached = vec![];
+        sproof.serialize(&mut cached).unwrap();
+        assert_eq!(cached, uncached);
+        assert_eq!(sproof.serialized_size(), dproof.serialized_size() + sproof.signature_on_decomp.serialized_size());
+
+        // ...and a deserialized proof recomputes the same digest.
+        let deserialized = SignedProof::<E>::deserialize(&cached[..]).unwrap();
+        assert!(deserialized.digest.get().is_none());
+        assert!(deserialized == sproof);
+        assert_eq!(deserialized.message(), sproof.message());
+    }
+
+
+    #[test]
+    fn test_signed_proof_invalid_nizk() {
+        let rng = &mut thread_rng();
+        let fixture = FixtureBuilder::new(3, 10).build::<E>().unwrap();
+        let keys = &fixture.keypairs[4];
+
+        let mut dproof = *fixture.shares[4].signed_proof.decomp_proof();
+
+        // Malform the proof and sign the malformed proof, so that only the NIZK is faulty.
+        dproof.proof.1 = <E as PairingEngine>::Fr::rand(rng);
+        let sig = Signature::new(&dproof.digest(), &keys.secret_key_ed);
+
+        let sproof = SignedProof::new(dproof, sig);
+
+        match sproof.verify(&fixture.config, &keys.public_key_ed, ParticipantId(4)) {
+            Err(PVSSError::NIZKProofDoesNotVerify { participant_id }) => assert_eq!(participant_id, 4),
+            _ => panic!("expected NIZKProofDoesNotVerify"),
+        }
+    }
+
+
+    #[test]
+    fn test_signed_proof_invalid_signature() {
+        let fixture = FixtureBuilder::new(3, 10).build::<E>().unwrap();
+        let sproof = &fixture.shares[7].signed_proof;
+
+        // Valid NIZK, but checked against the wrong key.
+        match sproof.verify(&fixture.config, &fixture.keypairs[6].public_key_ed, ParticipantId(7)) {
+            Err(PVSSError::SignatureVerificationFailed { participant_id }) => assert_eq!(participant_id, 7),
+            _ => panic!("expected SignatureVerificationFailed"),
+        }
+    }
+
+
+    #[test]
+    fn test_create_empty_aggregated_pvss_share() {
+        let t = 3;
+        let n = 10;
+
+        // Create an empty PVSSAggregated share.
+        let empty_share = PVSSAggregatedShare::<E>::empty(t, n);
+
+        // The expected result.
+        let exp_result = PVSSAggregatedShare {
+            num_participants: n,
+            degree: t,
+            config_digest: Digest::default(),
+            pvss_core: AffinePVSSCore {
+                encs:  vec![<E as PairingEngine>::G1Affine::zero(); n],
+                comms: vec![<E as PairingEngine>::G2Affine::zero(); n],
+            },
+            contributions: Contributions::new(),
+            provenance: Provenance::default(),
+        };
+
+        assert!(empty_share == exp_result);
+    }
+
+
+    #[test]
+    fn test_create_pvss_share() {
+        let t = 3;
+        let n = 10;
+
+        let id = 5_usize;
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let pvss_share = &fixture.shares[id];
+
+        // The share is bound to its issuer and to the configuration it was dealt under.
+        assert_eq!(pvss_share.participant_id, ParticipantId::from_index(id));
+        assert_eq!((pvss_share.degree, pvss_share.num_participants), (t, n));
+        assert_eq!(pvss_share.config_digest, fixture.config.digest());
+        pvss_share.verify(&fixture.config, &fixture.keypairs[id].public_key_ed).unwrap();
+
+        // Every encryption is consistent with its commitment under the committee's keys.
+        assert!(pvss_share.pvss_core.verify_encryptions(&fixture.config, &fixture.public_keys()));
+    }
+
+
+    #[test]
+    fn test_first_encryption() {
+        let t = 3;
+        let n = 10;
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let (a, b) = (&fixture.shares[2], &fixture.shares[7]);
+        assert_eq!(a.first_encryption(), Some(a.pvss_core.encs[0]));
+
+        let aggr_share = PVSSAggregatedShare::aggregate_from_iter(t, n, vec![a, b]).unwrap();
+        assert_eq!(aggr_share.first_encryption(), Some(a.first_encryption().unwrap() + b.first_encryption().unwrap()));
+
+        assert_eq!(PVSSAggregatedShare::<E>::empty(t, 0).first_encryption(), None);
+    }
+
+    #[test]
+    fn test_aggregation_of_pvss_share() {
+        let t = 3;
+        let n = 10;
+
+        let id = 5_usize;
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let pvss_share = &fixture.shares[id];
+
+        // Create an AggregatedPVSSShare to hold the result.
+        let mut aggr_share = PVSSAggregatedShare::<E>::empty(t, n);
+
+        // Aggregate pvss_share into aggr_share.
+        aggr_share = aggr_share.aggregate_pvss_share(pvss_share).unwrap();
+
+        // Create a map containing only the party's signed proof.
+        let mut contribs = Contributions::new();
+        contribs.insert(ParticipantId::from_index(id), pvss_share.signed_proof.clone()).unwrap();
+
+        // The expected result.
+        let exp_result = PVSSAggregatedShare {
+            num_participants: n,
+            config_digest: fixture.config.digest(),
+            degree: t,
+            pvss_core: AffinePVSSCore::from_projective(&pvss_share.pvss_core),
+            contributions: contribs,
+            provenance: Provenance::default(),
+        };
+
+        assert!(aggr_share == exp_result);
+    }
+
+
+    #[test]
+    fn test_aggregation_of_two_pvss_shares() {
+        let t = 3;
+        let n = 10;
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let (pvss_share_a, pvss_share_b) = (&fixture.shares[2], &fixture.shares[3]);
+
+        // Create an AggregatedPVSSShare to hold the result.
+        let mut aggr_share = PVSSAggregatedShare::<E>::empty(t, n);
+
+        // Aggregate pvss_shares into aggr_share.
+        // Note: Order of aggregation is irrelevant.
+        aggr_share = aggr_share.aggregate_pvss_share(pvss_share_a).unwrap();
+        aggr_share = aggr_share.aggregate_pvss_share(pvss_share_b).unwrap();
+
+        let pvss_core = PVSSCore::empty(n)
+            .aggregate(&pvss_share_a.pvss_core)
+            .unwrap()
+            .aggregate(&pvss_share_b.pvss_core)
+            .unwrap();
+
+        // Create a map containing party A and party B's signed proofs.
+        // Note: Order of insertion is irrelevant.
+        let mut contribs = Contributions::new();
+        contribs.insert(ParticipantId(3), pvss_share_b.signed_proof.clone()).unwrap();
+        contribs.insert(ParticipantId(2), pvss_share_a.signed_proof.clone()).unwrap();
+
+        // The expected result.
+        let exp_result = PVSSAggregatedShare {
+            num_participants: n,
+            config_digest: fixture.config.digest(),
+            degree: t,
+            pvss_core: AffinePVSSCore::from_projective(&pvss_core),
+            contributions: contribs,
+            provenance: Provenance::default(),
+        };
+
+        assert!(aggr_share == exp_result);
+
+        // Aggregating the shares of every party yields the fixture's transcript.
+        let mut full = PVSSAggregatedShare::<E>::empty(t, n);
+        for share in fixture.shares.iter() {
+            full = full.aggregate_pvss_share(share).unwrap();
+        }
+        assert!(full == fixture.transcript);
+    }
+
+
+    #[test]
+    fn test_fold_share_matches_aggregate_pvss_share() {
+        let t = 3;
+        let n = 10;
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+
+        // A few of the dealt shares.
+        let shares = [1, 4, 7].iter().map(|&id| fixture.shares[id].clone()).collect::<Vec<_>>();
+
+        // Aggregate via the allocating path.
+        let mut aggr_share = PVSSAggregatedShare::<E>::empty(t, n);
+        for share in shares.iter() {
+            aggr_share = aggr_share.aggregate_pvss_share(share).unwrap();
+        }
+
+        // Aggregate via the in-place path.
+        let mut folded_share = PVSSAggregatedShare::<E>::empty(t, n);
+        for share in shares.iter() {
+            folded_share.fold_share(share).unwrap();
+        }
+
+        assert!(aggr_share == folded_share);
+
+        // Folding a share whose core has the wrong size fails and leaves the aggregate unchanged.
+        let mut bad_share = shares[0].clone();
+        bad_share.participant_id = ParticipantId(9);
+        bad_share.pvss_core = PVSSCore::empty(n + 1);
+
+        assert!(folded_share.fold_share(&bad_share).is_err());
+        assert!(aggr_share == folded_share);
+    }
+
+    #[test]
+    fn test_aggregate_drain() {
+        let t = 3;
+        let n = 10;
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+
+        let shares = [0, 2, 5, 6, 8].iter().map(|&id| fixture.shares[id].clone()).collect::<Vec<_>>();
+
+        // Draining the whole backlog folds every share.
+        let mut drained = PVSSAggregatedShare::<E>::empty(t, n);
+        assert_eq!(drained.aggregate_drain(shares.clone().into_iter()).unwrap(), shares.len());
+
+        let mut folded = PVSSAggregatedShare::<E>::empty(t, n);
+        for share in shares.iter() {
+            folded.fold_share(share).unwrap();
+        }
+        assert!(drained == folded);
+        assert!(PVSSAggregatedShare::<E>::aggregate_from_iter(t, n, shares.iter()).unwrap() == folded);
+
+        // A share with a wrongly sized core mid-stream stops the draining.
+        let mut stream = shares.clone();
+        stream[2].pvss_core = PVSSCore::empty(n + 1);
+
+        let mut drained = PVSSAggregatedShare::<E>::empty(t, n);
+        let mut stream = stream.into_iter();
+        match drained.aggregate_drain(&mut stream) {
+            Err(PVSSError::DrainInterrupted { folded: 2, source }) => {
+                assert!(matches!(*source, PVSSError::MismatchedCommitmentsError(..)));
+            }
+            _ => panic!("expected DrainInterrupted"),
+        }
+
+        // Only the shares before the bad one were folded, and the rest of the stream is intact.
+        assert_eq!(drained.contributions.keys().collect::<Vec<_>>(), vec![ParticipantId(0), ParticipantId(2)]);
+        assert_eq!(stream.map(|share| share.participant_id).collect::<Vec<_>>(), vec![ParticipantId(6), ParticipantId(8)]);
+    }
+
+
+    #[test]
+    fn test_aggregate_opts_fast_merge_matches_aggregate() {
+        let t = 3;
+        let n = 10;
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+
+        // Two aggregated shares over disjoint contributor ids.
+        let mut halves = vec![];
+        for ids in [[0_usize, 3], [5, 8]].iter() {
+            let mut half = PVSSAggregatedShare::<E>::empty(t, n);
+            for &id in ids.iter() {
+                half.fold_share(&fixture.shares[id]).unwrap();
+            }
+            halves.push(half);
+        }
+
+        let checked = halves[0].aggregate(&halves[1]).unwrap();
+        let fast = halves[0].aggregate_opts(&halves[1], true).unwrap();
+        assert!(checked == fast);
+        assert_eq!(fast.contributions.len(), 4);
+
+        // Without fast_merge, aggregate_opts is aggregate.
+        assert!(halves[0].aggregate_opts(&halves[1], false).unwrap() == checked);
+    }
+
+
+    #[test]
+    fn test_remove_contribution() {
         let t = 3;
         let n = 10;
 
-        let p_0 = Scalar::<E>::from(10 as u64);
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
 
-        // PVSS setup
-        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
-        let conf = Config { srs, degree: t, num_participants: n };
+        let shares = [2, 5].iter().map(|&id| fixture.shares[id].clone()).collect::<Vec<_>>();
 
-        // EdDSA setup
-        let (pk_sig, sk_sig) = generate_production_keypair();
+        let before = PVSSAggregatedShare::<E>::empty(t, n).aggregate_pvss_share(&shares[0]).unwrap();
+        let after = before.aggregate_pvss_share(&shares[1]).unwrap();
 
-        // generate decomposition proof
-        let mut dproof = Decomp::<E>::generate(rng, &conf, &p_0).unwrap();
+        // Removing the added contribution recovers the prior aggregate.
+        assert!(after.remove_contribution(ParticipantId(5), &shares[1]).unwrap() == before);
+
+        // Unknown id.
+        assert!(before.remove_contribution(ParticipantId(5), &shares[1]).is_err());
+
+        // Share not matching the stored proof.
+        let mut forged = shares[1].clone();
+        forged.signed_proof = shares[0].signed_proof.clone();
+        assert!(after.remove_contribution(ParticipantId(5), &forged).is_err());
+    }
 
-        // sign the proof
-        let sig = Signature::new(&mut dproof.digest(), &sk_sig);
 
-        let mut sproof = SignedProof {decomp_proof: dproof, signature_on_decomp: sig};
+    #[test]
+    fn test_serialization() {
+        let t = 3;
+        let n = 10;
 
-        // Verify SignedProof instance
-        sproof.verify(&conf, &pk_sig).unwrap();
+        // Dealings of a committee with actual EdDSA and PVSS keys
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let share = &fixture.shares[0];
+
+        check_serialization(share.pvss_core.clone());
+        check_serialization(*share.signed_proof.decomp_proof());
+        check_serialization(share.signed_proof.clone());
+        check_serialization(share.clone());
+
+        // aggregated share holding several contributions
+        let agg = PVSSAggregatedShare::<E>::aggregate_from_iter(t, n, fixture.shares[..4].iter()).unwrap();
+        assert_eq!(agg.contributions.len(), 4);
+        check_serialization(agg);
+        check_serialization(fixture.transcript);
     }
 
 
     #[test]
-    fn test_create_empty_aggregated_pvss_share() {
+    fn test_deserialize_checked_reports_field() {
         let t = 3;
         let n = 10;
 
-        // Create an empty PVSSAggregated share.
-        let empty_share = PVSSAggregatedShare::<E>::empty(t, n);
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let share = fixture.shares[2].clone();
+        let dproof = *share.signed_proof.decomp_proof();
+
+        let mut bytes = vec![];
+        share.serialize(&mut bytes).unwrap();
+
+        // The untruncated encoding round-trips.
+        assert!(PVSSShare::<E>::deserialize_checked(&bytes[..]).unwrap() == share);
+
+        let id_len = share.participant_id.serialized_size();
+        let digest_len = share.config_digest.serialized_size();
+        let header_len = id_len + share.degree.serialized_size() + share.num_participants.serialized_size() + digest_len;
+        let encs_len = share.pvss_core.encs.serialized_size();
+        let core_len = share.pvss_core.serialized_size();
+        let proof_len = dproof.proof.serialized_size();
+
+        let cuts = [
+            (4, "participant_id"),
+            (id_len + 4, "degree"),
+            (header_len - 5, "config_digest"),
+            (header_len + encs_len + 10, "comms"),
+            (header_len + core_len + proof_len + 5, "gs"),
+        ];
+
+        for &(cut, expected) in cuts.iter() {
+            match PVSSShare::<E>::deserialize_checked(&bytes[..cut]) {
+                Err(PVSSError::DeserializationFailed { field, offset, .. }) => {
+                    assert_eq!(field, expected);
+                    assert_eq!(offset, cut);
+                }
+                _ => panic!("expected DeserializationFailed"),
+            }
+        }
 
-        // The expected result.
-        let exp_result = PVSSAggregatedShare {
-            num_participants: n,
-            degree: t,
-            pvss_core: PVSSCore {
-                encs:  vec![<E as PairingEngine>::G1Projective::zero(); n],
-                comms: vec![<E as PairingEngine>::G2Projective::zero(); n],
-            },
-            contributions: BTreeMap::new(),
-        };
+        // Same for an aggregated share holding the contribution.
+        let agg_share = PVSSAggregatedShare::<E>::empty(t, n).aggregate_pvss_share(&share).unwrap();
+        let mut bytes = vec![];
+        agg_share.serialize(&mut bytes).unwrap();
 
-        assert!(empty_share == exp_result);
+        assert!(PVSSAggregatedShare::<E>::deserialize_checked(&bytes[..]).unwrap() == agg_share);
+
+        let cut = 2 * id_len + digest_len + core_len + 4;   // within the contributions' length prefix
+        match PVSSAggregatedShare::<E>::deserialize_checked(&bytes[..cut]) {
+            Err(PVSSError::DeserializationFailed { field, .. }) => assert_eq!(field, "contributions"),
+            _ => panic!("expected DeserializationFailed"),
+        }
     }
 
 
     #[test]
-    fn test_create_pvss_share() {
+    fn test_diff_pinpoints_changed_encryption() {
         let rng = &mut thread_rng();
         let t = 3;
         let n = 10;
 
-        let id = 5_usize;
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
 
-        // Sample a random degree t polynomial.
-	    let poly = Poly::<E>::rand(t, rng);
-        let p_0 = poly[0];   // the free term
+        let aggr_share = PVSSAggregatedShare::<E>::empty(t, n).aggregate_pvss_share(&fixture.shares[2]).unwrap();
+        assert!(aggr_share.diff(&aggr_share.clone()).is_empty());
 
-        // PVSS setup
-        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
-        let conf = Config { srs, degree: t, num_participants: n };
+        // Change a single encryption.
+        let mut other = aggr_share.clone();
+        other.pvss_core.encs[4] = <E as PairingEngine>::G1Projective::rand(rng).into_affine();
+
+        let diff = aggr_share.diff(&other);
+        assert_eq!(diff.encs, vec![4]);
+        assert!(!diff.config && diff.comms.is_empty() && diff.differing_proofs.is_empty());
+        assert!(diff.only_in_self.is_empty() && diff.only_in_other.is_empty());
+    }
 
-        // Schnorr SRS (over group G1)
-        let schnorr_srs = SCHSRS::<<E as PairingEngine>::G1Affine>::setup(rng).unwrap();
-        let schnorr_sig = SchnorrSignature { srs: schnorr_srs };
+    #[test]
+    fn test_recoverability_report() {
+        let t = 3;
+        let n = 10;
 
-        // Schnorr setup
-        let (_schorr_sk, schnorr_pk) = schnorr_sig.generate_keypair(rng).unwrap();
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let sproof = fixture.shares[0].signed_proof.clone();
 
-        // EdDSA setup
-        let (_pk_sig, sk_sig) = generate_production_keypair();
+        // Only the structure matters here, so a single signed proof is reused.
+        let mut aggr_share = PVSSAggregatedShare::<E>::empty(t, n);
+        for id in 0..t {
+            aggr_share.contributions.insert(ParticipantId::from_index(id), sproof.clone()).unwrap();
+        }
 
-        // Generate decomposition proof.
-        let mut dproof = Decomp::<E>::generate(rng, &conf, &p_0).unwrap();
-
-        // Sign the proof.
-        let sig = Signature::new(&mut dproof.digest(), &sk_sig);
-
-        let sproof = SignedProof {decomp_proof: dproof, signature_on_decomp: sig};
-
-        // Evaluate poly(j) for all j in {1, ..., n}.
-        let evals = (1..=n)
-	        .map(|j| poly.evaluate(&Scalar::<E>::from(j as u64)))
-	        .collect::<Vec<_>>();
-
-        // Compute commitments for all nodes in {0, ..., n-1}.
-        // Recall that G2 is the commitment group.
-        let comms = (0..=(n-1))
-	        .map(|j| conf.srs.g2.mul(evals[j].into_repr()))
-	        .collect::<Vec<_>>();
-
-        // Dummy vector of random Schnorr public keys.
-        let mut schnorr_pks = vec![<E as PairingEngine>::G1Projective::rand(rng); n];
-        // For this test case, we only care about party "id"'s pk being genuine.
-        schnorr_pks[id] = schnorr_pk.into_projective();
-
-        // Compute encryptions for all nodes in {0, ..., n-1}.
-        let encs: Vec<_> = (0..=(n-1))
-	        .map(|j| {
-                schnorr_pks[j]
-                    .into_affine()
-                    .mul(evals[j].into_repr())
-                    })
-            .collect::<_>();
-
-        // Compose PVSS core.
-        let pvss_core = PVSSCore::<E> {comms, encs};
-
-        // Create PVSSShare.
-        let pvss_share = PVSSShare::<E> {
-            participant_id: id, 
-            pvss_core, 
-            signed_proof: sproof,
-        };
+        // Too few contributions.
+        let report = aggr_share.recoverability_report();
+        assert!(report.core_length_ok() && report.ids_in_range() && !report.threshold_met());
+        assert!(!aggr_share.is_recoverable());
+
+        // All preconditions hold.
+        aggr_share.contributions.insert(ParticipantId::from_index(t), sproof.clone()).unwrap();
+        let report = aggr_share.recoverability_report();
+        assert_eq!((report.contributions, report.threshold), (t + 1, t + 1));
+        assert!(report.is_recoverable() && aggr_share.is_recoverable());
+
+        // Short core.
+        let mut short = aggr_share.clone();
+        short.pvss_core.comms.pop();
+        let report = short.recoverability_report();
+        assert_eq!((report.encs_len, report.comms_len), (n, n - 1));
+        assert!(!report.core_length_ok() && report.ids_in_range() && report.threshold_met());
+        assert!(!short.is_recoverable());
+
+        // Contributor id out of range.
+        let mut out_of_range = aggr_share.clone();
+        out_of_range.contributions.insert(ParticipantId::from_index(n), sproof).unwrap();
+        let report = out_of_range.recoverability_report();
+        assert_eq!(report.out_of_range, vec![ParticipantId::from_index(n)]);
+        assert!(report.core_length_ok() && !report.ids_in_range() && report.threshold_met());
+        assert!(!out_of_range.is_recoverable());
     }
 
+    #[test]
+    fn test_contributor_ids() {
+        let t = 3;
+        let n = 10;
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let sproof = fixture.shares[0].signed_proof.clone();
+
+        let mut aggr_share = PVSSAggregatedShare::<E>::empty(t, n);
+        assert!(aggr_share.contributor_ids().is_empty());
+
+        // Insertion order does not matter.
+        for id in [7, 2, 9, 0, 4].iter() {
+            aggr_share.contributions.insert(ParticipantId(*id), sproof.clone()).unwrap();
+        }
+
+        let ids = aggr_share.contributor_ids();
+        assert_eq!(ids, [0, 2, 4, 7, 9].iter().map(|&id| ParticipantId(id)).collect::<Vec<_>>());
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(ids, aggr_share.contributions.keys().collect::<Vec<_>>());
+    }
 
     #[test]
-    fn test_aggregation_of_pvss_share() {
-        let rng = &mut thread_rng();
+    fn test_summary() {
         let t = 3;
         let n = 10;
 
-        let id = 5_usize;
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let sproof = fixture.shares[0].signed_proof.clone();
 
-        // Sample a random degree t polynomial.
-	    let poly = Poly::<E>::rand(t, rng);
-        let p_0 = poly[0];   // the free term
+        let mut aggr_share = PVSSAggregatedShare::<E>::empty(t, n);
+        assert!(aggr_share.summary().starts_with("transcript n=10 t=3 contributors=[] quorum=false id="));
 
-        // PVSS setup
-        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
-        let conf = Config { srs, degree: t, num_participants: n };
+        for id in [5, 2, 3].iter() {
+            aggr_share.contributions.insert(ParticipantId(*id), sproof.clone()).unwrap();
+        }
+        let summary = aggr_share.summary();
+        assert!(summary.contains("contributors=[2,3,5]") && summary.contains("quorum=false"));
+        assert!(summary.ends_with(&format!("id={}", &aggr_share.transcript_id().to_hex()[..8])));
+        assert!(!summary.contains('\n'));
+
+        // Degree + 1 contributions make a quorum.
+        aggr_share.contributions.insert(ParticipantId(9), sproof).unwrap();
+        let summary = aggr_share.summary();
+        assert!(summary.contains("contributors=[2,3,5,9]") && summary.contains("quorum=true"));
+    }
 
-        // Schnorr SRS (over group G1)
-        let schnorr_srs = SCHSRS::<<E as PairingEngine>::G1Affine>::setup(rng).unwrap();
-        let schnorr_sig = SchnorrSignature { srs: schnorr_srs };
+    #[test]
+    fn test_compact_debug() {
+        let rng = &mut thread_rng();
+        let t = 85;
+        let n = 256;
 
-        // Schnorr setup
-        let (_schorr_sk, schnorr_pk) = schnorr_sig.generate_keypair(rng).unwrap();
+        // PVSS setup
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
 
-        // EdDSA setup
+        let poly = Poly::<E>::rand(t, rng);
         let (_pk_sig, sk_sig) = generate_production_keypair();
+        let dproof = Decomp::<E>::generate(rng, &conf, &poly[0]).unwrap();
+        let sig = Signature::new(&dproof.digest(), &sk_sig);
+        let sproof = SignedProof::new(dproof, sig);
 
-        // Generate decomposition proof.
-        let mut dproof = Decomp::<E>::generate(rng, &conf, &p_0).unwrap();
-
-        // Sign the proof.
-        let sig = Signature::new(&mut dproof.digest(), &sk_sig);
-
-        let sproof = SignedProof {decomp_proof: dproof, signature_on_decomp: sig};
-
-        // Evaluate poly(j) for all j in {1, ..., n}.
-        let evals = (1..=n)
-	        .map(|j| poly.evaluate(&Scalar::<E>::from(j as u64)))
-	        .collect::<Vec<_>>();
-
-        // Compute commitments for all nodes in {0, ..., n-1}.
-        // Recall that G2 is the commitment group.
-        let comms = (0..=(n-1))
-	        .map(|j| conf.srs.g2.mul(evals[j].into_repr()))
-	        .collect::<Vec<_>>();
-
-        // Dummy vector of Schnorr public keys.
-        let mut schnorr_pks = vec![<E as PairingEngine>::G1Projective::rand(rng); n];
-        // We only care about party "id"'s pk being genuine.
-        schnorr_pks[id] = schnorr_pk.into_projective();
-
-        // Compute encryptions for all nodes in {0, ..., n-1}.
-        let encs: Vec<_> = (0..=(n-1))
-	        .map(|j| {
-                schnorr_pks[j]
-                    .into_affine()
-                    .mul(evals[j].into_repr())
-                    })
-            .collect::<_>();
-
-        // Compose PVSS core.
-        let pvss_core = PVSSCore::<E> {comms: comms.clone(), encs: encs.clone()};
-
-        // Create PVSSShare.
-        let pvss_share = PVSSShare::<E> {
-            participant_id: id,
-            pvss_core: pvss_core.clone(),
+        let share = PVSSShare::<E> {
+            participant_id: ParticipantId(17),
+            degree: t,
+            num_participants: n,
+            config_digest: conf.digest(),
+            pvss_core: PVSSCore::new(
+                (0..n).map(|_| <E as PairingEngine>::G1Projective::rand(rng)).collect(),
+                (0..n).map(|_| <E as PairingEngine>::G2Projective::rand(rng)).collect(),
+            ).unwrap(),
             signed_proof: sproof.clone(),
         };
 
-        // Create an AggregatedPVSSShare to hold the result.
         let mut aggr_share = PVSSAggregatedShare::<E>::empty(t, n);
+        aggr_share.pvss_core = AffinePVSSCore::from_projective(&share.pvss_core);
+        for id in 0..n {
+            aggr_share.contributions.insert(ParticipantId::from_index(id), sproof.clone()).unwrap();
+        }
 
-        // Aggregate pvss_share into aggr_share.
-        aggr_share = aggr_share.aggregate_pvss_share(&pvss_share).unwrap();
+        // The output lists every contributor, but no coordinates.
+        let debug = format!("{:?}", aggr_share);
+        assert!(debug.len() < 4096, "Debug output is {} bytes long", debug.len());
+        assert!(debug.contains(&format!("{:?}", aggr_share.contributor_ids())));
+        assert!(debug.contains("256 x G1") && debug.contains("256 x G2"));
+
+        // The affine and projective forms of a core have the same fingerprints.
+        let share_debug = format!("{:?}", share);
+        assert!(share_debug.len() < 1024, "Debug output is {} bytes long", share_debug.len());
+        assert!(share_debug.contains("participant_id: ParticipantId(17)"));
+        assert_eq!(
+            format!("{:?}", share.pvss_core).trim_start_matches("PVSSCore"),
+            format!("{:?}", aggr_share.pvss_core).trim_start_matches("AffinePVSSCore"),
+        );
+
+        // The summaries are single lines of key=value pairs.
+        let summary = aggr_share.to_hex_summary();
+        assert!(summary.contains("contributions=256") && !summary.contains('\n'));
+        assert!(summary.contains(&aggr_share.transcript_id().to_hex()[..16]));
+        assert!(share.to_hex_summary().starts_with("participant_id=17 encs=256#"));
+    }
 
-        // Create a BTreeMap containing only the party's signed proof.
-        let mut contribs = BTreeMap::new();
-	    contribs.insert(id, sproof);
+    #[test]
+    fn test_tolerates_faults() {
+        let rng = &mut thread_rng();
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
 
-        // The expected result.
-        let exp_result = PVSSAggregatedShare {
-            num_participants: n,
-            degree: t,
-            pvss_core,
-            contributions: contribs,
+        // (n, t, number of contributions, maximal tolerable faults)
+        let cases = [(4, 1, 4, 2), (4, 1, 2, 0), (4, 1, 1, 0), (10, 3, 10, 6), (10, 3, 4, 0), (10, 3, 3, 0), (10, 3, 0, 0), (64, 21, 43, 21)];
+
+        for &(n, t, contributions, max_faults) in cases.iter() {
+            let conf = Config::new(srs.clone(), t, n);
+            let poly = Poly::<E>::rand(t, rng);
+            let (_pk_sig, sk_sig) = generate_production_keypair();
+            let dproof = Decomp::<E>::generate(rng, &conf, &poly[0]).unwrap();
+            let sig = Signature::new(&dproof.digest(), &sk_sig);
+            let sproof = SignedProof::new(dproof, sig);
+
+            let mut aggr_share = PVSSAggregatedShare::<E>::empty(t, n);
+            for id in 0..contributions {
+                aggr_share.contributions.insert(ParticipantId::from_index(id), sproof.clone()).unwrap();
+            }
+
+            assert_eq!(aggr_share.max_tolerable_faults(), max_faults);
+            for f in 0..=n {
+                assert_eq!(aggr_share.tolerates_faults(f), contributions >= f + t + 1, "n={} t={} contributions={} f={}", n, t, contributions, f);
+            }
+        }
+    }
+
+    #[test]
+    fn test_serialize_and_hash() {
+        let t = 3;
+        let n = 10;
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let share = fixture.shares[4].clone();
+
+        for aggr_share in [PVSSAggregatedShare::<E>::empty(t, n), PVSSAggregatedShare::<E>::aggregate_from_iter(t, n, [share].iter()).unwrap()].iter() {
+            let mut bytes = vec![];
+            let hash = aggr_share.serialize_and_hash(&mut bytes).unwrap();
+
+            assert_eq!(Digest(hash), aggr_share.transcript_id());
+            assert!(PVSSAggregatedShare::<E>::deserialize(&bytes[..]).unwrap() == *aggr_share);
+        }
+    }
+
+    #[test]
+    fn test_verify_contributions_bound() {
+        let rng = &mut thread_rng();
+        let t = 2;
+        let n = 4;
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let (conf, registry) = (&fixture.config, &fixture.registry);
+
+        let secrets = fixture.secrets.iter().map(|dealt| dealt.p_0).collect::<Vec<_>>();
+        let sign = |dproof: DecompProof<E>, id: usize| {
+            let sig = Signature::new(&dproof.digest(), &fixture.keypairs[id].secret_key_ed);
+            SignedProof::new(dproof, sig)
         };
 
-        assert!(aggr_share == exp_result);
+        // Independently generated proofs verify individually, but not in bound mode.
+        let mut independent = PVSSAggregatedShare::<E>::empty(t, n);
+        for (id, p_0) in secrets.iter().enumerate() {
+            let dproof = Decomp::<E>::generate(rng, conf, p_0).unwrap();
+            independent.contributions.insert(ParticipantId::from_index(id), sign(dproof, id)).unwrap();
+        }
+        for (id, proof) in independent.contributions.iter() {
+            proof.verify(conf, &registry.get(id).unwrap().public_key_ed, id).unwrap();
+        }
+        match independent.verify_contributions_bound(conf, registry) {
+            Err(PVSSError::NIZKProofDoesNotVerify { participant_id }) => assert_eq!(participant_id, 0),
+            _ => panic!("expected NIZKProofDoesNotVerify"),
+        }
+
+        // Jointly generated proofs: the statements are agreed on first, then everyone proves
+        // against their joint context.
+        let statements = secrets
+            .iter()
+            .map(|p_0| conf.srs.g2.mul(p_0.into_repr()).into_affine())
+            .collect::<Vec<_>>();
+        let context = joint_context::<E, _>(
+            statements.iter().enumerate().map(|(id, statement)| (ParticipantId::from_index(id), statement)),
+        );
+
+        let mut joint = PVSSAggregatedShare::<E>::empty(t, n);
+        for (id, p_0) in secrets.iter().enumerate() {
+            let dproof = Decomp::<E>::generate_with_context(rng, conf, p_0, &context).unwrap();
+            joint.contributions.insert(ParticipantId::from_index(id), sign(dproof, id)).unwrap();
+        }
+        joint.verify_contributions_bound(conf, registry).unwrap();
+
+        // Dropping a contribution changes the joint context.
+        let mut dropped = joint.clone();
+        dropped.contributions.remove(&ParticipantId(2));
+        assert!(dropped.verify_contributions_bound(conf, registry).is_err());
+
+        // Signatures are still checked against the registry.
+        let mut forged = joint.clone();
+        let proof = forged.contributions.get(&ParticipantId(1)).unwrap().clone();
+        forged.contributions.insert(ParticipantId(1), sign(*proof.decomp_proof(), 3)).unwrap();
+        match forged.verify_contributions_bound(conf, registry) {
+            Err(PVSSError::SignatureVerificationFailed { participant_id }) => assert_eq!(participant_id, 1),
+            _ => panic!("expected SignatureVerificationFailed"),
+        }
     }
 
+    #[test]
+    fn test_aggregate_empty_inputs() {
+        let t = 3;
+        let n = 10;
+
+        let empty = PVSSAggregatedShare::<E>::empty(t, n);
+        assert!(empty.recoverability_report().core_length_ok());
+        assert!(!empty.is_recoverable());
+
+        // No shares.
+        let from_iter = PVSSAggregatedShare::<E>::aggregate_from_iter(t, n, Vec::<PVSSShare<E>>::new().iter()).unwrap();
+        assert!(from_iter == empty);
+
+        let mut drained = PVSSAggregatedShare::<E>::empty(t, n);
+        assert_eq!(drained.aggregate_drain(std::iter::empty()).unwrap(), 0);
+        assert!(drained == empty);
+
+        // Empty aggregates only.
+        assert!(empty.aggregate(&empty).unwrap() == empty);
+        assert!(empty.aggregate_opts(&empty, true).unwrap() == empty);
+        assert!(empty.clone().aggregate_owned(empty.clone()).unwrap() == empty);
+        assert_eq!(empty.contributor_ids(), Vec::<ParticipantId>::new());
+    }
 
     #[test]
-    fn test_aggregation_of_two_pvss_shares() {
-        let rng = &mut thread_rng();
+    fn test_transcript_signature() {
         let t = 3;
         let n = 10;
 
-        let idA = 2_usize;
-        let idB = 3_usize;
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let share = fixture.shares[2].clone();
+        let aggr_share = PVSSAggregatedShare::<E>::aggregate_from_iter(t, n, [share].iter()).unwrap();
+
+        // The collector attests to the transcript.
+        let (pk_collector, sk_collector) = generate_production_keypair();
+        let attestation = aggr_share.sign_transcript(&sk_collector);
+        aggr_share.verify_transcript_signature(&attestation, &pk_collector).unwrap();
+
+        // Someone else's key does not verify.
+        let (pk_other, _) = generate_production_keypair();
+        assert!(aggr_share.verify_transcript_signature(&attestation, &pk_other).is_err());
+
+        // Any change to the transcript invalidates the attestation.
+        let mut mutated_core = aggr_share.clone();
+        mutated_core.pvss_core.encs.swap(0, 1);
+        assert!(mutated_core.transcript_id() != aggr_share.transcript_id());
+        match mutated_core.verify_transcript_signature(&attestation, &pk_collector) {
+            Err(PVSSError::EdDSAInvalidSignatureError) => {},
+            _ => panic!("expected EdDSAInvalidSignatureError"),
+        }
+
+        let mut mutated_contributions = aggr_share.clone();
+        let contribution = mutated_contributions.contributions.remove(&ParticipantId(2)).unwrap();
+        mutated_contributions.contributions.insert(ParticipantId(3), contribution).unwrap();
+        assert!(mutated_contributions.verify_transcript_signature(&attestation, &pk_collector).is_err());
+    }
+
+    #[test]
+    fn test_aggregation_commutative_on_duplicate_contributions() {
+        let rng = &mut thread_rng();
+        let t = 3;
+        let n = 10;
 
         // PVSS setup
         let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
-        let conf = Config { srs, degree: t, num_participants: n };
-
-        // Schnorr SRS (over group G1)
-        let schnorr_srs = SCHSRS::<<E as PairingEngine>::G1Affine>::setup(rng).unwrap();
-        let schnorr_sig = SchnorrSignature { srs: schnorr_srs };
-
-        // Sample a random degree t polynomial for party A.
-	    let polyA = Poly::<E>::rand(t, rng);
-        let p_0A = polyA[0];   // the free term
-
-        // Sample a random degree t polynomial for party B.
-	    let polyB = Poly::<E>::rand(t, rng);
-        let p_0B = polyB[0];   // the free term
-
-        // Schnorr setup for party A
-        let (_schorr_skA, schnorr_pkA) = schnorr_sig.generate_keypair(rng).unwrap();
-
-        // Schnorr setup for party B
-        let (_schorr_skB, schnorr_pkB) = schnorr_sig.generate_keypair(rng).unwrap();
-
-        // EdDSA setup for party A
-        let (_pk_sigA, sk_sigA) = generate_production_keypair();
-
-        // EdDSA setup for party B
-        let (_pk_sigB, sk_sigB) = generate_production_keypair();
-
-        // Generate decomposition proof for party A.
-        let mut dproofA = Decomp::<E>::generate(rng, &conf, &p_0A).unwrap();
-
-        // Generate decomposition proof for party B.
-        let mut dproofB = Decomp::<E>::generate(rng, &conf, &p_0B).unwrap();
-
-        // Sign party A's proof.
-        let sigA = Signature::new(&mut dproofA.digest(), &sk_sigA);
-
-        // Sign party B's proof.
-        let sigB = Signature::new(&mut dproofB.digest(), &sk_sigB);
-
-        // Compose party A's signed proof.
-        let sproofA = SignedProof {decomp_proof: dproofA, signature_on_decomp: sigA};
-
-        // Compose party B's signed proof.
-        let sproofB = SignedProof {decomp_proof: dproofB, signature_on_decomp: sigB};
-
-        // Evaluate polyA(j) for all j in {1, ..., n}.
-        let evalsA = (1..=n)
-	        .map(|j| polyA.evaluate(&Scalar::<E>::from(j as u64)))
-	        .collect::<Vec<_>>();
-
-        // Evaluate polyB(j) for all j in {1, ..., n}.
-        let evalsB = (1..=n)
-	        .map(|j| polyB.evaluate(&Scalar::<E>::from(j as u64)))
-	        .collect::<Vec<_>>();
-
-        // Compute party A's commitments for all nodes in {0, ..., n-1}.
-        // Recall that G2 is the commitment group.
-        let commsA = (0..=(n-1))
-	        .map(|j| conf.srs.g2.mul(evalsA[j].into_repr()))
-	        .collect::<Vec<_>>();
-
-        // Compute party B's commitments for all nodes in {0, ..., n-1}.
-        // Recall that G2 is the commitment group.
-        let commsB = (0..=(n-1))
-	        .map(|j| conf.srs.g2.mul(evalsB[j].into_repr()))
-	        .collect::<Vec<_>>();
-
-        // Dummy vector of Schnorr public keys.
-        let mut schnorr_pks = vec![<E as PairingEngine>::G1Projective::rand(rng); n];
-        // We only care about party A and B's public keys being genuine.
-        schnorr_pks[idA] = schnorr_pkA.into_projective();
-        schnorr_pks[idB] = schnorr_pkB.into_projective();
-
-        // Compute party A's encryptions for all nodes in {0, ..., n-1}.
-        let encsA: Vec<_> = (0..=(n-1))
-	        .map(|j| {
-                schnorr_pks[j]
-                    .into_affine()
-                    .mul(evalsA[j].into_repr())
-                    })
-            .collect::<_>();
-
-        // Compute party B's encryptions for all nodes in {0, ..., n-1}.
-        let encsB: Vec<_> = (0..=(n-1))
-	        .map(|j| {
-                schnorr_pks[j]
-                    .into_affine()
-                    .mul(evalsB[j].into_repr())
-                    })
-            .collect::<_>();
-
-        // Compose A's PVSS core.
-        let pvss_coreA = PVSSCore::<E> {comms: commsA.clone(), encs: encsA.clone()};
-
-        // Compose B's PVSS core.
-        let pvss_coreB = PVSSCore::<E> {comms: commsB.clone(), encs: encsB.clone()};
-
-        // Create A's PVSSShare.
-        let pvss_shareA = PVSSShare::<E> {
-            participant_id: idA,
-            pvss_core: pvss_coreA.clone(),
-            signed_proof: sproofA.clone(),
-        };
+        let conf = Config::new(srs, t, n);
+
+        // The same decomposition proof, signed under two different keys, so that both signed
+        // proofs carry equal gs but serialize differently.
+        let poly = Poly::<E>::rand(t, rng);
+        let dproof = Decomp::<E>::generate(rng, &conf, &poly[0]).unwrap();
+        let shares = (0..2)
+            .map(|_| {
+                let (_pk_sig, sk_sig) = generate_production_keypair();
+                let sig = Signature::new(&dproof.digest(), &sk_sig);
+
+                PVSSShare::<E> {
+                    participant_id: ParticipantId(2),
+                    degree: t,
+                    num_participants: n,
+                    config_digest: conf.digest(),
+                    pvss_core: PVSSCore::new(
+                        (0..n).map(|_| <E as PairingEngine>::G1Projective::rand(rng)).collect(),
+                        (0..n).map(|_| <E as PairingEngine>::G2Projective::rand(rng)).collect(),
+                    ).unwrap(),
+                    signed_proof: SignedProof::new(dproof, sig),
+                }
+            })
+            .collect::<Vec<_>>();
 
-        // Create B's PVSSShare.
-        let pvss_shareB = PVSSShare::<E> {
-            participant_id: idB,
-            pvss_core: pvss_coreB.clone(),
-            signed_proof: sproofB.clone(),
+        let bytes = |share: &PVSSAggregatedShare<E>| {
+            let mut bytes = vec![];
+            share.serialize(&mut bytes).unwrap();
+            bytes
         };
 
-        // Create an AggregatedPVSSShare to hold the result.
-        let mut aggr_share = PVSSAggregatedShare::<E>::empty(t, n);
+        let a = PVSSAggregatedShare::<E>::empty(t, n).aggregate_pvss_share(&shares[0]).unwrap();
+        let b = PVSSAggregatedShare::<E>::empty(t, n).aggregate_pvss_share(&shares[1]).unwrap();
+        let expected = bytes(&a.aggregate(&b).unwrap());
+
+        assert_eq!(bytes(&b.aggregate(&a).unwrap()), expected);
+        assert_eq!(bytes(&a.clone().aggregate_owned(b.clone()).unwrap()), expected);
+        assert_eq!(bytes(&b.clone().aggregate_owned(a.clone()).unwrap()), expected);
+        assert_eq!(bytes(&a.aggregate_pvss_share(&shares[1]).unwrap()), expected);
+        assert_eq!(bytes(&b.aggregate_pvss_share(&shares[0]).unwrap()), expected);
+        assert_eq!(bytes(&a.clone().aggregate_pvss_share_owned(shares[1].clone()).unwrap()), expected);
+        assert_eq!(bytes(&b.clone().aggregate_pvss_share_owned(shares[0].clone()).unwrap()), expected);
+
+        let mut folded_ab = a.clone();
+        folded_ab.fold_share(&shares[1]).unwrap();
+        let mut folded_ba = b.clone();
+        folded_ba.fold_share(&shares[0]).unwrap();
+        assert_eq!(bytes(&folded_ab), expected);
+        assert_eq!(bytes(&folded_ba), expected);
+    }
 
-        // Aggregate pvss_shares into aggr_share.
-        // Note: Order of aggregation is irrelevant.
-        aggr_share = aggr_share.aggregate_pvss_share(&pvss_shareA).unwrap();
-        aggr_share = aggr_share.aggregate_pvss_share(&pvss_shareB).unwrap();
+    #[test]
+    fn test_equivalent_ignores_kept_signature() {
+        let rng = &mut thread_rng();
+        let (t, n) = (2, 5);
 
-        let pvss_core = PVSSCore::empty(n)
-            .aggregate(&pvss_coreA)
-            .unwrap()
-            .aggregate(&pvss_coreB)
-            .unwrap();
+        let mut simulation = Simulation::<E>::new(rng, t, n).unwrap();
+        let (share_1, _) = simulation.nodes[1].dealer.deal(0, rng).unwrap();
+        let (share_3, _) = simulation.nodes[3].dealer.deal(0, rng).unwrap();
 
-        // Create a BTreeMap containing party A and party B's signed proofs.
-        // Note: Order of insertion is irrelevant.
-        let mut contribs = BTreeMap::new();
-	contribs.insert(idA, sproofA);
-        contribs.insert(idB, sproofB);
+        // Participant 3's proof, signed a second time (under another key): equal gs, other bytes.
+        let (_pk_sig, sk_sig) = generate_production_keypair();
+        let mut resigned = share_3.clone();
+        let dproof = *share_3.signed_proof.decomp_proof();
+        resigned.signed_proof = SignedProof::new(dproof, Signature::new(&dproof.digest(), &sk_sig));
+        assert!(resigned.signed_proof != share_3.signed_proof);
+
+        // Two honest nodes merge the same shares in different orders, and agree byte for byte.
+        let node_a = PVSSAggregatedShare::<E>::aggregate_from_iter(t, n, [&share_3, &share_1, &resigned].iter().cloned()).unwrap();
+        let node_b = PVSSAggregatedShare::<E>::aggregate_from_iter(t, n, [&resigned, &share_3, &share_1].iter().cloned()).unwrap();
+        assert!(node_a == node_b && node_a.transcript_id() == node_b.transcript_id());
+        assert!(node_a.equivalent(&node_b));
+
+        // A transcript that kept the other signature differs in its bytes, but not semantically.
+        let dropped = if node_a.contributions.get(&ParticipantId(3)) == Some(&share_3.signed_proof) { &resigned } else { &share_3 };
+        let mut other_kept = node_a.clone();
+        other_kept.contributions.insert(ParticipantId(3), dropped.signed_proof.clone()).unwrap();
+        assert!(other_kept != node_a && other_kept.transcript_id() != node_a.transcript_id());
+        assert_eq!(node_a.diff(&other_kept).differing_proofs, vec![ParticipantId(3)]);
+        assert!(node_a.equivalent(&other_kept) && other_kept.equivalent(&node_a));
+
+        // Other gs, another core, or another set of contributors are not equivalent.
+        let (conflicting, _) = simulation.nodes[3].dealer.deal_override(0, rng).unwrap();
+        let mut other_gs = node_a.clone();
+        other_gs.contributions.insert(ParticipantId(3), conflicting.signed_proof.clone()).unwrap();
+        assert!(!node_a.equivalent(&other_gs));
+
+        let mut other_core = node_a.clone();
+        other_core.pvss_core = AffinePVSSCore::from_projective(&share_1.pvss_core);
+        assert!(!node_a.equivalent(&other_core));
+
+        let mut fewer = node_a.clone();
+        fewer.contributions.remove(&ParticipantId(1));
+        assert!(!node_a.equivalent(&fewer) && !fewer.equivalent(&node_a));
+    }
 
-        // The expected result.
-        let exp_result = PVSSAggregatedShare {
-            num_participants: n,
-            degree: t,
-            pvss_core,
-            contributions: contribs,
-        };
+    #[test]
+    fn test_provenance() {
+        let (t, n) = (2, 5);
 
-        assert!(aggr_share == exp_result);
+        let shares = FixtureBuilder::new(t, n).build::<E>().unwrap().shares;
+        let peer = |i: u8| PeerId([i; 32]);
+
+        // Shares 0 and 1 are relayed by peer 7, share 2 by peer 8, and share 3 by an unrecorded peer.
+        let mut tracked = PVSSAggregatedShare::<E>::empty(t, n);
+        for (share, relayer) in shares.iter().zip([7, 7, 8].iter()) {
+            tracked = tracked.aggregate_pvss_share_with_peer(share, peer(*relayer)).unwrap();
+        }
+        tracked = tracked.aggregate_pvss_share(&shares[3]).unwrap();
+        assert_eq!(tracked.relayer(ParticipantId(1)), Some(&peer(7)));
+        assert_eq!(tracked.relayer(ParticipantId(2)), Some(&peer(8)));
+        assert_eq!(tracked.relayer(ParticipantId(3)), None);
+
+        // A re-delivered share keeps its first relayer.
+        let redelivered = tracked.aggregate_pvss_share_with_peer(&shares[0], peer(9)).unwrap();
+        assert_eq!(redelivered.relayer(ParticipantId(0)), Some(&peer(7)));
+
+        // The transcript itself is untouched: same transcript_id, bytes and comparisons.
+        let plain = PVSSAggregatedShare::<E>::aggregate_from_iter(t, n, shares.iter().take(4)).unwrap();
+        assert_eq!(tracked.transcript_id(), plain.transcript_id());
+        assert!(tracked == plain);
+
+        let mut bytes = vec![];
+        tracked.serialize(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), plain.serialized_size());
+        let deserialized = PVSSAggregatedShare::<E>::deserialize(&bytes[..]).unwrap();
+        assert!(deserialized == plain && deserialized.provenance.0.is_empty());
+
+        // Merging keeps the relayers of both sides, and removing a contribution drops its relayer.
+        let last = PVSSAggregatedShare::<E>::empty(t, n).aggregate_pvss_share_with_peer(&shares[4], peer(8)).unwrap();
+        let merged = tracked.aggregate(&last).unwrap();
+        assert_eq!(merged.relayer(ParticipantId(0)), Some(&peer(7)));
+        assert_eq!(merged.relayer(ParticipantId(4)), Some(&peer(8)));
+        assert_eq!(tracked.clone().aggregate_owned(last).unwrap().provenance.0, merged.provenance.0);
+
+        let removed = merged.remove_contribution(ParticipantId(2), &shares[2]).unwrap();
+        assert_eq!(removed.relayer(ParticipantId(2)), None);
+        assert_eq!(removed.relayer(ParticipantId(1)), Some(&peer(7)));
     }
 
+    #[test]
+    fn test_aggregate_owned_matches_aggregate() {
+        let t = 3;
+        let n = 10;
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+
+        let shares = [0, 3, 5, 8].iter().map(|&id| fixture.shares[id].clone()).collect::<Vec<_>>();
+
+        // Folding shares one by one.
+        let mut borrowed = PVSSAggregatedShare::<E>::empty(t, n);
+        let mut owned = PVSSAggregatedShare::<E>::empty(t, n);
+        for share in shares.iter() {
+            borrowed = borrowed.aggregate_pvss_share(share).unwrap();
+            owned = owned.aggregate_pvss_share_owned(share.clone()).unwrap();
+        }
+        assert!(borrowed == owned);
+
+        // Aggregating two aggregated shares, with an overlapping contribution.
+        let a = PVSSAggregatedShare::<E>::empty(t, n).aggregate_pvss_share(&shares[0]).unwrap();
+        let b = a.aggregate_pvss_share(&shares[1]).unwrap().aggregate_pvss_share(&shares[2]).unwrap();
+        assert!(a.aggregate(&b).unwrap() == a.clone().aggregate_owned(b.clone()).unwrap());
+        assert!(b.aggregate(&a).unwrap() == b.clone().aggregate_owned(a.clone()).unwrap());
+
+        // Conflicting contributions are rejected.
+        let mut conflicting = shares[0].clone();
+        let mut dproof = *conflicting.signed_proof.decomp_proof();
+        dproof.gs = shares[1].signed_proof.decomp_proof().gs;
+        conflicting.signed_proof = SignedProof::new(dproof, conflicting.signed_proof.signature_on_decomp.clone());
+        match a.aggregate_pvss_share_owned(conflicting) {
+            Err(PVSSError::TranscriptDifferentCommitments) => (),
+            _ => panic!("expected TranscriptDifferentCommitments"),
+        }
+    }
 
     #[test]
-    fn test_serialization() {
+    fn test_aggregate_owned_at_scale() {
         let rng = &mut thread_rng();
         let t = 3;
-        let n = 10;
+        let n = 500;
+
+        // PVSS setup
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+
+        // A synthetic core (consecutive multiples of the generators) and signed proof, reused
+        // by all 500 shares.
+        let mut core = PVSSCore::<E>::empty(n);
+        for j in 1..n {
+            core.encs[j] = core.encs[j - 1] + conf.srs.g1.into_projective();
+            core.comms[j] = core.comms[j - 1] + conf.srs.g2.into_projective();
+        }
+        let poly = Poly::<E>::rand(t, rng);
+        let (_pk_sig, sk_sig) = generate_production_keypair();
+        let dproof = Decomp::<E>::generate(rng, &conf, &poly[0]).unwrap();
+        let sig = Signature::new(&dproof.digest(), &sk_sig);
+        let signed_proof = SignedProof::new(dproof, sig);
+
+        let mut owned = PVSSAggregatedShare::<E>::empty(t, n);
+        let buffer = owned.pvss_core.encs.as_ptr();
+
+        for id in 0..n {
+            let share = PVSSShare { participant_id: ParticipantId::from_index(id), degree: t, num_participants: n, config_digest: conf.digest(), pvss_core: core.clone(), signed_proof: signed_proof.clone() };
+            owned = owned.aggregate_pvss_share_owned(share).unwrap();
+        }
 
-        // EdDSA setup
-        let eddsa_keypair = generate_production_keypair();
+        // Every coordinate j now holds n * j times the generators.
+        assert_eq!(owned.contributions.len(), n);
+        for &j in [1_usize, n / 2, n - 1].iter() {
+            let factor = Scalar::<E>::from((n * j) as u64).into_repr();
+            assert_eq!(owned.pvss_core.encs[j], conf.srs.g1.mul(factor).into_affine());
+            assert_eq!(owned.pvss_core.comms[j], conf.srs.g2.mul(factor).into_affine());
+        }
+
+        // The accumulator's allocation is reused throughout.
+        assert_eq!(owned.pvss_core.encs.as_ptr(), buffer);
+    }
+
+    #[test]
+    fn test_compact_representation() {
+        let rng = &mut thread_rng();
+        let t = 3;
+        let n = 256;
 
         // PVSS setup
         let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
-        let g1 = srs.g1;   // affine
-        let g2 = srs.g2;   // affine
-        
-        let conf = Config { srs, degree: t, num_participants: n };
+        let conf = Config::new(srs, t, n);
+
         let poly = Poly::<E>::rand(t, rng);
+        let (_pk_sig, sk_sig) = generate_production_keypair();
+        let dproof = Decomp::<E>::generate(rng, &conf, &poly[0]).unwrap();
+        let sig = Signature::new(&dproof.digest(), &sk_sig);
+        let signed_proof = SignedProof::new(dproof, sig);
+
+        // A synthetic core: consecutive multiples of the generators.
+        let mut core = PVSSCore::<E>::empty(n);
+        for j in 1..n {
+            core.encs[j] = core.encs[j - 1] + conf.srs.g1.into_projective();
+            core.comms[j] = core.comms[j - 1] + conf.srs.g2.into_projective();
+        }
 
-        // vector of i's as scalars
-        let points = (1..=t)
-            .map(|j| Scalar::<E>::from(j as u64))
-            .collect::<Vec<_>>();
-/*
-        // vector of commitments: g_2^{s_i}
-	    let evals = (1..=t)
-            .map(|j| g2.mul(poly.evaluate(&points[(j-1) as usize]).into_repr()))
+        // Contributions are kept sorted by id, whatever the insertion order.
+        let mut aggr_share = PVSSAggregatedShare::<E>::empty(t, n);
+        aggr_share.pvss_core = AffinePVSSCore::from_projective(&core);
+        for &id in [200, 3, 255, 42].iter() {
+            assert!(aggr_share.contributions.insert(ParticipantId(id), signed_proof.clone()).is_none()).unwrap();
+        }
+        assert_eq!(aggr_share.contributions.keys().map(|id| id.0).collect::<Vec<_>>(), vec![3, 42, 200, 255]);
+        assert!(aggr_share.contributions.insert(ParticipantId(42), signed_proof.clone()).is_some()).unwrap();
+        assert!(aggr_share.contributions.get(&ParticipantId(42)).is_some() && aggr_share.contributions.get(&ParticipantId(43)).is_none());
+        assert!(aggr_share.contributions.get(&ParticipantId::from_index(MAX_CONTRIBUTOR_ID + 1)).is_none());
+        assert!(matches!(
+            aggr_share.contributions.insert(ParticipantId::from_index(MAX_CONTRIBUTOR_ID + 1), signed_proof.clone()),
+            Err(PVSSError::InvalidParticipantId(id)) if id == MAX_CONTRIBUTOR_ID + 1
+        ));
+
+        // The encoding is the one of a projective core along with a BTreeMap of contributions.
+        let mut legacy = vec![];
+        n.serialize(&mut legacy).unwrap();
+        t.serialize(&mut legacy).unwrap();
+        core.serialize(&mut legacy).unwrap();
+        aggr_share.contributions.iter().map(|(id, proof)| (id, proof.clone())).collect::<BTreeMap<_, _>>().serialize(&mut legacy).unwrap();
+
+        let mut bytes = vec![];
+        aggr_share.serialize(&mut bytes).unwrap();
+        assert_eq!(bytes, legacy);
+        assert_eq!(aggr_share.serialized_size(), legacy.len());
+        assert!(PVSSAggregatedShare::<E>::deserialize(&legacy[..]).unwrap() == aggr_share);
+        assert!(PVSSAggregatedShare::<E>::deserialize_checked(&legacy[..]).unwrap() == aggr_share);
+
+        // The affine core takes less memory than its projective counterpart.
+        let projective = n * (size_of::<<E as PairingEngine>::G1Projective>() + size_of::<<E as PairingEngine>::G2Projective>());
+        let contributions = aggr_share.contributions.memory_estimate();
+        assert!(aggr_share.memory_estimate() - contributions < projective);
+        assert!(contributions >= 4 * size_of::<SignedProof<E>>());
+    }
+
+    #[test]
+    fn test_out_of_range_participant_id() {
+        let t = 3;
+        let n = 10;
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+
+        // A share claiming an id beyond the committee (and beyond u16) is rejected, not folded.
+        for &id in [n, 70000].iter() {
+            let mut share = fixture.shares[2].clone();
+            share.participant_id = ParticipantId::from_index(id);
+
+            let mut aggr_share = PVSSAggregatedShare::<E>::aggregate_from_iter(t, n, fixture.shares[..2].iter()).unwrap();
+            assert!(matches!(aggr_share.aggregate_pvss_share(&share), Err(PVSSError::InvalidParticipantId(got)) if got == id));
+            assert!(matches!(aggr_share.clone().aggregate_pvss_share_owned(share.clone()), Err(PVSSError::InvalidParticipantId(got)) if got == id));
+            assert!(matches!(aggr_share.fold_share(&share), Err(PVSSError::InvalidParticipantId(got)) if got == id));
+            assert_eq!(aggr_share.contributions.len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_check_core_length() {
+        let t = 3;
+        let n = 10;
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let (conf, pk_sig) = (&fixture.config, fixture.keypairs[2].public_key_ed);
+
+        let mut share = fixture.shares[2].clone();
+        share.check_core_length(n).unwrap();
+        share.verify(conf, &pk_sig).unwrap();
+
+        // A short encryption vector.
+        share.pvss_core.encs.truncate(n - 3);
+        assert!(matches!(share.check_core_length(n), Err(PVSSError::WrongCoreLength(got, expected)) if (got, expected) == (n - 3, n)));
+        assert!(matches!(share.verify(conf, &pk_sig), Err(PVSSError::WrongCoreLength(..))));
+
+        // A long commitment vector.
+        share.pvss_core = PVSSCore::empty(n);
+        share.pvss_core.comms.push(<E as PairingEngine>::G2Projective::zero());
+        assert!(matches!(share.verify(conf, &pk_sig), Err(PVSSError::WrongCoreLength(got, expected)) if (got, expected) == (n + 1, n)));
+    }
+
+    #[test]
+    fn test_resize() {
+        let t = 3;
+        let (n, new_n) = (10, 15);
+
+        // Fixtures drawn from the same seed share their SRS.
+        let fixture = FixtureBuilder::new(t, n).with_seed(7).build::<E>().unwrap();
+        let new_fixture = FixtureBuilder::new(t, new_n).with_seed(7).build::<E>().unwrap();
+        let new_conf = &new_fixture.config;
+        assert!(new_conf.srs.g1 == fixture.config.srs.g1 && new_conf.srs.g2 == fixture.config.srs.g2);
+
+        let mut transcript = fixture.transcript.clone();
+        transcript.resize(new_conf).unwrap();
+
+        assert_eq!(transcript.num_participants, new_n);
+        assert_eq!(transcript.config_digest, new_conf.digest());
+        transcript.check_core_length().unwrap();
+        assert!(transcript.pvss_core.encs[..n] == fixture.transcript.pvss_core.encs[..]);
+        assert!(transcript.pvss_core.comms[..n] == fixture.transcript.pvss_core.comms[..]);
+        assert!(transcript.pvss_core.encs[n..].iter().all(|enc| enc.is_zero()));
+        assert!(transcript.pvss_core.comms[n..].iter().all(|comm| comm.is_zero()));
+        assert_eq!(transcript.contributor_ids(), fixture.transcript.contributor_ids());
+        transcript.validate_contribution_ids().unwrap();
+
+        // A share of a new member, dealt under the new configuration, merges into the resized
+        // transcript; it does not merge into the original one.
+        let joining = &new_fixture.shares[n + 2];
+        let merged = transcript.aggregate_pvss_share(joining).unwrap();
+        assert_eq!(merged.contributions.len(), n + 1);
+        assert!(merged.contributions.get(&ParticipantId::from_index(n + 2)).is_some());
+        assert!(merged.pvss_core.encs[n + 2] == joining.pvss_core.encs[n + 2].into_affine());
+        let mut folded = transcript.clone();
+        folded.fold_share(joining).unwrap();
+        assert!(folded == merged);
+        assert!(fixture.transcript.aggregate_pvss_share(joining).is_err());
+
+        // Resizing to the same size is a no-op; shrinking, or changing the degree, is rejected.
+        transcript.resize(new_conf).unwrap();
+        assert_eq!(transcript.pvss_core.encs.len(), new_n);
+        assert!(matches!(transcript.resize(&fixture.config), Err(PVSSError::InvalidConfig(_))));
+        let other_degree = Config::new(new_conf.srs.clone(), t + 1, new_n);
+        assert!(matches!(transcript.resize(&other_degree), Err(PVSSError::TranscriptDifferentConfig(..))));
+        assert_eq!(transcript.num_participants, new_n);
+    }
+
+    #[test]
+    fn test_share_config_mismatch_rejected() {
+        let t = 3;
+        let n = 10;
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let (conf, pk_sig) = (&fixture.config, fixture.keypairs[2].public_key_ed);
+
+        let share = fixture.shares[2].clone();
+        share.verify(conf, &pk_sig).unwrap();
+        PVSSAggregatedShare::empty(t, n).aggregate_pvss_share(&share).unwrap();
+
+        // A share claiming another degree, then another committee size.
+        for (degree, num_participants) in [(t + 1, n), (t, n + 1)].iter() {
+            let mut other = share.clone();
+            other.degree = *degree;
+            other.num_participants = *num_participants;
+
+            let is_mismatch = |res: Result<(), PVSSError<E>>| matches!(
+                res,
+                Err(PVSSError::TranscriptDifferentConfig(d1, d2, n1, n2)) if (d1, d2, n1, n2) == (t, *degree, n, *num_participants)
+            );
+
+            assert!(is_mismatch(other.verify(conf, &pk_sig)));
+            assert!(is_mismatch(PVSSAggregatedShare::empty(t, n).aggregate_pvss_share(&other).map(|_| ())));
+            assert!(is_mismatch(PVSSAggregatedShare::empty(t, n).aggregate_pvss_share_owned(other.clone()).map(|_| ())));
+
+            let mut agg = PVSSAggregatedShare::empty(t, n);
+            assert!(is_mismatch(agg.fold_share(&other)));
+            assert_eq!(agg, PVSSAggregatedShare::empty(t, n));
+        }
+    }
+
+    // Builds an aggregate of the shares dealt by every member of a committee, along with
+    // transcripts whose contributions 1 and 3 (resp. an id outside the committee) are faulty.
+    fn verify_fixtures() -> (Fixture<E>, PVSSAggregatedShare<E>, Vec<PVSSAggregatedShare<E>>) {
+        let (t, n) = (2, 5);
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let aggregated = fixture.transcript.clone();
+
+        let (_, other_sk) = generate_production_keypair();
+        let mut bad_signatures = aggregated.clone();
+        for &id in [3, 1].iter() {
+            let signed_proof = bad_signatures.contributions.get_mut(&ParticipantId(id)).unwrap();
+            signed_proof.signature_on_decomp = Signature::new(signed_proof.digest(), &other_sk);
+        }
+
+        let mut unknown_id = aggregated.clone();
+        let signed_proof = unknown_id.contributions.get(&ParticipantId(0)).unwrap().clone();
+        unknown_id.contributions.insert(ParticipantId::from_index(n), signed_proof).unwrap();
+
+        (fixture, aggregated, vec![bad_signatures, unknown_id, PVSSAggregatedShare::empty(t, n)])
+    }
+
+    #[test]
+    fn test_verify_contributions() {
+        let (fixture, aggregated, invalid) = verify_fixtures();
+        let (conf, registry) = (&fixture.config, &fixture.registry);
+
+        aggregated.verify(conf, registry).unwrap();
+        assert!(matches!(invalid[0].verify(conf, registry), Err(PVSSError::SignatureVerificationFailed { participant_id: 1 })));
+        assert!(matches!(invalid[1].verify(conf, registry), Err(PVSSError::InvalidParticipantId(5))));
+        assert!(matches!(invalid[2].verify(conf, registry), Err(PVSSError::EmptyTranscript)));
+    }
+
+    #[test]
+    fn test_verify_shared_transcript() {
+        use std::{sync::Arc, thread};
+
+        let (fixture, aggregated, _) = verify_fixtures();
+        let shared = Arc::new(aggregated);
+        let setup = Arc::new((fixture.config, fixture.registry));
+
+        // Verification only borrows the transcript, so threads can check the same one.
+        let handles = (0..2)
+            .map(|_| {
+                let (shared, setup) = (Arc::clone(&shared), Arc::clone(&setup));
+                thread::spawn(move || shared.verify(&setup.0, &setup.1).is_ok())
+            })
             .collect::<Vec<_>>();
 
-        let pid: usize = 1;
+        assert!(handles.into_iter().all(|handle| handle.join().unwrap()));
+        shared.verify(&setup.0, &setup.1).unwrap();
+    }
 
-	    let core = PVSSCore::<E> {
-            encs: //vec![<E as PairingEngine>::G1Projective::rand(rng); n],
-            comms: //vec![<E as PairingEngine>::G2Projective::rand(rng); n],
-        };
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_verify_par_agrees_with_verify() {
+        let (fixture, aggregated, invalid) = verify_fixtures();
+        let (conf, registry) = (&fixture.config, &fixture.registry);
+
+        aggregated.verify_par(conf, registry).unwrap();
+        for transcript in invalid.iter() {
+            let sequential = transcript.verify(conf, registry).unwrap_err();
+            let parallel = transcript.verify_par(conf, registry).unwrap_err();
+            assert_eq!(parallel.kind(), sequential.kind());
+        }
+    }
+
+    #[test]
+    fn test_aggregate_rejects_other_srs() {
+        let (t, n) = (1, 3);
+
+        // Two committees of equal (t, n), under independent SRSs.
+        let fixtures = [FixtureBuilder::new(t, n).build::<E>().unwrap(), FixtureBuilder::new(t, n).build::<E>().unwrap()];
+        let (share_a, share_b) = (fixtures[0].shares[0].clone(), fixtures[1].shares[1].clone());
+        let (conf_a, conf_b) = (&fixtures[0].config, &fixtures[1].config);
+        assert_eq!(share_a.config_digest, conf_a.digest());
+
+        let a = PVSSAggregatedShare::<E>::empty(t, n).aggregate_pvss_share(&share_a).unwrap();
+        let b = PVSSAggregatedShare::<E>::empty(t, n).aggregate_pvss_share(&share_b).unwrap();
+        assert_eq!(a.config_digest, conf_a.digest());
+        assert!(a.diff(&b).config);
+
+        assert!(matches!(a.aggregate(&b), Err(PVSSError::ConfigMismatch)));
+        assert!(matches!(a.clone().aggregate_owned(b.clone()), Err(PVSSError::ConfigMismatch)));
+        assert!(matches!(a.aggregate_pvss_share(&share_b), Err(PVSSError::ConfigMismatch)));
+        assert!(matches!(a.clone().aggregate_pvss_share_owned(share_b.clone()), Err(PVSSError::ConfigMismatch)));
+
+        let mut folded = a.clone();
+        assert!(matches!(folded.fold_share(&share_b), Err(PVSSError::ConfigMismatch)));
+        assert!(folded == a);
+
+        // Verification against the other configuration fails as well.
+        let pk_b = fixtures[1].keypairs[1].public_key_ed;
+        assert!(matches!(share_b.verify(conf_a, &pk_b), Err(PVSSError::ConfigMismatch)));
+        assert!(matches!(b.verify(conf_a, &fixtures[1].registry), Err(PVSSError::ConfigMismatch)));
+        b.verify(conf_b, &fixtures[1].registry).unwrap();
+
+        // An empty aggregate is not bound to a configuration until something is merged into it.
+        let empty = PVSSAggregatedShare::<E>::empty(t, n);
+        assert!(empty.aggregate(&b).unwrap() == b);
+        assert!(b.aggregate(&empty).unwrap() == b);
+    }
+
+    #[test]
+    fn test_fresh_aggregate_fast_path() {
+        let (t, n) = (1, 4);
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let (share_a, share_b) = (fixture.shares[0].clone(), fixture.shares[1].clone());
+
+        // The slow path: a round trip drops the flag of an empty core, so the additions with zero
+        // are carried out.
+        let mut bytes = vec![];
+        PVSSCore::<E>::empty(n).serialize(&mut bytes).unwrap();
+        let zero = PVSSCore::<E>::deserialize(&bytes[..]).unwrap();
+        let expected_core = AffinePVSSCore::from_projective(&zero.aggregate(&share_a.pvss_core).unwrap());
+
+        let fresh = PVSSAggregatedShare::<E>::empty(t, n);
+        assert!(fresh.is_fresh());
+
+        let single = fresh.aggregate_pvss_share(&share_a).unwrap();
+        assert!(single.pvss_core == expected_core && !single.is_fresh());
+        assert_eq!(single.config_digest, share_a.config_digest);
+        assert!(single.contributions.len() == 1 && single.contributions.get(&ParticipantId(0)) == Some(&share_a.signed_proof));
+
+        // All the ways into a fresh aggregate agree, also once it went through a round trip.
+        let mut bytes = vec![];
+        fresh.serialize(&mut bytes).unwrap();
+        let deserialized = PVSSAggregatedShare::<E>::deserialize(&bytes[..]).unwrap();
+        assert!(deserialized.is_fresh());
+
+        for start in [fresh, deserialized].iter() {
+            let mut folded = start.clone();
+            folded.fold_share(&share_a).unwrap();
+            assert!(folded == single);
+
+            assert!(start.clone().aggregate_pvss_share_owned(share_a.clone()).unwrap() == single);
+            assert!(start.aggregate(&single).unwrap() == single);
+            assert!(start.aggregate_opts(&single, true).unwrap() == single);
+            assert!(start.clone().aggregate_owned(single.clone()).unwrap() == single);
+        }
+
+        // Past the first share, aggregation takes the slow path, whatever the order.
+        let both = single.aggregate_pvss_share(&share_b).unwrap();
+        let other = PVSSAggregatedShare::<E>::empty(t, n).aggregate_pvss_share(&share_b).unwrap();
+        assert!(other.aggregate(&single).unwrap() == both);
+        assert!(other.aggregate_owned(single).unwrap() == both);
+    }
 
-        let dproof = Decomp::<E>::generate(rng, &conf, &poly.coeffs[0]).unwrap();
+    #[test]
+    fn test_verify_membership_and_proof() {
+        let rng = &mut thread_rng();
+        let (t, n) = (2, 5);
+
+        let mut simulations = [Simulation::<E>::new(rng, t, n).unwrap(), Simulation::<E>::new(rng, t, n).unwrap()];
+        let shares = (0..n).map(|id| simulations[0].nodes[id].dealer.deal(0, rng).unwrap().0).collect::<Vec<_>>();
+        let (redealt, _) = simulations[0].nodes[2].dealer.deal_override(0, rng).unwrap();
+        let (foreign, _) = simulations[1].nodes[2].dealer.deal(0, rng).unwrap();
+
+        let agg = PVSSAggregatedShare::<E>::aggregate_from_iter(t, n, shares.iter()).unwrap();
+        let root = agg.contributions_root().0;
+        let conf = &simulations[0].config;
+        let pk = |id: usize| simulations[0].registry.get_by_index(id).unwrap().public_key_ed;
+        let proof_of = |id: usize| agg.contributions.get(&ParticipantId::from_index(id)).unwrap();
+
+        // Every contribution checks out against the root (five leaves, so one is carried up as is).
+        for id in 0..n {
+            let merkle_proof = agg.membership_proof(ParticipantId::from_index(id)).unwrap();
+            verify_membership_and_proof(&root, ParticipantId::from_index(id), proof_of(id), &merkle_proof, conf, &pk(id)).unwrap();
+        }
+        assert!(agg.membership_proof(ParticipantId::from_index(n)).is_none());
+
+        // A single contribution is its own root.
+        let single = PVSSAggregatedShare::<E>::empty(t, n).aggregate_pvss_share(&shares[3]).unwrap();
+        let merkle_proof = single.membership_proof(ParticipantId(3)).unwrap();
+        assert!(merkle_proof.siblings.is_empty());
+        verify_membership_and_proof(&single.contributions_root().0, ParticipantId(3), proof_of(3), &merkle_proof, conf, &pk(3)).unwrap();
+
+        // Membership fails for another root, another id, a tampered path, or another (valid) proof.
+        let merkle_proof = agg.membership_proof(ParticipantId(2)).unwrap();
+        let is_not_member = |root: &[u8; 32], id: usize, signed_proof: &SignedProof<E>, merkle_proof: &MerkleProof| {
+            matches!(
+                verify_membership_and_proof(root, ParticipantId::from_index(id), signed_proof, merkle_proof, conf, &pk(id)),
+                Err(PVSSError::InvalidMembershipProof(bad_id)) if bad_id == id
+            )
+        };
+        assert!(is_not_member(&single.contributions_root().0, 2, proof_of(2), &merkle_proof));
+        assert!(is_not_member(&root, 1, proof_of(2), &merkle_proof));
+        assert!(is_not_member(&root, 1, proof_of(1), &merkle_proof));
+
+        let mut tampered = merkle_proof.clone();
+        tampered.siblings[0].0[0] ^= 1;
+        assert!(is_not_member(&root, 2, proof_of(2), &tampered));
+        let mut tampered = merkle_proof.clone();
+        tampered.siblings.push(Digest::default());
+        assert!(is_not_member(&root, 2, proof_of(2), &tampered));
+        let mut tampered = merkle_proof.clone();
+        tampered.index = tampered.num_leaves;
+        assert!(is_not_member(&root, 2, proof_of(2), &tampered));
+        assert!(is_not_member(&root, 2, &redealt.signed_proof, &merkle_proof));
+
+        // A member whose NIZK or signature does not verify is rejected as well.
+        match verify_membership_and_proof(&root, ParticipantId(2), proof_of(2), &merkle_proof, conf, &pk(4)) {
+            Err(PVSSError::SignatureVerificationFailed { participant_id }) => assert_eq!(participant_id, 2),
+            _ => panic!("expected SignatureVerificationFailed"),
+        }
 
-        check_serialization(core.clone());
-*/
+        let mut mixed = agg.clone();
+        mixed.contributions.insert(ParticipantId(2), foreign.signed_proof.clone()).unwrap();
+        let merkle_proof = mixed.membership_proof(ParticipantId(2)).unwrap();
+        match verify_membership_and_proof(&mixed.contributions_root().0, ParticipantId(2), &foreign.signed_proof, &merkle_proof, conf, &pk(2)) {
+            Err(PVSSError::NIZKProofDoesNotVerify { participant_id }) => assert_eq!(participant_id, 2),
+            _ => panic!("expected NIZKProofDoesNotVerify"),
+        }
     }
 
+
+    #[test]
+    fn test_deserialize_checked_runs_structural_checks() {
+        let t = 2;
+        let n = 4;
+
+        let share = FixtureBuilder::new(t, n).build::<E>().unwrap().shares.remove(1);
+        let agg = PVSSAggregatedShare::<E>::empty(t, n).aggregate_pvss_share(&share).unwrap();
+
+        let to_bytes = |agg: &PVSSAggregatedShare<E>| {
+            let mut bytes = vec![];
+            agg.serialize(&mut bytes).unwrap();
+            bytes
+        };
+
+        // A well-formed blob goes through.
+        let bytes = to_bytes(&agg);
+        assert!(deserialize_checked::<E, _>(&mut &bytes[..], n).unwrap() == agg);
+
+        // A blob holding a contribution from an out-of-range id is rejected, although it
+        // deserializes fine.
+        let mut out_of_range = agg.clone();
+        out_of_range.contributions.insert(ParticipantId::from_index(n + 2), share.signed_proof.clone()).unwrap();
+        let bytes = to_bytes(&out_of_range);
+        assert!(PVSSAggregatedShare::<E>::deserialize_checked(&bytes[..]).is_ok());
+        assert!(matches!(deserialize_checked::<E, _>(&mut &bytes[..], n), Err(PVSSError::InvalidParticipantId(id)) if id == n + 2));
+
+        // So are a truncated core and a committee larger than allowed.
+        let mut truncated = agg.clone();
+        truncated.pvss_core.encs.pop();
+        let bytes = to_bytes(&truncated);
+        assert!(matches!(deserialize_checked::<E, _>(&mut &bytes[..], n), Err(PVSSError::WrongCoreLength(got, expected)) if (got, expected) == (n - 1, n)));
+
+        let bytes = to_bytes(&agg);
+        match deserialize_checked::<E, _>(&mut &bytes[..], n - 1) {
+            Err(PVSSError::DeserializationFailed { field, .. }) => assert_eq!(field, "num_participants"),
+            _ => panic!("expected DeserializationFailed"),
+        }
+    }
 }
@@ -0,0 +1,62 @@
+use crate::{
+    modified_scrape::{
+        aggregator::PVSSAggregator,
+        checked::CountingReader,
+        errors::PVSSError,
+        share::PVSSAggregatedShare,
+    },
+    signature::schnorr::SchnorrSignature,
+};
+
+use ark_ec::PairingEngine;
+
+use rand::{CryptoRng, Rng};
+
+
+// Version byte prepended to every snapshot.
+pub const SNAPSHOT_VERSION: u8 = 2;
+
+
+// Function for opening a snapshot: checks its version byte and returns a reader over the
+// remaining bytes.
+pub(crate) fn open_snapshot<E: PairingEngine>(bytes: &[u8]) -> Result<CountingReader<&[u8]>, PVSSError<E>> {
+    let mut reader = CountingReader::new(bytes);
+
+    let version: u8 = reader.read_field("version")?;
+    if version != SNAPSHOT_VERSION {
+        return Err(PVSSError::SnapshotVersionError(version));
+    }
+
+    Ok(reader)
+}
+
+
+// Function for re-validating a restored (possibly partial) aggregated PVSS share: it must be
+// under the aggregator's configuration, and either be empty or pass aggregation verification,
+// with the contributions' decomposition proofs bound to the given context bytes.
+pub(crate) fn validate_aggregate<E: PairingEngine, R: Rng + CryptoRng>(
+    rng: &mut R,
+    aggregator: &mut PVSSAggregator<E, SchnorrSignature<E::G1Affine>>,
+    agg_share: &PVSSAggregatedShare<E>,
+    context: &[u8],
+) -> Result<(), PVSSError<E>> {
+    let (degree, num_participants) = (aggregator.config.degree, aggregator.config.num_participants);
+
+    if agg_share.degree != degree || agg_share.num_participants != num_participants {
+        return Err(PVSSError::TranscriptDifferentConfig(
+            agg_share.degree,
+            degree,
+            agg_share.num_participants,
+            num_participants,
+        ));
+    }
+
+    if agg_share.contributions.is_empty() {
+        if *agg_share != PVSSAggregatedShare::empty(degree, num_participants) {
+            return Err(PVSSError::SnapshotIntegrityError);
+        }
+        return Ok(());
+    }
+
+    aggregator.aggregation_verify_with_context(rng, agg_share, context)
+}
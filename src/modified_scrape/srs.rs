@@ -1,11 +1,14 @@
-use crate::modified_scrape::errors::PVSSError;
+use crate::{modified_scrape::errors::PVSSError, signature::utils::hash::hash_to_group};
 use ark_ec::{PairingEngine, ProjectiveCurve};
 use ark_ff::UniformRand;
-use rand::Rng;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use rand::{CryptoRng, Rng};
+
+const PERSONALIZATION: &[u8] = b"OPTRDSRS";   // persona for deriving SRS generators from a seed
 
 /* The Structured Reference String (SRS) of the modified SCRAPE PVSS scheme. */
 
-#[derive(Clone)]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone)]
 pub struct SRS<E: PairingEngine> {
     pub g1: E::G1Affine,        // generator g_1 of the public key group G_1
     pub g2: E::G2Affine,        // generator g_2 of the commitment group G_2
@@ -15,11 +18,59 @@ pub struct SRS<E: PairingEngine> {
 impl<E: PairingEngine> SRS<E> {
 
     // Function setup generates an SRS instance using a specified RNG.
-    pub fn setup<R: Rng>(rng: &mut R) -> Result<Self, PVSSError<E>> {
+    pub fn setup<R: Rng + CryptoRng>(rng: &mut R) -> Result<Self, PVSSError<E>> {
         Ok(Self {
             g1: E::G1Projective::rand(rng).into_affine(),
             g2: E::G2Projective::rand(rng).into_affine(),
             g2_prime: E::G2Projective::rand(rng).into_affine(),
         })
     }
+
+    // Function deriving an SRS instance from a public seed, by hashing the seed (along with a
+    // per-generator label) to each group. Since the generators are outputs of a hash function,
+    // nobody knows a discrete log relation between them. The RNG is unused, and only kept for
+    // symmetry with setup.
+    pub fn setup_with_nothing_up_my_sleeve<R: Rng>(_rng: &mut R, seed: &[u8]) -> Result<Self, PVSSError<E>> {
+        Ok(Self {
+            g1: hash_to_group::<E::G1Affine>(PERSONALIZATION, &[seed, b"g1"].concat())?.into_affine(),
+            g2: hash_to_group::<E::G2Affine>(PERSONALIZATION, &[seed, b"g2"].concat())?.into_affine(),
+            g2_prime: hash_to_group::<E::G2Affine>(PERSONALIZATION, &[seed, b"g2_prime"].concat())?.into_affine(),
+        })
+    }
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::SRS;
+    use crate::Scalar;
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ec::AffineCurve;
+    use ark_ff::{FpParameters, PrimeField, Zero};
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_setup_with_nothing_up_my_sleeve() {
+        let rng = &mut thread_rng();
+
+        let srs = SRS::<E>::setup_with_nothing_up_my_sleeve(rng, b"optrand").unwrap();
+        let same = SRS::<E>::setup_with_nothing_up_my_sleeve(rng, b"optrand").unwrap();
+        let other = SRS::<E>::setup_with_nothing_up_my_sleeve(rng, b"optrand'").unwrap();
+
+        // The same seed reproduces the same SRS; another seed does not.
+        assert_eq!((srs.g1, srs.g2, srs.g2_prime), (same.g1, same.g2, same.g2_prime));
+        assert!(srs.g1 != other.g1 && srs.g2 != other.g2 && srs.g2_prime != other.g2_prime);
+        assert!(srs.g2 != srs.g2_prime);
+
+        // The generators are non-trivial elements of the prime-order subgroups.
+        let order = <Scalar<E> as PrimeField>::Params::MODULUS;
+        assert!(srs.g1.is_on_curve() && !srs.g1.is_zero() && srs.g1.mul(order).is_zero());
+        assert!(srs.g2.is_on_curve() && !srs.g2.is_zero() && srs.g2.mul(order).is_zero());
+        assert!(srs.g2_prime.is_on_curve() && !srs.g2_prime.is_zero() && srs.g2_prime.mul(order).is_zero());
+    }
 }
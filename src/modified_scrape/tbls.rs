@@ -0,0 +1,325 @@
+use crate::{
+    modified_scrape::{
+        config::Config,
+        errors::PVSSError,
+        poly::{lagrange_coefficients, lagrange_interpolation_simple},
+        share::PVSSAggregatedShare,
+    },
+    signature::utils::{errors::SignatureError, hash::hash_to_group},
+    Scalar,
+};
+
+use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+use ark_ff::{One, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use ark_std::{collections::BTreeMap, vec::Vec};
+
+use ark_std::ops::Neg;
+
+
+const PERSONALIZATION: &[u8] = b"OPTRTBLS";   // persona for hashing messages to G1
+
+
+// Struct PartialSig models a participant's BLS signature share on some message,
+// i.e., H(m)^{p(j)} where p(j) is the participant's share of the secret.
+#[derive(Clone, Copy, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PartialSig<E: PairingEngine> {
+    pub sig: E::G1Affine,   // signature share (in G1)
+}
+
+// Struct ThresholdSignature models a combined BLS signature H(m)^{p(0)}, which verifies
+// against the group public key g2^{p(0)}.
+#[derive(Clone, Copy, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ThresholdSignature<E: PairingEngine> {
+    pub sig: E::G1Affine,   // combined signature (in G1)
+}
+
+// Struct ThresholdPubKey bundles the group public key g2^{p(0)} of an aggregated PVSS
+// transcript with its threshold parameters, for handing the transcript off to threshold
+// signing: any t + 1 of the n participants can sign (see combine and verify).
+#[derive(Clone, Copy, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ThresholdPubKey<E: PairingEngine> {
+    pub group_pk: E::G2Affine,   // group public key g2^{p(0)} (in G2)
+    pub n: usize,                // number of participants
+    pub t: usize,                // polynomial degree
+}
+
+impl<E: PairingEngine> ThresholdPubKey<E> {
+    // Function extracting the threshold public key of an aggregated PVSS transcript under conf.
+    // The transcript is expected to have been verified (see PVSSAggregator::aggregation_verify);
+    // only its configuration, and the consistency of the group public key interpolated from
+    // its commitments with the sum of its contributors' commitments gs, are checked here.
+    pub fn from_transcript(share: &PVSSAggregatedShare<E>, conf: &Config<E>) -> Result<Self, PVSSError<E>> {
+        if share.degree != conf.degree || share.num_participants != conf.num_participants {
+            return Err(PVSSError::TranscriptDifferentConfig(
+                conf.degree,
+                share.degree,
+                conf.num_participants,
+                share.num_participants,
+            ));
+        }
+        if share.contributions.is_empty() {
+            return Err(PVSSError::EmptyTranscript);
+        }
+        if share.config_digest != conf.digest() {
+            return Err(PVSSError::ConfigMismatch);
+        }
+
+        let comms = share.pvss_core.comms.iter().map(|comm| comm.into_projective()).collect::<Vec<_>>();
+        if comms.len() != conf.num_participants {
+            return Err(PVSSError::MismatchedCommitsEncryptionsParticipantsError(
+                share.pvss_core.encs.len(),
+                comms.len(),
+                conf.num_participants,
+            ));
+        }
+
+        let group_pk = lagrange_interpolation_simple::<E>(&comms, conf.degree as u64)?;
+
+        let mut gs_total = E::G2Projective::zero();
+        for signed_proof in share.contributions.values() {
+            gs_total.add_assign_mixed(&signed_proof.decomp_proof().gs);
+        }
+        if group_pk != gs_total {
+            return Err(PVSSError::AggregationReconstructionMismatchError);
+        }
+
+        Ok(Self { group_pk: group_pk.into_affine(), n: conf.num_participants, t: conf.degree })
+    }
+}
+
+
+// Utility function for hashing a message onto G1.
+fn hash_message<E: PairingEngine>(msg: &[u8]) -> Result<E::G1Projective, PVSSError<E>> {
+    Ok(hash_to_group::<E::G1Affine>(PERSONALIZATION, msg)?)
+}
+
+
+// Function for producing a participant's signature share on msg using its secret share p(j).
+pub fn partial_sign<E: PairingEngine>(msg: &[u8], share: &Scalar<E>) -> Result<PartialSig<E>, PVSSError<E>> {
+    let h = hash_message::<E>(msg)?;
+
+    Ok(PartialSig { sig: h.mul(share.into_repr()).into_affine() })
+}
+
+
+// Function for verifying a participant's signature share against its public commitment
+// g2^{p(j)} (i.e., the participant's entry in the aggregated commitment vector).
+pub fn verify_partial<E: PairingEngine>(
+    msg: &[u8],
+    partial: &PartialSig<E>,
+    share_comm: &E::G2Affine,
+    conf: &Config<E>,
+) -> Result<(), PVSSError<E>> {
+    verify_pairing(msg, &partial.sig, share_comm, conf)
+}
+
+
+// Function for combining at least degree + 1 signature shares into a threshold signature.
+// The map is keyed by participant id; participant j's evaluation point is j + 1.
+// Lagrange interpolation at zero is carried out in the exponent.
+pub fn combine<E: PairingEngine>(
+    partials: &BTreeMap<usize, PartialSig<E>>,
+    conf: &Config<E>,
+) -> Result<ThresholdSignature<E>, PVSSError<E>> {
+    if partials.len() < conf.degree + 1 {
+        return Err(PVSSError::InsufficientShares { needed: conf.degree + 1, got: partials.len() });
+    }
+
+    // Any degree + 1 shares determine the signature; use the first ones.
+    let selected = partials.iter().take(conf.degree + 1).collect::<Vec<_>>();
+
+    let points = selected
+        .iter()
+        .map(|(id, _)| Scalar::<E>::from((**id as u64) + 1))
+        .collect::<Vec<_>>();
+    let coeffs = lagrange_coefficients::<E>(&points)?;
+
+    let mut sig = E::G1Projective::zero();
+    for ((_, partial), lambda) in selected.iter().zip(coeffs.iter()) {
+        sig += partial.sig.mul(lambda.into_repr());
+    }
+
+    Ok(ThresholdSignature { sig: sig.into_affine() })
+}
+
+
+// Function for verifying a threshold signature on msg against the group public key g2^{p(0)}.
+pub fn verify<E: PairingEngine>(
+    msg: &[u8],
+    sig: &ThresholdSignature<E>,
+    group_pk: &E::G2Affine,
+    conf: &Config<E>,
+) -> Result<(), PVSSError<E>> {
+    verify_pairing(msg, &sig.sig, group_pk, conf)
+}
+
+
+// Utility function checking the BLS pairing equation e(sig, g2) = e(H(m), pk).
+fn verify_pairing<E: PairingEngine>(
+    msg: &[u8],
+    sig: &E::G1Affine,
+    pk: &E::G2Affine,
+    conf: &Config<E>,
+) -> Result<(), PVSSError<E>> {
+    let h = hash_message::<E>(msg)?;
+
+    let pairs = [
+        ((*sig).into(), conf.g2_prepared().clone()),
+        (h.neg().into_affine().into(), (*pk).into()),
+    ];
+
+    if !E::final_exponentiation(&E::miller_loop(pairs.iter())).map_or(false, |p| p.is_one()) {
+        return Err(PVSSError::SignatureError(SignatureError::BLSVerify));
+    }
+
+    Ok(())
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{combine, partial_sign, verify, verify_partial, ThresholdPubKey};
+    use crate::modified_scrape::{
+        config::Config,
+        errors::PVSSError,
+        poly::{Polynomial, lagrange_interpolation_simple},
+        share::PVSSAggregatedShare,
+        srs::SRS,
+    };
+    use crate::signature::utils::tests::check_serialization;
+    use crate::testing::simulation::Simulation;
+    use crate::Scalar;
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ec::{AffineCurve, ProjectiveCurve};
+    use ark_ff::PrimeField;
+    use ark_poly::{Polynomial as Poly, UVPolynomial};
+    use ark_std::collections::BTreeMap;
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_threshold_sign_end_to_end() {
+        let rng = &mut thread_rng();
+        let t = 3;
+        let n = 10;
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+
+        // Deal: sample a degree t polynomial, compute each participant's share and commitment.
+        let poly = Polynomial::<E>::rand(t, rng);
+        let shares = (0..n)
+            .map(|j| poly.evaluate(&Scalar::<E>::from((j + 1) as u64)))
+            .collect::<Vec<_>>();
+        let comms = shares
+            .iter()
+            .map(|s| conf.srs.g2.mul(s.into_repr()))
+            .collect::<Vec<_>>();
+
+        // The group public key is the commitment to the free term, obtained from the commitments.
+        let group_pk = lagrange_interpolation_simple::<E>(&comms, t as u64).unwrap().into_affine();
+        assert_eq!(group_pk, conf.srs.g2.mul(poly[0].into_repr()).into_affine());
+
+        let msg = b"optrand epoch 1";
+
+        // Partial-sign with an arbitrary set of t + 1 participants.
+        let mut partials = BTreeMap::new();
+        for &j in [1_usize, 4, 6, 9].iter() {
+            let partial = partial_sign::<E>(msg, &shares[j]).unwrap();
+            verify_partial(msg, &partial, &comms[j].into_affine(), &conf).unwrap();
+            partials.insert(j, partial);
+        }
+
+        let sig = combine(&partials, &conf).unwrap();
+        verify(msg, &sig, &group_pk, &conf).unwrap();
+
+        // The combined signature is independent of the chosen subset.
+        let mut other_partials = BTreeMap::new();
+        for &j in [0_usize, 2, 3, 8].iter() {
+            other_partials.insert(j, partial_sign::<E>(msg, &shares[j]).unwrap());
+        }
+        assert_eq!(combine(&other_partials, &conf).unwrap(), sig);
+
+        // Wrong message fails to verify.
+        assert!(verify(b"another message", &sig, &group_pk, &conf).is_err());
+
+        check_serialization(sig);
+    }
+
+    #[test]
+    fn test_combine_insufficient_partials() {
+        let rng = &mut thread_rng();
+        let t = 3;
+        let n = 10;
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+        let poly = Polynomial::<E>::rand(t, rng);
+
+        let partials = (0..t)
+            .map(|j| (j, partial_sign::<E>(b"msg", &poly.evaluate(&Scalar::<E>::from((j + 1) as u64))).unwrap()))
+            .collect::<BTreeMap<_, _>>();
+
+        match combine(&partials, &conf) {
+            Err(PVSSError::InsufficientShares { needed, got }) => assert_eq!((needed, got), (t + 1, t)),
+            _ => panic!("expected InsufficientShares"),
+        }
+    }
+
+    #[test]
+    fn test_invalid_partial_rejected() {
+        let rng = &mut thread_rng();
+        let t = 3;
+        let n = 10;
+
+        let srs = SRS::<E>::setup(rng).unwrap();   // setup PVSS scheme's SRS
+        let conf = Config::new(srs, t, n);
+        let poly = Polynomial::<E>::rand(t, rng);
+
+        let share = poly.evaluate(&Scalar::<E>::from(1u64));
+        let comm = conf.srs.g2.mul(share.into_repr()).into_affine();
+
+        // Signing with a different share does not verify against the commitment.
+        let partial = partial_sign::<E>(b"msg", &(share + Scalar::<E>::from(1u64))).unwrap();
+        assert!(verify_partial(b"msg", &partial, &comm, &conf).is_err());
+    }
+
+    #[test]
+    fn test_threshold_pub_key_from_transcript() {
+        let rng = &mut thread_rng();
+        let (t, n) = (2, 5);
+
+        let mut simulation = Simulation::<E>::new(rng, t, n).unwrap();
+        let mut aggregated = PVSSAggregatedShare::empty(t, n);
+        let mut secret = Scalar::<E>::from(0u64);
+        for node in simulation.nodes.iter_mut().take(t + 1) {
+            let (share, secrets) = node.dealer.deal(0, rng).unwrap();
+            aggregated.fold_share(&share).unwrap();
+            secret += &secrets.p_0;
+        }
+        let conf = &simulation.config;
+
+        // The group public key commits to the sum of the contributed secrets.
+        let tpk = ThresholdPubKey::from_transcript(&aggregated, conf).unwrap();
+        assert_eq!(tpk.group_pk, conf.srs.g2.mul(secret.into_repr()).into_affine());
+        assert_eq!((tpk.n, tpk.t), (n, t));
+        check_serialization(tpk);
+
+        // Empty transcripts, transcripts of another configuration, and tampered commitments are rejected.
+        assert!(matches!(ThresholdPubKey::from_transcript(&PVSSAggregatedShare::empty(t, n), conf), Err(PVSSError::EmptyTranscript)));
+
+        let other = Config::new(SRS::<E>::setup(rng).unwrap(), t, n);
+        assert!(matches!(ThresholdPubKey::from_transcript(&aggregated, &other), Err(PVSSError::ConfigMismatch)));
+        let other = Config::new(conf.srs.clone(), t + 1, n);
+        assert!(matches!(ThresholdPubKey::from_transcript(&aggregated, &other), Err(PVSSError::TranscriptDifferentConfig(..))));
+
+        let mut tampered = aggregated.clone();
+        tampered.pvss_core.comms = tampered.pvss_core.comms.iter().map(|comm| comm.mul(2u64).into_affine()).collect();
+        assert!(matches!(ThresholdPubKey::from_transcript(&tampered, conf), Err(PVSSError::AggregationReconstructionMismatchError)));
+    }
+}
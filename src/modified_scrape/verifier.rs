@@ -0,0 +1,490 @@
+use crate::modified_scrape::{
+    config::Config,
+    decomp::DecompProof,
+    decryption::{verify_decryption, DecryptionProof},
+    epoch::chain_context,
+    errors::PVSSError,
+    participant::ParticipantRegistry,
+    pvss::PVSSCore,
+    recon::{beacon, reconstruct},
+    roles::OptrandScheme,
+    share::{PVSSAggregatedShare, SignedProof},
+};
+use crate::{read_array, Digest, Scalar, Signature};
+
+use ark_ec::PairingEngine;
+use ark_ff::Zero;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use ark_std::{collections::BTreeMap, string::ToString, vec::Vec};
+
+use rand::{CryptoRng, Rng};
+
+#[cfg(feature = "std")]
+use std::{
+    fs::{self, File},
+    path::Path,
+};
+
+
+// Magic bytes opening a transcript archive (see write_to_path). They are followed by
+// ARCHIVE_VERSION and by the length of the serialized transcript, as a little-endian u64.
+#[cfg(feature = "std")]
+pub const ARCHIVE_MAGIC: [u8; 4] = *b"OPTX";
+
+// Version of the transcript archive format. Any change to the header or to the transcript's
+// serialization must bump it.
+#[cfg(feature = "std")]
+pub const ARCHIVE_VERSION: u8 = 2;
+
+#[cfg(feature = "std")]
+const ARCHIVE_HEADER_LEN: usize = 13;
+
+
+/* Struct EpochTranscript bundles everything an epoch publishes: the epoch number and the
+   previous epoch's output (which the contributions are bound to), the aggregated share, the
+   decrypted shares along with their proofs (keyed by the decryptor's id), and the claimed
+   beacon output. */
+#[derive(Clone)]
+pub struct EpochTranscript<E: PairingEngine> {
+    pub epoch: u64,                                                         // the epoch number
+    pub prev_output: [u8; 32],                                              // the previous epoch's output
+    pub aggregated: PVSSAggregatedShare<E>,                                 // the epoch's aggregated PVSS share
+    pub decryptions: BTreeMap<usize, (E::G1Affine, DecryptionProof<E>)>,   // decrypted shares with proofs
+    pub beacon: [u8; 32],                                                   // the claimed beacon output
+}
+
+
+impl<E: PairingEngine> CanonicalSerialize for EpochTranscript<E> {
+    fn serialize<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        self.epoch.serialize(&mut writer)?;
+        writer.write_all(&self.prev_output)?;
+        self.aggregated.serialize(&mut writer)?;
+
+        (self.decryptions.len() as u64).serialize(&mut writer)?;
+        for (id, (dec, proof)) in self.decryptions.iter() {
+            id.serialize(&mut writer)?;
+            dec.serialize(&mut writer)?;
+            proof.serialize(&mut writer)?;
+        }
+
+        writer.write_all(&self.beacon)?;
+        Ok(())
+    }
+
+    fn serialized_size(&self) -> usize {
+        let decryptions = self
+            .decryptions
+            .iter()
+            .map(|(id, (dec, proof))| id.serialized_size() + dec.serialized_size() + proof.serialized_size())
+            .sum::<usize>();
+
+        self.epoch.serialized_size() + self.prev_output.len() + self.aggregated.serialized_size()
+            + 8 + decryptions + self.beacon.len()
+    }
+}
+
+impl<E: PairingEngine> CanonicalDeserialize for EpochTranscript<E> {
+    fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let epoch = u64::deserialize(&mut reader)?;
+        let prev_output = read_array(&mut reader)?;
+        let aggregated = PVSSAggregatedShare::deserialize(&mut reader)?;
+
+        let len = u64::deserialize(&mut reader)?;
+        let mut decryptions = BTreeMap::new();
+        for _ in 0..len {
+            let id = usize::deserialize(&mut reader)?;
+            let dec = E::G1Affine::deserialize(&mut reader)?;
+            let proof = DecryptionProof::<E>::deserialize(&mut reader)?;
+            decryptions.insert(id, (dec, proof));
+        }
+
+        let beacon = read_array(&mut reader)?;
+
+        Ok(Self { epoch, prev_output, aggregated, decryptions, beacon })
+    }
+}
+
+impl<E: PairingEngine> EpochTranscript<E> {
+    // Function returning the serialized size of the largest transcript conf allows, i.e., one
+    // where every participant contributed and decrypted.
+    pub fn max_serialized_size(conf: &Config<E>) -> usize {
+        let n = conf.num_participants;
+        let id = 0usize.serialized_size();
+        let g1 = E::G1Affine::zero().serialized_size();
+        let fr = Scalar::<E>::zero().serialized_size();
+
+        let signed_proof = SignedProof::<E>::new(
+            DecompProof {
+                proof: (E::G2Affine::zero(), Scalar::<E>::zero(), Scalar::<E>::zero()),
+                gs: E::G2Affine::zero(),
+            },
+            Signature::default(),
+        )
+        .serialized_size();
+        let digest = Digest::default().serialized_size();
+        let aggregated = 2 * id + digest + PVSSCore::<E>::empty(n).serialized_size() + 8 + n * (id + signed_proof);
+        let decryption = id + g1 + 2 * g1 + 2 * fr;
+
+        8 + 32 + aggregated + 8 + n * decryption + 32
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: PairingEngine> EpochTranscript<E> {
+    // Method archiving the transcript at path, as a header (see ARCHIVE_MAGIC) followed by the
+    // serialized transcript. The archive is written to a temporary file next to path, synced,
+    // and renamed over path, so that a crash leaves either the previous file or the complete
+    // new one, but never a partial one.
+    pub fn write_to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), PVSSError<E>> {
+        let path = path.as_ref();
+        let mut tmp_name = path
+            .file_name()
+            .ok_or_else(|| PVSSError::IoError(format!("{} does not name a file", path.display())))?
+            .to_os_string();
+        tmp_name.push(".tmp");
+        let tmp_path = path.with_file_name(tmp_name);
+
+        let mut bytes = Vec::with_capacity(ARCHIVE_HEADER_LEN + self.serialized_size());
+        bytes.extend_from_slice(&ARCHIVE_MAGIC);
+        bytes.push(ARCHIVE_VERSION);
+        bytes.extend_from_slice(&(self.serialized_size() as u64).to_le_bytes());
+        self.serialize(&mut bytes)?;
+
+        let written = (|| -> std::io::Result<()> {
+            let mut file = File::create(&tmp_path)?;
+            file.write_all(&bytes)?;
+            file.sync_all()?;
+            fs::rename(&tmp_path, path)
+        })();
+        if let Err(e) = written {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(PVSSError::IoError(e.to_string()));
+        }
+
+        // Sync the directory as well, so that the rename itself survives a crash.
+        #[cfg(unix)]
+        {
+            let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or_else(|| Path::new("."));
+            File::open(dir)
+                .and_then(|dir| dir.sync_all())
+                .map_err(|e| PVSSError::IoError(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    // Function reading a transcript archived by write_to_path under conf. The header must be
+    // valid, the file may not exceed the largest transcript conf allows (so that a corrupt file
+    // cannot exhaust memory), and the transcript must be of conf's degree and number of
+    // participants. No verification is done beyond decoding (see read_verified_from_path).
+    pub fn read_from_path<P: AsRef<Path>>(path: P, conf: &Config<E>) -> Result<Self, PVSSError<E>> {
+        let max_len = Self::max_serialized_size(conf);
+
+        // Read at most one byte past the largest valid archive, which suffices to reject it.
+        let mut bytes = Vec::new();
+        File::open(path)
+            .and_then(|file| file.take((ARCHIVE_HEADER_LEN + max_len + 1) as u64).read_to_end(&mut bytes))
+            .map_err(|e| PVSSError::IoError(e.to_string()))?;
+
+        if bytes.len() < ARCHIVE_HEADER_LEN {
+            return Err(PVSSError::InvalidArchive(format!("truncated header ({} bytes)", bytes.len())));
+        }
+
+        let (header, payload) = bytes.split_at(ARCHIVE_HEADER_LEN);
+        if header[..4] != ARCHIVE_MAGIC {
+            return Err(PVSSError::InvalidArchive("not a transcript archive".to_string()));
+        }
+        if header[4] != ARCHIVE_VERSION {
+            return Err(PVSSError::InvalidArchive(format!("unsupported version {}", header[4])));
+        }
+
+        let mut len_bytes = [0u8; 8];
+        len_bytes.copy_from_slice(&header[5..]);
+        let len = u64::from_le_bytes(len_bytes);
+        if len > max_len as u64 {
+            return Err(PVSSError::InvalidArchive(format!("transcript of {} bytes, at most {} allowed", len, max_len)));
+        }
+        if payload.len() as u64 != len {
+            return Err(PVSSError::InvalidArchive(format!("{} transcript bytes, header claims {}", payload.len(), len)));
+        }
+
+        let mut reader = payload;
+        let transcript = Self::deserialize(&mut reader)?;
+        if !reader.is_empty() {
+            return Err(PVSSError::InvalidArchive(format!("{} trailing bytes", reader.len())));
+        }
+
+        let aggregated = &transcript.aggregated;
+        if aggregated.degree != conf.degree || aggregated.num_participants != conf.num_participants {
+            return Err(PVSSError::TranscriptDifferentConfig(
+                conf.degree,
+                aggregated.degree,
+                conf.num_participants,
+                aggregated.num_participants,
+            ));
+        }
+
+        Ok(transcript)
+    }
+
+    // Function reading a transcript as read_from_path does, then verifying it (see verify_epoch).
+    // Returns the transcript along with its beacon output.
+    pub fn read_verified_from_path<R: Rng + CryptoRng, P: AsRef<Path>>(
+        rng: &mut R,
+        path: P,
+        conf: &Config<E>,
+        registry: &ParticipantRegistry<E>,
+    ) -> Result<(Self, [u8; 32]), PVSSError<E>> {
+        let transcript = Self::read_from_path(path, conf)?;
+        let value = verify_epoch(rng, conf, registry, &transcript)?;
+
+        Ok((transcript, value))
+    }
+}
+
+
+// Function for verifying an epoch's transcript without holding any keys.
+// The aggregated share, its contributions (which must be bound to the transcript's epoch and
+// previous output) and every decryption are checked, the secret is reconstructed from the
+// decryptions, and the recomputed beacon output (which is returned) must match the claimed one.
+pub fn verify_epoch<E: PairingEngine, R: Rng + CryptoRng>(
+    rng: &mut R,
+    conf: &Config<E>,
+    registry: &ParticipantRegistry<E>,
+    transcript: &EpochTranscript<E>,
+) -> Result<[u8; 32], PVSSError<E>> {
+    let aggregated = &transcript.aggregated;
+
+    // Verify the aggregated share, and that enough parties contributed to it.
+    let context = chain_context(transcript.epoch, &transcript.prev_output);
+    OptrandScheme::new(conf.clone())
+        .aggregator(registry)
+        .aggregation_verify_with_context(rng, aggregated, &context)?;
+
+    let threshold = conf.degree + 1;
+    if aggregated.contributions.len() < threshold {
+        return Err(PVSSError::ThresholdNotMet { threshold, got: aggregated.contributions.len() });
+    }
+
+    // Verify every decrypted share against the encryption it stems from.
+    for (id, (dec, proof)) in transcript.decryptions.iter() {
+        let participant = registry.get_by_index(*id)?;
+        let enc = aggregated
+            .pvss_core
+            .encs
+            .get(*id)
+            .ok_or(PVSSError::InvalidParticipantId(*id))?;
+
+        if !verify_decryption(conf, &participant.public_key_sig, enc, dec, proof) {
+            return Err(PVSSError::DecryptionProofVerificationFailed { participant_id: *id });
+        }
+    }
+
+    // Reconstruct the secret and recompute the beacon output.
+    let shares = transcript
+        .decryptions
+        .iter()
+        .map(|(id, (dec, _))| (*id, *dec))
+        .collect::<BTreeMap<_, _>>();
+    let value = beacon(conf, &reconstruct(&shares, conf)?)?;
+
+    if value != transcript.beacon {
+        return Err(PVSSError::BeaconMismatchError);
+    }
+
+    Ok(value)
+}
+
+
+// Function for verifying a chain of consecutive epoch transcripts, starting at epoch 0 from
+// the genesis value: each transcript must verify and point to its predecessor's output.
+// Returns the epochs' outputs.
+pub fn verify_chain<E: PairingEngine, R: Rng + CryptoRng>(
+    rng: &mut R,
+    conf: &Config<E>,
+    registry: &ParticipantRegistry<E>,
+    genesis: [u8; 32],
+    transcripts: &[EpochTranscript<E>],
+) -> Result<Vec<[u8; 32]>, PVSSError<E>> {
+    let mut prev_output = genesis;
+    let mut outputs = Vec::with_capacity(transcripts.len());
+
+    for (epoch, transcript) in transcripts.iter().enumerate() {
+        if transcript.epoch != epoch as u64 || transcript.prev_output != prev_output {
+            return Err(PVSSError::EpochChainError { epoch: epoch as u64 });
+        }
+
+        prev_output = verify_epoch(rng, conf, registry, transcript)?;
+        outputs.push(prev_output);
+    }
+
+    Ok(outputs)
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{verify_epoch, EpochTranscript, ARCHIVE_VERSION};
+    use crate::{
+        generate_production_keypair,
+        modified_scrape::{
+            config::Config,
+            decryption::decrypt_with_proof,
+            epoch::EpochState,
+            errors::PVSSError,
+            participant::{ParticipantId, ParticipantRegistry},
+            recon::beacon,
+        },
+        testing::fixtures::FixtureBuilder,
+        Scalar,
+        Signature,
+    };
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ec::{AffineCurve, ProjectiveCurve};
+    use ark_ff::{PrimeField, Zero};
+
+    use ark_serialize::CanonicalSerialize;
+
+    use rand::{thread_rng, Rng};
+
+    use std::fs;
+
+    // Runs an honest epoch with n participants and threshold degree t.
+    fn honest_epoch(t: usize, n: usize) -> (Config<E>, ParticipantRegistry<E>, EpochTranscript<E>, Scalar<E>) {
+        let rng = &mut thread_rng();
+
+        // The committee's dealings are bound to the epoch, so only its keys are used.
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let (conf, registry) = (fixture.config, fixture.registry);
+
+        // Everyone deals; the shares are aggregated.
+        let mut state = EpochState::new(&conf, [0u8; 32]);
+        let mut secret = Scalar::<E>::zero();
+        for (id, keys) in fixture.keypairs.iter().enumerate() {
+            let (share, secrets) = state.deal(rng, &conf, &registry, id, &keys.secret_key_ed).unwrap();
+            state.receive_share(rng, &conf, &registry, &share).unwrap();
+            secret += secrets.p_0;
+        }
+
+        // Everyone decrypts its share.
+        let decryptions = fixture
+            .keypairs
+            .iter()
+            .enumerate()
+            .map(|(id, keys)| (id, decrypt_with_proof(rng, &conf, &state.aggregate.pvss_core.encs[id], &keys.secret_key).unwrap()))
+            .collect();
+
+        let value = beacon(&conf, &conf.srs.g1.mul(secret.into_repr()).into_affine()).unwrap();
+        let transcript = state.transcript(decryptions, value);
+
+        (conf, registry, transcript, secret)
+    }
+
+    #[test]
+    fn test_verify_epoch() {
+        let rng = &mut thread_rng();
+        let (conf, registry, transcript, _) = honest_epoch(2, 5);
+
+        assert_eq!(verify_epoch(rng, &conf, &registry, &transcript).unwrap(), transcript.beacon);
+    }
+
+    #[test]
+    fn test_verify_epoch_bad_contribution_signature() {
+        let rng = &mut thread_rng();
+        let (conf, registry, mut transcript, _) = honest_epoch(2, 5);
+
+        let (_pk, sk) = generate_production_keypair();
+        let contribution = transcript.aggregated.contributions.get_mut(&ParticipantId(3)).unwrap();
+        contribution.signature_on_decomp = Signature::new(contribution.digest(), &sk);
+
+        match verify_epoch(rng, &conf, &registry, &transcript) {
+            Err(PVSSError::SignatureVerificationFailed { participant_id }) => assert_eq!(participant_id, 3),
+            _ => panic!("expected SignatureVerificationFailed"),
+        }
+    }
+
+    #[test]
+    fn test_verify_epoch_bad_decryption_proof() {
+        let rng = &mut thread_rng();
+        let (conf, registry, mut transcript, _) = honest_epoch(2, 5);
+
+        // Claim another participant's decryption, along with its (valid) proof.
+        let other = transcript.decryptions[&2];
+        transcript.decryptions.insert(1, other);
+
+        match verify_epoch(rng, &conf, &registry, &transcript) {
+            Err(PVSSError::DecryptionProofVerificationFailed { participant_id }) => assert_eq!(participant_id, 1),
+            _ => panic!("expected DecryptionProofVerificationFailed"),
+        }
+    }
+
+    #[test]
+    fn test_verify_epoch_wrong_beacon() {
+        let rng = &mut thread_rng();
+        let (conf, registry, mut transcript, secret) = honest_epoch(2, 5);
+
+        // The beacon of a different secret.
+        let wrong = conf.srs.g1.mul((secret + Scalar::<E>::from(1u64)).into_repr()).into_affine();
+        transcript.beacon = beacon(&conf, &wrong).unwrap();
+
+        match verify_epoch(rng, &conf, &registry, &transcript) {
+            Err(PVSSError::BeaconMismatchError) => (),
+            _ => panic!("expected BeaconMismatchError"),
+        }
+    }
+
+    #[test]
+    fn test_transcript_archive() {
+        let rng = &mut thread_rng();
+        let (conf, registry, transcript, _) = honest_epoch(2, 5);
+
+        let dir = std::env::temp_dir().join(format!("optrand-archive-{}-{:x}", std::process::id(), rng.gen::<u64>()));
+        fs::create_dir(&dir).unwrap();
+        let path = dir.join("epoch.bin");
+
+        let bytes = |transcript: &EpochTranscript<E>| {
+            let mut bytes = vec![];
+            transcript.serialize(&mut bytes).unwrap();
+            bytes
+        };
+
+        // Round trip, leaving no temporary file behind; rewriting replaces the archive.
+        transcript.write_to_path(&path).unwrap();
+        transcript.write_to_path(&path).unwrap();
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        let read = EpochTranscript::<E>::read_from_path(&path, &conf).unwrap();
+        assert_eq!(bytes(&read), bytes(&transcript));
+        let (read, value) = EpochTranscript::<E>::read_verified_from_path(rng, &path, &conf, &registry).unwrap();
+        assert_eq!(bytes(&read), bytes(&transcript));
+        assert_eq!(value, transcript.beacon);
+
+        // Truncated files, and files with a corrupt header, are rejected.
+        let archive = fs::read(&path).unwrap();
+        let mut bad_version = archive.clone();
+        bad_version[4] = ARCHIVE_VERSION + 1;
+        let mut bad_len = archive.clone();
+        bad_len[5..13].copy_from_slice(&u64::MAX.to_le_bytes());
+        for corrupt in [archive[..archive.len() - 10].to_vec(), archive[..5].to_vec(), bad_version, bad_len].iter() {
+            fs::write(&path, corrupt).unwrap();
+            assert!(matches!(EpochTranscript::<E>::read_from_path(&path, &conf), Err(PVSSError::InvalidArchive(_))));
+        }
+
+        // A transcript of another configuration is rejected.
+        fs::write(&path, &archive).unwrap();
+        let other_conf = Config::new(conf.srs.clone(), 3, 5);
+        match EpochTranscript::<E>::read_from_path(&path, &other_conf) {
+            Err(PVSSError::TranscriptDifferentConfig(d1, d2, n1, n2)) => assert_eq!((d1, d2, n1, n2), (3, 2, 5, 5)),
+            _ => panic!("expected TranscriptDifferentConfig"),
+        }
+
+        // So is a missing file.
+        assert!(matches!(EpochTranscript::<E>::read_from_path(dir.join("missing.bin"), &conf), Err(PVSSError::IoError(_))));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
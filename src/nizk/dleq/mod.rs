@@ -7,7 +7,7 @@ use ark_ff::{PrimeField, UniformRand};
 use ark_serialize::{CanonicalSerialize, CanonicalDeserialize};
 
 use rand::Rng;
-use std::fmt::Debug;
+use ark_std::fmt::Debug;
 
 pub mod srs;
 
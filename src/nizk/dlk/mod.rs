@@ -1,13 +1,13 @@
 use super::dlk::srs::SRS;
 use crate::nizk::{scheme::NIZKProof, utils::{errors::NIZKError, hash::hash_to_field}};
 
-use ark_ec::{ProjectiveCurve, AffineCurve};
-use ark_ff::{PrimeField, UniformRand};
+use ark_ec::{msm::VariableBaseMSM, ProjectiveCurve, AffineCurve};
+use ark_ff::{One, PrimeField, UniformRand, Zero};
 
-use std::fmt::Debug;
+use ark_std::{fmt::Debug, ops::Neg};
 use rand::Rng;
 
-use std::hash::Hash;
+use ark_std::hash::Hash;
 
 pub mod srs;
 
@@ -58,9 +58,33 @@ impl<C: AffineCurve> NIZKProof for DLKProof<C> {
         rng: &mut R,
         w: &Self::Witness,
     ) -> Result<Self::Proof, NIZKError> {
+        self.prove_with_context(rng, w, &[])
+    }
+
+    // Function for verifying a NIZKPoK of discrete logarithm.
+    fn verify(
+        &self,
+        stmnt: &Self::Statement,
+        proof: &Self::Proof,
+    ) -> Result<(), NIZKError> {
+        self.verify_with_context(stmnt, proof, &[])
+    }
+}
+
+
+impl<C: AffineCurve> DLKProof<C> {
+
+    // Function for generating a NIZKPoK of discrete logarithm whose challenge is additionally
+    // bound to some context bytes; an empty context yields a plain proof.
+    pub fn prove_with_context<R: Rng>(
+        &self,
+        rng: &mut R,
+        w: &C::ScalarField,
+        context: &[u8],
+    ) -> Result<<Self as NIZKProof>::Proof, NIZKError> {
 
         // Sample a random nonce
-        let r = Self::Witness::rand(rng);
+        let r = C::ScalarField::rand(rng);
 
         // Compute commitment to nonce as: g_r := r * g
         let g_r = self.srs.g_public_key.mul(r.into_repr()).into_affine();
@@ -74,8 +98,8 @@ impl<C: AffineCurve> NIZKProof for DLKProof<C> {
         g_r.serialize(&mut g_r_bytes)?;
 
         // Compute the "challenge" part of the proof
-        let hashed_message = hash_to_field::<Self::Challenge>(
-            PERSONALIZATION, &[&g_bytes[..], &g_r_bytes].concat()
+        let hashed_message = hash_to_field::<C::ScalarField>(
+            PERSONALIZATION, &[&g_bytes[..], &g_r_bytes, context].concat()
         )?;
 
         // Compute the "response" part of the proof
@@ -86,11 +110,12 @@ impl<C: AffineCurve> NIZKProof for DLKProof<C> {
         Ok(proof)
     }
 
-    // Function for verifying a NIZKPoK of discrete logarithm.
-    fn verify(
+    // Function for verifying a NIZKPoK of discrete logarithm bound to some context bytes.
+    pub fn verify_with_context(
         &self,
-        stmnt: &Self::Statement,
-        proof: &Self::Proof,
+        stmnt: &C,
+        proof: &<Self as NIZKProof>::Proof,
+        context: &[u8],
     ) -> Result<(), NIZKError> {
 
         // serialize g into writer g_bytes
@@ -102,8 +127,8 @@ impl<C: AffineCurve> NIZKProof for DLKProof<C> {
         proof.0.serialize(&mut g_r_bytes)?;
 
 	// compute the challenge corresponding to what was provided
-        let hashed_message = hash_to_field::<Self::Challenge>(
-            PERSONALIZATION, &[&g_bytes[..], &g_r_bytes].concat()
+        let hashed_message = hash_to_field::<C::ScalarField>(
+            PERSONALIZATION, &[&g_bytes[..], &g_r_bytes, context].concat()
         )?;
 
 	// compute LHS of the verification condition
@@ -119,6 +144,67 @@ impl<C: AffineCurve> NIZKProof for DLKProof<C> {
 
         Ok(())
     }
+
+    // Method for verifying a batch of NIZKPoKs of discrete logarithm against matching
+    // statements. The verification conditions are combined under random weights and
+    // checked with a single multi-scalar multiplication.
+    pub fn batch_verify<R: Rng>(
+        &self,
+        rng: &mut R,
+        stmnts: &[C],
+        proofs: &[<Self as NIZKProof>::Proof],
+    ) -> Result<(), NIZKError> {
+        if stmnts.len() != proofs.len() {
+            return Err(NIZKError::DLKVerify);
+        }
+
+        // serialize g into writer g_bytes
+        let mut g_bytes = vec![];
+        self.srs.g_public_key.serialize(&mut g_bytes)?;
+
+        // Probabilistic verification
+        let alpha = C::ScalarField::rand(rng);
+        let mut current_alpha = C::ScalarField::one();
+
+        let mut bases = vec![];
+        let mut scalars = vec![];
+
+        for (stmnt, proof) in stmnts.iter().zip(proofs.iter()) {
+            // serialize g_r into writer g_r_bytes
+            let mut g_r_bytes = vec![];
+            proof.0.serialize(&mut g_r_bytes)?;
+
+            // the challenge must match the one derived from the proof
+            let hashed_message = hash_to_field::<C::ScalarField>(
+                PERSONALIZATION, &[&g_bytes[..], &g_r_bytes].concat()
+            )?;
+
+            if hashed_message != proof.1 {
+                return Err(NIZKError::DLKVerify);
+            }
+
+            // alpha^i * (g*z + stmnt*c - g_r) must vanish
+            bases.push(self.srs.g_public_key.into_projective());
+            scalars.push((proof.2 * &current_alpha).into_repr());
+
+            bases.push(stmnt.into_projective());
+            scalars.push((hashed_message * &current_alpha).into_repr());
+
+            bases.push(proof.0.into_projective());
+            scalars.push(current_alpha.neg().into_repr());
+
+            current_alpha *= &alpha;
+        }
+
+        let bases = C::Projective::batch_normalization_into_affine(&bases);
+        let accumulated_check = VariableBaseMSM::multi_scalar_mul(&bases, &scalars);
+
+        if !accumulated_check.is_zero() {
+            return Err(NIZKError::DLKVerify);
+        }
+
+        Ok(())
+    }
 }
 
 
@@ -276,6 +362,37 @@ mod test {
     }
 
 
+    #[test]
+    fn test_batch_nizk_g1() {
+        test_batch_nizk::<G1Affine>();
+    }
+
+    #[test]
+    fn test_batch_nizk_g2() {
+        test_batch_nizk::<G2Affine>();
+    }
+
+    fn test_batch_nizk<C: AffineCurve>() {
+        let rng = &mut thread_rng();
+        let srs = SRS::<C>::setup(rng).unwrap();
+        let dlk = DLKProof { srs };
+
+        let mut stmnts = vec![];
+        let mut proofs = vec![];
+        for _ in 0..5 {
+            let pair = dlk.generate_pair(rng).unwrap();
+            proofs.push(dlk.prove(rng, &pair.0).unwrap());
+            stmnts.push(pair.1);
+        }
+
+        dlk.batch_verify(rng, &stmnts, &proofs).unwrap();
+
+        // A single wrong statement makes the whole batch fail.
+        stmnts[2] = dlk.generate_pair(rng).unwrap().1;
+        assert!(dlk.batch_verify(rng, &stmnts, &proofs).is_err());
+    }
+
+
     #[test]
     fn test_serialization_g1() {
         test_serialization::<G1Affine>();
@@ -3,7 +3,7 @@ use ark_ec::AffineCurve;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
 use rand::Rng;
 
-use std::hash::Hash;
+use ark_std::hash::Hash;
 
 #[derive(Debug, CanonicalSerialize, CanonicalDeserialize, Clone, PartialEq, Hash)]
 pub struct SRS<C: AffineCurve> {
@@ -1,7 +1,7 @@
 use crate::nizk::utils::errors::NIZKError;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use rand::Rng;
-use std::fmt::Debug;
+use ark_std::fmt::Debug;
 
 // NIZK proof systems must implement the NIZKProof trait.
 pub trait NIZKProof: Debug + Clone + PartialEq + Sized {
@@ -14,5 +14,13 @@ pub enum NIZKError {
     #[error("Failed verifying DLEQ proof")]
     DLEQVerify,
     #[error("SerializationError: {0}")]
-    SerializationError(#[from] SerializationError),
+    SerializationError(#[cfg_attr(feature = "std", from)] SerializationError),
+}
+
+// Conversion written out for builds without std, where SerializationError cannot be a source.
+#[cfg(not(feature = "std"))]
+impl From<SerializationError> for NIZKError {
+    fn from(err: SerializationError) -> Self {
+        NIZKError::SerializationError(err)
+    }
 }
@@ -4,6 +4,7 @@ use ark_ff::{PrimeField, Zero};
 use blake2s_simd::Params;
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaChaRng;
+use ark_std::vec::Vec;
 
 fn rng_from_message(personalization: &[u8], message: &[u8]) -> ChaChaRng {
     let hash = Params::new()
@@ -0,0 +1,73 @@
+// Re-exports of the commonly used types, so that downstream users can simply
+// `use optrand_pvss::prelude::*`.
+
+pub use crate::{
+    modified_scrape::{
+        config::{Config, ConfigBuilder},
+        decomp::{Decomp, DecompProof},
+        errors::PVSSError,
+        participant::ParticipantId,
+        pvss::PVSSCore,
+        share::{PVSSAggregatedShare, PVSSShare, SignedProof},
+        srs::SRS,
+    },
+    signature::schnorr::SchnorrSignature,
+    PublicKey,
+    Scalar,
+    Signature,
+};
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::{
+        generate_keypair,
+        signature::{scheme::SignatureScheme, schnorr::srs::SRS as SCHSRS},
+    };
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use rand::thread_rng;
+
+    // A share dealt and verified with the prelude's names alone (and the keys to sign it).
+    #[test]
+    fn test_prelude_deal_and_verify() {
+        let rng = &mut thread_rng();
+        let (t, n) = (1, 3);
+
+        let config = Config::<E>::builder().srs(SRS::setup(rng).unwrap()).degree(t).num_participants(n).build().unwrap();
+
+        let schnorr_sig = SchnorrSignature { srs: SCHSRS { g_public_key: config.srs.g1 } };
+        let public_keys = (0..n).map(|_| schnorr_sig.generate_keypair(rng).unwrap().1).collect::<Vec<_>>();
+        let (pk_ed, sk_ed) = generate_keypair(rng);
+
+        // Participant 1 encrypts the shares, proves knowledge of the secret and signs the proof.
+        let id = ParticipantId(1);
+        let (pvss_core, secrets) = PVSSCore::<E>::deal(rng, &config, &public_keys, id.index()).unwrap();
+        let secret: Scalar<E> = secrets.p_0;
+        let decomp_proof: DecompProof<E> = Decomp::generate(rng, &config, &secret).unwrap();
+        let signature = Signature::new(&decomp_proof.digest(), &sk_ed);
+
+        let share = PVSSShare {
+            participant_id: id,
+            degree: t,
+            num_participants: n,
+            config_digest: config.digest(),
+            pvss_core,
+            signed_proof: SignedProof::new(decomp_proof, signature),
+        };
+        share.verify(&config, &pk_ed).unwrap();
+        assert!(share.pvss_core.verify_encryptions_batched(rng, &config, &public_keys));
+
+        // The share aggregates, and is checked against the issuer's key only.
+        let transcript = PVSSAggregatedShare::aggregate_from_iter(t, n, [share.clone()].iter()).unwrap();
+        assert_eq!(transcript.contributions.len(), 1);
+
+        let (other_pk_ed, _) = generate_keypair(rng);
+        let result: Result<(), PVSSError<E>> = share.verify(&config, &other_pk_ed);
+        assert!(matches!(result, Err(PVSSError::SignatureVerificationFailed { participant_id: 1 })));
+    }
+}
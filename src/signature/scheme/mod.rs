@@ -1,7 +1,7 @@
 use crate::signature::utils::errors::SignatureError;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use rand::Rng;
-use std::fmt::Debug;
+use ark_std::fmt::Debug;
 
 // All signature schemes must implement the SignatureScheme trait.
 pub trait SignatureScheme: Debug + Clone + PartialEq + Sized {
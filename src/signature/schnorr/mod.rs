@@ -1,17 +1,18 @@
 use crate::signature::{
     scheme::{BatchVerifiableSignatureScheme, SignatureScheme},
-    utils::{errors::SignatureError, hash::hash_to_field}
+    utils::{errors::SignatureError, hash::{hash_to_field, hkdf}}
 };
 use ark_ec::{msm::VariableBaseMSM, AffineCurve, ProjectiveCurve};
 use ark_ff::{One, PrimeField, UniformRand, Zero};
 use rand::Rng;
 use srs::SRS;
-use std::{fmt::Debug, ops::Neg};
+use ark_std::{fmt::Debug, ops::Neg, vec::Vec};
 
 
 pub mod srs;
 
 const PERSONALIZATION: &[u8] = b"SCHSIGNA";   // persona for the Schnorr signature scheme
+const KEYRING_SALT: &[u8] = b"SCHKEYRG";      // HKDF salt for deterministic keyrings
 
 // SchnorrSignature type wraps around the SRS and represents the scheme's
 // system-wide parameters.
@@ -20,6 +21,35 @@ pub struct SchnorrSignature<C: AffineCurve> {
     pub srs: SRS<C>,   // SRS for the Schnorr signature
 }
 
+impl<C: AffineCurve> SchnorrSignature<C> {
+
+    // Deterministically derives n key pairs from a 32-byte master seed (e.g., for test setups).
+    // Participant i's secret key is 64 bytes of HKDF output (info = i), reduced modulo the
+    // group order; the same seed always yields the same keyring.
+    pub fn derive_keyring(&self, seed: [u8; 32], n: usize) -> Result<Vec<(C::ScalarField, C)>, SignatureError> {
+        (0..n)
+            .map(|i| {
+                let okm = hkdf(KEYRING_SALT, &seed, &(i as u64).to_le_bytes(), 64)?;
+                let sk = C::ScalarField::from_le_bytes_mod_order(&okm);
+                Ok((sk, self.srs.g_public_key.mul(sk.into_repr()).into_affine()))
+            })
+            .collect()
+    }
+}
+
+// Function aggregating public keys into the single key sum_i pk_i, i.e., the public key of the
+// secret sum_i sk_i. The aggregate is only meaningful for relations that are linear in the key
+// alone; see PVSSCore::verify_encryptions_batched for why it cannot replace per-key checks of
+// PVSS encryptions.
+pub fn aggregate_pubkey<C: AffineCurve>(pks: &[C]) -> C::Projective {
+    let mut aggregate = C::Projective::zero();
+    for pk in pks {
+        aggregate.add_assign_mixed(pk);
+    }
+
+    aggregate
+}
+
 // SchnorrSignature implements the SignatureScheme trait.
 impl<C: AffineCurve> SignatureScheme for SchnorrSignature<C> {
     type SRS = SRS<C>;                      // SRS for Schnorr is just a generator (i.e., an EC point)
@@ -202,8 +232,9 @@ impl<C: AffineCurve> BatchVerifiableSignatureScheme for SchnorrSignature<C> {
 mod test {
     use ark_bls12_381::{G1Affine, G2Affine};
     use ark_ec::AffineCurve;
+    use ark_ff::{PrimeField, Zero};
 
-    use super::{SchnorrSignature, SRS};
+    use super::{aggregate_pubkey, SchnorrSignature, SRS};
     use crate::signature::{
         scheme::{BatchVerifiableSignatureScheme, SignatureScheme},
         utils::tests::check_serialization,
@@ -321,6 +352,44 @@ mod test {
             .unwrap();
     }
 
+    #[test]
+    fn test_derive_keyring() {
+        let rng = &mut thread_rng();
+        let srs = SRS::<G1Affine>::setup(rng).unwrap();
+        let schnorr = SchnorrSignature { srs };
+        let n = 16;
+
+        // Same seed, same keyring.
+        let keyring = schnorr.derive_keyring([7u8; 32], n).unwrap();
+        assert_eq!(keyring, schnorr.derive_keyring([7u8; 32], n).unwrap());
+        assert_eq!(keyring.len(), n);
+
+        // Keys are distinct, and each public key matches its secret key.
+        for (i, (sk, pk)) in keyring.iter().enumerate() {
+            assert_eq!(schnorr.from_sk(sk).unwrap().1, *pk);
+            assert!(keyring[i + 1..].iter().all(|(other, _)| other != sk));
+        }
+
+        // A different seed yields a different keyring.
+        assert!(schnorr.derive_keyring([8u8; 32], n).unwrap()[0] != keyring[0]);
+    }
+
+    #[test]
+    fn test_aggregate_pubkey() {
+        let rng = &mut thread_rng();
+        let srs = SRS::<G1Affine>::setup(rng).unwrap();
+        let schnorr = SchnorrSignature { srs };
+        let keyring = schnorr.derive_keyring([9u8; 32], 8).unwrap();
+
+        // The aggregate key is the public key of the sum of the secret keys.
+        let pks = keyring.iter().map(|(_, pk)| *pk).collect::<Vec<_>>();
+        let sk_sum: <G1Affine as AffineCurve>::ScalarField = keyring.iter().map(|(sk, _)| *sk).sum();
+        assert_eq!(aggregate_pubkey(&pks), schnorr.srs.g_public_key.mul(sk_sum.into_repr()));
+
+        assert!(aggregate_pubkey::<G1Affine>(&[]).is_zero());
+        assert_eq!(aggregate_pubkey(&pks[..1]), pks[0].into_projective());
+    }
+
     #[test]
     fn test_serialization_g1() {
         test_serialization::<G1Affine>();
@@ -1,5 +1,5 @@
 use ark_serialize::SerializationError;
-use std::fmt::Display;
+use ark_std::fmt::Display;
 use thiserror::Error;
 
 #[derive(Debug)]
@@ -13,7 +13,7 @@ pub enum VerifyProofEquation {
 
 // VerifyProofEquation implemetns the Display trait.
 impl Display for VerifyProofEquation {
-    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+    fn fmt(&self, f: &mut ark_std::fmt::Formatter) -> Result<(), ark_std::fmt::Error> {
         match *self {
             VerifyProofEquation::Eq1 => f.write_str("Eq1"),
             VerifyProofEquation::Eq2 => f.write_str("Eq2"),
@@ -39,7 +39,19 @@ pub enum SignatureError {
     #[error("SRS is different")]
     SRSDifferent,
     #[error("SerializationError: {0}")]
-    SerializationError(#[from] SerializationError),
+    SerializationError(#[cfg_attr(feature = "std", from)] SerializationError),
     #[error("Different lengths in batch verification: {0}, {1}, {2}")]
     BatchVerification(usize, usize, usize),
+    #[error("HKDF salt too long: {0} bytes, expected <= 32")]
+    HkdfSaltTooLong(usize),
+    #[error("HKDF output too long: {0} bytes, expected <= 255 * 32")]
+    HkdfOutputTooLong(usize),
+}
+
+// Same as #[from], for builds without std (see NIZKError).
+#[cfg(not(feature = "std"))]
+impl From<SerializationError> for SignatureError {
+    fn from(err: SerializationError) -> Self {
+        SignatureError::SerializationError(err)
+    }
 }
@@ -4,6 +4,7 @@ use ark_ff::{PrimeField, Zero};
 use blake2s_simd::Params;
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaChaRng;
+use ark_std::vec::Vec;
 
 fn rng_from_message(personalization: &[u8], message: &[u8]) -> ChaChaRng {
     let hash = Params::new()
@@ -50,3 +51,59 @@ pub fn hash_to_field<F: PrimeField>(
         }
     }
 }
+
+// Keyed BLAKE2s, used as the PRF of hkdf below.
+fn keyed_hash(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let hash = Params::new()
+        .hash_length(32)
+        .key(key)
+        .to_state()
+        .update(message)
+        .finalize();
+    let mut out = [0u8; 32];
+    out.copy_from_slice(hash.as_bytes());
+    out
+}
+
+// HKDF (RFC 5869) extract-and-expand, instantiated with keyed BLAKE2s in place of HMAC.
+// The salt may be at most 32 bytes long, and at most 255 * 32 bytes can be expanded.
+pub fn hkdf(salt: &[u8], ikm: &[u8], info: &[u8], length: usize) -> Result<Vec<u8>, SignatureError> {
+    if salt.len() > 32 {
+        return Err(SignatureError::HkdfSaltTooLong(salt.len()));
+    }
+    if length > 255 * 32 {
+        return Err(SignatureError::HkdfOutputTooLong(length));
+    }
+
+    // Extract
+    let prk = keyed_hash(salt, ikm);
+
+    // Expand
+    let mut okm = Vec::with_capacity(length);
+    let mut block: Vec<u8> = vec![];
+    let mut counter = 1u8;
+    while okm.len() < length {
+        block = keyed_hash(&prk, &[&block[..], info, &[counter]].concat()).to_vec();
+        okm.extend_from_slice(&block);
+        counter = counter.wrapping_add(1);
+    }
+    okm.truncate(length);
+    Ok(okm)
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::hkdf;
+    use crate::signature::utils::errors::SignatureError;
+
+    #[test]
+    fn test_hkdf_lengths() {
+        assert_eq!(hkdf(b"salt", b"ikm", b"info", 255 * 32).unwrap().len(), 255 * 32);
+        assert_eq!(hkdf(&[0u8; 32], b"ikm", b"info", 0).unwrap().len(), 0);
+
+        // Overlong salts and outputs are rejected rather than panicking.
+        assert!(matches!(hkdf(&[0u8; 33], b"ikm", b"info", 32), Err(SignatureError::HkdfSaltTooLong(33))));
+        assert!(matches!(hkdf(b"salt", b"ikm", b"info", 255 * 32 + 1), Err(SignatureError::HkdfOutputTooLong(8161))));
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{
+    generate_keypair,
+    modified_scrape::{
+        config::Config,
+        errors::PVSSError,
+        participant::{Participant, ParticipantId, ParticipantRegistry},
+        pvss::PVSSShareSecrets,
+        roles::{Dealer as _, OptrandScheme},
+        share::{PVSSAggregatedShare, PVSSShare},
+        srs::SRS,
+    },
+    signature::{
+        scheme::SignatureScheme,
+        schnorr::{SchnorrSignature, srs::SRS as SCHSRS},
+    },
+    PublicKey,
+    Scalar,
+    SecretKey,
+};
+
+use ark_ec::PairingEngine;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaChaRng;
+
+use std::marker::PhantomData;
+
+
+/* Struct NodeKeys gathers the keys of a committee member. */
+#[derive(Clone)]
+pub struct NodeKeys<E: PairingEngine> {
+    pub secret_key: Scalar<E>,       // secret (decryption) key
+    pub public_key: E::G1Affine,     // public (encryption) key
+    pub secret_key_ed: SecretKey,    // EdDSA secret (signing) key
+    pub public_key_ed: PublicKey,    // EdDSA public (verification) key
+}
+
+
+/* Struct Fixture holds a committee set up with real keys, along with one dealing of every
+   member: the keys, share and secrets of node i are at index i of the corresponding vectors,
+   and the transcript aggregates all the shares. */
+pub struct Fixture<E: PairingEngine> {
+    pub config: Config<E>,                      // the "global" configuration parameters
+    pub registry: ParticipantRegistry<E>,       // the committee's public keys
+    pub keypairs: Vec<NodeKeys<E>>,             // the keys of every node
+    pub shares: Vec<PVSSShare<E>>,              // the share dealt by every node
+    pub secrets: Vec<PVSSShareSecrets<E>>,      // the secrets of every dealt share
+    pub transcript: PVSSAggregatedShare<E>,     // the aggregation of all the shares
+    pub rng: ChaChaRng,                         // the rest of the fixture's random stream
+}
+
+impl<E: PairingEngine> Fixture<E> {
+    // Method returning the public (encryption) keys of the committee, in id order.
+    pub fn public_keys(&self) -> Vec<E::G1Affine> {
+        self.keypairs.iter().map(|keys| keys.public_key).collect()
+    }
+}
+
+
+/* Struct FixtureBuilder sets up fixtures of a committee of n nodes with threshold degree t.
+   Fixtures are drawn from a seeded random stream, so that a given seed always yields the
+   same fixture; without a seed, a fresh random one is used. */
+#[derive(Clone, Copy, Debug)]
+pub struct FixtureBuilder {
+    degree: usize,             // polynomial degree (t)
+    num_participants: usize,   // committee size (n)
+    seed: Option<u64>,         // seed of the random stream
+}
+
+impl FixtureBuilder {
+
+    // Function for starting the setup of a committee of n nodes with threshold degree t.
+    pub fn new(t: usize, n: usize) -> Self {
+        Self { degree: t, num_participants: n, seed: None }
+    }
+
+    // Method for drawing the fixture from the given seed.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+
+    // Method for setting up the committee and dealing every member's share.
+    pub fn build<E: PairingEngine>(&self) -> Result<Fixture<E>, PVSSError<E>> {
+        let seed = self.seed.unwrap_or_else(|| rand::thread_rng().gen());
+        self.build_from_rng(ChaChaRng::seed_from_u64(seed))
+    }
+
+    // Method for setting up the committee from the given random stream (ignoring the seed), in
+    // order: the SRS, then every node's Schnorr and EdDSA keys, then every node's dealing.
+    pub fn build_from_rng<E: PairingEngine>(&self, mut rng: ChaChaRng) -> Result<Fixture<E>, PVSSError<E>> {
+        let srs = SRS::<E>::setup(&mut rng)?;
+        let config = Config::new(srs, self.degree, self.num_participants);
+
+        let schnorr_sig = SchnorrSignature { srs: SCHSRS { g_public_key: config.srs.g1 } };
+
+        let mut keypairs = vec![];
+        for _ in 0..self.num_participants {
+            let (secret_key, public_key) = schnorr_sig.generate_keypair(&mut rng)?;
+            let (public_key_ed, secret_key_ed) = generate_keypair(&mut rng);
+            keypairs.push(NodeKeys { secret_key, public_key, secret_key_ed, public_key_ed });
+        }
+
+        let registry = ParticipantRegistry::new(
+            keypairs
+                .iter()
+                .enumerate()
+                .map(|(id, keys)| Participant {
+                    pairing_type: PhantomData,
+                    id: ParticipantId::from_index(id),
+                    public_key_sig: keys.public_key,
+                    public_key_ed: keys.public_key_ed,
+                })
+                .collect(),
+        );
+
+        let scheme = OptrandScheme::new(config.clone());
+        let mut shares = vec![];
+        let mut secrets = vec![];
+        for (id, keys) in keypairs.iter().enumerate() {
+            let (share, share_secrets) = scheme.deal(&mut rng, &registry, id, &keys.secret_key_ed)?;
+            shares.push(share);
+            secrets.push(share_secrets);
+        }
+
+        let transcript = PVSSAggregatedShare::aggregate_from_iter(self.degree, self.num_participants, shares.iter())?;
+
+        Ok(Fixture { config, registry, keypairs, shares, secrets, transcript, rng })
+    }
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::FixtureBuilder;
+    use crate::modified_scrape::roles::{OptrandScheme, Verifier};
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_ec::{AffineCurve, ProjectiveCurve};
+    use ark_ff::PrimeField;
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_fixture() {
+        let rng = &mut thread_rng();
+        let (t, n) = (3, 7);
+
+        let fixture = FixtureBuilder::new(t, n).with_seed(42).build::<E>().unwrap();
+        assert_eq!((fixture.keypairs.len(), fixture.shares.len(), fixture.secrets.len()), (n, n, n));
+        assert_eq!(fixture.transcript.contributions.len(), n);
+
+        // The keys match, and every share and the transcript verify under the committee.
+        for keys in fixture.keypairs.iter() {
+            assert_eq!(fixture.config.srs.g1.mul(keys.secret_key.into_repr()).into_affine(), keys.public_key);
+        }
+        let scheme = OptrandScheme::new(fixture.config.clone());
+        for share in fixture.shares.iter() {
+            scheme.verify_share(rng, &fixture.registry, share).unwrap();
+        }
+        scheme.verify_aggregated(rng, &fixture.registry, &fixture.transcript).unwrap();
+
+        // A seed always yields the same fixture.
+        let again = FixtureBuilder::new(t, n).with_seed(42).build::<E>().unwrap();
+        assert_eq!(again.transcript.transcript_id(), fixture.transcript.transcript_id());
+        assert!(again.shares == fixture.shares);
+
+        let other = FixtureBuilder::new(t, n).with_seed(43).build::<E>().unwrap();
+        assert_ne!(other.transcript.transcript_id(), fixture.transcript.transcript_id());
+    }
+}
@@ -0,0 +1,5 @@
+// Test utilities shared across modules, and with dependent crates through the "testing" feature.
+
+pub mod fixtures;
+pub mod simulation;
+pub mod tamper;
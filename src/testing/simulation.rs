@@ -0,0 +1,479 @@
+use crate::{
+    generate_production_keypair,
+    modified_scrape::{
+        config::Config,
+        decomp::Decomp,
+        decryption::DecryptedShare,
+        errors::PVSSError,
+        node::{Aggregator, Complaint, DealerNode, Equivocation, ReceiveOutcome, ReconstructionOutcome, ReconstructionTracker},
+        participant::{Participant, ParticipantId, ParticipantRegistry},
+        poly::Polynomial,
+        pvss::{PVSSCore, DEFAULT_MIN_CHUNK},
+        recon::beacon,
+        roles::{OptrandScheme, Verifier},
+        share::{PVSSAggregatedShare, PVSSShare, SignedProof},
+        srs::SRS,
+    },
+    signature::{
+        scheme::SignatureScheme,
+        schnorr::{SchnorrSignature, srs::SRS as SCHSRS},
+    },
+    Scalar,
+    SecretKey,
+    Signature,
+};
+
+use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+use ark_ff::{PrimeField, UniformRand, Zero};
+use ark_poly::UVPolynomial;
+use ark_std::collections::BTreeMap;
+
+use rand::{CryptoRng, Rng};
+
+use std::marker::PhantomData;
+
+
+// A dealt share, along with its secret p(0).
+type DealtShare<E> = (PVSSShare<E>, Scalar<E>);
+
+
+/* Enumeration Fault lists the misbehaviours that can be injected into a simulation. */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Fault {
+    Crash(usize),              // the node neither deals nor decrypts
+    DropShare(usize),          // the node's share is lost on its way to the leader
+    CorruptSignature(usize),   // the node's share carries a signature under another key
+    Equivocate(usize),         // the node deals two different shares to the leader
+    HighDegree(usize),         // the node shares a polynomial of degree t + 2
+    MisEncrypt(usize, usize),  // the node encrypts the share of the second id to another node's key
+    InconsistentGs(usize),     // the node's decomposition proof is of another secret than its commitments'
+}
+
+impl Fault {
+    // Method returning whether the fault has node id deal a malformed share (see deal_malformed).
+    fn malforms(&self, id: usize) -> bool {
+        match *self {
+            Fault::HighDegree(i) | Fault::MisEncrypt(i, _) | Fault::InconsistentGs(i) => i == id,
+            _ => false,
+        }
+    }
+}
+
+
+/* Struct SimulatedNode is a committee member of the simulation, along with its keys. */
+pub struct SimulatedNode<E: PairingEngine> {
+    pub id: usize,                  // participant id
+    pub secret_key: Scalar<E>,      // secret (decryption) key
+    pub secret_key_ed: SecretKey,   // EdDSA secret (signing) key
+    pub dealer: DealerNode<E>,      // the dealing side of the node
+}
+
+
+/* Struct SimulationReport gathers the outcome of a simulated epoch. */
+pub struct SimulationReport<E: PairingEngine> {
+    pub transcript: PVSSAggregatedShare<E>,     // the leader's finalized transcript
+    pub outputs: BTreeMap<usize, [u8; 32]>,     // beacon output computed by each live node
+    pub expected: [u8; 32],                     // beacon output of the contributing dealers' secrets
+    pub complaints: Vec<Complaint<E>>,          // complaints raised by the leader
+    pub equivocations: Vec<Equivocation<E>>,    // equivocation evidence collected by the leader
+}
+
+
+/* Struct Simulation runs the Optrand flow among n in-memory nodes holding real keys: every
+   node deals to the leader, the leader aggregates and broadcasts the finalized transcript,
+   every node verifies it and sends out its decrypted share, and every node reconstructs the
+   beacon output. Messages are delivered in id order. */
+pub struct Simulation<E: PairingEngine> {
+    pub config: Config<E>,                    // the "global" configuration parameters
+    pub registry: ParticipantRegistry<E>,     // the committee's public keys
+    pub nodes: Vec<SimulatedNode<E>>,         // the committee members
+    pub leader: usize,                        // id of the aggregating node
+    faults: Vec<Fault>,                       // injected faults
+}
+
+impl<E: PairingEngine> Simulation<E> {
+
+    // Function for setting up a committee of n nodes with threshold degree t, led by node 0.
+    pub fn new<R: Rng + CryptoRng>(rng: &mut R, t: usize, n: usize) -> Result<Self, PVSSError<E>> {
+        let srs = SRS::<E>::setup(rng)?;
+        let config = Config::new(srs, t, n);
+
+        let schnorr_sig = SchnorrSignature { srs: SCHSRS { g_public_key: config.srs.g1 } };
+
+        let mut keys = vec![];
+        let mut participants = vec![];
+        for id in 0..n {
+            let (sk, pk) = schnorr_sig.generate_keypair(rng).unwrap();
+            let (pk_ed, sk_ed) = generate_production_keypair();
+            keys.push((sk, sk_ed));
+            participants.push(Participant { pairing_type: PhantomData, id: ParticipantId::from_index(id), public_key_sig: pk, public_key_ed: pk_ed });
+        }
+        let registry = ParticipantRegistry::new(participants);
+
+        let nodes = keys
+            .into_iter()
+            .enumerate()
+            .map(|(id, (secret_key, sk_ed))| {
+                let dealer = DealerNode::new(config.clone(), registry.clone(), sk_ed.clone(), id)?;
+                Ok(SimulatedNode { id, secret_key, secret_key_ed: sk_ed, dealer })
+            })
+            .collect::<Result<Vec<_>, PVSSError<E>>>()?;
+
+        Ok(Self { config, registry, nodes, leader: 0, faults: vec![] })
+    }
+
+
+    // Method for injecting a fault into subsequent runs.
+    pub fn inject(&mut self, fault: Fault) {
+        self.faults.push(fault);
+    }
+
+    // Method returning whether a node has crashed.
+    pub fn is_crashed(&self, id: usize) -> bool {
+        self.faults.contains(&Fault::Crash(id))
+    }
+
+
+    // Method for running one epoch of the protocol.
+    pub fn run<R: Rng + CryptoRng>(&mut self, rng: &mut R, epoch: u64) -> Result<SimulationReport<E>, PVSSError<E>> {
+        let dealt = self.deal(rng, epoch)?;
+
+        // Leader aggregation.
+        let mut aggregator = Aggregator::new(self.config.clone(), self.registry.clone());
+        let mut seen: BTreeMap<ParticipantId, SignedProof<E>> = BTreeMap::new();
+        let mut transcript = None;
+        let mut complaints = vec![];
+        let mut equivocations = vec![];
+
+        for (share, _) in dealt.iter().cloned() {
+            let id = share.participant_id;
+            match seen.get(&id) {
+                Some(first) if *first != share.signed_proof => {
+                    equivocations.push(Equivocation { participant_id: id, first: first.clone(), second: share.signed_proof });
+                    continue;
+                }
+                Some(_) => continue,
+                None => (),
+            }
+
+            match aggregator.receive_share(rng, share.clone()) {
+                Ok(ReceiveOutcome::Finalized(tx)) => transcript = Some(tx),
+                Ok(_) => (),
+                Err(_) => {
+                    complaints.push(Complaint::generate(rng, &self.config, &self.registry, self.leader, share.clone())?);
+                }
+            }
+            seen.insert(id, share.signed_proof);
+        }
+
+        let threshold = self.config.degree + 1;
+        let transcript = transcript.ok_or(PVSSError::ThresholdNotMet {
+            threshold,
+            got: aggregator.transcript().contributions.len(),
+        })?;
+
+        // Transcript broadcast: every live node verifies the transcript and tracks its decryption.
+        let scheme = OptrandScheme::new(self.config.clone());
+        let live = self.nodes.iter().filter(|node| !self.is_crashed(node.id)).collect::<Vec<_>>();
+
+        let mut trackers = BTreeMap::new();
+        for node in live.iter() {
+            scheme.verify_aggregated(rng, &self.registry, &transcript)?;
+            trackers.insert(node.id, ReconstructionTracker::new(self.config.clone(), self.registry.clone(), transcript.pvss_core.clone()));
+        }
+
+        // Decryption exchange and reconstruction.
+        let mut outputs = BTreeMap::new();
+        for node in live.iter() {
+            let enc = transcript.pvss_core.encs[node.id];
+            let decrypted = DecryptedShare::generate(rng, &self.config, &enc, &node.secret_key, ParticipantId::from_index(node.id))?;
+
+            for (id, tracker) in trackers.iter_mut() {
+                if let ReconstructionOutcome::Reconstructed { beacon, .. } = tracker.receive(decrypted.clone())? {
+                    outputs.insert(*id, beacon);
+                }
+            }
+        }
+
+        // The output expected from the secrets of the shares included in the transcript.
+        let secret = dealt
+            .iter()
+            .filter(|(share, _)| transcript.contributions.get(&share.participant_id) == Some(&share.signed_proof))
+            .fold(Scalar::<E>::zero(), |secret, (_, p_0)| secret + p_0);
+        let expected = beacon(&self.config, &self.config.srs.g1.mul(secret.into_repr()).into_affine())?;
+
+        Ok(SimulationReport { transcript, outputs, expected, complaints, equivocations })
+    }
+
+
+    // Utility method for dealing the shares of an epoch (along with their secrets), in delivery
+    // order, applying the faults.
+    fn deal<R: Rng + CryptoRng>(&mut self, rng: &mut R, epoch: u64) -> Result<Vec<DealtShare<E>>, PVSSError<E>> {
+        let faults = self.faults.clone();
+        let mut shares = vec![];
+
+        for node in self.nodes.iter_mut() {
+            let id = node.id;
+            if faults.contains(&Fault::Crash(id)) {
+                continue;
+            }
+
+            let (share, secrets) = node.dealer.deal(epoch, rng)?;
+            let (mut share, p_0) = match faults.iter().find(|fault| fault.malforms(id)) {
+                Some(fault) => deal_malformed(rng, &self.config, &self.registry, node, *fault)?,
+                None => (share, secrets.p_0),
+            };
+
+            if faults.contains(&Fault::CorruptSignature(id)) {
+                let (_, other_sk) = generate_production_keypair();
+                share.signed_proof.signature_on_decomp = Signature::new(share.signed_proof.digest(), &other_sk);
+            }
+
+            if faults.contains(&Fault::DropShare(id)) {
+                continue;
+            }
+            shares.push((share, p_0));
+
+            if faults.contains(&Fault::Equivocate(id)) {
+                let (other, secrets) = node.dealer.deal_override(epoch, rng)?;
+                shares.push((other, secrets.p_0));
+            }
+        }
+
+        Ok(shares)
+    }
+}
+
+
+// Utility function dealing the share of a node malformed as prescribed by the fault. The share is
+// otherwise well formed and signed with the node's key, so that only the malformation is at
+// fault. The secret returned is the p(0) underlying the commitments.
+fn deal_malformed<E: PairingEngine, R: Rng + CryptoRng>(
+    rng: &mut R,
+    config: &Config<E>,
+    registry: &ParticipantRegistry<E>,
+    node: &SimulatedNode<E>,
+    fault: Fault,
+) -> Result<DealtShare<E>, PVSSError<E>> {
+    let degree = match fault {
+        Fault::HighDegree(_) => config.degree + 2,
+        _ => config.degree,
+    };
+    let poly = Polynomial::<E>::rand(degree, rng);
+
+    let mut public_keys = registry.public_keys_sig()?;
+    if let Fault::MisEncrypt(_, j) = fault {
+        public_keys[j] = public_keys[(j + 1) % config.num_participants];
+    }
+    let (pvss_core, _) = PVSSCore::deal_polynomial(&poly, config, &public_keys, node.id, DEFAULT_MIN_CHUNK)?;
+
+    let p_0 = poly.coeffs[0];
+    let proven = match fault {
+        Fault::InconsistentGs(_) => Scalar::<E>::rand(rng),
+        _ => p_0,
+    };
+    let decomp_proof = Decomp::<E>::generate(rng, config, &proven)?;
+    let signature_on_decomp = Signature::new(&decomp_proof.digest(), &node.secret_key_ed);
+
+    let share = PVSSShare {
+        participant_id: ParticipantId::from_index(node.id),
+        degree: config.degree,
+        num_participants: config.num_participants,
+        config_digest: config.digest(),
+        pvss_core,
+        signed_proof: SignedProof::new(decomp_proof, signature_on_decomp),
+    };
+
+    Ok((share, p_0))
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{Fault, Simulation};
+    use crate::modified_scrape::{
+        errors::{PVSSError, PVSSErrorCode, PVSSErrorKind},
+        node::{Aggregator, InventoryTracker, Message},
+        participant::ParticipantId,
+    };
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+    use rand::thread_rng;
+
+    use std::collections::VecDeque;
+
+    #[test]
+    fn test_honest_run() {
+        let rng = &mut thread_rng();
+        let (t, n) = (3, 10);
+
+        let mut simulation = Simulation::<E>::new(rng, t, n).unwrap();
+        let report = simulation.run(rng, 0).unwrap();
+
+        assert_eq!(report.transcript.contributions.len(), t + 1);
+        assert_eq!(report.outputs.len(), n);
+        assert!(report.outputs.values().all(|output| *output == report.expected));
+        assert!(report.complaints.is_empty() && report.equivocations.is_empty());
+    }
+
+    #[test]
+    fn test_crashed_dealer() {
+        let rng = &mut thread_rng();
+        let (t, n) = (3, 10);
+
+        let mut simulation = Simulation::<E>::new(rng, t, n).unwrap();
+        simulation.inject(Fault::Crash(2));
+        let report = simulation.run(rng, 0).unwrap();
+
+        assert!(!report.transcript.contributions.contains_key(&ParticipantId(2)));
+        assert!(!report.outputs.contains_key(&2));
+        assert_eq!(report.outputs.len(), n - 1);
+        assert!(report.outputs.values().all(|output| *output == report.expected));
+    }
+
+    #[test]
+    fn test_equivocating_dealer() {
+        let rng = &mut thread_rng();
+        let (t, n) = (3, 10);
+
+        let mut simulation = Simulation::<E>::new(rng, t, n).unwrap();
+        simulation.inject(Fault::Equivocate(1));
+        let report = simulation.run(rng, 0).unwrap();
+
+        assert_eq!(report.equivocations.len(), 1);
+        let evidence = &report.equivocations[0];
+        assert_eq!(evidence.participant_id, ParticipantId(1));
+        evidence.verify(&simulation.registry).unwrap();
+
+        // Evidence made of a single proof does not hold.
+        let mut bogus = evidence.clone();
+        bogus.second = bogus.first.clone();
+        assert!(matches!(bogus.verify(&simulation.registry), Err(PVSSError::InvalidComplaint { accused_id: 1 })));
+
+        assert!(report.outputs.values().all(|output| *output == report.expected));
+    }
+
+    #[test]
+    fn test_faulty_shares() {
+        let rng = &mut thread_rng();
+        let (t, n) = (2, 6);
+
+        let mut simulation = Simulation::<E>::new(rng, t, n).unwrap();
+        simulation.inject(Fault::CorruptSignature(0));
+        simulation.inject(Fault::DropShare(1));
+        let report = simulation.run(rng, 0).unwrap();
+
+        assert_eq!(report.complaints.len(), 1);
+        assert_eq!(report.complaints[0].failure.code, PVSSErrorCode::SignatureVerificationFailed);
+        report.complaints[0].verify(rng, &simulation.config, &simulation.registry).unwrap();
+        assert_eq!(report.transcript.contributions.keys().collect::<Vec<_>>(), [2, 3, 4].iter().map(|&id| ParticipantId(id)).collect::<Vec<_>>());
+        assert!(report.outputs.values().all(|output| *output == report.expected));
+
+        // Too many silent dealers: the threshold is not met.
+        let mut simulation = Simulation::<E>::new(rng, t, n).unwrap();
+        for id in 0..n - t {
+            simulation.inject(Fault::DropShare(id));
+        }
+        assert!(matches!(simulation.run(rng, 0), Err(PVSSError::ThresholdNotMet { .. })));
+    }
+
+    #[test]
+    fn test_inventory_gossip() {
+        let rng = &mut thread_rng();
+        let (t, n) = (3, 4);
+
+        let mut simulation = Simulation::<E>::new(rng, t, n).unwrap();
+        let dealt = simulation.deal(rng, 0).unwrap();
+
+        // Two nodes holding the shares of disjoint sets of dealers.
+        let mut nodes = (0..2)
+            .map(|_| (Aggregator::new(simulation.config.clone(), simulation.registry.clone()), InventoryTracker::<E>::new(0)))
+            .collect::<Vec<_>>();
+        for (share, _) in dealt {
+            let (aggregator, inventory) = &mut nodes[share.participant_id.index() % 2];
+            aggregator.receive_share(rng, share.clone()).unwrap();
+            inventory.insert_share(share);
+        }
+        assert!(nodes.iter().all(|(aggregator, _)| !aggregator.is_finalized()));
+
+        // Both nodes advertise their inventory; messages then travel serialized, and are answered,
+        // until none is left in flight.
+        let encode = |msg: &Message<E>| {
+            let mut bytes = vec![];
+            msg.serialize(&mut bytes).unwrap();
+            bytes
+        };
+        let mut in_flight = VecDeque::new();
+        for (from, (_, inventory)) in nodes.iter().enumerate() {
+            in_flight.extend(inventory.have().iter().map(|msg| (1 - from, encode(msg))));
+        }
+
+        while let Some((to, bytes)) = in_flight.pop_front() {
+            let (aggregator, inventory) = &mut nodes[to];
+            let replies: Vec<Message<E>> = match Message::<E>::deserialize(&bytes[..]).unwrap() {
+                Message::Have { epoch, digests } => inventory.receive_have(epoch, &digests).unwrap().into_iter().collect(),
+                Message::Want { epoch, digests } => inventory.receive_want(epoch, &digests).unwrap(),
+                Message::Share { share, .. } => {
+                    aggregator.receive_share(rng, share.clone()).unwrap();
+                    inventory.insert_share(share);
+                    vec![]
+                }
+                _ => panic!("unexpected message"),
+            };
+            in_flight.extend(replies.iter().map(|msg| (1 - to, encode(msg))));
+        }
+
+        // Both nodes hold every share, and finalized identical transcripts.
+        assert!(nodes.iter().all(|(aggregator, inventory)| aggregator.is_finalized() && inventory.len() == n));
+        assert!(nodes[0].0.transcript() == nodes[1].0.transcript());
+        assert_eq!(nodes[0].0.transcript().contributions.len(), n);
+    }
+
+    #[test]
+    fn test_adversarial_dealers() {
+        let rng = &mut thread_rng();
+        let (t, n) = (3, 10);
+        let dealer = 2;
+
+        // Every malformation, along with the error the leader's aggregator must reject it with.
+        let cases = [
+            (Fault::HighDegree(dealer), PVSSErrorKind::DualCodeError),
+            (Fault::MisEncrypt(dealer, 6), PVSSErrorKind::EncryptionCorrectnessError),
+            (Fault::CorruptSignature(dealer), PVSSErrorKind::SignatureVerificationFailed { participant_id: dealer }),
+            (Fault::InconsistentGs(dealer), PVSSErrorKind::GSCheckError),
+        ];
+
+        for (fault, expected) in cases.iter() {
+            let mut simulation = Simulation::<E>::new(rng, t, n).unwrap();
+            simulation.inject(*fault);
+
+            // The aggregator rejects the malformed share, and accepts the honest ones.
+            let mut aggregator = Aggregator::new(simulation.config.clone(), simulation.registry.clone());
+            for (share, _) in simulation.deal(rng, 0).unwrap() {
+                let id = share.participant_id;
+                match aggregator.receive_share(rng, share) {
+                    Err(err) if id == ParticipantId::from_index(dealer) => {
+                        assert_eq!(err.kind(), *expected, "{:?} tripped the wrong check", fault)
+                    }
+                    Ok(_) if id == ParticipantId::from_index(dealer) => panic!("{:?} went unnoticed", fault),
+                    result => assert!(result.is_ok()),
+                }
+            }
+
+            // End to end, the leader complains about the dealer, and the transcript of the
+            // honest dealers still yields the expected output at every node.
+            let report = simulation.run(rng, 1).unwrap();
+            assert_eq!(report.complaints.len(), 1);
+            assert_eq!(report.complaints[0].failure.code, expected.code());
+            report.complaints[0].verify(rng, &simulation.config, &simulation.registry).unwrap();
+            assert!(!report.transcript.contributions.contains_key(&ParticipantId::from_index(dealer)));
+            assert_eq!(report.outputs.len(), n);
+            assert!(report.outputs.values().all(|output| *output == report.expected));
+        }
+    }
+}
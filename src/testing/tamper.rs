@@ -0,0 +1,123 @@
+use crate::{
+    modified_scrape::{
+        pvss::PVSSCore,
+        share::{PVSSShare, SignedProof},
+    },
+    Signature,
+};
+
+use ark_ec::{PairingEngine, ProjectiveCurve};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+
+// Function moving the commitment at index idx off the dealt polynomial, by adding g2 to it.
+// The commitments then no longer lie in the code of degree t polynomials, which trips the
+// dual code check (DualCodeError) of share and aggregation verification.
+pub fn flip_commitment<E: PairingEngine>(core: &mut PVSSCore<E>, idx: usize) {
+    core.comms[idx] += &E::G2Projective::prime_subgroup_generator();
+}
+
+// Function corrupting the EdDSA signature on the decomposition proof, by flipping the lowest
+// bit of its serialized s half. The proof itself stays valid, so only the signature check
+// fails (SignatureVerificationFailed).
+pub fn corrupt_signature<E: PairingEngine>(signed_proof: &mut SignedProof<E>) {
+    let mut bytes = vec![];
+    signed_proof.signature_on_decomp.serialize(&mut bytes).unwrap();
+    bytes[32] ^= 1;
+
+    signed_proof.signature_on_decomp = Signature::deserialize(&bytes[..]).unwrap();
+}
+
+// Function swapping the encryptions at indices i and j (i != j), so that each is paired with
+// the other's commitment and public key. This trips the correctness of encryption check
+// (EncryptionCorrectnessError).
+pub fn swap_encryption<E: PairingEngine>(core: &mut PVSSCore<E>, i: usize, j: usize) {
+    core.encs.swap(i, j);
+}
+
+// Function replacing the signed decomposition proof of target by that of source, as a dealer
+// replaying another dealer's proof would. The signature and the NIZK proof remain valid, but
+// the proof's gs does not match the secret committed to by target's core, which trips the gs
+// check (GSCheckError).
+pub fn reuse_proof_from<E: PairingEngine>(source: &PVSSShare<E>, target: &mut PVSSShare<E>) {
+    target.signed_proof = source.signed_proof.clone();
+}
+
+
+/* Unit tests: */
+
+#[cfg(test)]
+mod test {
+
+    use super::{corrupt_signature, flip_commitment, reuse_proof_from, swap_encryption};
+    use crate::modified_scrape::{
+        errors::PVSSErrorKind,
+        roles::{OptrandScheme, Verifier},
+        share::PVSSShare,
+    };
+    use crate::testing::fixtures::FixtureBuilder;
+
+    use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_tampers_trip_their_checks() {
+        const DEALER: usize = 2;   // dealer of the tampered share
+        const OTHER: usize = 4;    // another member of the committee
+
+        let rng = &mut thread_rng();
+        let (t, n) = (3, 7);
+
+        let fixture = FixtureBuilder::new(t, n).build::<E>().unwrap();
+        let scheme = OptrandScheme::new(fixture.config.clone());
+        let (share, other_share) = (&fixture.shares[DEALER], &fixture.shares[OTHER]);
+        scheme.verify_share(rng, &fixture.registry, share).unwrap();
+
+        // Every tamper, given the share to tamper with and another dealer's share, along with
+        // the error it must cause.
+        let tampers: [(&str, fn(&mut PVSSShare<E>, &PVSSShare<E>), PVSSErrorKind); 6] = [
+            (
+                "flip_commitment",
+                |s, _| flip_commitment(&mut s.pvss_core, OTHER),
+                PVSSErrorKind::DualCodeError,
+            ),
+            (
+                "flip_own_commitment",
+                |s, _| flip_commitment(&mut s.pvss_core, DEALER),
+                PVSSErrorKind::DualCodeError,
+            ),
+            (
+                "corrupt_signature",
+                |s, _| corrupt_signature(&mut s.signed_proof),
+                PVSSErrorKind::SignatureVerificationFailed { participant_id: DEALER },
+            ),
+            (
+                "swap_encryption",
+                |s, _| swap_encryption(&mut s.pvss_core, DEALER, OTHER),
+                PVSSErrorKind::EncryptionCorrectnessError,
+            ),
+            (
+                "swap_other_encryptions",
+                |s, _| swap_encryption(&mut s.pvss_core, OTHER, OTHER + 1),
+                PVSSErrorKind::EncryptionCorrectnessError,
+            ),
+            (
+                "reuse_proof_from",
+                |s, other| reuse_proof_from(other, s),
+                PVSSErrorKind::GSCheckError,
+            ),
+        ];
+
+        for (name, tamper, expected) in tampers.iter() {
+            let mut tampered = share.clone();
+            tamper(&mut tampered, other_share);
+            assert!(tampered != *share, "{} left the share unchanged", name);
+
+            match scheme.verify_share(rng, &fixture.registry, &tampered) {
+                Err(err) => assert_eq!(err.kind(), *expected, "{} tripped the wrong check", name),
+                Ok(()) => panic!("{} went unnoticed", name),
+            }
+        }
+    }
+}
@@ -0,0 +1,55 @@
+// Wire-compatibility check against the committed vectors in tests/vectors/ (see
+// examples/gen_vectors.rs): every fixture must still deserialize, verify, and re-serialize to
+// the exact same bytes. A failure here means the serialized layout changed; only regenerate the
+// vectors if that change is intended, as it breaks mixed-version networks.
+
+use optrand_pvss::modified_scrape::{
+    config::Config,
+    decomp::DecompProof,
+    decryption::DecryptedShare,
+    participant::ParticipantRegistry,
+    roles::{OptrandScheme, Verifier},
+    share::{PVSSAggregatedShare, PVSSShare},
+};
+
+use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+use rand::thread_rng;
+
+// Deserializes a fixture, asserting that it re-serializes to the same bytes.
+fn load<T: CanonicalDeserialize + CanonicalSerialize>(name: &str, bytes: &[u8]) -> T {
+    let value = T::deserialize(bytes).unwrap_or_else(|e| panic!("{} does not deserialize: {:?}", name, e));
+
+    let mut reserialized = vec![];
+    value.serialize(&mut reserialized).unwrap();
+    assert!(reserialized == bytes, "{} does not re-serialize to the same bytes", name);
+
+    value
+}
+
+#[test]
+fn test_vectors() {
+    let rng = &mut thread_rng();
+
+    let conf: Config<E> = load("config", include_bytes!("vectors/config.bin"));
+    let registry: ParticipantRegistry<E> = load("registry", include_bytes!("vectors/registry.bin"));
+    let share: PVSSShare<E> = load("share", include_bytes!("vectors/share.bin"));
+    let aggregated: PVSSAggregatedShare<E> = load("aggregated", include_bytes!("vectors/aggregated.bin"));
+    let decomp_proof: DecompProof<E> = load("decomp_proof", include_bytes!("vectors/decomp_proof.bin"));
+    let decrypted: DecryptedShare<E> = load("decrypted_share", include_bytes!("vectors/decrypted_share.bin"));
+
+    // The fixtures still verify cryptographically.
+    let scheme = OptrandScheme::new(conf.clone());
+    scheme.verify_share(rng, &registry, &share).unwrap();
+    scheme.verify_aggregated(rng, &registry, &aggregated).unwrap();
+    assert!(aggregated.contributions.contains_key(&share.participant_id));
+
+    decomp_proof.verify(&conf).unwrap();
+    assert!(decomp_proof == *share.signed_proof.decomp_proof());
+
+    let id = decrypted.participant_id;
+    decrypted
+        .verify(&conf, &registry.get(id).unwrap().public_key_sig, &aggregated.pvss_core.encs[id.index()])
+        .unwrap();
+}
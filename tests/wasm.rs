@@ -0,0 +1,30 @@
+// Deal -> aggregate -> verify cycle in a headless browser, run with
+// `wasm-pack test --headless --firefox --features testing` (or --chrome). All randomness is injected.
+#![cfg(target_arch = "wasm32")]
+
+use optrand_pvss::{
+    modified_scrape::roles::{OptrandScheme, Verifier},
+    testing::fixtures::FixtureBuilder,
+};
+
+use ark_bls12_381::Bls12_381 as E;   // implements PairingEngine
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn test_deal_aggregate_verify() {
+    let mut fixture = FixtureBuilder::new(1, 3).build_from_rng::<E>(ChaChaRng::from_seed([7u8; 32])).unwrap();
+    let rng = &mut fixture.rng;
+
+    let scheme = OptrandScheme::new(fixture.config.clone());
+    for share in fixture.shares.iter() {
+        scheme.verify_share(rng, &fixture.registry, share).unwrap();
+    }
+
+    scheme.verify_aggregated(rng, &fixture.registry, &fixture.transcript).unwrap();
+    assert!(fixture.transcript.is_recoverable());
+}